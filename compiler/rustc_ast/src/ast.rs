@@ -986,6 +986,9 @@ pub struct Local {
     pub ty: Option<P<Ty>>,
     /// Initializer expression to set the value, if any.
     pub init: Option<P<Expr>>,
+    /// The `else` block of a `let <pat> = <expr> else { <block> };` statement, which is run when
+    /// the pattern doesn't match. Only ever present along with an initializer.
+    pub els: Option<P<Block>>,
     pub span: Span,
     pub attrs: AttrVec,
 }
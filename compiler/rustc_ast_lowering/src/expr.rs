@@ -299,7 +299,7 @@ fn lower_expr_let(&mut self, span: Span, pat: &Pat, scrutinee: &Expr) -> hir::Ex
             self.sess
                 .struct_span_err(span, "`let` expressions are not supported here")
                 .note("only supported directly in conditions of `if`- and `while`-expressions")
-                .note("as well as when nested within `&&` and parenthesis in those conditions")
+                .note("as well as when chained with `&&` (but not parentheses) in those conditions")
                 .emit();
         } else {
             self.sess
@@ -342,7 +342,9 @@ fn lower_expr_if(
         then: &Block,
         else_opt: Option<&Expr>,
     ) -> hir::ExprKind<'hir> {
-        // FIXME(#53667): handle lowering of && and parens.
+        if let Some(chain) = let_chain(cond) {
+            return self.lower_expr_if_let_chain(span, &chain, then, else_opt);
+        }
 
         // `_ => else_block` where `else_block` is `{}` if there's `None`:
         let else_pat = self.pat_wild(span);
@@ -387,7 +389,9 @@ fn lower_expr_while_in_loop_scope(
         body: &Block,
         opt_label: Option<Label>,
     ) -> hir::ExprKind<'hir> {
-        // FIXME(#53667): handle lowering of && and parens.
+        if let Some(chain) = let_chain(cond) {
+            return self.lower_expr_while_let_chain(span, &chain, body, opt_label);
+        }
 
         // Note that the block AND the condition are evaluated in the loop scope.
         // This is done to allow `break` from inside the condition of the loop.
@@ -450,6 +454,136 @@ fn lower_expr_while_in_loop_scope(
         hir::ExprKind::Loop(self.block_expr(self.arena.alloc(match_expr)), opt_label, source)
     }
 
+    /// Lowers an `if` whose condition is a chain of `&&`s with `let`s in it. For instance,
+    /// `if let <pat> = <scrutinee> && <cond> <then> else <else>` becomes:
+    /// ```rust
+    /// {
+    ///     match <scrutinee> {
+    ///         <pat> => match drop-temps { <cond> } {
+    ///             true => break <then>,
+    ///             _ => {}
+    ///         },
+    ///         _ => {}
+    ///     }
+    ///     <else>
+    /// }
+    /// ```
+    /// where the `break` is out of the outer block. The bindings of each `let` are in scope for
+    /// the rest of the chain and for `<then>`, and the temporaries of each scrutinee live until
+    /// the end of `<then>`, but they're all dropped before `<else>` runs.
+    fn lower_expr_if_let_chain(
+        &mut self,
+        span: Span,
+        chain: &[&Expr],
+        then: &Block,
+        else_opt: Option<&Expr>,
+    ) -> hir::ExprKind<'hir> {
+        let bool_source = hir::MatchSource::IfDesugar { contains_else_clause: else_opt.is_some() };
+        let chain = self.lower_let_chain(chain, bool_source);
+        let block_id = self.next_id();
+        let then_expr = self.arena.alloc(self.lower_block_expr(then));
+        // `break <then>`
+        let break_span = self.mark_span_with_reason(DesugaringKind::LetChain, then.span, None);
+        let destination = hir::Destination { label: None, target_id: Ok(block_id) };
+        let then_expr = hir::ExprKind::Break(destination, Some(then_expr));
+        let then_expr = self.arena.alloc(self.expr(break_span, then_expr, ThinVec::new()));
+        let chain_expr = self.nest_let_chain(span, chain, then_expr, |this| {
+            // `_ => {}`
+            this.expr_block_empty(span)
+        });
+        let else_expr = else_opt.map(|els| self.lower_expr(els));
+
+        let stmt = self.stmt(span, hir::StmtKind::Expr(chain_expr));
+        let block = hir::Block {
+            hir_id: block_id,
+            stmts: arena_vec![self; stmt],
+            expr: else_expr,
+            rules: hir::BlockCheckMode::DefaultBlock,
+            span,
+            targeted_by_break: true,
+        };
+        hir::ExprKind::Block(self.arena.alloc(block), None)
+    }
+
+    /// Lowers a `while` whose condition is a chain of `&&`s with `let`s in it. For instance,
+    /// `'label: while let <pat> = <scrutinee> && <cond> <body>` becomes:
+    /// ```rust
+    /// 'label: loop {
+    ///     match <scrutinee> {
+    ///         <pat> => match drop-temps { <cond> } {
+    ///             true => <body>,
+    ///             _ => break,
+    ///         },
+    ///         _ => break,
+    ///     }
+    /// }
+    /// ```
+    fn lower_expr_while_let_chain(
+        &mut self,
+        span: Span,
+        chain: &[&Expr],
+        body: &Block,
+        opt_label: Option<Label>,
+    ) -> hir::ExprKind<'hir> {
+        let chain = self.with_loop_condition_scope(|this| {
+            this.lower_let_chain(chain, hir::MatchSource::WhileDesugar)
+        });
+        let body = self.lower_block_expr(body);
+        let match_expr = self.nest_let_chain(span, chain, self.arena.alloc(body), |this| {
+            // `_ => break`
+            this.expr_break(span, ThinVec::new())
+        });
+        hir::ExprKind::Loop(self.block_expr(match_expr), opt_label, hir::LoopSource::WhileLet)
+    }
+
+    /// Lowers each operand of a chain of `&&`s with `let`s in it to the pattern and scrutinee of
+    /// the `match` it's desugared to: `<pat> => ...` on `<scrutinee>` for a `let`, and
+    /// `true => ...` on `drop-temps { <cond> }` for any other operand, with `bool_source` as the
+    /// source of the `match`.
+    fn lower_let_chain(
+        &mut self,
+        chain: &[&Expr],
+        bool_source: hir::MatchSource,
+    ) -> Vec<(&'hir hir::Pat<'hir>, &'hir hir::Expr<'hir>, hir::MatchSource)> {
+        let mut lowered = Vec::with_capacity(chain.len());
+        for cond in chain {
+            lowered.push(match cond.kind {
+                ExprKind::Let(ref pat, ref scrutinee) => {
+                    let scrutinee = self.lower_expr(scrutinee);
+                    (self.lower_pat(pat), scrutinee, hir::MatchSource::LetChainDesugar)
+                }
+                _ => {
+                    let cond = self.lower_expr(cond);
+                    let span_block =
+                        self.mark_span_with_reason(DesugaringKind::CondTemporary, cond.span, None);
+                    // Like for a plain `if` or `while`, don't let temporaries live outside of
+                    // `cond`.
+                    let cond = self.expr_drop_temps(span_block, cond, ThinVec::new());
+                    (self.pat_bool(cond.span, true), cond, bool_source)
+                }
+            });
+        }
+        lowered
+    }
+
+    /// Nests the `match`es of a chain lowered by `lower_let_chain`, with `then` in the first arm
+    /// of the last one, and `otherwise` building the body of the wildcard arm of each of them.
+    fn nest_let_chain(
+        &mut self,
+        span: Span,
+        chain: Vec<(&'hir hir::Pat<'hir>, &'hir hir::Expr<'hir>, hir::MatchSource)>,
+        then: &'hir hir::Expr<'hir>,
+        mut otherwise: impl FnMut(&mut Self) -> &'hir hir::Expr<'hir>,
+    ) -> &'hir hir::Expr<'hir> {
+        chain.into_iter().rev().fold(then, |then, (pat, scrutinee, source)| {
+            let then_arm = self.arm(pat, then);
+            let else_pat = self.pat_wild(span);
+            let else_arm = self.arm(else_pat, otherwise(self));
+            let arms = arena_vec![self; then_arm, else_arm];
+            self.arena.alloc(self.expr_match(span, scrutinee, arms, source))
+        })
+    }
+
     /// Desugar `try { <stmts>; <expr> }` into `{ <stmts>; ::std::ops::Try::from_ok(<expr>) }`,
     /// `try { <stmts>; }` into `{ <stmts>; ::std::ops::Try::from_ok(()) }`
     /// and save the block id to use it as a break target for desugaring of the `?` operator.
@@ -2059,3 +2193,26 @@ fn arm(&mut self, pat: &'hir hir::Pat<'hir>, expr: &'hir hir::Expr<'hir>) -> hir
         }
     }
 }
+
+/// Returns the operands of the chain of `&&`s that `cond` is, if any of them is a `let`. A `let`
+/// nested in anything else, parentheses included, isn't part of the chain, and is rejected when
+/// it's lowered.
+fn let_chain(cond: &Expr) -> Option<Vec<&Expr>> {
+    fn collect<'e>(expr: &'e Expr, chain: &mut Vec<&'e Expr>) {
+        match expr.kind {
+            ExprKind::Binary(Spanned { node: BinOpKind::And, .. }, ref lhs, ref rhs) => {
+                collect(lhs, chain);
+                collect(rhs, chain);
+            }
+            _ => chain.push(expr),
+        }
+    }
+
+    // A lone `let` is a plain `if let` or `while let`.
+    if let ExprKind::Let(..) = cond.kind {
+        return None;
+    }
+    let mut chain = Vec::new();
+    collect(cond, &mut chain);
+    if chain.iter().any(|e| matches!(e.kind, ExprKind::Let(..))) { Some(chain) } else { None }
+}
@@ -622,6 +622,7 @@ macro_rules! gate_all {
     }
     gate_all!(if_let_guard, "`if let` guard is not implemented");
     gate_all!(let_chains, "`let` expressions in this position are experimental");
+    gate_all!(let_else, "`let...else` statements are unstable");
     gate_all!(async_closure, "async closures are unstable");
     gate_all!(generators, "yield syntax is experimental");
     gate_all!(or_patterns, "or-patterns syntax is experimental");
@@ -1511,6 +1511,12 @@ fn print_poly_trait_ref(&mut self, t: &ast::PolyTraitRef) {
                     self.word_space("=");
                     self.print_expr(init);
                 }
+                if let Some(ref els) = loc.els {
+                    self.cbox(INDENT_UNIT);
+                    self.ibox(INDENT_UNIT);
+                    self.s.word(" else ");
+                    self.print_block(els);
+                }
                 self.s.word(";");
                 self.end();
             }
@@ -53,6 +53,12 @@ struct Context<'a, 'b> {
     arg_unique_types: Vec<Vec<ArgumentType>>,
     /// Map from named arguments to their resolved indices.
     names: FxHashMap<Symbol, usize>,
+    /// The text of the arguments that are inlined into the string literals, by argument.
+    ///
+    /// Literal arguments which are only ever formatted with `{}` have a known output,
+    /// so they are formatted at expansion time instead of through `fmt::Display`.
+    /// An inlined argument has no `rt::Argument`, and is not evaluated at all.
+    inlined_args: Vec<Option<String>>,
 
     /// The latest consecutive literal strings, or empty if there weren't any.
     literal: String,
@@ -586,6 +592,36 @@ fn verify_arg_type(&mut self, arg: Position, ty: ArgumentType) {
         }
     }
 
+    /// Finds the arguments that can be inlined into the string literals, and removes
+    /// their types so that they take no slot in the generated argument array.
+    fn inline_literal_args(&mut self, pieces: &[parse::Piece<'_>]) {
+        for (i, expr) in self.args.iter().enumerate() {
+            if self.arg_unique_types[i][..] != [Placeholder("Display")]
+                || self.count_positions.contains_key(&i)
+            {
+                continue;
+            }
+            let default_spec_only = pieces.iter().all(|piece| match piece {
+                parse::NextArgument(arg) if arg.position.index() == Some(i) => {
+                    let spec = &arg.format;
+                    spec.fill.is_none()
+                        && spec.align == parse::AlignUnknown
+                        && spec.flags == 0
+                        && spec.precision == parse::CountImplied
+                        && spec.width == parse::CountImplied
+                }
+                _ => true,
+            });
+            if !default_spec_only {
+                continue;
+            }
+            if let Some(text) = literal_display_text(expr) {
+                self.inlined_args[i] = Some(text);
+                self.arg_unique_types[i].clear();
+            }
+        }
+    }
+
     /// Builds the mapping between format placeholders and argument objects.
     fn build_index_map(&mut self) {
         // NOTE: Keep the ordering the same as `into_expr`'s expansion would do!
@@ -658,30 +694,35 @@ fn build_piece(
                 None
             }
             parse::NextArgument(ref arg) => {
+                if let Some(i) = arg.position.index() {
+                    if let Some(Some(text)) = self.inlined_args.get(i) {
+                        self.literal.push_str(text);
+                        return None;
+                    }
+                }
+
                 // Build the position
-                let pos = {
-                    match arg.position {
-                        parse::ArgumentIs(i) | parse::ArgumentImplicitlyIs(i) => {
-                            // Map to index in final generated argument array
-                            // in case of multiple types specified
-                            let arg_idx = match arg_index_consumed.get_mut(i) {
-                                None => 0, // error already emitted elsewhere
-                                Some(offset) => {
-                                    let idx_map = &self.arg_index_map[i];
-                                    // unwrap_or branch: error already emitted elsewhere
-                                    let arg_idx = *idx_map.get(*offset).unwrap_or(&0);
-                                    *offset += 1;
-                                    arg_idx
-                                }
-                            };
-                            self.ecx.expr_usize(sp, arg_idx)
+                let arg_idx = match arg.position {
+                    parse::ArgumentIs(i) | parse::ArgumentImplicitlyIs(i) => {
+                        // Map to index in final generated argument array
+                        // in case of multiple types specified
+                        match arg_index_consumed.get_mut(i) {
+                            None => 0, // error already emitted elsewhere
+                            Some(offset) => {
+                                let idx_map = &self.arg_index_map[i];
+                                // unwrap_or branch: error already emitted elsewhere
+                                let arg_idx = *idx_map.get(*offset).unwrap_or(&0);
+                                *offset += 1;
+                                arg_idx
+                            }
                         }
-
-                        // should never be the case, because names are already
-                        // resolved.
-                        parse::ArgumentNamed(_) => panic!("should never happen"),
                     }
+
+                    // should never be the case, because names are already
+                    // resolved.
+                    parse::ArgumentNamed(_) => panic!("should never happen"),
                 };
+                let pos = self.ecx.expr_usize(sp, arg_idx);
 
                 let simple_arg = parse::Argument {
                     position: {
@@ -706,7 +747,9 @@ fn build_piece(
 
                 let fill = arg.format.fill.unwrap_or(' ');
 
-                let pos_simple = arg.position.index() == simple_arg.position.index();
+                // The arguments are used in order if every placeholder refers to the next
+                // slot of the argument array, which skips the inlined arguments.
+                let pos_simple = Some(arg_idx) == simple_arg.position.index();
 
                 if arg.format.precision_span.is_some() || arg.format.width_span.is_some() {
                     self.arg_with_formatting.push(arg.format);
@@ -788,6 +831,9 @@ fn into_expr(self) -> P<ast::Expr> {
         // of each variable because we don't want to move out of the arguments
         // passed to this function.
         for (i, e) in self.args.into_iter().enumerate() {
+            if self.inlined_args[i].is_some() {
+                continue;
+            }
             let name = names_pos[i];
             let span = self.ecx.with_def_site_ctxt(e.span);
             pats.push(self.ecx.pat_ident(span, name));
@@ -885,6 +931,32 @@ fn format_arg(
     }
 }
 
+/// Returns the output of formatting `expr` with `{}`, if it is a literal whose output is
+/// known without type checking.
+fn literal_display_text(expr: &ast::Expr) -> Option<String> {
+    let lit = match &expr.kind {
+        ast::ExprKind::Lit(lit) => lit,
+        _ => return None,
+    };
+    match lit.kind {
+        ast::LitKind::Str(s, _) => Some(s.to_string()),
+        ast::LitKind::Char(c) => Some(c.to_string()),
+        ast::LitKind::Bool(b) => Some(b.to_string()),
+        ast::LitKind::Int(n, ty) => {
+            // Only inline the values that fit their type, leaving the others to the
+            // `overflowing_literals` lint. Unsuffixed literals fall back to `i32`, and
+            // pointer-sized integers may be as small as 16 bits.
+            let bits = match ty {
+                ast::LitIntType::Signed(ty) => ty.bit_width().unwrap_or(16) - 1,
+                ast::LitIntType::Unsigned(ty) => ty.bit_width().unwrap_or(16),
+                ast::LitIntType::Unsuffixed => 31,
+            };
+            if bits >= 128 || n >> bits == 0 { Some(n.to_string()) } else { None }
+        }
+        _ => None,
+    }
+}
+
 fn expand_format_args_impl<'cx>(
     ecx: &'cx mut ExtCtxt<'_>,
     mut sp: Span,
@@ -933,6 +1005,7 @@ pub fn expand_preparsed_format_args(
     // `ArgumentType` does not derive `Clone`.
     let arg_types: Vec<_> = (0..args.len()).map(|_| Vec::new()).collect();
     let arg_unique_types: Vec<_> = (0..args.len()).map(|_| Vec::new()).collect();
+    let inlined_args = vec![None; args.len()];
 
     let mut macsp = ecx.call_site();
     macsp = ecx.with_def_site_ctxt(macsp);
@@ -1013,6 +1086,7 @@ pub fn expand_preparsed_format_args(
         arg_types,
         arg_unique_types,
         names,
+        inlined_args,
         curarg: 0,
         curpiece: 0,
         arg_index_map: Vec::new(),
@@ -1050,6 +1124,7 @@ pub fn expand_preparsed_format_args(
         },
     });
 
+    cx.inline_literal_args(&pieces);
     cx.build_index_map();
 
     let mut arg_index_consumed = vec![0usize; cx.arg_index_map.len()];
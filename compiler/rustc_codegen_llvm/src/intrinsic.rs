@@ -856,9 +856,22 @@ macro_rules! require_simd {
     }
 
     if name_str.starts_with("simd_shuffle") {
-        let n: u64 = name_str["simd_shuffle".len()..].parse().unwrap_or_else(|_| {
-            span_bug!(span, "bad `simd_shuffle` instruction only caught in codegen?")
-        });
+        let n: u64 = if name == sym::simd_shuffle {
+            // The length-generic form takes its length from the `[u32; N]` index array.
+            match arg_tys[2].kind() {
+                ty::Array(elem, len) if *elem == tcx.types.u32 => {
+                    len.eval_usize(tcx, ty::ParamEnv::reveal_all())
+                }
+                _ => return_error!(
+                    "simd_shuffle index must be an array of `u32`, got `{}`",
+                    arg_tys[2]
+                ),
+            }
+        } else {
+            name_str["simd_shuffle".len()..].parse().unwrap_or_else(|_| {
+                span_bug!(span, "bad `simd_shuffle` instruction only caught in codegen?")
+            })
+        };
 
         require_simd!(ret_ty, "return");
 
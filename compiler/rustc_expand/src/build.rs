@@ -159,6 +159,7 @@ pub fn stmt_let(&self, sp: Span, mutbl: bool, ident: Ident, ex: P<ast::Expr>) ->
             pat,
             ty: None,
             init: Some(ex),
+            els: None,
             id: ast::DUMMY_NODE_ID,
             span: sp,
             attrs: AttrVec::new(),
@@ -177,6 +178,7 @@ pub fn stmt_let_type_only(&self, span: Span, ty: P<ast::Ty>) -> ast::Stmt {
             pat: self.pat_wild(span),
             ty: Some(ty),
             init: None,
+            els: None,
             id: ast::DUMMY_NODE_ID,
             span,
             attrs: AttrVec::new(),
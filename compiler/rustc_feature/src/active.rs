@@ -619,6 +619,9 @@ pub fn set(&self, features: &mut Features, span: Span) {
     /// Allows capturing disjoint fields in a closure/generator (RFC 2229).
     (active, capture_disjoint_fields, "1.49.0", Some(53488), None),
 
+    /// Allows `let...else` statements, whose `else` block runs when the pattern doesn't match.
+    (active, let_else, "1.50.0", None, None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
@@ -632,7 +635,6 @@ pub fn set(&self, features: &mut Features, span: Span) {
     sym::impl_trait_in_bindings,
     sym::generic_associated_types,
     sym::const_generics,
-    sym::let_chains,
     sym::raw_dylib,
     sym::const_evaluatable_checked,
     sym::const_trait_impl,
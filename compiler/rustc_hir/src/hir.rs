@@ -1118,6 +1118,8 @@ pub struct Local<'hir> {
     pub ty: Option<&'hir Ty<'hir>>,
     /// Initializer expression to set the value, if any.
     pub init: Option<&'hir Expr<'hir>>,
+    /// Diverging block to run if `pat` does not match, as in `let <pat> = <expr> else { .. };`.
+    pub els: Option<&'hir Block<'hir>>,
     pub hir_id: HirId,
     pub span: Span,
     pub attrs: AttrVec,
@@ -1717,6 +1719,9 @@ pub enum MatchSource {
     TryDesugar,
     /// A desugared `<expr>.await`.
     AwaitDesugar,
+    /// A `let _ = _` in a chain of `&&`s in the condition of an `if` or a `while` (which was
+    /// desugared to a `match` for each `let`, nested in the one before).
+    LetChainDesugar,
 }
 
 impl MatchSource {
@@ -1729,6 +1734,7 @@ pub fn name(self) -> &'static str {
             ForLoopDesugar => "for",
             TryDesugar => "?",
             AwaitDesugar => ".await",
+            LetChainDesugar => "let",
         }
     }
 }
@@ -490,6 +490,7 @@ pub fn walk_local<'v, V: Visitor<'v>>(visitor: &mut V, local: &'v Local<'v>) {
     walk_list!(visitor, visit_attribute, local.attrs.iter());
     visitor.visit_id(local.hir_id);
     visitor.visit_pat(&local.pat);
+    walk_list!(visitor, visit_block, local.els);
     walk_list!(visitor, visit_ty, &local.ty);
 }
 
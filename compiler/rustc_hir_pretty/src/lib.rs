@@ -1024,6 +1024,12 @@ pub fn print_stmt(&mut self, st: &hir::Stmt<'_>) {
         match st.kind {
             hir::StmtKind::Local(ref loc) => {
                 self.print_local(loc.init.as_deref(), |this| this.print_local_decl(&loc));
+                if let Some(els) = loc.els {
+                    self.cbox(INDENT_UNIT);
+                    self.ibox(INDENT_UNIT);
+                    self.s.word(" else ");
+                    self.print_block(els);
+                }
             }
             hir::StmtKind::Item(item) => self.ann.nested(self, Nested::Item(item)),
             hir::StmtKind::Expr(ref expr) => {
@@ -631,10 +631,54 @@ fn layout_raw_uncached(&self, ty: Ty<'tcx>) -> Result<&'tcx Layout, LayoutError<
             }
 
             // SIMD vector types.
-            ty::Adt(def, ..) if def.repr.simd() => {
-                let element = self.layout_of(ty.simd_type(tcx))?;
-                let count = ty.simd_size(tcx);
-                assert!(count > 0);
+            ty::Adt(def, substs) if def.repr.simd() => {
+                // Supported SIMD vectors are homogeneous ADTs with at least one field:
+                //
+                // * #[repr(simd)] struct S(T, T, T, T);
+                // * #[repr(simd)] struct S { x: T, y: T, z: T, w: T }
+                // * #[repr(simd)] struct S([T; 4])
+                //
+                // where T is a primitive scalar (integer/float/pointer).
+                let variant = def.non_enum_variant();
+
+                // Type of the first ADT field:
+                let f0_ty = variant.fields[0].ty(tcx, substs);
+
+                // The element type and number of elements of the SIMD vector
+                // are obtained from:
+                //
+                // * the element type and length of the single array field, if
+                // the first field is of array type, or
+                //
+                // * the homogenous field type and the number of fields.
+                let (e_ty, e_len, is_array) = if let ty::Array(e_ty, _) = f0_ty.kind() {
+                    // SIMD vectors with multiple array fields are not supported:
+                    // (should be caught by typeck)
+                    if variant.fields.len() != 1 {
+                        tcx.sess.fatal(&format!(
+                            "monomorphising SIMD type `{}` with more than one array field",
+                            ty
+                        ));
+                    }
+
+                    // Extract the number of elements from the layout of the array field:
+                    let len = match self.layout_of(f0_ty)?.fields {
+                        FieldsShape::Array { count, .. } => count,
+                        _ => return Err(LayoutError::Unknown(ty)),
+                    };
+
+                    (*e_ty, len, true)
+                } else {
+                    (f0_ty, variant.fields.len() as u64, false)
+                };
+
+                // SIMD vectors of zero length are not supported. This can't be caught
+                // in typeck if the array length is generic.
+                if e_len == 0 {
+                    tcx.sess.fatal(&format!("monomorphising SIMD type `{}` of zero length", ty));
+                }
+
+                let element = self.layout_of(e_ty)?;
                 let scalar = match element.abi {
                     Abi::Scalar(ref scalar) => scalar.clone(),
                     _ => {
@@ -646,14 +690,22 @@ fn layout_raw_uncached(&self, ty: Ty<'tcx>) -> Result<&'tcx Layout, LayoutError<
                     }
                 };
                 let size =
-                    element.size.checked_mul(count, dl).ok_or(LayoutError::SizeOverflow(ty))?;
+                    element.size.checked_mul(e_len, dl).ok_or(LayoutError::SizeOverflow(ty))?;
                 let align = dl.vector_align(size);
                 let size = size.align_to(align.abi);
 
+                // The array-based form has a single field, the array itself, which starts
+                // at offset zero; the other form has one field per element.
+                let fields = if is_array {
+                    FieldsShape::Arbitrary { offsets: vec![Size::ZERO], memory_index: vec![0] }
+                } else {
+                    FieldsShape::Array { stride: element.size, count: e_len }
+                };
+
                 tcx.intern_layout(Layout {
                     variants: Variants::Single { index: VariantIdx::new(0) },
-                    fields: FieldsShape::Array { stride: element.size, count },
-                    abi: Abi::Vector { element: scalar, count },
+                    fields,
+                    abi: Abi::Vector { element: scalar, count: e_len },
                     largest_niche: element.largest_niche.clone(),
                     size,
                     align,
@@ -2121,9 +2173,6 @@ fn field(this: TyAndLayout<'tcx>, cx: &C, i: usize) -> C::TyAndLayout {
 
             ty::Tuple(tys) => tys[i].expect_ty(),
 
-            // SIMD vector types.
-            ty::Adt(def, ..) if def.repr.simd() => this.ty.simd_type(tcx),
-
             // ADTs.
             ty::Adt(def, substs) => {
                 match this.variants {
@@ -1960,26 +1960,32 @@ pub fn sequence_element_type(&self, tcx: TyCtxt<'tcx>) -> Ty<'tcx> {
     }
 
     pub fn simd_type(&self, tcx: TyCtxt<'tcx>) -> Ty<'tcx> {
-        match self.kind() {
-            Adt(def, substs) => def.non_enum_variant().fields[0].ty(tcx, substs),
-            _ => bug!("`simd_type` called on invalid type"),
-        }
+        self.simd_size_and_type(tcx).1
     }
 
-    pub fn simd_size(&self, _tcx: TyCtxt<'tcx>) -> u64 {
-        // Parameter currently unused, but probably needed in the future to
-        // allow `#[repr(simd)] struct Simd<T, const N: usize>([T; N]);`.
-        match self.kind() {
-            Adt(def, _) => def.non_enum_variant().fields.len() as u64,
-            _ => bug!("`simd_size` called on invalid type"),
-        }
+    pub fn simd_size(&self, tcx: TyCtxt<'tcx>) -> u64 {
+        self.simd_size_and_type(tcx).0
     }
 
+    /// Returns the number of lanes and the lane type of a `#[repr(simd)]` type.
+    ///
+    /// Both `#[repr(simd)] struct S(T, T, T, T);` and `#[repr(simd)] struct S([T; 4]);`
+    /// describe a vector of four `T`s.
     pub fn simd_size_and_type(&self, tcx: TyCtxt<'tcx>) -> (u64, Ty<'tcx>) {
         match self.kind() {
             Adt(def, substs) => {
                 let variant = def.non_enum_variant();
-                (variant.fields.len() as u64, variant.fields[0].ty(tcx, substs))
+                let f0_ty = variant.fields[0].ty(tcx, substs);
+
+                match f0_ty.kind() {
+                    // The way we evaluate the `N` in `[T; N]` here only works since we use
+                    // `simd_size_and_type` post-monomorphization. It will ICE if used on a
+                    // type whose length still depends on a generic parameter.
+                    Array(f0_elem_ty, f0_len) => {
+                        (f0_len.eval_usize(tcx, ParamEnv::empty()) as u64, *f0_elem_ty)
+                    }
+                    _ => (variant.fields.len() as u64, f0_ty),
+                }
             }
             _ => bug!("`simd_size_and_type` called on invalid type"),
         }
@@ -14,7 +14,7 @@
 use rustc_middle::ty::subst::SubstsRef;
 use rustc_middle::ty::{Ty, TyCtxt};
 use rustc_span::symbol::{sym, Symbol};
-use rustc_target::abi::{Abi, LayoutOf as _, Primitive, Size};
+use rustc_target::abi::{Abi, FieldsShape, LayoutOf as _, Primitive, Size};
 
 use super::{
     util::ensure_monomorphic_enough, CheckInAllocMsg, ImmTy, InterpCx, Machine, OpTy, PlaceTy,
@@ -408,9 +408,11 @@ pub fn emulate_intrinsic(
                     elem.layout.ty, e_ty
                 );
 
+                let dest_lanes = self.simd_lanes_place(dest)?;
+                let input_lanes = self.simd_lanes_operand(input)?;
                 for i in 0..len {
-                    let place = self.place_index(dest, i)?;
-                    let value = if i == index { elem } else { self.operand_index(input, i)? };
+                    let place = self.place_index(dest_lanes, i)?;
+                    let value = if i == index { elem } else { self.operand_index(input_lanes, i)? };
                     self.copy_op(value, place)?;
                 }
             }
@@ -429,7 +431,8 @@ pub fn emulate_intrinsic(
                     "Return type `{}` must match vector element type `{}`",
                     dest.layout.ty, e_ty
                 );
-                self.copy_op(self.operand_index(args[0], index)?, dest)?;
+                let lanes = self.simd_lanes_operand(args[0])?;
+                self.copy_op(self.operand_index(lanes, index)?, dest)?;
             }
             sym::likely | sym::unlikely => {
                 // These just return their argument
@@ -449,6 +452,29 @@ pub fn emulate_intrinsic(
         Ok(true)
     }
 
+    /// Returns the operand whose elements are the lanes of the SIMD vector `op`. For the
+    /// `#[repr(simd)] struct S([T; N])` form that is the array field, otherwise `op` itself.
+    fn simd_lanes_operand(
+        &self,
+        op: OpTy<'tcx, M::PointerTag>,
+    ) -> InterpResult<'tcx, OpTy<'tcx, M::PointerTag>> {
+        match op.layout.fields {
+            FieldsShape::Array { .. } => Ok(op),
+            _ => self.operand_field(op, 0),
+        }
+    }
+
+    /// Like `simd_lanes_operand`, but for places.
+    fn simd_lanes_place(
+        &mut self,
+        place: PlaceTy<'tcx, M::PointerTag>,
+    ) -> InterpResult<'tcx, PlaceTy<'tcx, M::PointerTag>> {
+        match place.layout.fields {
+            FieldsShape::Array { .. } => Ok(place),
+            _ => self.place_field(place, 0),
+        }
+    }
+
     pub fn exact_div(
         &mut self,
         a: ImmTy<'tcx, M::PointerTag>,
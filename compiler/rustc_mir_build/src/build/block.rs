@@ -97,7 +97,14 @@ fn ast_block_stmts(
                         )
                     );
                 }
-                StmtKind::Let { remainder_scope, init_scope, pattern, initializer, lint_level } => {
+                StmtKind::Let {
+                    remainder_scope,
+                    init_scope,
+                    pattern,
+                    initializer,
+                    else_block,
+                    lint_level,
+                } => {
                     let ignores_expr_result = matches!(*pattern.kind, PatKind::Wild);
                     this.block_context.push(BlockFrame::Statement { ignores_expr_result });
 
@@ -129,7 +136,13 @@ fn ast_block_stmts(
                                             ArmHasGuard(false),
                                             Some((None, initializer_span)),
                                         );
-                                        this.expr_into_pattern(block, pattern, init)
+                                        if let Some(else_block) = else_block {
+                                            this.let_else_into_pattern(
+                                                block, pattern, init, else_block,
+                                            )
+                                        } else {
+                                            this.expr_into_pattern(block, pattern, init)
+                                        }
                                     })
                                 }
                             )
@@ -14,7 +14,7 @@
     fx::{FxHashSet, FxIndexMap},
     stack::ensure_sufficient_stack,
 };
-use rustc_hir::HirId;
+use rustc_hir::{self as hir, HirId};
 use rustc_index::bit_set::BitSet;
 use rustc_middle::middle::region;
 use rustc_middle::mir::*;
@@ -103,7 +103,7 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
             arm_candidates.iter_mut().map(|(_, candidate)| candidate).collect::<Vec<_>>();
 
         let fake_borrow_temps =
-            self.lower_match_tree(block, scrutinee_span, match_has_guard, &mut candidates);
+            self.lower_match_tree(block, scrutinee_span, match_has_guard, None, &mut candidates);
 
         self.lower_match_arms(
             destination,
@@ -167,12 +167,16 @@ fn create_match_candidates<'pat>(
     /// Modifies `candidates` to store the bindings and type ascriptions for
     /// that candidate.
     ///
+    /// If `else_block` is given, it's where the values that none of the
+    /// candidates match go.
+    ///
     /// Returns the places that need fake borrows because we bind or test them.
     fn lower_match_tree<'pat>(
         &mut self,
         block: BasicBlock,
         scrutinee_span: Span,
         match_has_guard: bool,
+        else_block: Option<BasicBlock>,
         candidates: &mut [&mut Candidate<'pat, 'tcx>],
     ) -> Vec<(Place<'tcx>, Local)> {
         // The set of places that we are creating fake borrows of. If there are
@@ -187,11 +191,15 @@ fn lower_match_tree<'pat>(
         self.match_candidates(scrutinee_span, block, &mut otherwise, candidates, &mut fake_borrows);
 
         if let Some(otherwise_block) = otherwise {
-            // See the doc comment on `match_candidates` for why we may have an
-            // otherwise block. Match checking will ensure this is actually
-            // unreachable.
             let source_info = self.source_info(scrutinee_span);
-            self.cfg.terminate(otherwise_block, source_info, TerminatorKind::Unreachable);
+            if let Some(else_block) = else_block {
+                self.cfg.goto(otherwise_block, source_info, else_block);
+            } else {
+                // See the doc comment on `match_candidates` for why we may have an
+                // otherwise block. Match checking will ensure this is actually
+                // unreachable.
+                self.cfg.terminate(otherwise_block, source_info, TerminatorKind::Unreachable);
+            }
         }
 
         // Link each leaf candidate to the `pre_binding_block` of the next one.
@@ -444,22 +452,50 @@ pub(super) fn expr_into_pattern(
 
             _ => {
                 let place = unpack!(block = self.as_place(block, initializer));
-                self.place_into_pattern(block, irrefutable_pat, place, true)
+                self.place_into_pattern(block, irrefutable_pat, place, true, None)
             }
         }
     }
 
+    /// Lowers `let <pattern> = <initializer> else { <else_block> };`. The
+    /// `else` block runs when `pattern` doesn't match, after `initializer`
+    /// has been evaluated but before any of the bindings are, and it must
+    /// diverge.
+    pub(super) fn let_else_into_pattern(
+        &mut self,
+        mut block: BasicBlock,
+        pattern: Pat<'tcx>,
+        initializer: ExprRef<'tcx>,
+        else_block: &'tcx hir::Block<'tcx>,
+    ) -> BlockAnd<()> {
+        let place = unpack!(block = self.as_place(block, initializer));
+
+        let else_entry = self.cfg.start_new_block();
+        let source_info = self.source_info(else_block.span);
+        let never = self.temp(self.hir.tcx().types.never, else_block.span);
+        let else_end = unpack!(self.ast_block(never, else_entry, else_block, source_info));
+        self.cfg.terminate(else_end, source_info, TerminatorKind::Unreachable);
+
+        self.place_into_pattern(block, pattern, place, true, Some(else_entry))
+    }
+
     crate fn place_into_pattern(
         &mut self,
         block: BasicBlock,
         irrefutable_pat: Pat<'tcx>,
         initializer: Place<'tcx>,
         set_match_place: bool,
+        else_block: Option<BasicBlock>,
     ) -> BlockAnd<()> {
         let mut candidate = Candidate::new(initializer, &irrefutable_pat, false);
 
-        let fake_borrow_temps =
-            self.lower_match_tree(block, irrefutable_pat.span, false, &mut [&mut candidate]);
+        let fake_borrow_temps = self.lower_match_tree(
+            block,
+            irrefutable_pat.span,
+            false,
+            else_block,
+            &mut [&mut candidate],
+        );
 
         // For matches and function arguments, the place that is being matched
         // can be set when creating the variables. But the place for
@@ -928,7 +928,9 @@ fn args_and_body(
                             matches::ArmHasGuard(false),
                             Some((Some(&place), span)),
                         );
-                        unpack!(block = self.place_into_pattern(block, pattern, place, false));
+                        unpack!(
+                            block = self.place_into_pattern(block, pattern, place, false, None)
+                        );
                     }
                 }
                 self.source_scope = original_source_scope;
@@ -90,6 +90,11 @@
         /// let pat: ty = <INIT> ...
         initializer: Option<ExprRef<'tcx>>,
 
+        /// let pat: ty = init else { <ELSE> };
+        ///
+        /// the block to run if `pattern` doesn't match, which diverges
+        else_block: Option<&'tcx hir::Block<'tcx>>,
+
         /// the lint level for this let-statement
         lint_level: LintLevel,
     },
@@ -71,7 +71,11 @@ fn visit_local(&mut self, loc: &'tcx hir::Local<'tcx>) {
             hir::LocalSource::AwaitDesugar => ("`await` future binding", None),
             hir::LocalSource::AssignDesugar(_) => ("destructuring assignment binding", None),
         };
-        self.check_irrefutable(&loc.pat, msg, sp);
+        if loc.els.is_some() {
+            self.check_let_else(loc);
+        } else {
+            self.check_irrefutable(&loc.pat, msg, sp);
+        }
         self.check_patterns(&loc.pat);
     }
 
@@ -200,6 +204,21 @@ fn check_match(
         }
     }
 
+    /// The pattern of a `let...else` may be refutable, since the `else` block handles the values
+    /// it doesn't match, but there's no point in the `else` block if it's irrefutable.
+    fn check_let_else(&self, loc: &'tcx hir::Local<'tcx>) {
+        let pat = loc.pat;
+        let mut cx = self.new_cx(pat.hir_id);
+        let (pattern, pattern_ty) = self.lower_pattern(&mut cx, pat, &mut false);
+        let arms = vec![MatchArm { pat: pattern, hir_id: pat.hir_id, has_guard: false }];
+        let report = compute_match_usefulness(&cx, &arms, pat.hir_id, pattern_ty);
+        if report.non_exhaustiveness_witnesses.is_empty() {
+            self.tcx.struct_span_lint_hir(IRREFUTABLE_LET_PATTERNS, loc.hir_id, loc.span, |lint| {
+                lint.build("irrefutable let-else pattern").emit()
+            });
+        }
+    }
+
     fn check_irrefutable(&self, pat: &'tcx Pat<'tcx>, origin: &str, sp: Option<Span>) {
         let mut cx = self.new_cx(pat.hir_id);
 
@@ -394,6 +413,14 @@ fn report_arm_reachability<'p, 'tcx>(
                         unreachable_pattern(cx.tcx, arm.pat.span, arm.hir_id, catchall);
                     }
 
+                    hir::MatchSource::LetChainDesugar => {
+                        // An irrefutable `let` in a chain is fine, as it binds variables for the
+                        // rest of the chain, so only the arm with the user's pattern is checked.
+                        if arm_index == 0 {
+                            unreachable_pattern(cx.tcx, arm.pat.span, arm.hir_id, None);
+                        }
+                    }
+
                     // Unreachable patterns in try and await expressions occur when one of
                     // the arms are an uninhabited type. Which is OK.
                     hir::MatchSource::AwaitDesugar | hir::MatchSource::TryDesugar => {}
@@ -218,8 +218,43 @@ fn parse_local(&mut self, attrs: AttrVec) -> PResult<'a, P<Local>> {
                 return Err(err);
             }
         };
+        let els = match init {
+            Some(ref init) if self.eat_keyword(kw::Else) => {
+                self.check_let_else_init(init);
+                let els = self.parse_block()?;
+                self.sess.gated_spans.gate(sym::let_else, lo.to(els.span));
+                Some(els)
+            }
+            _ => None,
+        };
         let hi = if self.token == token::Semi { self.token.span } else { self.prev_token.span };
-        Ok(P(ast::Local { ty, pat, init, id: DUMMY_NODE_ID, span: lo.to(hi), attrs }))
+        Ok(P(ast::Local { ty, pat, init, els, id: DUMMY_NODE_ID, span: lo.to(hi), attrs }))
+    }
+
+    /// Rejects the initializers of a `let...else` which would read as something else with the
+    /// `else` after them: `let x = if a { b } else { c } else { .. };` looks like an `if` with two
+    /// `else` blocks, and `let x = a && b else { .. };` like a `let` chain.
+    fn check_let_else_init(&self, init: &Expr) {
+        let msg = if !classify::expr_requires_semi_to_be_stmt(init) {
+            "right curly brace `}` before `else` in a `let...else` statement not allowed"
+        } else if let ExprKind::Binary(op, ..) = init.kind {
+            if !op.node.lazy() {
+                return;
+            }
+            "a lazy boolean expression cannot be directly assigned in `let...else`"
+        } else {
+            return;
+        };
+        self.struct_span_err(init.span, msg)
+            .multipart_suggestion(
+                "wrap the expression in parentheses",
+                vec![
+                    (init.span.shrink_to_lo(), "(".to_string()),
+                    (init.span.shrink_to_hi(), ")".to_string()),
+                ],
+                Applicability::MachineApplicable,
+            )
+            .emit();
     }
 
     /// Parses the RHS of a local variable declaration (e.g., '= 14;').
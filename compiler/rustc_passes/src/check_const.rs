@@ -48,7 +48,12 @@ fn required_feature_gates(self) -> Option<&'static [Symbol]> {
             // All other expressions are allowed.
             Self::Loop(Loop | While | WhileLet)
             | Self::Match(
-                WhileDesugar | WhileLetDesugar | Normal | IfDesugar { .. } | IfLetDesugar { .. },
+                WhileDesugar
+                | WhileLetDesugar
+                | Normal
+                | IfDesugar { .. }
+                | IfLetDesugar { .. }
+                | LetChainDesugar,
             ) => &[],
         };
 
@@ -353,6 +353,10 @@ fn visit_body(&mut self, body: &'tcx hir::Body<'tcx>) {
 
     fn visit_local(&mut self, local: &'tcx hir::Local<'tcx>) {
         self.add_from_pat(&local.pat);
+        if local.els.is_some() {
+            // A `let...else` branches like a `match`.
+            self.add_live_node_for_node(local.hir_id, ExprNode(local.span));
+        }
         intravisit::walk_local(self, local);
     }
 
@@ -951,8 +955,30 @@ fn propagate_through_stmt(&mut self, stmt: &hir::Stmt<'_>, succ: LiveNode) -> Li
                 // initialization, which is mildly more complex than checking
                 // once at the func header but otherwise equivalent.
 
-                let succ = self.propagate_through_opt_expr(local.init.as_deref(), succ);
-                self.define_bindings_in_pat(&local.pat, succ)
+                if let Some(els) = local.els {
+                    // Like a `match` with two arms, one for the pattern and one for `els`:
+                    //
+                    //     (init)
+                    //       |
+                    //       v
+                    //    (local)
+                    //     /   \
+                    //    v     v
+                    //  (pat) (els)
+                    //    |     |
+                    //    v     v
+                    //    (succ)
+                    //
+                    let ln = self.live_node(local.hir_id, local.span);
+                    let else_ln = self.propagate_through_block(els, succ);
+                    let then_ln = self.define_bindings_in_pat(&local.pat, succ);
+                    self.init_from_succ(ln, then_ln);
+                    self.merge_from_succ(ln, else_ln, false);
+                    self.propagate_through_opt_expr(local.init.as_deref(), ln)
+                } else {
+                    let succ = self.propagate_through_opt_expr(local.init.as_deref(), succ);
+                    self.define_bindings_in_pat(&local.pat, succ)
+                }
             }
             hir::StmtKind::Item(..) => succ,
             hir::StmtKind::Expr(ref expr) | hir::StmtKind::Semi(ref expr) => {
@@ -204,7 +204,10 @@ fn require_label_in_labeled_block(
         label: &Destination,
         cf_type: &str,
     ) -> bool {
-        if !span.is_desugaring(DesugaringKind::QuestionMark) && self.cx == LabeledBlock {
+        if !span.is_desugaring(DesugaringKind::QuestionMark)
+            && !span.is_desugaring(DesugaringKind::LetChain)
+            && self.cx == LabeledBlock
+        {
             if label.label.is_none() {
                 struct_span_err!(
                     self.sess,
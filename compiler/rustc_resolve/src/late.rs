@@ -1384,6 +1384,9 @@ fn resolve_local(&mut self, local: &'ast Local) {
         // Resolve the initializer.
         walk_list!(self, visit_expr, &local.init);
 
+        // Resolve the `else` block, which the bindings of the pattern aren't in scope for.
+        walk_list!(self, visit_block, &local.els);
+
         // Resolve the pattern.
         self.resolve_pattern_top(&local.pat, PatternSource::Let);
     }
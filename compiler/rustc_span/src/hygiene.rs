@@ -857,6 +857,9 @@ pub enum DesugaringKind {
     Async,
     Await,
     ForLoop(ForLoopLoc),
+    /// We desugar an `if` whose condition is a chain of `&&`s with `let`s in it to a block,
+    /// which the `then` block `break`s out of when the whole chain matches.
+    LetChain,
 }
 
 /// A location in the desugaring of a `for` loop
@@ -877,6 +880,7 @@ fn descr(self) -> &'static str {
             DesugaringKind::TryBlock => "`try` block",
             DesugaringKind::OpaqueTy => "`impl Trait`",
             DesugaringKind::ForLoop(_) => "`for` loop",
+            DesugaringKind::LetChain => "`let` chain",
         }
     }
 }
@@ -632,6 +632,7 @@
         lazy_normalization_consts,
         le,
         let_chains,
+        let_else,
         lhs,
         lib,
         libc,
@@ -1036,6 +1037,7 @@
         simd_select_bitmask,
         simd_shl,
         simd_shr,
+        simd_shuffle,
         simd_sub,
         simd_xor,
         since,
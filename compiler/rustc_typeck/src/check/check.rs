@@ -1092,9 +1092,29 @@ pub fn check_simd(tcx: TyCtxt<'_>, sp: Span, def_id: LocalDefId) {
                     .emit();
                 return;
             }
+            if let ty::Array(_, len) = e.kind() {
+                if fields.len() != 1 {
+                    struct_span_err!(
+                        tcx.sess,
+                        sp,
+                        E0076,
+                        "SIMD vector with an array field should have exactly one field"
+                    )
+                    .emit();
+                    return;
+                }
+                if let Some(0) = len.try_eval_usize(tcx, tcx.param_env(def.did)) {
+                    struct_span_err!(tcx.sess, sp, E0075, "SIMD vector cannot be empty").emit();
+                    return;
+                }
+            }
             match e.kind() {
                 ty::Param(_) => { /* struct<T>(T, T, T, T) is ok */ }
                 _ if e.is_machine() => { /* struct(u8, u8, u8, u8) is ok */ }
+                ty::Array(ty, _) if ty.is_machine() => { /* struct([f32; 4]) is ok */ }
+                ty::Array(ty, _) if matches!(ty.kind(), ty::Param(_)) => {
+                    /* struct<T, const N: usize>([T; N]) is ok */
+                }
                 _ => {
                     struct_span_err!(
                         tcx.sess,
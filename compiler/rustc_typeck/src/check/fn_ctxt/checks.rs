@@ -536,6 +536,14 @@ pub fn check_decl_local(&self, local: &'tcx hir::Local<'tcx>) {
         self.check_pat_top(&local.pat, ty, ty_span, origin_expr);
         let pat_ty = self.node_ty(local.pat.hir_id);
         self.overwrite_local_ty_if_err(local, ty, pat_ty);
+
+        if let Some(els) = local.els {
+            // The `else` block of a `let...else` has to diverge, but the code after the statement
+            // is only reached when the pattern matches, so it doesn't make that code unreachable.
+            let prev_diverges = self.diverges.get();
+            self.check_block_with_expected(els, ExpectHasType(self.tcx.types.never));
+            self.diverges.set(prev_diverges);
+        }
     }
 
     pub fn check_stmt(&self, stmt: &'tcx hir::Stmt<'tcx>) {
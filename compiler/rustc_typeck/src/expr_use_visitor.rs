@@ -368,9 +368,19 @@ fn walk_local(&mut self, local: &hir::Local<'_>) {
             // initializers are considered
             // "assigns", which is handled by
             // `walk_pat`:
-            self.walk_expr(&expr);
-            let init_place = return_if_err!(self.mc.cat_expr(&expr));
-            self.walk_irrefutable_pat(&init_place, &local.pat);
+            if let Some(els) = local.els {
+                // Like the scrutinee of a `match`, the initializer
+                // of a `let...else` is borrowed while its pattern is
+                // tested.
+                let init_place = return_if_err!(self.mc.cat_expr(&expr));
+                self.borrow_expr(&expr, ty::ImmBorrow);
+                self.walk_pat(&init_place, &local.pat);
+                self.walk_block(els);
+            } else {
+                self.walk_expr(&expr);
+                let init_place = return_if_err!(self.mc.cat_expr(&expr));
+                self.walk_irrefutable_pat(&init_place, &local.pat);
+            }
         }
     }
 
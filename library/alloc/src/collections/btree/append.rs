@@ -1,6 +1,7 @@
 use super::map::MIN_LEN;
 use super::merge_iter::MergeIterInner;
 use super::node::{self, ForceResult::*, Root};
+use crate::alloc::AllocRef;
 use core::iter::FusedIterator;
 
 impl<K, V> Root<K, V> {
@@ -15,8 +16,13 @@ impl<K, V> Root<K, V> {
     /// a `BTreeMap`, both iterators should produce keys in strictly ascending
     /// order, each greater than all keys in the tree, including any keys
     /// already in the tree upon entry.
-    pub fn append_from_sorted_iters<I>(&mut self, left: I, right: I, length: &mut usize)
-    where
+    pub fn append_from_sorted_iters<I, A: AllocRef + Clone>(
+        &mut self,
+        left: I,
+        right: I,
+        length: &mut usize,
+        alloc: A,
+    ) where
         K: Ord,
         I: Iterator<Item = (K, V)> + FusedIterator,
     {
@@ -24,13 +30,13 @@ pub fn append_from_sorted_iters<I>(&mut self, left: I, right: I, length: &mut us
         let iter = MergeIter(MergeIterInner::new(left, right));
 
         // Meanwhile, we build a tree from the sorted sequence in linear time.
-        self.bulk_push(iter, length)
+        self.bulk_push(iter, length, alloc)
     }
 
     /// Pushes all key-value pairs to the end of the tree, incrementing a
     /// `length` variable along the way. The latter makes it easier for the
     /// caller to avoid a leak when the iterator panicks.
-    fn bulk_push<I>(&mut self, iter: I, length: &mut usize)
+    fn bulk_push<I, A: AllocRef + Clone>(&mut self, iter: I, length: &mut usize, alloc: A)
     where
         I: Iterator<Item = (K, V)>,
     {
@@ -59,7 +65,7 @@ fn bulk_push<I>(&mut self, iter: I, length: &mut usize)
                         }
                         Err(_) => {
                             // We are at the top, create a new root node and push there.
-                            open_node = self.push_internal_level();
+                            open_node = self.push_internal_level(alloc.clone());
                             break;
                         }
                     }
@@ -67,9 +73,9 @@ fn bulk_push<I>(&mut self, iter: I, length: &mut usize)
 
                 // Push key-value pair and new right subtree.
                 let tree_height = open_node.height() - 1;
-                let mut right_tree = Root::new_leaf();
+                let mut right_tree = Root::new_leaf(alloc.clone());
                 for _ in 0..tree_height {
-                    right_tree.push_internal_level();
+                    right_tree.push_internal_level(alloc.clone());
                 }
                 open_node.push(key, value, right_tree);
 
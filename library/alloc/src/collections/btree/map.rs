@@ -5,15 +5,22 @@
 use core::iter::{FromIterator, FusedIterator};
 use core::marker::PhantomData;
 use core::mem::{self, ManuallyDrop};
-use core::ops::{Index, RangeBounds};
+use core::ops::{Bound, Index, RangeBounds};
 use core::ptr;
 
+use crate::alloc::{AllocRef, Global};
+use crate::collections::TryReserveError;
+
 use super::borrow::DormantMutRef;
 use super::node::{self, marker, ForceResult::*, Handle, NodeRef};
+use super::reserve::NodeReserve;
 use super::search::{self, SearchResult::*};
 use super::unwrap_unchecked;
 
+mod cursor;
 mod entry;
+#[unstable(feature = "btree_cursors", issue = "none")]
+pub use cursor::{Cursor, CursorMut};
 pub use entry::{Entry, OccupiedEntry, VacantEntry};
 use Entry::*;
 
@@ -127,13 +134,19 @@
 /// *stat += random_stat_buff();
 /// ```
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct BTreeMap<K, V> {
+pub struct BTreeMap<
+    K,
+    V,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: AllocRef + Clone = Global,
+> {
     root: Option<node::Root<K, V>>,
     length: usize,
+    // `ManuallyDrop` because `Drop` moves the allocator out into an `IntoIter`.
+    alloc: ManuallyDrop<A>,
 }
 
 #[stable(feature = "btree_drop", since = "1.7.0")]
-unsafe impl<#[may_dangle] K, #[may_dangle] V> Drop for BTreeMap<K, V> {
+unsafe impl<#[may_dangle] K, #[may_dangle] V, A: AllocRef + Clone> Drop for BTreeMap<K, V, A> {
     fn drop(&mut self) {
         unsafe {
             drop(ptr::read(self).into_iter());
@@ -142,18 +155,23 @@ fn drop(&mut self) {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Clone, V: Clone> Clone for BTreeMap<K, V> {
-    fn clone(&self) -> BTreeMap<K, V> {
-        fn clone_subtree<'a, K: Clone, V: Clone>(
+impl<K: Clone, V: Clone, A: AllocRef + Clone> Clone for BTreeMap<K, V, A> {
+    fn clone(&self) -> BTreeMap<K, V, A> {
+        fn clone_subtree<'a, K: Clone, V: Clone, A: AllocRef + Clone>(
             node: node::NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>,
-        ) -> BTreeMap<K, V>
+            alloc: A,
+        ) -> BTreeMap<K, V, A>
         where
             K: 'a,
             V: 'a,
         {
             match node.force() {
                 Leaf(leaf) => {
-                    let mut out_tree = BTreeMap { root: Some(node::Root::new_leaf()), length: 0 };
+                    let mut out_tree = BTreeMap {
+                        root: Some(node::Root::new_leaf(alloc.clone())),
+                        length: 0,
+                        alloc: ManuallyDrop::new(alloc),
+                    };
 
                     {
                         let root = out_tree.root.as_mut().unwrap(); // unwrap succeeds because we just wrapped
@@ -175,11 +193,12 @@ fn clone_subtree<'a, K: Clone, V: Clone>(
                     out_tree
                 }
                 Internal(internal) => {
-                    let mut out_tree = clone_subtree(internal.first_edge().descend());
+                    let mut out_tree =
+                        clone_subtree(internal.first_edge().descend(), alloc.clone());
 
                     {
-                        let out_root = BTreeMap::ensure_is_owned(&mut out_tree.root);
-                        let mut out_node = out_root.push_internal_level();
+                        let out_root = BTreeMap::ensure_is_owned(&mut out_tree.root, alloc.clone());
+                        let mut out_node = out_root.push_internal_level(alloc.clone());
                         let mut in_edge = internal.first_edge();
                         while let Ok(kv) = in_edge.right_kv() {
                             let (k, v) = kv.into_kv();
@@ -187,18 +206,26 @@ fn clone_subtree<'a, K: Clone, V: Clone>(
 
                             let k = (*k).clone();
                             let v = (*v).clone();
-                            let subtree = clone_subtree(in_edge.descend());
+                            let subtree = clone_subtree(in_edge.descend(), alloc.clone());
 
                             // We can't destructure subtree directly
                             // because BTreeMap implements Drop
-                            let (subroot, sublength) = unsafe {
+                            let (subroot, sublength, suballoc) = unsafe {
                                 let subtree = ManuallyDrop::new(subtree);
                                 let root = ptr::read(&subtree.root);
                                 let length = subtree.length;
-                                (root, length)
+                                let alloc = ManuallyDrop::into_inner(ptr::read(&subtree.alloc));
+                                (root, length, alloc)
                             };
-
-                            out_node.push(k, v, subroot.unwrap_or_else(node::Root::new_leaf));
+                            // The subtree's nodes now belong to `out_node`, but its
+                            // copy of the allocator still has to be dropped.
+                            drop(suballoc);
+
+                            out_node.push(
+                                k,
+                                v,
+                                subroot.unwrap_or_else(|| node::Root::new_leaf(alloc.clone())),
+                            );
                             out_tree.length += 1 + sublength;
                         }
                     }
@@ -211,14 +238,15 @@ fn clone_subtree<'a, K: Clone, V: Clone>(
         if self.is_empty() {
             // Ideally we'd call `BTreeMap::new` here, but that has the `K:
             // Ord` constraint, which this method lacks.
-            BTreeMap { root: None, length: 0 }
+            BTreeMap { root: None, length: 0, alloc: self.alloc.clone() }
         } else {
-            clone_subtree(self.root.as_ref().unwrap().reborrow()) // unwrap succeeds because not empty
+            // unwrap succeeds because not empty
+            clone_subtree(self.root.as_ref().unwrap().reborrow(), (*self.alloc).clone())
         }
     }
 }
 
-impl<K, Q: ?Sized> super::Recover<Q> for BTreeMap<K, ()>
+impl<K, Q: ?Sized, A: AllocRef + Clone> super::Recover<Q> for BTreeMap<K, (), A>
 where
     K: Borrow<Q> + Ord,
     Q: Ord,
@@ -235,22 +263,24 @@ fn get(&self, key: &Q) -> Option<&K> {
 
     fn take(&mut self, key: &Q) -> Option<K> {
         let (map, dormant_map) = DormantMutRef::new(self);
+        let alloc = (*map.alloc).clone();
         let root_node = map.root.as_mut()?.borrow_mut();
         match search::search_tree(root_node, key) {
-            Found(handle) => {
-                Some(OccupiedEntry { handle, dormant_map, _marker: PhantomData }.remove_kv().0)
-            }
+            Found(handle) => Some(
+                OccupiedEntry { handle, dormant_map, alloc, _marker: PhantomData }.remove_kv().0,
+            ),
             GoDown(_) => None,
         }
     }
 
     fn replace(&mut self, key: K) -> Option<K> {
         let (map, dormant_map) = DormantMutRef::new(self);
-        let root_node = Self::ensure_is_owned(&mut map.root).borrow_mut();
+        let alloc = (*map.alloc).clone();
+        let root_node = Self::ensure_is_owned(&mut map.root, alloc.clone()).borrow_mut();
         match search::search_tree::<marker::Mut<'_>, K, (), K>(root_node, &key) {
             Found(handle) => Some(mem::replace(handle.into_key_mut(), key)),
             GoDown(handle) => {
-                VacantEntry { key, handle, dormant_map, _marker: PhantomData }.insert(());
+                VacantEntry { key, handle, dormant_map, alloc, _marker: PhantomData }.insert(());
                 None
             }
         }
@@ -296,13 +326,18 @@ pub struct IterMut<'a, K: 'a, V: 'a> {
 ///
 /// [`into_iter`]: IntoIterator::into_iter
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct IntoIter<K, V> {
+pub struct IntoIter<
+    K,
+    V,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: AllocRef + Clone = Global,
+> {
     front: Option<Handle<NodeRef<marker::Owned, K, V, marker::Leaf>, marker::Edge>>,
     back: Option<Handle<NodeRef<marker::Owned, K, V, marker::Leaf>, marker::Edge>>,
     length: usize,
+    alloc: A,
 }
 
-impl<K, V> IntoIter<K, V> {
+impl<K, V, A: AllocRef + Clone> IntoIter<K, V, A> {
     /// Returns an iterator of references over the remaining items.
     #[inline]
     pub(super) fn iter(&self) -> Iter<'_, K, V> {
@@ -316,7 +351,7 @@ pub(super) fn iter(&self) -> Iter<'_, K, V> {
 }
 
 #[stable(feature = "collection_debug", since = "1.17.0")]
-impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for IntoIter<K, V> {
+impl<K: fmt::Debug, V: fmt::Debug, A: AllocRef + Clone> fmt::Debug for IntoIter<K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.iter()).finish()
     }
@@ -383,12 +418,16 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 ///
 /// [`into_keys`]: BTreeMap::into_keys
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-pub struct IntoKeys<K, V> {
-    inner: IntoIter<K, V>,
+pub struct IntoKeys<
+    K,
+    V,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: AllocRef + Clone = Global,
+> {
+    inner: IntoIter<K, V, A>,
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K: fmt::Debug, V> fmt::Debug for IntoKeys<K, V> {
+impl<K: fmt::Debug, V, A: AllocRef + Clone> fmt::Debug for IntoKeys<K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.inner.iter().map(|(key, _)| key)).finish()
     }
@@ -401,12 +440,16 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 ///
 /// [`into_values`]: BTreeMap::into_values
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-pub struct IntoValues<K, V> {
-    inner: IntoIter<K, V>,
+pub struct IntoValues<
+    K,
+    V,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: AllocRef + Clone = Global,
+> {
+    inner: IntoIter<K, V, A>,
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V: fmt::Debug> fmt::Debug for IntoValues<K, V> {
+impl<K, V: fmt::Debug, A: AllocRef + Clone> fmt::Debug for IntoValues<K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.inner.iter().map(|(_, val)| val)).finish()
     }
@@ -477,7 +520,33 @@ impl<K: Ord, V> BTreeMap<K, V> {
     #[stable(feature = "rust1", since = "1.0.0")]
     #[rustc_const_unstable(feature = "const_btree_new", issue = "71835")]
     pub const fn new() -> BTreeMap<K, V> {
-        BTreeMap { root: None, length: 0 }
+        BTreeMap { root: None, length: 0, alloc: ManuallyDrop::new(Global) }
+    }
+}
+
+impl<K: Ord, V, A: AllocRef + Clone> BTreeMap<K, V, A> {
+    /// Makes a new empty BTreeMap with the provided allocator.
+    ///
+    /// Does not allocate anything on its own.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new_in(System);
+    ///
+    /// // entries can now be inserted into the empty map
+    /// map.insert(1, "a");
+    /// ```
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn new_in(alloc: A) -> BTreeMap<K, V, A> {
+        BTreeMap { root: None, length: 0, alloc: ManuallyDrop::new(alloc) }
     }
 
     /// Clears the map, removing all elements.
@@ -496,7 +565,12 @@ pub const fn new() -> BTreeMap<K, V> {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn clear(&mut self) {
-        *self = BTreeMap::new();
+        // Keep the allocator in place and drop the nodes through a temporary map.
+        drop(BTreeMap {
+            root: self.root.take(),
+            length: mem::replace(&mut self.length, 0),
+            alloc: self.alloc.clone(),
+        });
     }
 
     /// Returns a reference to the value corresponding to the key.
@@ -601,11 +675,17 @@ pub fn first_key_value(&self) -> Option<(&K, &V)> {
     /// assert_eq!(*map.get(&2).unwrap(), "b");
     /// ```
     #[unstable(feature = "map_first_last", issue = "62924")]
-    pub fn first_entry(&mut self) -> Option<OccupiedEntry<'_, K, V>> {
+    pub fn first_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, A>> {
         let (map, dormant_map) = DormantMutRef::new(self);
+        let alloc = (*map.alloc).clone();
         let root_node = map.root.as_mut()?.borrow_mut();
         let kv = root_node.first_leaf_edge().right_kv().ok()?;
-        Some(OccupiedEntry { handle: kv.forget_node_type(), dormant_map, _marker: PhantomData })
+        Some(OccupiedEntry {
+            handle: kv.forget_node_type(),
+            dormant_map,
+            alloc,
+            _marker: PhantomData,
+        })
     }
 
     /// Removes and returns the first element in the map.
@@ -675,11 +755,17 @@ pub fn last_key_value(&self) -> Option<(&K, &V)> {
     /// assert_eq!(*map.get(&2).unwrap(), "last");
     /// ```
     #[unstable(feature = "map_first_last", issue = "62924")]
-    pub fn last_entry(&mut self) -> Option<OccupiedEntry<'_, K, V>> {
+    pub fn last_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, A>> {
         let (map, dormant_map) = DormantMutRef::new(self);
+        let alloc = (*map.alloc).clone();
         let root_node = map.root.as_mut()?.borrow_mut();
         let kv = root_node.last_leaf_edge().left_kv().ok()?;
-        Some(OccupiedEntry { handle: kv.forget_node_type(), dormant_map, _marker: PhantomData })
+        Some(OccupiedEntry {
+            handle: kv.forget_node_type(),
+            dormant_map,
+            alloc,
+            _marker: PhantomData,
+        })
     }
 
     /// Removes and returns the last element in the map.
@@ -802,6 +888,42 @@ pub fn insert(&mut self, key: K, value: V) -> Option<V> {
         }
     }
 
+    /// Tries to insert a key-value pair into the map, returning an error
+    /// instead of aborting if allocating a node fails.
+    ///
+    /// On success, the return value is the same as that of [`insert`].
+    /// On failure, the map is left unchanged and the key and value are dropped.
+    ///
+    /// [`insert`]: BTreeMap::insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// assert_eq!(map.try_insert(37, "a")?, None);
+    /// assert_eq!(map.try_insert(37, "b")?, Some("a"));
+    /// assert_eq!(map[&37], "b");
+    /// # Ok::<(), std::collections::TryReserveError>(())
+    /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, TryReserveError> {
+        if self.root.is_none() {
+            let reserve = NodeReserve::<K, V, A>::new((*self.alloc).clone());
+            reserve.try_reserve_root()?;
+            self.root = Some(node::Root::new_leaf(&reserve));
+        }
+        match self.entry(key) {
+            Occupied(mut entry) => Ok(Some(entry.insert(value))),
+            Vacant(entry) => {
+                entry.try_insert(value)?;
+                Ok(None)
+            }
+        }
+    }
+
     /// Removes a key from the map, returning the value at the key if the key
     /// was previously in the map.
     ///
@@ -854,11 +976,12 @@ pub fn remove_entry<Q: ?Sized>(&mut self, key: &Q) -> Option<(K, V)>
         Q: Ord,
     {
         let (map, dormant_map) = DormantMutRef::new(self);
+        let alloc = (*map.alloc).clone();
         let root_node = map.root.as_mut()?.borrow_mut();
         match search::search_tree(root_node, key) {
-            Found(handle) => {
-                Some(OccupiedEntry { handle, dormant_map, _marker: PhantomData }.remove_entry())
-            }
+            Found(handle) => Some(
+                OccupiedEntry { handle, dormant_map, alloc, _marker: PhantomData }.remove_entry(),
+            ),
             GoDown(_) => None,
         }
     }
@@ -928,10 +1051,11 @@ pub fn append(&mut self, other: &mut Self) {
             return;
         }
 
-        let self_iter = mem::take(self).into_iter();
-        let other_iter = mem::take(other).into_iter();
-        let root = BTreeMap::ensure_is_owned(&mut self.root);
-        root.append_from_sorted_iters(self_iter, other_iter, &mut self.length)
+        let alloc = (*self.alloc).clone();
+        let self_iter = mem::replace(self, Self::new_in(alloc.clone())).into_iter();
+        let other_iter = mem::replace(other, Self::new_in((*other.alloc).clone())).into_iter();
+        let root = BTreeMap::ensure_is_owned(&mut self.root, alloc.clone());
+        root.append_from_sorted_iters(self_iter, other_iter, &mut self.length, alloc)
     }
 
     /// Constructs a double-ended iterator over a sub-range of elements in the map.
@@ -1025,6 +1149,196 @@ pub fn range_mut<T: ?Sized, R>(&mut self, range: R) -> RangeMut<'_, K, V>
         }
     }
 
+    /// Returns a [`Cursor`] pointing at the gap before the smallest key
+    /// greater than the given bound.
+    ///
+    /// Passing `Bound::Included(x)` will return a cursor pointing to the
+    /// gap before the smallest key greater than or equal to `x`.
+    ///
+    /// Passing `Bound::Excluded(x)` will return a cursor pointing to the
+    /// gap before the smallest key greater than `x`.
+    ///
+    /// Passing `Bound::Unbounded` will return a cursor pointing to the
+    /// gap before the smallest key in the map.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    /// map.insert(4, "d");
+    ///
+    /// let cursor = map.lower_bound(Bound::Included(&2));
+    /// assert_eq!(cursor.peek_prev(), Some((&1, &"a")));
+    /// assert_eq!(cursor.peek_next(), Some((&2, &"b")));
+    ///
+    /// let cursor = map.lower_bound(Bound::Excluded(&2));
+    /// assert_eq!(cursor.peek_prev(), Some((&2, &"b")));
+    /// assert_eq!(cursor.peek_next(), Some((&3, &"c")));
+    ///
+    /// let cursor = map.lower_bound(Bound::Unbounded);
+    /// assert_eq!(cursor.peek_prev(), None);
+    /// assert_eq!(cursor.peek_next(), Some((&1, &"a")));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn lower_bound<Q: ?Sized>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        Cursor { current: self.root.as_ref().map(|root| root.reborrow().lower_bound(bound)) }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the gap before the smallest key
+    /// greater than the given bound.
+    ///
+    /// Passing `Bound::Included(x)` will return a cursor pointing to the
+    /// gap before the smallest key greater than or equal to `x`.
+    ///
+    /// Passing `Bound::Excluded(x)` will return a cursor pointing to the
+    /// gap before the smallest key greater than `x`.
+    ///
+    /// Passing `Bound::Unbounded` will return a cursor pointing to the
+    /// gap before the smallest key in the map.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut map: BTreeMap<_, _> = vec![(1, "a"), (2, "b"), (4, "d")].into_iter().collect();
+    ///
+    /// let mut cursor = map.lower_bound_mut(Bound::Included(&3));
+    /// assert_eq!(cursor.peek_next(), Some((&4, &mut "d")));
+    /// cursor.insert_after(3, "c");
+    /// assert_eq!(cursor.next(), Some((&3, &mut "c")));
+    /// assert_eq!(cursor.remove_next(), Some((4, "d")));
+    /// drop(cursor);
+    ///
+    /// assert_eq!(map.into_iter().collect::<Vec<_>>(), vec![(1, "a"), (2, "b"), (3, "c")]);
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn lower_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V, A>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let alloc = (*self.alloc).clone();
+        let (root, dormant_root) = DormantMutRef::new(&mut self.root);
+        let current = root.as_mut().map(|root| root.borrow_mut().lower_bound(bound));
+        CursorMut { current, root: dormant_root, length: &mut self.length, alloc }
+    }
+
+    /// Returns a [`Cursor`] pointing at the gap after the greatest key
+    /// smaller than the given bound.
+    ///
+    /// Passing `Bound::Included(x)` will return a cursor pointing to the
+    /// gap after the greatest key smaller than or equal to `x`.
+    ///
+    /// Passing `Bound::Excluded(x)` will return a cursor pointing to the
+    /// gap after the greatest key smaller than `x`.
+    ///
+    /// Passing `Bound::Unbounded` will return a cursor pointing to the
+    /// gap after the greatest key in the map.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    /// map.insert(4, "d");
+    ///
+    /// let cursor = map.upper_bound(Bound::Included(&3));
+    /// assert_eq!(cursor.peek_prev(), Some((&3, &"c")));
+    /// assert_eq!(cursor.peek_next(), Some((&4, &"d")));
+    ///
+    /// let cursor = map.upper_bound(Bound::Excluded(&3));
+    /// assert_eq!(cursor.peek_prev(), Some((&2, &"b")));
+    /// assert_eq!(cursor.peek_next(), Some((&3, &"c")));
+    ///
+    /// let cursor = map.upper_bound(Bound::Unbounded);
+    /// assert_eq!(cursor.peek_prev(), Some((&4, &"d")));
+    /// assert_eq!(cursor.peek_next(), None);
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn upper_bound<Q: ?Sized>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        Cursor { current: self.root.as_ref().map(|root| root.reborrow().upper_bound(bound)) }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the gap after the greatest key
+    /// smaller than the given bound.
+    ///
+    /// Passing `Bound::Included(x)` will return a cursor pointing to the
+    /// gap after the greatest key smaller than or equal to `x`.
+    ///
+    /// Passing `Bound::Excluded(x)` will return a cursor pointing to the
+    /// gap after the greatest key smaller than `x`.
+    ///
+    /// Passing `Bound::Unbounded` will return a cursor pointing to the
+    /// gap after the greatest key in the map.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut map: BTreeMap<_, _> = vec![(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+    ///
+    /// let mut cursor = map.upper_bound_mut(Bound::Unbounded);
+    /// cursor.insert_before(4, "d");
+    /// assert_eq!(cursor.prev(), Some((&4, &mut "d")));
+    /// if let Some((_, v)) = cursor.prev() {
+    ///     *v = "C";
+    /// }
+    /// assert_eq!(cursor.remove_prev(), Some((2, "b")));
+    /// drop(cursor);
+    ///
+    /// assert_eq!(map.into_iter().collect::<Vec<_>>(), vec![(1, "a"), (3, "C"), (4, "d")]);
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn upper_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V, A>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let alloc = (*self.alloc).clone();
+        let (root, dormant_root) = DormantMutRef::new(&mut self.root);
+        let current = root.as_mut().map(|root| root.borrow_mut().upper_bound(bound));
+        CursorMut { current, root: dormant_root, length: &mut self.length, alloc }
+    }
+
     /// Gets the given key's corresponding entry in the map for in-place manipulation.
     ///
     /// # Examples
@@ -1044,14 +1358,17 @@ pub fn range_mut<T: ?Sized, R>(&mut self, range: R) -> RangeMut<'_, K, V>
     /// assert_eq!(count["a"], 3);
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, A> {
         // FIXME(@porglezomp) Avoid allocating if we don't insert
         let (map, dormant_map) = DormantMutRef::new(self);
-        let root_node = Self::ensure_is_owned(&mut map.root).borrow_mut();
+        let alloc = (*map.alloc).clone();
+        let root_node = Self::ensure_is_owned(&mut map.root, alloc.clone()).borrow_mut();
         match search::search_tree(root_node, &key) {
-            Found(handle) => Occupied(OccupiedEntry { handle, dormant_map, _marker: PhantomData }),
+            Found(handle) => {
+                Occupied(OccupiedEntry { handle, dormant_map, alloc, _marker: PhantomData })
+            }
             GoDown(handle) => {
-                Vacant(VacantEntry { key, handle, dormant_map, _marker: PhantomData })
+                Vacant(VacantEntry { key, handle, dormant_map, alloc, _marker: PhantomData })
             }
         }
     }
@@ -1091,16 +1408,17 @@ pub fn split_off<Q: ?Sized + Ord>(&mut self, key: &Q) -> Self
         K: Borrow<Q>,
     {
         if self.is_empty() {
-            return Self::new();
+            return Self::new_in((*self.alloc).clone());
         }
 
         let total_num = self.len();
+        let alloc = (*self.alloc).clone();
         let left_root = self.root.as_mut().unwrap(); // unwrap succeeds because not empty
 
-        let mut right = Self::new();
-        let right_root = Self::ensure_is_owned(&mut right.root);
+        let mut right = Self::new_in(alloc.clone());
+        let right_root = Self::ensure_is_owned(&mut right.root, alloc.clone());
 
-        left_root.split_off(right_root, key);
+        left_root.split_off(right_root, key, alloc);
 
         if left_root.height() < right_root.height() {
             self.length = left_root.reborrow().calc_length();
@@ -1144,14 +1462,15 @@ pub fn split_off<Q: ?Sized + Ord>(&mut self, key: &Q) -> Self
     /// assert_eq!(odds.keys().copied().collect::<Vec<_>>(), vec![1, 3, 5, 7]);
     /// ```
     #[unstable(feature = "btree_drain_filter", issue = "70530")]
-    pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<'_, K, V, F>
+    pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<'_, K, V, F, A>
     where
         F: FnMut(&K, &mut V) -> bool,
     {
         DrainFilter { pred, inner: self.drain_filter_inner() }
     }
 
-    pub(super) fn drain_filter_inner(&mut self) -> DrainFilterInner<'_, K, V> {
+    pub(super) fn drain_filter_inner(&mut self) -> DrainFilterInner<'_, K, V, A> {
+        let alloc = (*self.alloc).clone();
         if let Some(root) = self.root.as_mut() {
             let (root, dormant_root) = DormantMutRef::new(root);
             let front = root.borrow_mut().first_leaf_edge();
@@ -1159,9 +1478,15 @@ pub(super) fn drain_filter_inner(&mut self) -> DrainFilterInner<'_, K, V> {
                 length: &mut self.length,
                 dormant_root: Some(dormant_root),
                 cur_leaf_edge: Some(front),
+                alloc,
             }
         } else {
-            DrainFilterInner { length: &mut self.length, dormant_root: None, cur_leaf_edge: None }
+            DrainFilterInner {
+                length: &mut self.length,
+                dormant_root: None,
+                cur_leaf_edge: None,
+                alloc,
+            }
         }
     }
 
@@ -1184,7 +1509,7 @@ pub(super) fn drain_filter_inner(&mut self) -> DrainFilterInner<'_, K, V> {
     /// ```
     #[inline]
     #[unstable(feature = "map_into_keys_values", issue = "75294")]
-    pub fn into_keys(self) -> IntoKeys<K, V> {
+    pub fn into_keys(self) -> IntoKeys<K, V, A> {
         IntoKeys { inner: self.into_iter() }
     }
 
@@ -1207,13 +1532,13 @@ pub fn into_keys(self) -> IntoKeys<K, V> {
     /// ```
     #[inline]
     #[unstable(feature = "map_into_keys_values", issue = "75294")]
-    pub fn into_values(self) -> IntoValues<K, V> {
+    pub fn into_values(self) -> IntoValues<K, V, A> {
         IntoValues { inner: self.into_iter() }
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, K, V> IntoIterator for &'a BTreeMap<K, V> {
+impl<'a, K, V, A: AllocRef + Clone> IntoIterator for &'a BTreeMap<K, V, A> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V>;
 
@@ -1282,7 +1607,7 @@ fn clone(&self) -> Self {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, K, V> IntoIterator for &'a mut BTreeMap<K, V> {
+impl<'a, K, V, A: AllocRef + Clone> IntoIterator for &'a mut BTreeMap<K, V, A> {
     type Item = (&'a K, &'a mut V);
     type IntoIter = IterMut<'a, K, V>;
 
@@ -1354,28 +1679,30 @@ pub(super) fn iter(&self) -> Iter<'_, K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V> IntoIterator for BTreeMap<K, V> {
+impl<K, V, A: AllocRef + Clone> IntoIterator for BTreeMap<K, V, A> {
     type Item = (K, V);
-    type IntoIter = IntoIter<K, V>;
+    type IntoIter = IntoIter<K, V, A>;
 
-    fn into_iter(self) -> IntoIter<K, V> {
+    fn into_iter(self) -> IntoIter<K, V, A> {
         let mut me = ManuallyDrop::new(self);
+        // SAFETY: `me` is never dropped, so the allocator is moved out only once.
+        let alloc = unsafe { ManuallyDrop::take(&mut me.alloc) };
         if let Some(root) = me.root.take() {
             let (f, b) = root.full_range();
 
-            IntoIter { front: Some(f), back: Some(b), length: me.length }
+            IntoIter { front: Some(f), back: Some(b), length: me.length, alloc }
         } else {
-            IntoIter { front: None, back: None, length: 0 }
+            IntoIter { front: None, back: None, length: 0, alloc }
         }
     }
 }
 
 #[stable(feature = "btree_drop", since = "1.7.0")]
-impl<K, V> Drop for IntoIter<K, V> {
+impl<K, V, A: AllocRef + Clone> Drop for IntoIter<K, V, A> {
     fn drop(&mut self) {
-        struct DropGuard<'a, K, V>(&'a mut IntoIter<K, V>);
+        struct DropGuard<'a, K, V, A: AllocRef + Clone>(&'a mut IntoIter<K, V, A>);
 
-        impl<'a, K, V> Drop for DropGuard<'a, K, V> {
+        impl<'a, K, V, A: AllocRef + Clone> Drop for DropGuard<'a, K, V, A> {
             fn drop(&mut self) {
                 // Continue the same loop we perform below. This only runs when unwinding, so we
                 // don't have to care about panics this time (they'll abort).
@@ -1384,7 +1711,7 @@ fn drop(&mut self) {
                 unsafe {
                     let mut node =
                         unwrap_unchecked(ptr::read(&self.0.front)).into_node().forget_type();
-                    while let Some(parent) = node.deallocate_and_ascend() {
+                    while let Some(parent) = node.deallocate_and_ascend(self.0.alloc.clone()) {
                         node = parent.into_node().forget_type();
                     }
                 }
@@ -1402,7 +1729,7 @@ fn drop(&mut self) {
                 let mut node = front.into_node().forget_type();
                 // Most of the nodes have been deallocated while traversing
                 // but one pile from a leaf up to the root is left standing.
-                while let Some(parent) = node.deallocate_and_ascend() {
+                while let Some(parent) = node.deallocate_and_ascend(self.alloc.clone()) {
                     node = parent.into_node().forget_type();
                 }
             }
@@ -1411,7 +1738,7 @@ fn drop(&mut self) {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V> Iterator for IntoIter<K, V> {
+impl<K, V, A: AllocRef + Clone> Iterator for IntoIter<K, V, A> {
     type Item = (K, V);
 
     fn next(&mut self) -> Option<(K, V)> {
@@ -1419,7 +1746,7 @@ fn next(&mut self) -> Option<(K, V)> {
             None
         } else {
             self.length -= 1;
-            Some(unsafe { self.front.as_mut().unwrap().next_unchecked() })
+            Some(unsafe { self.front.as_mut().unwrap().next_unchecked(self.alloc.clone()) })
         }
     }
 
@@ -1429,26 +1756,26 @@ fn size_hint(&self) -> (usize, Option<usize>) {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+impl<K, V, A: AllocRef + Clone> DoubleEndedIterator for IntoIter<K, V, A> {
     fn next_back(&mut self) -> Option<(K, V)> {
         if self.length == 0 {
             None
         } else {
             self.length -= 1;
-            Some(unsafe { self.back.as_mut().unwrap().next_back_unchecked() })
+            Some(unsafe { self.back.as_mut().unwrap().next_back_unchecked(self.alloc.clone()) })
         }
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V> ExactSizeIterator for IntoIter<K, V> {
+impl<K, V, A: AllocRef + Clone> ExactSizeIterator for IntoIter<K, V, A> {
     fn len(&self) -> usize {
         self.length
     }
 }
 
 #[stable(feature = "fused", since = "1.26.0")]
-impl<K, V> FusedIterator for IntoIter<K, V> {}
+impl<K, V, A: AllocRef + Clone> FusedIterator for IntoIter<K, V, A> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<'a, K, V> Iterator for Keys<'a, K, V> {
@@ -1542,18 +1869,23 @@ fn clone(&self) -> Self {
 
 /// An iterator produced by calling `drain_filter` on BTreeMap.
 #[unstable(feature = "btree_drain_filter", issue = "70530")]
-pub struct DrainFilter<'a, K, V, F>
-where
+pub struct DrainFilter<
+    'a,
+    K,
+    V,
+    F,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: AllocRef + Clone = Global,
+> where
     K: 'a,
     V: 'a,
     F: 'a + FnMut(&K, &mut V) -> bool,
 {
     pred: F,
-    inner: DrainFilterInner<'a, K, V>,
+    inner: DrainFilterInner<'a, K, V, A>,
 }
 /// Most of the implementation of DrainFilter, independent of the type
 /// of the predicate, thus also serving for BTreeSet::DrainFilter.
-pub(super) struct DrainFilterInner<'a, K: 'a, V: 'a> {
+pub(super) struct DrainFilterInner<'a, K: 'a, V: 'a, A: AllocRef + Clone = Global> {
     /// Reference to the length field in the borrowed map, updated live.
     length: &'a mut usize,
     /// Burried reference to the root field in the borrowed map.
@@ -1563,10 +1895,12 @@ pub(super) struct DrainFilterInner<'a, K: 'a, V: 'a> {
     /// Empty if the map has no root, if iteration went beyond the last leaf edge,
     /// or if a panic occurred in the predicate.
     cur_leaf_edge: Option<Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>>,
+    /// Allocator of the borrowed map, used when removal shrinks the tree.
+    alloc: A,
 }
 
 #[unstable(feature = "btree_drain_filter", issue = "70530")]
-impl<K, V, F> Drop for DrainFilter<'_, K, V, F>
+impl<K, V, F, A: AllocRef + Clone> Drop for DrainFilter<'_, K, V, F, A>
 where
     F: FnMut(&K, &mut V) -> bool,
 {
@@ -1576,7 +1910,7 @@ fn drop(&mut self) {
 }
 
 #[unstable(feature = "btree_drain_filter", issue = "70530")]
-impl<K, V, F> fmt::Debug for DrainFilter<'_, K, V, F>
+impl<K, V, F, A: AllocRef + Clone> fmt::Debug for DrainFilter<'_, K, V, F, A>
 where
     K: fmt::Debug,
     V: fmt::Debug,
@@ -1588,7 +1922,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 }
 
 #[unstable(feature = "btree_drain_filter", issue = "70530")]
-impl<K, V, F> Iterator for DrainFilter<'_, K, V, F>
+impl<K, V, F, A: AllocRef + Clone> Iterator for DrainFilter<'_, K, V, F, A>
 where
     F: FnMut(&K, &mut V) -> bool,
 {
@@ -1603,7 +1937,7 @@ fn size_hint(&self) -> (usize, Option<usize>) {
     }
 }
 
-impl<'a, K: 'a, V: 'a> DrainFilterInner<'a, K, V> {
+impl<'a, K: 'a, V: 'a, A: AllocRef + Clone> DrainFilterInner<'a, K, V, A> {
     /// Allow Debug implementations to predict the next element.
     pub(super) fn peek(&self) -> Option<(&K, &V)> {
         let edge = self.cur_leaf_edge.as_ref()?;
@@ -1619,13 +1953,17 @@ pub(super) fn next<F>(&mut self, pred: &mut F) -> Option<(K, V)>
             let (k, v) = kv.kv_mut();
             if pred(k, v) {
                 *self.length -= 1;
-                let (kv, pos) = kv.remove_kv_tracking(|| {
-                    // SAFETY: we will touch the root in a way that will not
-                    // invalidate the position returned.
-                    let root = unsafe { self.dormant_root.take().unwrap().awaken() };
-                    root.pop_internal_level();
-                    self.dormant_root = Some(DormantMutRef::new(root).1);
-                });
+                let alloc = self.alloc.clone();
+                let (kv, pos) = kv.remove_kv_tracking(
+                    || {
+                        // SAFETY: we will touch the root in a way that will not
+                        // invalidate the position returned.
+                        let root = unsafe { self.dormant_root.take().unwrap().awaken() };
+                        root.pop_internal_level(alloc.clone());
+                        self.dormant_root = Some(DormantMutRef::new(root).1);
+                    },
+                    alloc.clone(),
+                );
                 self.cur_leaf_edge = Some(pos);
                 return Some(kv);
             }
@@ -1645,7 +1983,10 @@ pub(super) fn size_hint(&self) -> (usize, Option<usize>) {
 }
 
 #[unstable(feature = "btree_drain_filter", issue = "70530")]
-impl<K, V, F> FusedIterator for DrainFilter<'_, K, V, F> where F: FnMut(&K, &mut V) -> bool {}
+impl<K, V, F, A: AllocRef + Clone> FusedIterator for DrainFilter<'_, K, V, F, A> where
+    F: FnMut(&K, &mut V) -> bool
+{
+}
 
 #[stable(feature = "btree_range", since = "1.17.0")]
 impl<'a, K, V> Iterator for Range<'a, K, V> {
@@ -1713,7 +2054,7 @@ unsafe fn next_unchecked(&mut self) -> (&'a K, &'a V) {
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V> Iterator for IntoKeys<K, V> {
+impl<K, V, A: AllocRef + Clone> Iterator for IntoKeys<K, V, A> {
     type Item = K;
 
     fn next(&mut self) -> Option<K> {
@@ -1738,24 +2079,24 @@ fn max(mut self) -> Option<K> {
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V> DoubleEndedIterator for IntoKeys<K, V> {
+impl<K, V, A: AllocRef + Clone> DoubleEndedIterator for IntoKeys<K, V, A> {
     fn next_back(&mut self) -> Option<K> {
         self.inner.next_back().map(|(k, _)| k)
     }
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V> ExactSizeIterator for IntoKeys<K, V> {
+impl<K, V, A: AllocRef + Clone> ExactSizeIterator for IntoKeys<K, V, A> {
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V> FusedIterator for IntoKeys<K, V> {}
+impl<K, V, A: AllocRef + Clone> FusedIterator for IntoKeys<K, V, A> {}
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V> Iterator for IntoValues<K, V> {
+impl<K, V, A: AllocRef + Clone> Iterator for IntoValues<K, V, A> {
     type Item = V;
 
     fn next(&mut self) -> Option<V> {
@@ -1772,21 +2113,21 @@ fn last(mut self) -> Option<V> {
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V> DoubleEndedIterator for IntoValues<K, V> {
+impl<K, V, A: AllocRef + Clone> DoubleEndedIterator for IntoValues<K, V, A> {
     fn next_back(&mut self) -> Option<V> {
         self.inner.next_back().map(|(_, v)| v)
     }
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V> ExactSizeIterator for IntoValues<K, V> {
+impl<K, V, A: AllocRef + Clone> ExactSizeIterator for IntoValues<K, V, A> {
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V> FusedIterator for IntoValues<K, V> {}
+impl<K, V, A: AllocRef + Clone> FusedIterator for IntoValues<K, V, A> {}
 
 #[stable(feature = "btree_range", since = "1.17.0")]
 impl<'a, K, V> DoubleEndedIterator for Range<'a, K, V> {
@@ -1887,7 +2228,7 @@ fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> BTreeMap<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Ord, V> Extend<(K, V)> for BTreeMap<K, V> {
+impl<K: Ord, V, A: AllocRef + Clone> Extend<(K, V)> for BTreeMap<K, V, A> {
     #[inline]
     fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
         iter.into_iter().for_each(move |(k, v)| {
@@ -1902,7 +2243,7 @@ fn extend_one(&mut self, (k, v): (K, V)) {
 }
 
 #[stable(feature = "extend_ref", since = "1.2.0")]
-impl<'a, K: Ord + Copy, V: Copy> Extend<(&'a K, &'a V)> for BTreeMap<K, V> {
+impl<'a, K: Ord + Copy, V: Copy, A: AllocRef + Clone> Extend<(&'a K, &'a V)> for BTreeMap<K, V, A> {
     fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
         self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
     }
@@ -1914,7 +2255,7 @@ fn extend_one(&mut self, (&k, &v): (&'a K, &'a V)) {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Hash, V: Hash> Hash for BTreeMap<K, V> {
+impl<K: Hash, V: Hash, A: AllocRef + Clone> Hash for BTreeMap<K, V, A> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         for elt in self {
             elt.hash(state);
@@ -1931,40 +2272,40 @@ fn default() -> BTreeMap<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: PartialEq, V: PartialEq> PartialEq for BTreeMap<K, V> {
-    fn eq(&self, other: &BTreeMap<K, V>) -> bool {
+impl<K: PartialEq, V: PartialEq, A: AllocRef + Clone> PartialEq for BTreeMap<K, V, A> {
+    fn eq(&self, other: &BTreeMap<K, V, A>) -> bool {
         self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Eq, V: Eq> Eq for BTreeMap<K, V> {}
+impl<K: Eq, V: Eq, A: AllocRef + Clone> Eq for BTreeMap<K, V, A> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: PartialOrd, V: PartialOrd> PartialOrd for BTreeMap<K, V> {
+impl<K: PartialOrd, V: PartialOrd, A: AllocRef + Clone> PartialOrd for BTreeMap<K, V, A> {
     #[inline]
-    fn partial_cmp(&self, other: &BTreeMap<K, V>) -> Option<Ordering> {
+    fn partial_cmp(&self, other: &BTreeMap<K, V, A>) -> Option<Ordering> {
         self.iter().partial_cmp(other.iter())
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Ord, V: Ord> Ord for BTreeMap<K, V> {
+impl<K: Ord, V: Ord, A: AllocRef + Clone> Ord for BTreeMap<K, V, A> {
     #[inline]
-    fn cmp(&self, other: &BTreeMap<K, V>) -> Ordering {
+    fn cmp(&self, other: &BTreeMap<K, V, A>) -> Ordering {
         self.iter().cmp(other.iter())
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Debug, V: Debug> Debug for BTreeMap<K, V> {
+impl<K: Debug, V: Debug, A: AllocRef + Clone> Debug for BTreeMap<K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_map().entries(self.iter()).finish()
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Ord, Q: ?Sized, V> Index<&Q> for BTreeMap<K, V>
+impl<K: Ord, Q: ?Sized, V, A: AllocRef + Clone> Index<&Q> for BTreeMap<K, V, A>
 where
     K: Borrow<Q>,
     Q: Ord,
@@ -1982,7 +2323,7 @@ fn index(&self, key: &Q) -> &V {
     }
 }
 
-impl<K, V> BTreeMap<K, V> {
+impl<K, V, A: AllocRef + Clone> BTreeMap<K, V, A> {
     /// Gets an iterator over the entries of the map, sorted by key.
     ///
     /// # Examples
@@ -2160,8 +2501,8 @@ pub const fn is_empty(&self) -> bool {
 
     /// If the root node is the empty (non-allocated) root node, allocate our
     /// own node. Is an associated function to avoid borrowing the entire BTreeMap.
-    fn ensure_is_owned(root: &mut Option<node::Root<K, V>>) -> &mut node::Root<K, V> {
-        root.get_or_insert_with(node::Root::new_leaf)
+    fn ensure_is_owned(root: &mut Option<node::Root<K, V>>, alloc: A) -> &mut node::Root<K, V> {
+        root.get_or_insert_with(|| node::Root::new_leaf(alloc))
     }
 }
 
@@ -0,0 +1,401 @@
+use core::fmt::{self, Debug};
+
+use crate::alloc::{AllocRef, Global};
+
+use super::super::borrow::DormantMutRef;
+use super::super::node::{self, marker, Handle, InsertResult::*, NodeRef};
+
+/// A cursor over a `BTreeMap`.
+///
+/// A `Cursor` is like an iterator, except that it can freely seek back-and-forth.
+///
+/// Cursors always point to a gap between two elements in the map, and can
+/// operate on the two immediately adjacent elements.
+///
+/// A `Cursor` is created with the [`BTreeMap::lower_bound`] and [`BTreeMap::upper_bound`]
+/// methods.
+///
+/// [`BTreeMap::lower_bound`]: super::BTreeMap::lower_bound
+/// [`BTreeMap::upper_bound`]: super::BTreeMap::upper_bound
+#[unstable(feature = "btree_cursors", issue = "none")]
+pub struct Cursor<'a, K: 'a, V: 'a> {
+    // If `current` is `None`, the map is empty and has no root node.
+    pub(super) current:
+        Option<Handle<NodeRef<marker::Immut<'a>, K, V, marker::Leaf>, marker::Edge>>,
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<K, V> Clone for Cursor<'_, K, V> {
+    fn clone(&self) -> Self {
+        Cursor { current: self.current }
+    }
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<K: Debug, V: Debug> Debug for Cursor<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Cursor").field(&self.peek_prev()).field(&self.peek_next()).finish()
+    }
+}
+
+/// A cursor over a `BTreeMap` with editing operations.
+///
+/// A `Cursor` is like an iterator, except that it can freely seek back-and-forth, and can
+/// safely mutate the map during iteration. This is because the lifetime of its yielded
+/// references is tied to its own lifetime, instead of just the underlying map. This means
+/// cursors cannot yield multiple elements at once.
+///
+/// Cursors always point to a gap between two elements in the map, and can
+/// operate on the two immediately adjacent elements.
+///
+/// A `CursorMut` is created with the [`BTreeMap::lower_bound_mut`] and
+/// [`BTreeMap::upper_bound_mut`] methods.
+///
+/// [`BTreeMap::lower_bound_mut`]: super::BTreeMap::lower_bound_mut
+/// [`BTreeMap::upper_bound_mut`]: super::BTreeMap::upper_bound_mut
+#[unstable(feature = "btree_cursors", issue = "none")]
+pub struct CursorMut<
+    'a,
+    K: 'a,
+    V: 'a,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: AllocRef + Clone = Global,
+> {
+    // If `current` is `None`, the map is empty and has no root node.
+    pub(super) current: Option<Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>>,
+    pub(super) root: DormantMutRef<'a, Option<node::Root<K, V>>>,
+    pub(super) length: &'a mut usize,
+    pub(super) alloc: A,
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<K: Debug, V: Debug, A: AllocRef + Clone> Debug for CursorMut<'_, K, V, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let current = self.current.as_ref().map(|edge| edge.reborrow());
+        let cursor = Cursor { current };
+        f.debug_tuple("CursorMut").field(&cursor.peek_prev()).field(&cursor.peek_next()).finish()
+    }
+}
+
+impl<'a, K, V> Cursor<'a, K, V> {
+    /// Advances the cursor to the next gap, returning the key and value of the
+    /// element that it moved over.
+    ///
+    /// If the cursor is already at the end of the map then `None` is returned
+    /// and the cursor is not moved.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        let current = self.current.take()?;
+        match current.next_kv() {
+            Ok(kv) => {
+                self.current = Some(kv.next_leaf_edge());
+                Some(kv.into_kv())
+            }
+            Err(root) => {
+                self.current = Some(root.last_leaf_edge());
+                None
+            }
+        }
+    }
+
+    /// Advances the cursor to the previous gap, returning the key and value of
+    /// the element that it moved over.
+    ///
+    /// If the cursor is already at the start of the map then `None` is returned
+    /// and the cursor is not moved.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn prev(&mut self) -> Option<(&'a K, &'a V)> {
+        let current = self.current.take()?;
+        match current.next_back_kv() {
+            Ok(kv) => {
+                self.current = Some(kv.next_back_leaf_edge());
+                Some(kv.into_kv())
+            }
+            Err(root) => {
+                self.current = Some(root.first_leaf_edge());
+                None
+            }
+        }
+    }
+
+    /// Returns a reference to the key and value of the next element without
+    /// moving the cursor.
+    ///
+    /// If the cursor is at the end of the map then `None` is returned.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        self.current?.next_kv().ok().map(Handle::into_kv)
+    }
+
+    /// Returns a reference to the key and value of the previous element
+    /// without moving the cursor.
+    ///
+    /// If the cursor is at the start of the map then `None` is returned.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        self.current?.next_back_kv().ok().map(Handle::into_kv)
+    }
+}
+
+impl<'a, K, V, A: AllocRef + Clone> CursorMut<'a, K, V, A> {
+    /// Advances the cursor to the next gap, returning the key and value of the
+    /// element that it moved over.
+    ///
+    /// If the cursor is already at the end of the map then `None` is returned
+    /// and the cursor is not moved.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn next(&mut self) -> Option<(&K, &mut V)> {
+        let current = self.current.take()?;
+        match current.next_kv() {
+            Ok(mut kv) => {
+                let (k, v) = kv.kv_mut();
+                let (k, v): (*const K, *mut V) = (k, v);
+                self.current = Some(kv.next_leaf_edge());
+                // SAFETY: the element stays where it is when the cursor moves on,
+                // and the returned references keep the cursor borrowed.
+                Some(unsafe { (&*k, &mut *v) })
+            }
+            Err(root) => {
+                self.current = Some(root.last_leaf_edge());
+                None
+            }
+        }
+    }
+
+    /// Advances the cursor to the previous gap, returning the key and value of
+    /// the element that it moved over.
+    ///
+    /// If the cursor is already at the start of the map then `None` is returned
+    /// and the cursor is not moved.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn prev(&mut self) -> Option<(&K, &mut V)> {
+        let current = self.current.take()?;
+        match current.next_back_kv() {
+            Ok(mut kv) => {
+                let (k, v) = kv.kv_mut();
+                let (k, v): (*const K, *mut V) = (k, v);
+                self.current = Some(kv.next_back_leaf_edge());
+                // SAFETY: the element stays where it is when the cursor moves on,
+                // and the returned references keep the cursor borrowed.
+                Some(unsafe { (&*k, &mut *v) })
+            }
+            Err(root) => {
+                self.current = Some(root.first_leaf_edge());
+                None
+            }
+        }
+    }
+
+    /// Returns a reference to the key and value of the next element without
+    /// moving the cursor.
+    ///
+    /// If the cursor is at the end of the map then `None` is returned.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_next(&mut self) -> Option<(&K, &mut V)> {
+        let current = self.current.as_mut()?;
+        let kv = unsafe { current.reborrow_mut() }.next_kv().ok()?;
+        let (k, v) = kv.into_kv_mut();
+        Some((&*k, v))
+    }
+
+    /// Returns a reference to the key and value of the previous element
+    /// without moving the cursor.
+    ///
+    /// If the cursor is at the start of the map then `None` is returned.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_prev(&mut self) -> Option<(&K, &mut V)> {
+        let current = self.current.as_mut()?;
+        let kv = unsafe { current.reborrow_mut() }.next_back_kv().ok()?;
+        let (k, v) = kv.into_kv_mut();
+        Some((&*k, v))
+    }
+
+    /// Returns a read-only cursor pointing to the same location as the
+    /// `CursorMut`.
+    ///
+    /// The lifetime of the returned `Cursor` is bound to that of the
+    /// `CursorMut`, which means it cannot outlive the `CursorMut` and that the
+    /// `CursorMut` is frozen for the lifetime of the `Cursor`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn as_cursor(&self) -> Cursor<'_, K, V> {
+        Cursor { current: self.current.as_ref().map(|edge| edge.reborrow()) }
+    }
+
+    /// Inserts a new element into the map in the gap that the cursor is
+    /// currently pointing to.
+    ///
+    /// After the insertion the cursor will be pointing at the gap before the
+    /// newly inserted element.
+    ///
+    /// # Safety
+    ///
+    /// You must ensure that the `BTreeMap` invariants are maintained.
+    /// Specifically:
+    ///
+    /// * The key of the newly inserted element must be unique in the tree.
+    /// * All keys in the tree must remain in sorted order.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub unsafe fn insert_after_unchecked(&mut self, key: K, value: V) {
+        let inserted = self.insert_unchecked(key, value);
+        self.current = Some(inserted.left_edge());
+    }
+
+    /// Inserts a new element into the map in the gap that the cursor is
+    /// currently pointing to.
+    ///
+    /// After the insertion the cursor will be pointing at the gap after the
+    /// newly inserted element.
+    ///
+    /// # Safety
+    ///
+    /// You must ensure that the `BTreeMap` invariants are maintained.
+    /// Specifically:
+    ///
+    /// * The key of the newly inserted element must be unique in the tree.
+    /// * All keys in the tree must remain in sorted order.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub unsafe fn insert_before_unchecked(&mut self, key: K, value: V) {
+        let inserted = self.insert_unchecked(key, value);
+        self.current = Some(inserted.right_edge());
+    }
+
+    /// Inserts a new element into the map in the gap that the cursor is
+    /// currently pointing to.
+    ///
+    /// After the insertion the cursor will be pointing at the gap before the
+    /// newly inserted element.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if:
+    /// - the given key compares less than or equal to the previous element (if
+    ///   any).
+    /// - the given key compares greater than or equal to the next element (if
+    ///   any).
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn insert_after(&mut self, key: K, value: V)
+    where
+        K: Ord,
+    {
+        self.check_gap(&key);
+        unsafe { self.insert_after_unchecked(key, value) }
+    }
+
+    /// Inserts a new element into the map in the gap that the cursor is
+    /// currently pointing to.
+    ///
+    /// After the insertion the cursor will be pointing at the gap after the
+    /// newly inserted element.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if:
+    /// - the given key compares greater than or equal to the next element (if
+    ///   any).
+    /// - the given key compares less than or equal to the previous element (if
+    ///   any).
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn insert_before(&mut self, key: K, value: V)
+    where
+        K: Ord,
+    {
+        self.check_gap(&key);
+        unsafe { self.insert_before_unchecked(key, value) }
+    }
+
+    /// Removes the next element from the `BTreeMap`.
+    ///
+    /// The element that was removed is returned. The cursor position is
+    /// unchanged (before the removed element).
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn remove_next(&mut self) -> Option<(K, V)> {
+        let current = self.current.take()?;
+        match current.next_kv() {
+            Ok(kv) => Some(self.remove_kv(kv)),
+            Err(root) => {
+                self.current = Some(root.last_leaf_edge());
+                None
+            }
+        }
+    }
+
+    /// Removes the preceding element from the `BTreeMap`.
+    ///
+    /// The element that was removed is returned. The cursor position is
+    /// unchanged (after the removed element).
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn remove_prev(&mut self) -> Option<(K, V)> {
+        let current = self.current.take()?;
+        match current.next_back_kv() {
+            Ok(kv) => Some(self.remove_kv(kv)),
+            Err(root) => {
+                self.current = Some(root.first_leaf_edge());
+                None
+            }
+        }
+    }
+
+    /// Panics unless `key` sorts strictly between the elements around the gap.
+    fn check_gap(&mut self, key: &K)
+    where
+        K: Ord,
+    {
+        if let Some((prev, _)) = self.peek_prev() {
+            if key <= prev {
+                panic!("key must be ordered above the previous element");
+            }
+        }
+        if let Some((next, _)) = self.peek_next() {
+            if key >= next {
+                panic!("key must be ordered below the next element");
+            }
+        }
+    }
+
+    /// Inserts a pair into the gap and grows the tree as needed, returning a handle
+    /// to the new pair. The cursor is left empty for the caller to reposition.
+    fn insert_unchecked(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
+        let edge = match self.current.take() {
+            Some(edge) => edge,
+            None => {
+                // SAFETY: the map is empty, so nothing else refers into the tree.
+                let root = unsafe { self.root.reborrow() };
+                let alloc = &self.alloc;
+                root.get_or_insert_with(|| node::Root::new_leaf(alloc.clone()))
+                    .borrow_mut()
+                    .first_leaf_edge()
+            }
+        };
+        let (result, inserted) = edge.insert_recursing(key, value, self.alloc.clone());
+        if let Split(ins) = result {
+            drop(ins.left);
+            // SAFETY: we have consumed the edge and the handles derived from it,
+            // except for `inserted`, which we only use after growing the tree.
+            let root = unsafe { self.root.reborrow() }.as_mut().unwrap();
+            root.push_internal_level(self.alloc.clone()).push(ins.kv.0, ins.kv.1, ins.right);
+        }
+        *self.length += 1;
+        inserted
+    }
+
+    /// Removes the pair at `kv`, leaving the cursor in the gap it collapsed into.
+    fn remove_kv(
+        &mut self,
+        kv: Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV>,
+    ) -> (K, V) {
+        let mut emptied_internal_root = false;
+        let (old_kv, pos) =
+            kv.remove_kv_tracking(|| emptied_internal_root = true, self.alloc.clone());
+        self.current = Some(pos);
+        *self.length -= 1;
+        if emptied_internal_root {
+            // SAFETY: `pos` is a leaf edge and remains valid when the root above it goes.
+            let root = unsafe { self.root.reborrow() }.as_mut().unwrap();
+            root.pop_internal_level(self.alloc.clone());
+        }
+        old_kv
+    }
+}
@@ -1989,3 +1989,232 @@ fn test_insert_remove_intertwined_ord_chaos() {
     }
     map.check_invariants();
 }
+
+#[test]
+fn test_new_in() {
+    use std::alloc::System;
+
+    let mut map = BTreeMap::new_in(System);
+    for i in 0..MIN_INSERTS_HEIGHT_2 {
+        assert_eq!(map.insert(i, i * 10), None);
+    }
+    assert_eq!(map.len(), MIN_INSERTS_HEIGHT_2);
+    *map.entry(3).or_insert(0) += 1;
+    assert_eq!(map[&3], 31);
+    assert_eq!(map.remove(&0), Some(0));
+
+    let mut right = map.split_off(&(MIN_INSERTS_HEIGHT_2 / 2));
+    assert_eq!(map.len() + right.len(), MIN_INSERTS_HEIGHT_2 - 1);
+    right.append(&mut map);
+    assert!(map.is_empty());
+    assert_eq!(right.len(), MIN_INSERTS_HEIGHT_2 - 1);
+
+    let copy = right.clone();
+    assert_eq!(copy, right);
+    right.clear();
+    assert!(right.is_empty());
+    assert!(copy.into_keys().eq(1..MIN_INSERTS_HEIGHT_2));
+}
+
+#[test]
+fn test_clone_alloc() {
+    use crate::alloc::{AllocError, Global, Layout};
+    use core::cell::Cell;
+    use core::ptr::NonNull;
+
+    // Counts live copies of itself and live allocations made through it.
+    struct Tracking<'a> {
+        copies: &'a Cell<usize>,
+        blocks: &'a Cell<usize>,
+    }
+
+    impl Clone for Tracking<'_> {
+        fn clone(&self) -> Self {
+            self.copies.set(self.copies.get() + 1);
+            Tracking { copies: self.copies, blocks: self.blocks }
+        }
+    }
+
+    impl Drop for Tracking<'_> {
+        fn drop(&mut self) {
+            self.copies.set(self.copies.get() - 1);
+        }
+    }
+
+    unsafe impl AllocRef for Tracking<'_> {
+        fn alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            let ptr = Global.alloc(layout)?;
+            self.blocks.set(self.blocks.get() + 1);
+            Ok(ptr)
+        }
+
+        unsafe fn dealloc(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.blocks.set(self.blocks.get() - 1);
+            unsafe { Global.dealloc(ptr, layout) }
+        }
+    }
+
+    let copies = Cell::new(1);
+    let blocks = Cell::new(0);
+    let mut map = BTreeMap::new_in(Tracking { copies: &copies, blocks: &blocks });
+    for i in 0..MIN_INSERTS_HEIGHT_2 {
+        map.insert(i, i);
+    }
+    let map_blocks = blocks.get();
+    assert!(map_blocks > 1);
+
+    let copy = map.clone();
+    assert_eq!(copy, map);
+    assert_eq!(copies.get(), 2);
+    assert_eq!(blocks.get(), 2 * map_blocks);
+
+    drop(copy);
+    assert_eq!(copies.get(), 1);
+    assert_eq!(blocks.get(), map_blocks);
+
+    drop(map);
+    assert_eq!(copies.get(), 0);
+    assert_eq!(blocks.get(), 0);
+}
+
+#[test]
+fn test_try_insert() {
+    let mut map = BTreeMap::new();
+    for i in (0..MIN_INSERTS_HEIGHT_2).rev() {
+        assert_eq!(map.try_insert(i, i * 10), Ok(None));
+    }
+    assert_eq!(map.try_insert(3, 0), Ok(Some(30)));
+    assert_eq!(map.len(), MIN_INSERTS_HEIGHT_2);
+    assert_eq!(map.height(), Some(2));
+    map.check();
+}
+
+#[test]
+fn test_try_insert_alloc_failure() {
+    use crate::alloc::{AllocError, Global, Layout};
+    use crate::collections::TryReserveError;
+    use core::cell::Cell;
+    use core::ptr::NonNull;
+
+    // Hands out a limited number of allocations from `Global`.
+    struct Limited(Cell<usize>);
+
+    unsafe impl AllocRef for Limited {
+        fn alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            match self.0.get() {
+                0 => Err(AllocError),
+                n => {
+                    self.0.set(n - 1);
+                    Global.alloc(layout)
+                }
+            }
+        }
+
+        unsafe fn dealloc(&self, ptr: NonNull<u8>, layout: Layout) {
+            unsafe { Global.dealloc(ptr, layout) }
+        }
+    }
+
+    // Enough for the root leaf, then one split of the root into two leaves and a new root.
+    let limited = Limited(Cell::new(3));
+    let mut map = BTreeMap::new_in(&limited);
+    let mut len = 0;
+    let err = loop {
+        match map.try_insert(len, ()) {
+            Ok(None) => len += 1,
+            Ok(Some(_)) => unreachable!(),
+            Err(err) => break err,
+        }
+    };
+    assert!(matches!(err, TryReserveError::AllocError { .. }));
+    assert!(len > NODE_CAPACITY);
+    assert_eq!(map.len(), len);
+    assert!(map.keys().copied().eq(0..len));
+
+    // Existing keys need no allocation.
+    assert_eq!(map.try_insert(0, ()), Ok(Some(())));
+
+    limited.0.set(usize::MAX);
+    assert_eq!(map.try_insert(len, ()), Ok(None));
+    assert!(map.into_keys().eq(0..=len));
+}
+
+#[test]
+fn test_cursor() {
+    let map = BTreeMap::from_iter((0..MIN_INSERTS_HEIGHT_2).map(|i| (i, i)));
+
+    let mut cur = map.lower_bound(Bound::Unbounded);
+    assert_eq!(cur.peek_prev(), None);
+    for i in 0..MIN_INSERTS_HEIGHT_2 {
+        assert_eq!(cur.next(), Some((&i, &i)));
+    }
+    assert_eq!(cur.next(), None);
+    assert_eq!(cur.peek_prev(), Some((&(MIN_INSERTS_HEIGHT_2 - 1), &(MIN_INSERTS_HEIGHT_2 - 1))));
+    for i in (0..MIN_INSERTS_HEIGHT_2).rev() {
+        assert_eq!(cur.prev(), Some((&i, &i)));
+    }
+    assert_eq!(cur.prev(), None);
+
+    let cur = map.lower_bound(Included(&10));
+    assert_eq!((cur.peek_prev(), cur.peek_next()), (Some((&9, &9)), Some((&10, &10))));
+    let cur = map.lower_bound(Excluded(&10));
+    assert_eq!((cur.peek_prev(), cur.peek_next()), (Some((&10, &10)), Some((&11, &11))));
+    let cur = map.upper_bound(Included(&10));
+    assert_eq!((cur.peek_prev(), cur.peek_next()), (Some((&10, &10)), Some((&11, &11))));
+    let cur = map.upper_bound(Excluded(&10));
+    assert_eq!((cur.peek_prev(), cur.peek_next()), (Some((&9, &9)), Some((&10, &10))));
+    let cur = map.upper_bound(Unbounded);
+    assert_eq!(cur.peek_next(), None);
+
+    let empty = BTreeMap::<i32, i32>::new();
+    let mut cur = empty.lower_bound(Included(&0));
+    assert_eq!((cur.peek_prev(), cur.peek_next()), (None, None));
+    assert_eq!((cur.prev(), cur.next()), (None, None));
+}
+
+#[test]
+fn test_cursor_mut() {
+    let mut map = BTreeMap::new();
+    let mut cur = map.lower_bound_mut(Unbounded);
+    assert_eq!(cur.remove_next(), None);
+    // Grow the tree through the cursor alone, splitting nodes up to the root.
+    for i in (0..MIN_INSERTS_HEIGHT_2).rev() {
+        cur.insert_after(i * 2, i);
+    }
+    assert_eq!(cur.peek_next(), Some((&0, &mut 0)));
+    drop(cur);
+    map.check();
+    assert_eq!(map.len(), MIN_INSERTS_HEIGHT_2);
+
+    let mut cur = map.upper_bound_mut(Excluded(&20));
+    assert_eq!(cur.peek_prev(), Some((&18, &mut 9)));
+    cur.insert_before(19, 100);
+    assert_eq!(cur.prev(), Some((&19, &mut 100)));
+    if let Some((_, v)) = cur.next() {
+        *v = 200;
+    }
+    assert_eq!(cur.remove_next(), Some((20, 10)));
+    assert_eq!(cur.remove_prev(), Some((19, 200)));
+    assert_eq!((cur.peek_prev(), cur.peek_next()), (Some((&18, &mut 9)), Some((&22, &mut 11))));
+    drop(cur);
+    map.check();
+    assert_eq!(map.len(), MIN_INSERTS_HEIGHT_2 - 1);
+
+    // Shrink the tree through the cursor alone, merging nodes down to the root.
+    let mut cur = map.upper_bound_mut(Unbounded);
+    while let Some((k, v)) = cur.remove_prev() {
+        assert_eq!(k, v * 2);
+    }
+    assert_eq!(cur.peek_next(), None);
+    drop(cur);
+    map.check();
+    assert!(map.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "key must be ordered above the previous element")]
+fn test_cursor_mut_insert_unordered() {
+    let mut map = BTreeMap::from_iter([(1, 1), (3, 3)].iter().copied());
+    let mut cur = map.lower_bound_mut(Included(&3));
+    cur.insert_after(1, 1);
+}
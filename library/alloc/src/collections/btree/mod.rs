@@ -6,6 +6,7 @@
 mod navigate;
 mod node;
 mod remove;
+mod reserve;
 mod search;
 pub mod set;
 mod split;
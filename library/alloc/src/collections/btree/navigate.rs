@@ -1,9 +1,11 @@
 use core::borrow::Borrow;
 use core::cmp::Ordering;
-use core::ops::Bound::{Excluded, Included, Unbounded};
+use core::ops::Bound::{self, Excluded, Included, Unbounded};
 use core::ops::RangeBounds;
 use core::ptr;
 
+use crate::alloc::AllocRef;
+
 use super::node::{marker, ForceResult::*, Handle, NodeRef};
 use super::search::{self, SearchResult};
 use super::unwrap_unchecked;
@@ -203,6 +205,86 @@ pub fn full_range(
     }
 }
 
+impl<BorrowType, K, V> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
+    /// Finds the leaf edge in or underneath a node that precedes the smallest key
+    /// admitted by `bound`, as the lower end of a range starting at `bound`.
+    pub fn lower_bound<Q: ?Sized>(
+        self,
+        bound: Bound<&Q>,
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>
+    where
+        Q: Ord,
+        K: Borrow<Q>,
+    {
+        let mut node = self;
+        let mut found = false;
+        loop {
+            let edge = match (found, bound) {
+                (false, Included(key)) => match search::search_node(node, key) {
+                    SearchResult::Found(kv) => {
+                        found = true;
+                        kv.left_edge()
+                    }
+                    SearchResult::GoDown(edge) => edge,
+                },
+                (false, Excluded(key)) => match search::search_node(node, key) {
+                    SearchResult::Found(kv) => {
+                        found = true;
+                        kv.right_edge()
+                    }
+                    SearchResult::GoDown(edge) => edge,
+                },
+                (true, Included(_)) => node.last_edge(),
+                (true, Excluded(_)) => node.first_edge(),
+                (_, Unbounded) => node.first_edge(),
+            };
+            match edge.force() {
+                Leaf(leaf_edge) => return leaf_edge,
+                Internal(internal_edge) => node = internal_edge.descend(),
+            }
+        }
+    }
+
+    /// Finds the leaf edge in or underneath a node that follows the greatest key
+    /// admitted by `bound`, as the upper end of a range ending at `bound`.
+    pub fn upper_bound<Q: ?Sized>(
+        self,
+        bound: Bound<&Q>,
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>
+    where
+        Q: Ord,
+        K: Borrow<Q>,
+    {
+        let mut node = self;
+        let mut found = false;
+        loop {
+            let edge = match (found, bound) {
+                (false, Included(key)) => match search::search_node(node, key) {
+                    SearchResult::Found(kv) => {
+                        found = true;
+                        kv.right_edge()
+                    }
+                    SearchResult::GoDown(edge) => edge,
+                },
+                (false, Excluded(key)) => match search::search_node(node, key) {
+                    SearchResult::Found(kv) => {
+                        found = true;
+                        kv.left_edge()
+                    }
+                    SearchResult::GoDown(edge) => edge,
+                },
+                (true, Included(_)) => node.first_edge(),
+                (true, Excluded(_)) => node.last_edge(),
+                (_, Unbounded) => node.last_edge(),
+            };
+            match edge.force() {
+                Leaf(leaf_edge) => return leaf_edge,
+                Internal(internal_edge) => node = internal_edge.descend(),
+            }
+        }
+    }
+}
+
 impl<BorrowType, K, V> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge> {
     /// Given a leaf edge handle, returns [`Result::Ok`] with a handle to the neighboring KV
     /// on the right side, which is either in the same leaf node or in an ancestor node.
@@ -280,8 +362,9 @@ macro_rules! def_next_kv_uncheched_dealloc {
         /// - The leaf edge must not be the last one in the direction travelled.
         /// - The node carrying the next KV returned must not have been deallocated by a
         ///   previous call on any handle obtained for this tree.
-        unsafe fn $name <K, V>(
+        unsafe fn $name <K, V, A: AllocRef + Clone>(
             leaf_edge: Handle<NodeRef<marker::Owned, K, V, marker::Leaf>, marker::Edge>,
+            alloc: A,
         ) -> Handle<NodeRef<marker::Owned, K, V, marker::LeafOrInternal>, marker::KV> {
             let mut edge = leaf_edge.forget_node_type();
             loop {
@@ -289,7 +372,7 @@ unsafe fn $name <K, V>(
                     Ok(internal_kv) => return internal_kv,
                     Err(last_edge) => {
                         unsafe {
-                            let parent_edge = last_edge.into_node().deallocate_and_ascend();
+                            let parent_edge = last_edge.into_node().deallocate_and_ascend(alloc.clone());
                             unwrap_unchecked(parent_edge).forget_node_type()
                         }
                     }
@@ -375,9 +458,9 @@ impl<K, V> Handle<NodeRef<marker::Owned, K, V, marker::Leaf>, marker::Edge> {
     /// The only safe way to proceed with the updated handle is to compare it, drop it,
     /// call this method again subject to its safety conditions, or call counterpart
     /// `next_back_unchecked` subject to its safety conditions.
-    pub unsafe fn next_unchecked(&mut self) -> (K, V) {
+    pub unsafe fn next_unchecked<A: AllocRef + Clone>(&mut self, alloc: A) -> (K, V) {
         super::mem::replace(self, |leaf_edge| {
-            let kv = unsafe { next_kv_unchecked_dealloc(leaf_edge) };
+            let kv = unsafe { next_kv_unchecked_dealloc(leaf_edge, alloc) };
             let k = unsafe { ptr::read(kv.reborrow().into_kv().0) };
             let v = unsafe { ptr::read(kv.reborrow().into_kv().1) };
             (kv.next_leaf_edge(), (k, v))
@@ -396,9 +479,9 @@ pub unsafe fn next_unchecked(&mut self) -> (K, V) {
     /// The only safe way to proceed with the updated handle is to compare it, drop it,
     /// call this method again subject to its safety conditions, or call counterpart
     /// `next_unchecked` subject to its safety conditions.
-    pub unsafe fn next_back_unchecked(&mut self) -> (K, V) {
+    pub unsafe fn next_back_unchecked<A: AllocRef + Clone>(&mut self, alloc: A) -> (K, V) {
         super::mem::replace(self, |leaf_edge| {
-            let kv = unsafe { next_back_kv_unchecked_dealloc(leaf_edge) };
+            let kv = unsafe { next_back_kv_unchecked_dealloc(leaf_edge, alloc) };
             let k = unsafe { ptr::read(kv.reborrow().into_kv().0) };
             let v = unsafe { ptr::read(kv.reborrow().into_kv().1) };
             (kv.next_back_leaf_edge(), (k, v))
@@ -33,7 +33,7 @@
 use core::mem::{self, MaybeUninit};
 use core::ptr::{self, NonNull};
 
-use crate::alloc::{AllocRef, Global, Layout};
+use crate::alloc::{AllocRef, Layout};
 use crate::boxed::Box;
 
 const B: usize = 6;
@@ -107,6 +107,17 @@ unsafe fn new() -> Self {
     }
 }
 
+/// The memory layout of a leaf node, as allocated by `Root::new_leaf` and by leaf splits.
+pub fn leaf_layout<K, V>() -> Layout {
+    Layout::new::<LeafNode<K, V>>()
+}
+
+/// The memory layout of an internal node, as allocated by `push_internal_level` and by
+/// internal splits.
+pub fn internal_layout<K, V>() -> Layout {
+    Layout::new::<InternalNode<K, V>>()
+}
+
 /// A managed, non-null pointer to a node. This is either an owned pointer to
 /// `LeafNode<K, V>` or an owned pointer to `InternalNode<K, V>`.
 ///
@@ -134,23 +145,23 @@ fn as_ptr(&self) -> NonNull<LeafNode<K, V>> {
 
 impl<K, V> Root<K, V> {
     /// Returns a new owned tree, with its own root node that is initially empty.
-    pub fn new_leaf() -> Self {
-        NodeRef::new().forget_type()
+    pub fn new_leaf<A: AllocRef>(alloc: A) -> Self {
+        NodeRef::new(alloc).forget_type()
     }
 }
 
 impl<K, V> NodeRef<marker::Owned, K, V, marker::Leaf> {
-    fn new() -> Self {
-        Self::from_new_leaf(Box::new(unsafe { LeafNode::new() }))
+    fn new<A: AllocRef>(alloc: A) -> Self {
+        Self::from_new_leaf(Box::new_in(unsafe { LeafNode::new() }, alloc))
     }
 
-    fn from_new_leaf(leaf: Box<LeafNode<K, V>>) -> Self {
+    fn from_new_leaf<A: AllocRef>(leaf: Box<LeafNode<K, V>, A>) -> Self {
         NodeRef { height: 0, node: NonNull::from(Box::leak(leaf)), _marker: PhantomData }
     }
 }
 
 impl<K, V> NodeRef<marker::Owned, K, V, marker::Internal> {
-    fn from_new_internal(internal: Box<InternalNode<K, V>>, height: usize) -> Self {
+    fn from_new_internal<A: AllocRef>(internal: Box<InternalNode<K, V>, A>, height: usize) -> Self {
         NodeRef { height, node: NonNull::from(Box::leak(internal)).cast(), _marker: PhantomData }
     }
 }
@@ -179,8 +190,11 @@ impl<K, V> NodeRef<marker::Owned, K, V, marker::LeafOrInternal> {
     /// Adds a new internal node with a single edge pointing to the previous root node,
     /// make that new node the root node, and return it. This increases the height by 1
     /// and is the opposite of `pop_internal_level`.
-    pub fn push_internal_level(&mut self) -> NodeRef<marker::Mut<'_>, K, V, marker::Internal> {
-        let mut new_node = Box::new(unsafe { InternalNode::new() });
+    pub fn push_internal_level<A: AllocRef>(
+        &mut self,
+        alloc: A,
+    ) -> NodeRef<marker::Mut<'_>, K, V, marker::Internal> {
+        let mut new_node = Box::new_in(unsafe { InternalNode::new() }, alloc);
         new_node.edges[0].write(BoxedNode::from_owned(self.node));
         let mut new_root = NodeRef::from_new_internal(new_node, self.height + 1);
         new_root.borrow_mut().first_edge().correct_parent_link();
@@ -199,7 +213,7 @@ pub fn push_internal_level(&mut self) -> NodeRef<marker::Mut<'_>, K, V, marker::
     /// it will not invalidate other handles or references to the root node.
     ///
     /// Panics if there is no internal level, i.e., if the root node is a leaf.
-    pub fn pop_internal_level(&mut self) {
+    pub fn pop_internal_level<A: AllocRef>(&mut self, alloc: A) {
         assert!(self.height > 0);
 
         let top = self.node;
@@ -209,7 +223,7 @@ pub fn pop_internal_level(&mut self) {
         self.borrow_mut().clear_parent_link();
 
         unsafe {
-            Global.dealloc(top.cast(), Layout::new::<InternalNode<K, V>>());
+            alloc.dealloc(top.cast(), Layout::new::<InternalNode<K, V>>());
         }
     }
 }
@@ -455,14 +469,15 @@ impl<K, V> NodeRef<marker::Owned, K, V, marker::LeafOrInternal> {
     /// Similar to `ascend`, gets a reference to a node's parent node, but also
     /// deallocate the current node in the process. This is unsafe because the
     /// current node will still be accessible despite being deallocated.
-    pub unsafe fn deallocate_and_ascend(
+    pub unsafe fn deallocate_and_ascend<A: AllocRef>(
         self,
+        alloc: A,
     ) -> Option<Handle<NodeRef<marker::Owned, K, V, marker::Internal>, marker::Edge>> {
         let height = self.height;
         let node = self.node;
         let ret = self.ascend().ok();
         unsafe {
-            Global.dealloc(
+            alloc.dealloc(
                 node.cast(),
                 if height > 0 {
                     Layout::new::<InternalNode<K, V>>()
@@ -978,17 +993,13 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// Inserts a new key/value pair between the key/value pairs to the right and left of
     /// this edge. This method assumes that there is enough space in the node for the new
     /// pair to fit.
-    ///
-    /// The returned pointer points to the inserted value.
-    fn insert_fit(&mut self, key: K, val: V) -> *mut V {
+    fn insert_fit(&mut self, key: K, val: V) {
         debug_assert!(self.node.len() < CAPACITY);
 
         unsafe {
             *self.node.reborrow_mut().into_len_mut() += 1;
             slice_insert(self.node.reborrow_mut().into_key_area_slice(), self.idx, key);
             slice_insert(self.node.reborrow_mut().into_val_area_slice(), self.idx, val);
-
-            self.node.reborrow_mut().into_val_area_mut_at(self.idx).assume_init_mut()
         }
     }
 }
@@ -997,26 +1008,35 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// Inserts a new key/value pair between the key/value pairs to the right and left of
     /// this edge. This method splits the node if there isn't enough room.
     ///
-    /// The returned pointer points to the inserted value.
-    fn insert(mut self, key: K, val: V) -> (InsertResult<'a, K, V, marker::Leaf>, *mut V) {
+    /// The returned handle points to the inserted pair. It aliases the returned
+    /// result, and must not be used until the caller is done with that result.
+    fn insert<A: AllocRef>(
+        mut self,
+        key: K,
+        val: V,
+        alloc: A,
+    ) -> (
+        InsertResult<'a, K, V, marker::Leaf>,
+        Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV>,
+    ) {
         if self.node.len() < CAPACITY {
-            let val_ptr = self.insert_fit(key, val);
+            self.insert_fit(key, val);
             let kv = unsafe { Handle::new_kv(self.node, self.idx) };
-            (InsertResult::Fit(kv), val_ptr)
+            let inserted = unsafe { ptr::read(&kv) };
+            (InsertResult::Fit(kv), inserted)
         } else {
             let (middle_kv_idx, insertion) = splitpoint(self.idx);
             let middle = unsafe { Handle::new_kv(self.node, middle_kv_idx) };
-            let mut result = middle.split();
-            let mut insertion_edge = match insertion {
-                LeftOrRight::Left(insert_idx) => unsafe {
-                    Handle::new_edge(result.left.reborrow_mut(), insert_idx)
-                },
-                LeftOrRight::Right(insert_idx) => unsafe {
-                    Handle::new_edge(result.right.borrow_mut(), insert_idx)
-                },
+            let result = middle.split(alloc);
+            let (insertion_node, insert_idx) = match insertion {
+                LeftOrRight::Left(insert_idx) => (result.left.node, insert_idx),
+                LeftOrRight::Right(insert_idx) => (result.right.node, insert_idx),
             };
-            let val_ptr = insertion_edge.insert_fit(key, val);
-            (InsertResult::Split(result), val_ptr)
+            let insertion_node = NodeRef { height: 0, node: insertion_node, _marker: PhantomData };
+            let mut insertion_edge = unsafe { Handle::new_edge(insertion_node, insert_idx) };
+            insertion_edge.insert_fit(key, val);
+            let inserted = unsafe { Handle::new_kv(insertion_edge.into_node(), insert_idx) };
+            (InsertResult::Split(result), inserted)
         }
     }
 }
@@ -1055,11 +1075,12 @@ fn insert_fit(&mut self, key: K, val: V, edge: Root<K, V>) {
     /// Inserts a new key/value pair and an edge that will go to the right of that new pair
     /// between this edge and the key/value pair to the right of this edge. This method splits
     /// the node if there isn't enough room.
-    fn insert(
+    fn insert<A: AllocRef>(
         mut self,
         key: K,
         val: V,
         edge: Root<K, V>,
+        alloc: A,
     ) -> InsertResult<'a, K, V, marker::Internal> {
         assert!(edge.height == self.node.height - 1);
 
@@ -1070,7 +1091,7 @@ fn insert(
         } else {
             let (middle_kv_idx, insertion) = splitpoint(self.idx);
             let middle = unsafe { Handle::new_kv(self.node, middle_kv_idx) };
-            let mut result = middle.split();
+            let mut result = middle.split(alloc);
             let mut insertion_edge = match insertion {
                 LeftOrRight::Left(insert_idx) => unsafe {
                     Handle::new_edge(result.left.reborrow_mut(), insert_idx)
@@ -1092,29 +1113,36 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     ///
     /// If the returned result is a `Fit`, its handle's node can be this edge's node or an ancestor.
     /// If the returned result is a `Split`, the `left` field will be the root node.
-    /// The returned pointer points to the inserted value.
-    pub fn insert_recursing(
+    /// The returned handle points to the inserted pair, and must not be used until the
+    /// caller is done with the returned result, e.g. has grown the tree to absorb a `Split`.
+    pub fn insert_recursing<A: AllocRef + Clone>(
         self,
         key: K,
         value: V,
-    ) -> (InsertResult<'a, K, V, marker::LeafOrInternal>, *mut V) {
-        let (mut split, val_ptr) = match self.insert(key, value) {
-            (InsertResult::Fit(handle), ptr) => {
-                return (InsertResult::Fit(handle.forget_node_type()), ptr);
+        alloc: A,
+    ) -> (
+        InsertResult<'a, K, V, marker::LeafOrInternal>,
+        Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV>,
+    ) {
+        let (mut split, inserted) = match self.insert(key, value, alloc.clone()) {
+            (InsertResult::Fit(handle), inserted) => {
+                return (InsertResult::Fit(handle.forget_node_type()), inserted);
             }
-            (InsertResult::Split(split), val_ptr) => (split.forget_node_type(), val_ptr),
+            (InsertResult::Split(split), inserted) => (split.forget_node_type(), inserted),
         };
 
         loop {
             split = match split.left.ascend() {
-                Ok(parent) => match parent.insert(split.kv.0, split.kv.1, split.right) {
-                    InsertResult::Fit(handle) => {
-                        return (InsertResult::Fit(handle.forget_node_type()), val_ptr);
+                Ok(parent) => {
+                    match parent.insert(split.kv.0, split.kv.1, split.right, alloc.clone()) {
+                        InsertResult::Fit(handle) => {
+                            return (InsertResult::Fit(handle.forget_node_type()), inserted);
+                        }
+                        InsertResult::Split(split) => split.forget_node_type(),
                     }
-                    InsertResult::Split(split) => split.forget_node_type(),
-                },
+                }
                 Err(root) => {
-                    return (InsertResult::Split(SplitResult { left: root, ..split }), val_ptr);
+                    return (InsertResult::Split(SplitResult { left: root, ..split }), inserted);
                 }
             };
         }
@@ -1154,6 +1182,17 @@ pub fn into_key_mut(self) -> &'a mut K {
     pub fn into_val_mut(self) -> &'a mut V {
         unsafe { self.node.into_val_area_mut_at(self.idx).assume_init_mut() }
     }
+
+    pub fn into_kv_mut(mut self) -> (&'a mut K, &'a mut V) {
+        // We cannot call separate key and value methods, because calling the second one
+        // invalidates the reference returned by the first.
+        let leaf = NodeRef::as_leaf_mut(&mut self.node);
+        unsafe {
+            let key = leaf.keys.get_unchecked_mut(self.idx).assume_init_mut();
+            let val = leaf.vals.get_unchecked_mut(self.idx).assume_init_mut();
+            (key, val)
+        }
+    }
 }
 
 impl<'a, K, V, NodeType> Handle<NodeRef<marker::ValMut<'a>, K, V, NodeType>, marker::KV> {
@@ -1217,9 +1256,9 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// - The key and value pointed to by this handle are extracted.
     /// - All the key/value pairs to the right of this handle are put into a newly
     ///   allocated node.
-    pub fn split(mut self) -> SplitResult<'a, K, V, marker::Leaf> {
+    pub fn split<A: AllocRef>(mut self, alloc: A) -> SplitResult<'a, K, V, marker::Leaf> {
         unsafe {
-            let mut new_node = Box::new(LeafNode::new());
+            let mut new_node = Box::new_in(LeafNode::new(), alloc);
 
             let kv = self.split_leaf_data(&mut new_node);
 
@@ -1250,9 +1289,9 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>,
     /// - The key and value pointed to by this handle are extracted.
     /// - All the edges and key/value pairs to the right of this handle are put into
     ///   a newly allocated node.
-    pub fn split(mut self) -> SplitResult<'a, K, V, marker::Internal> {
+    pub fn split<A: AllocRef>(mut self, alloc: A) -> SplitResult<'a, K, V, marker::Internal> {
         unsafe {
-            let mut new_node = Box::new(InternalNode::new());
+            let mut new_node = Box::new_in(InternalNode::new(), alloc);
             let new_len = self.split_new_node_len();
             // Move edges out before reducing length:
             ptr::copy_nonoverlapping(
@@ -1359,9 +1398,10 @@ impl<'a, K: 'a, V: 'a> BalancingContext<'a, K, V> {
     /// to where the edge in that child node ended up,
     ///
     /// Panics unless we `.can_merge()`.
-    pub fn merge(
+    pub fn merge<A: AllocRef>(
         mut self,
         track_edge_idx: Option<LeftOrRight<usize>>,
+        alloc: A,
     ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::Edge> {
         let mut left_node = self.left_child;
         let left_len = left_node.len();
@@ -1421,9 +1461,9 @@ pub fn merge(
 
                 left_node.correct_childrens_parent_links(left_len + 1..=left_len + 1 + right_len);
 
-                Global.dealloc(right_node.node.cast(), Layout::new::<InternalNode<K, V>>());
+                alloc.dealloc(right_node.node.cast(), Layout::new::<InternalNode<K, V>>());
             } else {
-                Global.dealloc(right_node.node.cast(), Layout::new::<LeafNode<K, V>>());
+                alloc.dealloc(right_node.node.cast(), Layout::new::<LeafNode<K, V>>());
             }
 
             let new_idx = match track_edge_idx {
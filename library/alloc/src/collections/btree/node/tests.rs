@@ -1,5 +1,6 @@
 use super::super::navigate;
 use super::*;
+use crate::alloc::Global;
 use crate::fmt::Debug;
 use crate::string::String;
 use core::cmp::Ordering::*;
@@ -74,12 +75,12 @@ fn test_splitpoint() {
 
 #[test]
 fn test_partial_cmp_eq() {
-    let mut root1 = NodeRef::new();
+    let mut root1 = NodeRef::new(Global);
     let mut leaf1 = root1.borrow_mut();
     leaf1.push(1, ());
     let mut root1 = root1.forget_type();
-    root1.push_internal_level();
-    let root2 = Root::new_leaf();
+    root1.push_internal_level(Global);
+    let root2 = Root::new_leaf(Global);
     root1.reborrow().assert_back_pointers();
     root2.reborrow().assert_back_pointers();
 
@@ -102,9 +103,9 @@ fn test_partial_cmp_eq() {
     assert_eq!(top_edge_1.partial_cmp(&top_edge_1), Some(Equal));
     assert_eq!(top_edge_1.partial_cmp(&top_edge_2), None);
 
-    root1.pop_internal_level();
-    unsafe { root1.deallocate_and_ascend() };
-    unsafe { root2.deallocate_and_ascend() };
+    root1.pop_internal_level(Global);
+    unsafe { root1.deallocate_and_ascend(Global) };
+    unsafe { root2.deallocate_and_ascend(Global) };
 }
 
 #[test]
@@ -1,6 +1,7 @@
 use super::map::MIN_LEN;
 use super::node::{marker, ForceResult::*, Handle, LeftOrRight::*, NodeRef};
 use super::unwrap_unchecked;
+use crate::alloc::AllocRef;
 use core::mem;
 
 impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV> {
@@ -8,21 +9,23 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInter
     /// the leaf edge corresponding to that former pair. It's possible this empties
     /// a root node that is internal, which the caller should pop from the map
     /// holding the tree. The caller should also decrement the map's length.
-    pub fn remove_kv_tracking<F: FnOnce()>(
+    pub fn remove_kv_tracking<F: FnOnce(), A: AllocRef + Clone>(
         self,
         handle_emptied_internal_root: F,
+        alloc: A,
     ) -> ((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>) {
         match self.force() {
-            Leaf(node) => node.remove_leaf_kv(handle_emptied_internal_root),
-            Internal(node) => node.remove_internal_kv(handle_emptied_internal_root),
+            Leaf(node) => node.remove_leaf_kv(handle_emptied_internal_root, alloc),
+            Internal(node) => node.remove_internal_kv(handle_emptied_internal_root, alloc),
         }
     }
 }
 
 impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
-    fn remove_leaf_kv<F: FnOnce()>(
+    fn remove_leaf_kv<F: FnOnce(), A: AllocRef + Clone>(
         self,
         handle_emptied_internal_root: F,
+        alloc: A,
     ) -> ((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>) {
         let (old_kv, mut pos) = self.remove();
         let len = pos.reborrow().into_node().len();
@@ -34,7 +37,7 @@ fn remove_leaf_kv<F: FnOnce()>(
                 Ok(Left(left_parent_kv)) => {
                     debug_assert!(left_parent_kv.right_child_len() == MIN_LEN - 1);
                     if left_parent_kv.can_merge() {
-                        left_parent_kv.merge(Some(Right(idx)))
+                        left_parent_kv.merge(Some(Right(idx)), alloc.clone())
                     } else {
                         debug_assert!(left_parent_kv.left_child_len() > MIN_LEN);
                         left_parent_kv.steal_left(idx)
@@ -43,7 +46,7 @@ fn remove_leaf_kv<F: FnOnce()>(
                 Ok(Right(right_parent_kv)) => {
                     debug_assert!(right_parent_kv.left_child_len() == MIN_LEN - 1);
                     if right_parent_kv.can_merge() {
-                        right_parent_kv.merge(Some(Left(idx)))
+                        right_parent_kv.merge(Some(Left(idx)), alloc.clone())
                     } else {
                         debug_assert!(right_parent_kv.right_child_len() > MIN_LEN);
                         right_parent_kv.steal_right(idx)
@@ -62,7 +65,9 @@ fn remove_leaf_kv<F: FnOnce()>(
             // rearrange the parent through the grandparent, thus change the
             // leaf's parent pointer.
             if let Ok(parent) = unsafe { pos.reborrow_mut() }.into_node().ascend() {
-                parent.into_node().handle_shrunk_node_recursively(handle_emptied_internal_root);
+                parent
+                    .into_node()
+                    .handle_shrunk_node_recursively(handle_emptied_internal_root, alloc);
             }
         }
         (old_kv, pos)
@@ -70,16 +75,17 @@ fn remove_leaf_kv<F: FnOnce()>(
 }
 
 impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::KV> {
-    fn remove_internal_kv<F: FnOnce()>(
+    fn remove_internal_kv<F: FnOnce(), A: AllocRef + Clone>(
         self,
         handle_emptied_internal_root: F,
+        alloc: A,
     ) -> ((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>) {
         // Remove an adjacent KV from its leaf and then put it back in place of
         // the element we were asked to remove. Prefer the left adjacent KV,
         // for the reasons listed in `choose_parent_kv`.
         let left_leaf_kv = self.left_edge().descend().last_leaf_edge().left_kv();
         let left_leaf_kv = unsafe { unwrap_unchecked(left_leaf_kv.ok()) };
-        let (left_kv, left_hole) = left_leaf_kv.remove_leaf_kv(handle_emptied_internal_root);
+        let (left_kv, left_hole) = left_leaf_kv.remove_leaf_kv(handle_emptied_internal_root, alloc);
 
         // The internal node may have been stolen from or merged. Go back right
         // to find where the original KV ended up.
@@ -94,7 +100,11 @@ fn remove_internal_kv<F: FnOnce()>(
 impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
     /// Stocks up a possibly underfull internal node, recursively.
     /// Climbs up until it reaches an ancestor that has elements to spare or the root.
-    fn handle_shrunk_node_recursively<F: FnOnce()>(mut self, handle_emptied_internal_root: F) {
+    fn handle_shrunk_node_recursively<F: FnOnce(), A: AllocRef + Clone>(
+        mut self,
+        handle_emptied_internal_root: F,
+        alloc: A,
+    ) {
         loop {
             self = match self.len() {
                 0 => {
@@ -106,7 +116,7 @@ fn handle_shrunk_node_recursively<F: FnOnce()>(mut self, handle_emptied_internal
                     return;
                 }
                 1..MIN_LEN => {
-                    if let Some(parent) = self.handle_underfull_node_locally() {
+                    if let Some(parent) = self.handle_underfull_node_locally(alloc.clone()) {
                         parent
                     } else {
                         return;
@@ -119,14 +129,15 @@ fn handle_shrunk_node_recursively<F: FnOnce()>(mut self, handle_emptied_internal
 
     /// Stocks up an underfull internal node, possibly at the cost of shrinking
     /// its parent instead, which is then returned.
-    fn handle_underfull_node_locally(
+    fn handle_underfull_node_locally<A: AllocRef>(
         self,
+        alloc: A,
     ) -> Option<NodeRef<marker::Mut<'a>, K, V, marker::Internal>> {
         match self.forget_type().choose_parent_kv() {
             Ok(Left(left_parent_kv)) => {
                 debug_assert!(left_parent_kv.right_child_len() == MIN_LEN - 1);
                 if left_parent_kv.can_merge() {
-                    let pos = left_parent_kv.merge(None);
+                    let pos = left_parent_kv.merge(None, alloc);
                     let parent_edge = unsafe { unwrap_unchecked(pos.into_node().ascend().ok()) };
                     Some(parent_edge.into_node())
                 } else {
@@ -138,7 +149,7 @@ fn handle_underfull_node_locally(
             Ok(Right(right_parent_kv)) => {
                 debug_assert!(right_parent_kv.left_child_len() == MIN_LEN - 1);
                 if right_parent_kv.can_merge() {
-                    let pos = right_parent_kv.merge(None);
+                    let pos = right_parent_kv.merge(None, alloc);
                     let parent_edge = unsafe { unwrap_unchecked(pos.into_node().ascend().ok()) };
                     Some(parent_edge.into_node())
                 } else {
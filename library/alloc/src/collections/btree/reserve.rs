@@ -0,0 +1,142 @@
+use core::cell::Cell;
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+use crate::alloc::{AllocError, AllocRef, Layout};
+use crate::collections::TryReserveError;
+
+use super::node::{self, marker, Handle, NodeRef, CAPACITY};
+
+/// An allocator that hands out nodes allocated ahead of time, so that the tree can be
+/// grown through it without the possibility of allocation failure half way through.
+///
+/// Requests for which nothing was reserved are passed on to the underlying allocator,
+/// as are all deallocations. Whatever is left unused is freed when the reserve is dropped.
+pub struct NodeReserve<K, V, A: AllocRef> {
+    alloc: A,
+    leaves: FreeList,
+    internals: FreeList,
+    _marker: PhantomData<(K, V)>,
+}
+
+/// A singly linked list of unused blocks, threaded through the blocks themselves.
+/// Every node starts with a pointer-sized, pointer-aligned field, so any node fits a link.
+struct FreeList {
+    head: Cell<Option<NonNull<u8>>>,
+}
+
+impl FreeList {
+    fn new() -> Self {
+        FreeList { head: Cell::new(None) }
+    }
+
+    /// Links in a block, which must be large and aligned enough to hold a pointer.
+    unsafe fn push(&self, block: NonNull<u8>) {
+        unsafe { block.cast::<Option<NonNull<u8>>>().as_ptr().write(self.head.get()) };
+        self.head.set(Some(block));
+    }
+
+    fn pop(&self) -> Option<NonNull<u8>> {
+        let block = self.head.get()?;
+        self.head.set(unsafe { block.cast::<Option<NonNull<u8>>>().as_ptr().read() });
+        Some(block)
+    }
+}
+
+impl<K, V, A: AllocRef> NodeReserve<K, V, A> {
+    pub fn new(alloc: A) -> Self {
+        NodeReserve {
+            alloc,
+            leaves: FreeList::new(),
+            internals: FreeList::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Reserves the root leaf of a tree that has none yet.
+    pub fn try_reserve_root(&self) -> Result<(), TryReserveError> {
+        self.try_reserve(&self.leaves, node::leaf_layout::<K, V>(), 1)
+    }
+
+    /// Reserves every node that `insert_recursing` on this edge, followed by
+    /// `push_internal_level` if the root splits, allocates.
+    pub fn try_reserve_insert(
+        &self,
+        edge: Handle<NodeRef<marker::Immut<'_>, K, V, marker::Leaf>, marker::Edge>,
+    ) -> Result<(), TryReserveError> {
+        let leaf = edge.into_node();
+        if leaf.len() < CAPACITY {
+            return Ok(());
+        }
+        // The leaf splits, and so does each full ancestor. If the split reaches the root,
+        // a new root is pushed on top of it.
+        let mut internals = 0;
+        let mut parent = leaf.ascend().ok();
+        loop {
+            match parent {
+                Some(edge) => {
+                    let node = edge.into_node();
+                    if node.len() < CAPACITY {
+                        break;
+                    }
+                    internals += 1;
+                    parent = node.ascend().ok();
+                }
+                None => {
+                    internals += 1;
+                    break;
+                }
+            }
+        }
+        self.try_reserve(&self.leaves, node::leaf_layout::<K, V>(), 1)?;
+        self.try_reserve(&self.internals, node::internal_layout::<K, V>(), internals)
+    }
+
+    fn try_reserve(
+        &self,
+        list: &FreeList,
+        layout: Layout,
+        count: usize,
+    ) -> Result<(), TryReserveError> {
+        for _ in 0..count {
+            let block = self
+                .alloc
+                .alloc(layout)
+                .map_err(|_| TryReserveError::AllocError { layout, non_exhaustive: () })?;
+            // SAFETY: nodes begin with their parent pointer, so they can hold a link.
+            unsafe { list.push(block.cast()) };
+        }
+        Ok(())
+    }
+}
+
+unsafe impl<K, V, A: AllocRef> AllocRef for NodeReserve<K, V, A> {
+    fn alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let list = if layout == node::leaf_layout::<K, V>() {
+            &self.leaves
+        } else if layout == node::internal_layout::<K, V>() {
+            &self.internals
+        } else {
+            return self.alloc.alloc(layout);
+        };
+        match list.pop() {
+            Some(block) => Ok(NonNull::slice_from_raw_parts(block, layout.size())),
+            None => self.alloc.alloc(layout),
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { self.alloc.dealloc(ptr, layout) }
+    }
+}
+
+impl<K, V, A: AllocRef> Drop for NodeReserve<K, V, A> {
+    fn drop(&mut self) {
+        while let Some(block) = self.leaves.pop() {
+            unsafe { self.alloc.dealloc(block, node::leaf_layout::<K, V>()) }
+        }
+        while let Some(block) = self.internals.pop() {
+            unsafe { self.alloc.dealloc(block, node::internal_layout::<K, V>()) }
+        }
+    }
+}
@@ -741,3 +741,24 @@ fn test_split_off_large_random_sorted() {
     assert!(set.into_iter().eq(data.clone().into_iter().filter(|x| *x < key)));
     assert!(right.into_iter().eq(data.into_iter().filter(|x| *x >= key)));
 }
+
+#[test]
+fn test_cursor() {
+    let mut set = BTreeSet::from_iter((0..100).map(|i| i * 2));
+
+    let mut cur = set.lower_bound(Bound::Included(&51));
+    assert_eq!(cur.peek_prev(), Some(&50));
+    assert_eq!(cur.next(), Some(&52));
+    assert_eq!(cur.prev(), Some(&52));
+    assert_eq!(cur.prev(), Some(&50));
+
+    let mut cur = set.upper_bound_mut(Bound::Included(&50));
+    cur.insert_before(51);
+    assert_eq!(cur.peek_prev(), Some(&51));
+    assert_eq!(cur.remove_next(), Some(52));
+    assert_eq!(cur.as_cursor().peek_next(), Some(&54));
+    assert_eq!(cur.remove_prev(), Some(51));
+    assert_eq!(cur.remove_prev(), Some(50));
+    assert_eq!(set.len(), 98);
+    assert!(set.range(46..58).eq(&[46, 48, 54, 56]));
+}
@@ -50,11 +50,41 @@
 //! The [`alloc`](alloc/index.html) module defines the low-level interface to the
 //! default global allocator. It is not compatible with the libc allocator API.
 //!
+//! ## Handling allocation failure
+//!
+//! By default, operations that need to allocate call [`handle_alloc_error`]
+//! when the allocator fails, which aborts the process. Where that is not
+//! acceptable, the `try_` methods such as [`Vec::try_push`],
+//! [`String::try_push_str`] and [`Box::try_new`] return a
+//! [`TryReserveError`] instead.
+//!
+//! Building this crate with `--cfg no_global_oom_handling` removes every API
+//! that would call [`handle_alloc_error`], so that only the fallible variants
+//! remain. The following are not available at all in that configuration,
+//! since they have not been audited for it yet:
+//!
+//! * the reference counted pointers [`Rc`] and [`Arc`], and the `task` module,
+//! * `fmt::format` and the `format!` and `vec!` macros,
+//! * [`BTreeMap`], [`BTreeSet`], [`LinkedList`] and [`BinaryHeap`].
+//!
+//! `Vec`, `String` and `VecDeque` remain, keeping their non-allocating and fallible
+//! methods. The configuration is only supported when building `alloc` on its
+//! own; `std` requires the infallible APIs.
+//!
 //! [`Arc`]: sync
+//! [`BinaryHeap`]: collections::BinaryHeap
+//! [`BTreeMap`]: collections::BTreeMap
+//! [`BTreeSet`]: collections::BTreeSet
 //! [`Box`]: boxed
 //! [`Cell`]: core::cell
+//! [`LinkedList`]: collections::LinkedList
 //! [`Rc`]: rc
 //! [`RefCell`]: core::cell
+//! [`TryReserveError`]: collections::TryReserveError
+//! [`Vec::try_push`]: vec::Vec::try_push
+//! [`String::try_push_str`]: string::String::try_push_str
+//! [`Box::try_new`]: boxed::Box::try_new
+//! [`handle_alloc_error`]: alloc::handle_alloc_error
 
 #![allow(unused_attributes)]
 #![stable(feature = "alloc", since = "1.36.0")]
@@ -140,6 +170,7 @@
 #![feature(try_trait)]
 #![feature(type_alias_impl_trait)]
 #![feature(associated_type_bounds)]
+#![feature(async_stream)]
 // Allow testing this library
 
 #[cfg(test)]
@@ -172,13 +203,14 @@ mod boxed {
 pub mod fmt;
 pub mod prelude;
 pub mod raw_vec;
+#[cfg(not(no_global_oom_handling))]
 pub mod rc;
 pub mod slice;
 pub mod str;
 pub mod string;
-#[cfg(target_has_atomic = "ptr")]
+#[cfg(all(not(no_global_oom_handling), target_has_atomic = "ptr"))]
 pub mod sync;
-#[cfg(target_has_atomic = "ptr")]
+#[cfg(all(not(no_global_oom_handling), target_has_atomic = "ptr"))]
 pub mod task;
 #[cfg(test)]
 mod tests;
@@ -1,3 +1,4 @@
+use std::alloc::System;
 use std::any::Any;
 use std::cell::RefCell;
 use std::cmp::PartialEq;
@@ -195,3 +196,20 @@ fn next(&mut self) -> Option<Self::Item> {
     assert_trusted_len(&iter);
     assert_eq!(&[Box::new(42), Box::new(24)], &*iter.collect::<Rc<[_]>>());
 }
+
+#[test]
+fn new_in() {
+    let x = Arc::new_in(RefCell::new(5), System);
+    let y = x.clone();
+    *y.borrow_mut() = 20;
+    assert_eq!(*x.borrow(), 20);
+    assert_eq!(Arc::strong_count(&x), 2);
+
+    let w = Arc::downgrade(&x);
+    drop(y);
+    assert_eq!(*w.upgrade().unwrap().borrow(), 20);
+
+    let x = Arc::try_unwrap(x).unwrap();
+    assert_eq!(x.into_inner(), 20);
+    assert!(w.upgrade().is_none());
+}
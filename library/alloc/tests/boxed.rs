@@ -57,3 +57,16 @@ fn box_deref_lval() {
     x.set(1000);
     assert_eq!(x.get(), 1000);
 }
+
+#[test]
+fn box_try_new() {
+    let x = Box::try_new(Cell::new(5)).unwrap();
+    x.set(1000);
+    assert_eq!(x.get(), 1000);
+
+    let zeroed = Box::<u64>::try_new_zeroed().unwrap();
+    assert_eq!(unsafe { zeroed.assume_init() }, Box::new(0));
+
+    let unit = Box::try_new(()).unwrap();
+    assert_eq!(&*unit as *const (), NonNull::<()>::dangling().as_ptr());
+}
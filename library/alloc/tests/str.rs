@@ -895,6 +895,49 @@ macro_rules! test {
     test!(b"A\xC3\xA9 \xF1\x80\x80 ", 4, Some(3));
 }
 
+#[test]
+fn from_utf8_error_long_input() {
+    // errors in inputs long enough to be validated a vector at a time, at every position
+    // relative to the vector boundaries
+    let cases: &[(&[u8], Option<usize>)] = &[
+        (b"\xFF", Some(1)),
+        (b"\x80", Some(1)),
+        (b"\xC1\x80", Some(1)),
+        (b"\xC2 ", Some(1)),
+        (b"\xE0\x9F\x80", Some(1)),
+        (b"\xE0\xA0 ", Some(2)),
+        (b"\xED\xA0\x80", Some(1)),
+        (b"\xF0\x8F\x80\x80", Some(1)),
+        (b"\xF4\x90\x80\x80", Some(1)),
+        (b"\xF1\x80\x80 ", Some(3)),
+        (b"\xF1\x80\x80", None),
+    ];
+    for &(invalid, error_len) in cases {
+        for prefix in &["", "é", "中", "😀"] {
+            for i in 0..70 {
+                let mut data = prefix.repeat(i / prefix.len().max(1)).into_bytes();
+                data.resize(i, b'a');
+                let valid_up_to = data.len();
+                data.extend_from_slice(invalid);
+                if error_len.is_some() {
+                    data.extend_from_slice("aéa中a😀".repeat(8).as_bytes());
+                }
+                let error = from_utf8(&data).unwrap_err();
+                assert_eq!(error.valid_up_to(), valid_up_to);
+                assert_eq!(error.error_len(), error_len);
+            }
+        }
+    }
+}
+
+#[test]
+fn from_utf8_long_input() {
+    let s = "aé中😀 ".repeat(100);
+    for start in 0..16 {
+        assert!(from_utf8(&s.as_bytes()[start..]).is_ok() == s.is_char_boundary(start));
+    }
+}
+
 #[test]
 fn test_as_bytes() {
     // no null
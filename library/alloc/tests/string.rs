@@ -762,6 +762,24 @@ fn test_try_reserve_exact() {
     }
 }
 
+#[test]
+#[cfg_attr(miri, ignore)] // Miri does not support signalling OOM
+#[cfg_attr(target_os = "android", ignore)] // Android used in CI has a broken dlmalloc
+fn test_try_push_str() {
+    let mut s = String::try_with_capacity(4).unwrap();
+    assert!(s.capacity() >= 4);
+    s.try_push_str("hello").unwrap();
+    s.try_push(' ').unwrap();
+    s.try_push('ö').unwrap();
+    s.try_push_str("").unwrap();
+    assert_eq!(s, "hello ö");
+
+    if let Err(AllocError { .. }) | Err(CapacityOverflow) = String::try_with_capacity(usize::MAX) {
+    } else {
+        panic!("usize::MAX should fail to allocate!")
+    }
+}
+
 #[test]
 fn test_from_char() {
     assert_eq!(String::from('a'), 'a'.to_string());
@@ -776,3 +794,22 @@ fn test_str_concat() {
     let s: String = format!("{}{}", a, b);
     assert_eq!(s.as_bytes()[9], 'd' as u8);
 }
+
+#[test]
+fn test_new_in() {
+    use std::alloc::System;
+
+    let mut s = String::new_in(System);
+    assert!(s.is_empty());
+    s.push_str("hello");
+    s.push(' ');
+    s += "world";
+    assert_eq!(s, "hello world");
+
+    let t = s.clone();
+    assert_eq!(t, s);
+    assert_eq!(t.into_bytes(), b"hello world");
+
+    let s = String::with_capacity_in(10, System);
+    assert!(s.capacity() >= 10);
+}
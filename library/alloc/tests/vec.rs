@@ -1,3 +1,4 @@
+use std::alloc::System;
 use std::borrow::Cow;
 use std::cell::Cell;
 use std::collections::TryReserveError::*;
@@ -1539,6 +1540,28 @@ fn test_try_reserve_exact() {
     }
 }
 
+#[test]
+fn test_try_push() {
+    let mut v: Vec<u32> = Vec::try_with_capacity(2).unwrap();
+    assert!(v.capacity() >= 2);
+    for i in 0..10 {
+        v.try_push(i).unwrap();
+    }
+    v.try_insert(0, 10).unwrap();
+    v.try_extend_from_slice(&[11, 12]).unwrap();
+    assert_eq!(v, [10, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 11, 12]);
+
+    let strings = vec![String::from("a"), String::from("b")];
+    let mut cloned = Vec::new();
+    cloned.try_extend_from_slice(&strings).unwrap();
+    assert_eq!(cloned, strings);
+
+    if let Err(CapacityOverflow) = Vec::<u32>::try_with_capacity(usize::MAX) {
+    } else {
+        panic!("usize::MAX u32s should trigger an overflow!")
+    }
+}
+
 #[test]
 fn test_stable_pointers() {
     /// Pull an element from the iterator, then drop it.
@@ -1954,3 +1977,22 @@ fn test_vec_swap() {
     assert_eq!(a[0], 42);
     assert_eq!(n, 0);
 }
+
+#[test]
+fn test_vec_new_in() {
+    let mut v = Vec::new_in(System);
+    assert_eq!(v.capacity(), 0);
+    v.extend_from_slice(&[1, 2, 3]);
+    v.push(4);
+
+    let w = v.clone();
+    assert_eq!(w, [1, 2, 3, 4]);
+
+    let b = w.into_boxed_slice();
+    assert_eq!(&*b, &[1, 2, 3, 4]);
+
+    let mut v = Vec::with_capacity_in(10, System);
+    assert!(v.capacity() >= 10);
+    v.push(String::from("a"));
+    assert_eq!(v.len(), 1);
+}
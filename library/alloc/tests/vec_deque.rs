@@ -1357,6 +1357,22 @@ fn test_try_reserve_exact() {
     }
 }
 
+#[test]
+fn test_try_push() {
+    let mut deq: VecDeque<u32> = VecDeque::try_with_capacity(1).unwrap();
+    for i in 0..10 {
+        deq.try_push_back(i).unwrap();
+        deq.try_push_front(i).unwrap();
+    }
+    assert_eq!(deq.len(), 20);
+    assert!(deq.iter().copied().eq((0..10).rev().chain(0..10)));
+
+    if let Err(CapacityOverflow) = VecDeque::<u32>::try_with_capacity(usize::MAX) {
+    } else {
+        panic!("usize::MAX should trigger an overflow!")
+    }
+}
+
 #[test]
 fn test_rotate_nop() {
     let mut v: VecDeque<_> = (0..10).collect();
@@ -1728,3 +1744,23 @@ fn test_zero_sized_push() {
         }
     }
 }
+
+#[test]
+fn test_new_in() {
+    use std::alloc::System;
+
+    let mut d = VecDeque::new_in(System);
+    assert!(d.is_empty());
+    d.push_back(2);
+    d.push_front(1);
+    d.push_back(3);
+    assert_eq!(d.pop_front(), Some(1));
+
+    let e = d.clone();
+    assert_eq!(e, d);
+    let v: Vec<i32, System> = e.into();
+    assert_eq!(v, [2, 3]);
+
+    let d = VecDeque::<u8, _>::with_capacity_in(10, System);
+    assert!(d.capacity() >= 10);
+}
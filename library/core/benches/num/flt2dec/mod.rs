@@ -1,6 +1,7 @@
 mod strategy {
     mod dragon;
     mod grisu;
+    mod ryu;
 }
 
 use core::num::flt2dec::MAX_SIG_DIGITS;
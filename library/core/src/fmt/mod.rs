@@ -421,7 +421,10 @@ impl<'a> Arguments<'a> {
     ///
     /// assert_eq!(format_args!("hello").as_str(), Some("hello"));
     /// assert_eq!(format_args!("").as_str(), Some(""));
-    /// assert_eq!(format_args!("{}", 1).as_str(), None);
+    /// assert_eq!(format_args!("{}", "literal").as_str(), Some("literal"));
+    ///
+    /// let x = 1;
+    /// assert_eq!(format_args!("{}", x).as_str(), None);
     /// ```
     #[unstable(feature = "fmt_as_str", issue = "74442")]
     #[inline]
@@ -1425,6 +1428,13 @@ fn write_bytes(buf: &mut dyn Write, s: &[u8]) -> Result {
             buf.write_str(unsafe { str::from_utf8_unchecked(s) })
         }
 
+        // Most numbers fit in a small buffer, and a single `write_str` is
+        // considerably cheaper than one per part.
+        let mut out = [0; 64];
+        if let Some(len) = formatted.write(&mut out) {
+            return write_bytes(self.buf, &out[..len]);
+        }
+
         if !formatted.sign.is_empty() {
             self.buf.write_str(formatted.sign)?;
         }
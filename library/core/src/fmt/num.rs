@@ -209,8 +209,8 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 macro_rules! impl_Display {
     ($($t:ident),* as $u:ident via $conv_fn:ident named $name:ident) => {
         fn $name(mut n: $u, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            // 2^128 is about 3*10^38, so 39 gives an extra byte of space
-            let mut buf = [MaybeUninit::<u8>::uninit(); 39];
+            // 2^128 is about 3*10^38, so 39 bytes hold every digit, plus one for the sign
+            let mut buf = [MaybeUninit::<u8>::uninit(); 40];
             let mut curr = buf.len() as isize;
             let buf_ptr = MaybeUninit::slice_as_mut_ptr(&mut buf);
             let lut_ptr = DEC_DIGITS_LUT.as_ptr();
@@ -218,7 +218,7 @@ fn $name(mut n: $u, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::Re
             // SAFETY: Since `d1` and `d2` are always less than or equal to `198`, we
             // can copy from `lut_ptr[d1..d1 + 1]` and `lut_ptr[d2..d2 + 1]`. To show
             // that it's OK to copy into `buf_ptr`, notice that at the beginning
-            // `curr == buf.len() == 39 > log(n)` since `n < 2^128 < 10^39`, and at
+            // `curr == buf.len() == 40 > log(n)` since `n < 2^128 < 10^39`, and at
             // each step this is kept the same as `n` is divided. Since `n` is always
             // non-negative, this means that `curr > 0` so `buf_ptr[curr..curr + 1]`
             // is safe to access.
@@ -264,13 +264,29 @@ fn $name(mut n: $u, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::Re
                 }
             }
 
+            // Without any width or flags there is nothing to pad, so the sign can go
+            // straight into the buffer and the whole number is written in one go.
+            let fast_path = f.width.is_none() && f.flags == 0;
+            if fast_path && !is_nonnegative {
+                curr -= 1;
+                // SAFETY: at most 39 digits were written into the 40 byte `buf`, so
+                // `curr >= 0` still holds.
+                unsafe {
+                    *buf_ptr.offset(curr) = b'-';
+                }
+            }
+
             // SAFETY: `curr` > 0 (since we made `buf` large enough), and all the chars are valid
             // UTF-8 since `DEC_DIGITS_LUT` is
             let buf_slice = unsafe {
                 str::from_utf8_unchecked(
                     slice::from_raw_parts(buf_ptr.offset(curr), buf.len() - curr as usize))
             };
-            f.pad_integral(is_nonnegative, "", buf_slice)
+            if fast_path {
+                f.buf.write_str(buf_slice)
+            } else {
+                f.pad_integral(is_nonnegative, "", buf_slice)
+            }
         }
 
         $(#[stable(feature = "rust1", since = "1.0.0")]
@@ -0,0 +1,69 @@
+use crate::fmt;
+use crate::future::Future;
+use crate::pin::Pin;
+use crate::task::{Context, Poll};
+
+/// Creates a future that polls two futures with the same output, and
+/// completes with the output of whichever finishes first.
+///
+/// The futures are polled in order, so if both are ready at the same time,
+/// the output of `a` is returned. The future that loses the race is dropped
+/// along with the [`Select`] without being polled again.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(future_select)]
+/// # async fn run() {
+/// use core::future::{self, select};
+///
+/// let winner = select(future::pending(), future::ready("ready"));
+/// assert_eq!(winner.await, "ready");
+/// # };
+/// ```
+#[unstable(feature = "future_select", issue = "none")]
+pub fn select<A, B>(a: A, b: B) -> Select<A, B>
+where
+    A: Future,
+    B: Future<Output = A::Output>,
+{
+    Select { a, b }
+}
+
+/// A future that completes with the output of the first of two futures
+/// to finish.
+///
+/// This `struct` is created by [`select()`]. See its
+/// documentation for more.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[unstable(feature = "future_select", issue = "none")]
+pub struct Select<A, B> {
+    a: A,
+    b: B,
+}
+
+#[unstable(feature = "future_select", issue = "none")]
+impl<A: fmt::Debug, B: fmt::Debug> fmt::Debug for Select<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Select").field("a", &self.a).field("b", &self.b).finish()
+    }
+}
+
+#[unstable(feature = "future_select", issue = "none")]
+impl<A, B> Future for Select<A, B>
+where
+    A: Future,
+    B: Future<Output = A::Output>,
+{
+    type Output = A::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<A::Output> {
+        // SAFETY: `Select` is only `Unpin` if both fields are, it has no `Drop`
+        // impl, and the fields are never moved out, so pinning is structural.
+        let this = unsafe { self.get_unchecked_mut() };
+        if let Poll::Ready(output) = unsafe { Pin::new_unchecked(&mut this.a) }.poll(cx) {
+            return Poll::Ready(output);
+        }
+        unsafe { Pin::new_unchecked(&mut this.b) }.poll(cx)
+    }
+}
@@ -0,0 +1,52 @@
+use crate::future::Future;
+use crate::pin::Pin;
+use crate::task::{Context, Poll};
+
+/// Creates a future that yields control back to the executor once.
+///
+/// The first time it is polled, the future wakes its task and returns
+/// [`Poll::Pending`], giving other tasks a chance to run. It completes the
+/// next time it is polled.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(future_yield_now)]
+/// # async fn run() {
+/// use core::future::yield_now;
+///
+/// for _ in 0..10 {
+///     // ... do a slice of a long running computation
+///     yield_now().await;
+/// }
+/// # };
+/// ```
+#[unstable(feature = "future_yield_now", issue = "none")]
+pub fn yield_now() -> YieldNow {
+    YieldNow { yielded: false }
+}
+
+/// A future that yields control back to the executor once.
+///
+/// This `struct` is created by [`yield_now()`]. See its
+/// documentation for more.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[unstable(feature = "future_yield_now", issue = "none")]
+pub struct YieldNow {
+    yielded: bool,
+}
+
+#[unstable(feature = "future_yield_now", issue = "none")]
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
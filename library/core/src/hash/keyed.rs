@@ -0,0 +1,333 @@
+//! A fast keyed hash function, the algorithm behind `std`'s `DefaultHasher`.
+
+use crate::fmt;
+use crate::hash::Hasher;
+
+/// A fast keyed hasher.
+///
+/// This is the hashing function used by the standard library's
+/// `collections::HashMap` by default, through `RandomState`. It is several
+/// times faster than SipHash 1-3 on short inputs, such as integer keys.
+///
+/// On targets compiled with AES support (the `aes` target feature on x86 and
+/// x86_64, `crypto` on AArch64) the input is mixed with hardware AES rounds.
+/// Elsewhere a portable construction based on folded 64-bit multiplications
+/// is used. The choice is made when `core` is compiled, not at runtime, so
+/// the AES rounds are only used if they are enabled for the whole build. The
+/// two produce different hashes, and neither is guaranteed to stay the same
+/// across releases.
+///
+/// # Security
+///
+/// When both keys are secret and chosen at random, as `RandomState` does,
+/// an attacker who cannot observe hash values cannot construct inputs that
+/// collide more often than chance, which defeats hash flooding attacks.
+///
+/// Unlike SipHash, this function is not designed to be a pseudorandom
+/// function. If hash values are revealed to an untrusted party, for example
+/// through timing or through the iteration order of a long-lived map, the
+/// keys might be recovered from them. Do not use it where its output must be
+/// unpredictable, nor for any cryptographic purpose.
+#[unstable(feature = "keyed_hasher", issue = "none")]
+#[derive(Clone)]
+pub struct KeyedHasher {
+    state: imp::State,
+}
+
+impl KeyedHasher {
+    /// Creates a `KeyedHasher` that is keyed off the provided keys.
+    #[inline]
+    #[unstable(feature = "keyed_hasher", issue = "none")]
+    pub fn new_with_keys(key0: u64, key1: u64) -> KeyedHasher {
+        KeyedHasher { state: imp::State::new(key0, key1) }
+    }
+}
+
+#[unstable(feature = "keyed_hasher", issue = "none")]
+impl Hasher for KeyedHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.write(bytes)
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.state.write_u64(i as u64)
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.state.write_u64(i as u64)
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.state.write_u64(i as u64)
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.state.write_u64(i)
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.state.write_u128(i)
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.state.write_u64(i as u64)
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.state.finish()
+    }
+}
+
+#[unstable(feature = "keyed_hasher", issue = "none")]
+impl fmt::Debug for KeyedHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The state is derived from the keys, so keep it out of logs.
+        f.pad("KeyedHasher { .. }")
+    }
+}
+
+#[inline]
+fn read_u16(bytes: &[u8], at: usize) -> u16 {
+    let mut buf = [0; 2];
+    buf.copy_from_slice(&bytes[at..at + 2]);
+    u16::from_le_bytes(buf)
+}
+
+#[inline]
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    let mut buf = [0; 4];
+    buf.copy_from_slice(&bytes[at..at + 4]);
+    u32::from_le_bytes(buf)
+}
+
+#[inline]
+fn read_u64(bytes: &[u8], at: usize) -> u64 {
+    let mut buf = [0; 8];
+    buf.copy_from_slice(&bytes[at..at + 8]);
+    u64::from_le_bytes(buf)
+}
+
+/// Feeds `bytes` to `update` as 128-bit blocks.
+///
+/// Inputs longer than 16 bytes are covered by whole blocks, the first of which
+/// is the (possibly overlapping) last 16 bytes. Shorter inputs are read as a
+/// single block, using overlapping loads instead of a byte-by-byte loop. The
+/// caller mixes in the length, which tells the overlapping cases apart.
+#[inline]
+fn for_each_block(bytes: &[u8], mut update: impl FnMut(u64, u64)) {
+    let len = bytes.len();
+    if len > 16 {
+        update(read_u64(bytes, len - 16), read_u64(bytes, len - 8));
+        let mut at = 0;
+        while len - at > 16 {
+            update(read_u64(bytes, at), read_u64(bytes, at + 8));
+            at += 16;
+        }
+    } else if len > 8 {
+        update(read_u64(bytes, 0), read_u64(bytes, len - 8));
+    } else if len >= 4 {
+        update(read_u32(bytes, 0) as u64, read_u32(bytes, len - 4) as u64);
+    } else if len >= 2 {
+        update(read_u16(bytes, 0) as u64, bytes[len - 1] as u64);
+    } else if len == 1 {
+        update(bytes[0] as u64, bytes[0] as u64);
+    } else {
+        update(0, 0);
+    }
+}
+
+/// The first digits of pi, used to derive independent words from the keys,
+/// and to keep the state away from zero when the keys are.
+const PI: [u64; 4] =
+    [0x243f_6a88_85a3_08d3, 0x1319_8a2e_0370_7344, 0xa409_3822_299f_31d0, 0x082e_fa98_ec4e_6c89];
+
+#[cfg(any(
+    all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "aes"),
+    all(target_arch = "aarch64", target_feature = "crypto"),
+))]
+mod imp {
+    #[cfg(target_arch = "aarch64")]
+    use crate::arch::aarch64::*;
+    #[cfg(target_arch = "x86")]
+    use crate::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use crate::arch::x86_64::*;
+    use crate::mem::transmute;
+
+    use super::{for_each_block, PI};
+
+    /// One AES encryption round.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[inline(always)]
+    fn aesenc(value: u128, key: u128) -> u128 {
+        // SAFETY: this module is only compiled with the `aes` target feature.
+        unsafe { transmute(_mm_aesenc_si128(transmute(value), transmute(key))) }
+    }
+
+    /// One AES decryption round.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[inline(always)]
+    fn aesdec(value: u128, key: u128) -> u128 {
+        // SAFETY: this module is only compiled with the `aes` target feature.
+        unsafe { transmute(_mm_aesdec_si128(transmute(value), transmute(key))) }
+    }
+
+    /// One AES encryption round. AESE adds the round key before substituting
+    /// rather than after mixing like AESENC, so it gets a zero key instead.
+    #[cfg(target_arch = "aarch64")]
+    #[inline(always)]
+    fn aesenc(value: u128, key: u128) -> u128 {
+        // SAFETY: this module is only compiled with the `crypto` target feature.
+        let mixed: u128 =
+            unsafe { transmute(vaesmcq_u8(vaeseq_u8(transmute(value), transmute(0u128)))) };
+        mixed ^ key
+    }
+
+    /// One AES decryption round, see `aesenc`.
+    #[cfg(target_arch = "aarch64")]
+    #[inline(always)]
+    fn aesdec(value: u128, key: u128) -> u128 {
+        // SAFETY: this module is only compiled with the `crypto` target feature.
+        let mixed: u128 =
+            unsafe { transmute(vaesimcq_u8(vaesdq_u8(transmute(value), transmute(0u128)))) };
+        mixed ^ key
+    }
+
+    /// Adds the two 64-bit lanes separately, so no carry crosses between them.
+    #[inline(always)]
+    fn add_by_64s(a: u128, b: u128) -> u128 {
+        let lo = (a as u64).wrapping_add(b as u64);
+        let hi = ((a >> 64) as u64).wrapping_add((b >> 64) as u64);
+        (hi as u128) << 64 | lo as u128
+    }
+
+    /// Each block goes through an AES round in `enc` and is added into `sum`,
+    /// two independent chains that `finish` folds together with `key`.
+    #[derive(Clone)]
+    pub(super) struct State {
+        enc: u128,
+        sum: u128,
+        key: u128,
+    }
+
+    impl State {
+        #[inline]
+        pub(super) fn new(key0: u64, key1: u64) -> State {
+            let enc = ((key1 ^ PI[3]) as u128) << 64 | (key0 ^ PI[2]) as u128;
+            let sum = aesenc(enc, (PI[1] as u128) << 64 | PI[0] as u128);
+            State { enc, sum, key: enc ^ sum }
+        }
+
+        #[inline(always)]
+        fn update(&mut self, block: u128) {
+            self.enc = aesdec(self.enc, block);
+            self.sum = add_by_64s(self.sum, block).rotate_left(23);
+        }
+
+        #[inline]
+        pub(super) fn write(&mut self, bytes: &[u8]) {
+            self.enc = add_by_64s(self.enc, bytes.len() as u128);
+            for_each_block(bytes, |lo, hi| self.update((hi as u128) << 64 | lo as u128));
+        }
+
+        #[inline]
+        pub(super) fn write_u64(&mut self, i: u64) {
+            self.update(i as u128)
+        }
+
+        #[inline]
+        pub(super) fn write_u128(&mut self, i: u128) {
+            self.update(i)
+        }
+
+        #[inline]
+        pub(super) fn finish(&self) -> u64 {
+            let combined = aesenc(self.sum, self.enc);
+            aesdec(aesdec(combined, self.key), combined) as u64
+        }
+    }
+}
+
+#[cfg(not(any(
+    all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "aes"),
+    all(target_arch = "aarch64", target_feature = "crypto"),
+)))]
+mod imp {
+    use super::{for_each_block, PI};
+
+    /// The multiplier of PCG's 64-bit LCG, an odd constant with well spread bits.
+    const MULTIPLE: u64 = 6364136223846793005;
+
+    /// Multiplies to 128 bits and folds the halves together with xor, which
+    /// spreads every input bit over the whole output.
+    #[inline(always)]
+    fn folded_multiply(s: u64, by: u64) -> u64 {
+        let result = (s as u128).wrapping_mul(by as u128);
+        (result as u64) ^ ((result >> 64) as u64)
+    }
+
+    /// Words go through one folded multiply into `buffer`. Blocks of two
+    /// words are first multiplied with each other, each xored with a key,
+    /// so that an attacker cannot choose a block that cancels out.
+    #[derive(Clone)]
+    pub(super) struct State {
+        buffer: u64,
+        pad: u64,
+        extra_keys: [u64; 2],
+    }
+
+    impl State {
+        #[inline]
+        pub(super) fn new(key0: u64, key1: u64) -> State {
+            State {
+                buffer: key0 ^ PI[2],
+                pad: key1 ^ PI[3],
+                extra_keys: [
+                    folded_multiply(key0 ^ PI[0], MULTIPLE),
+                    folded_multiply(key1 ^ PI[1], MULTIPLE),
+                ],
+            }
+        }
+
+        #[inline(always)]
+        fn update(&mut self, word: u64) {
+            self.buffer = folded_multiply(word ^ self.buffer, MULTIPLE);
+        }
+
+        #[inline(always)]
+        fn large_update(&mut self, lo: u64, hi: u64) {
+            let combined = folded_multiply(lo ^ self.extra_keys[0], hi ^ self.extra_keys[1]);
+            self.buffer = (self.buffer.wrapping_add(self.pad) ^ combined).rotate_left(23);
+        }
+
+        #[inline]
+        pub(super) fn write(&mut self, bytes: &[u8]) {
+            self.buffer = self.buffer.wrapping_add(bytes.len() as u64).wrapping_mul(MULTIPLE);
+            for_each_block(bytes, |lo, hi| self.large_update(lo, hi));
+        }
+
+        #[inline]
+        pub(super) fn write_u64(&mut self, i: u64) {
+            self.update(i)
+        }
+
+        #[inline]
+        pub(super) fn write_u128(&mut self, i: u128) {
+            self.large_update(i as u64, (i >> 64) as u64)
+        }
+
+        #[inline]
+        pub(super) fn finish(&self) -> u64 {
+            let rot = (self.buffer & 63) as u32;
+            folded_multiply(self.buffer, self.pad).rotate_left(rot)
+        }
+    }
+}
@@ -91,6 +91,10 @@
 #[doc(hidden)]
 pub use self::sip::SipHasher13;
 
+#[unstable(feature = "keyed_hasher", issue = "none")]
+pub use self::keyed::KeyedHasher;
+
+mod keyed;
 mod sip;
 
 /// A hashable type.
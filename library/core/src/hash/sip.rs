@@ -9,8 +9,10 @@
 
 /// An implementation of SipHash 1-3.
 ///
-/// This is currently the default hashing function used by standard library
-/// (e.g., `collections::HashMap` uses it by default).
+/// This used to be the default hashing function used by the standard library
+/// (e.g., `collections::HashMap`), which now uses [`KeyedHasher`].
+///
+/// [`KeyedHasher`]: super::KeyedHasher
 ///
 /// See: <https://131002.net/siphash>
 #[unstable(feature = "hashmap_internals", issue = "none")]
@@ -262,6 +262,7 @@
 
 /* Async */
 pub mod future;
+pub mod stream;
 pub mod task;
 
 /* Heap memory allocator trait */
@@ -276,6 +277,9 @@
 #[stable(feature = "core_primitive", since = "1.43.0")]
 pub mod primitive;
 
+#[unstable(feature = "portable_simd", issue = "none")]
+pub mod simd;
+
 // Pull in the `core_arch` crate directly into libcore. The contents of
 // `core_arch` are in a different repository: rust-lang/stdarch.
 //
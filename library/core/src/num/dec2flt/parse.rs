@@ -1,124 +1,212 @@
-//! Validating and decomposing a decimal string of the form:
+//! Functions to parse floating-point numbers.
+//!
+//! The accepted grammar is:
 //!
 //! `(digits | digits? '.'? digits?) (('e' | 'E') ('+' | '-')? digits)?`
 //!
 //! In other words, standard floating-point syntax, with two exceptions: No sign, and no
 //! handling of "inf" and "NaN". These are handled by the driver function (super::dec2flt).
 //!
-//! Although recognizing valid inputs is relatively easy, this module also has to reject the
-//! countless invalid variations, never panic, and perform numerous checks that the other
-//! modules rely on to not panic (or overflow) in turn.
-//! To make matters worse, all that happens in a single pass over the input.
-//! So, be careful when modifying anything, and double-check with the other modules.
-use self::ParseResult::{Invalid, ShortcutToInf, ShortcutToZero, Valid};
-use super::num;
-
-#[derive(Debug)]
-pub enum Sign {
-    Positive,
-    Negative,
-}
+//! There is no limit on the number of digits. Only the first 19 significant digits, which
+//! always fit into a `u64`, are stored in the returned `Number`; if any further digits were
+//! present, `many_digits` is set and the driver decides whether they affect rounding.
+
+use crate::num::dec2flt::common::{is_8digits, ByteSlice};
+use crate::num::dec2flt::number::Number;
 
-#[derive(Debug, PartialEq, Eq)]
-/// The interesting parts of a decimal string.
-pub struct Decimal<'a> {
-    pub integral: &'a [u8],
-    pub fractional: &'a [u8],
-    /// The decimal exponent, guaranteed to have fewer than 18 decimal digits.
-    pub exp: i64,
+const MIN_19DIGIT_INT: u64 = 100_0000_0000_0000_0000;
+
+/// Parse 8 digits, loaded as bytes in little-endian order.
+///
+/// This uses the trick where every digit is in [0x30, 0x39],
+/// and therefore can be parsed in 3 multiplications, much
+/// faster than the normal 8.
+///
+/// This is based off the algorithm described in "Fast numeric string to
+/// int", available here: <https://johnnylee-sde.github.io/Fast-numeric-string-to-int/>.
+fn parse_8digits(mut v: u64) -> u64 {
+    const MASK: u64 = 0x0000_00FF_0000_00FF;
+    const MUL1: u64 = 0x000F_4240_0000_0064;
+    const MUL2: u64 = 0x0000_2710_0000_0001;
+    v -= 0x3030_3030_3030_3030;
+    v = (v * 10) + (v >> 8); // will not overflow, fits in 63 bits
+    let v1 = (v & MASK).wrapping_mul(MUL1);
+    let v2 = ((v >> 16) & MASK).wrapping_mul(MUL2);
+    ((v1.wrapping_add(v2) >> 32) as u32) as u64
 }
 
-impl<'a> Decimal<'a> {
-    pub fn new(integral: &'a [u8], fractional: &'a [u8], exp: i64) -> Decimal<'a> {
-        Decimal { integral, fractional, exp }
+/// Parse digits until a non-digit character is found.
+fn try_parse_digits(mut s: &[u8], mut x: u64) -> (&[u8], u64) {
+    // may cause overflows, to be handled later
+    while s.len() >= 8 {
+        let num = s.read_u64();
+        if is_8digits(num) {
+            x = x.wrapping_mul(1_0000_0000).wrapping_add(parse_8digits(num));
+            s = &s[8..];
+        } else {
+            break;
+        }
     }
-}
 
-#[derive(Debug, PartialEq, Eq)]
-pub enum ParseResult<'a> {
-    Valid(Decimal<'a>),
-    ShortcutToInf,
-    ShortcutToZero,
-    Invalid,
+    s = s.parse_digits(|digit| {
+        x = x.wrapping_mul(10).wrapping_add(digit as _);
+    });
+
+    (s, x)
 }
 
-/// Checks if the input string is a valid floating point number and if so, locate the integral
-/// part, the fractional part, and the exponent in it. Does not handle signs.
-pub fn parse_decimal(s: &str) -> ParseResult<'_> {
-    if s.is_empty() {
-        return Invalid;
-    }
+/// Parse up to 19 digits (the max that can be stored in a 64-bit integer).
+fn try_parse_19digits(s_ref: &mut &[u8], x: &mut u64) {
+    let mut s = *s_ref;
 
-    let s = s.as_bytes();
-    let (integral, s) = eat_digits(s);
+    while *x < MIN_19DIGIT_INT {
+        if let Some((&c, s_next)) = s.split_first() {
+            let digit = c.wrapping_sub(b'0');
 
-    match s.first() {
-        None => Valid(Decimal::new(integral, b"", 0)),
-        Some(&b'e' | &b'E') => {
-            if integral.is_empty() {
-                return Invalid; // No digits before 'e'
+            if digit < 10 {
+                *x = (*x * 10) + digit as u64; // no overflows here
+                s = s_next;
+            } else {
+                break;
             }
+        } else {
+            break;
+        }
+    }
+
+    *s_ref = s;
+}
+
+/// Parse the scientific notation component of a float.
+fn parse_scientific(s_ref: &mut &[u8]) -> Option<i64> {
+    // Exponents this large always produce zero or infinity: saturate well before `i64`
+    // could overflow, but far enough out that no realistic number of leading or trailing
+    // zeros can bring the value back into the finite range.
+    const MAX_EXPONENT: i64 = 0x1000_0000_0000;
 
-            parse_exp(integral, b"", &s[1..])
+    let mut exponent = 0_i64;
+    let mut negative = false;
+
+    let mut s = *s_ref;
+
+    if let Some((&c, s_next)) = s.split_first() {
+        negative = c == b'-';
+        if c == b'-' || c == b'+' {
+            s = s_next;
         }
-        Some(&b'.') => {
-            let (fractional, s) = eat_digits(&s[1..]);
-            if integral.is_empty() && fractional.is_empty() {
-                // We require at least a single digit before or after the point.
-                return Invalid;
-            }
+    }
 
-            match s.first() {
-                None => Valid(Decimal::new(integral, fractional, 0)),
-                Some(&b'e' | &b'E') => parse_exp(integral, fractional, &s[1..]),
-                _ => Invalid, // Trailing junk after fractional part
+    if matches!(s.first(), Some(&x) if x.is_ascii_digit()) {
+        *s_ref = s.parse_digits(|digit| {
+            // no overflows here, saturate well before overflow
+            if exponent < MAX_EXPONENT {
+                exponent = 10 * exponent + digit as i64;
             }
-        }
-        _ => Invalid, // Trailing junk after first digit string
+        });
+        if negative { Some(-exponent) } else { Some(exponent) }
+    } else {
+        *s_ref = s;
+        None
     }
 }
 
-/// Carves off decimal digits up to the first non-digit character.
-fn eat_digits(s: &[u8]) -> (&[u8], &[u8]) {
-    let mut i = 0;
-    while i < s.len() && b'0' <= s[i] && s[i] <= b'9' {
-        i += 1;
+/// Parse a partial, non-special floating point number.
+///
+/// This creates a representation of the float as the
+/// significant digits and the decimal exponent.
+fn parse_partial_number(mut s: &[u8], negative: bool) -> Option<(Number, usize)> {
+    debug_assert!(!s.is_empty());
+
+    // parse initial digits before dot
+    let mut mantissa = 0_u64;
+    let start = s;
+    let tmp = try_parse_digits(s, mantissa);
+    s = tmp.0;
+    mantissa = tmp.1;
+    let mut n_digits = s.offset_from(start);
+
+    // handle dot with the following digits
+    let mut n_after_dot = 0;
+    let mut exponent = 0_i64;
+    let int_end = s;
+
+    if let Some((&b'.', s_next)) = s.split_first() {
+        s = s_next;
+        let before = s;
+        let tmp = try_parse_digits(s, mantissa);
+        s = tmp.0;
+        mantissa = tmp.1;
+        n_after_dot = s.offset_from(before);
+        exponent = -n_after_dot as i64;
     }
-    (&s[..i], &s[i..])
-}
 
-/// Exponent extraction and error checking.
-fn parse_exp<'a>(integral: &'a [u8], fractional: &'a [u8], rest: &'a [u8]) -> ParseResult<'a> {
-    let (sign, rest) = match rest.first() {
-        Some(&b'-') => (Sign::Negative, &rest[1..]),
-        Some(&b'+') => (Sign::Positive, &rest[1..]),
-        _ => (Sign::Positive, rest),
-    };
-    let (mut number, trailing) = eat_digits(rest);
-    if !trailing.is_empty() {
-        return Invalid; // Trailing junk after exponent
+    n_digits += n_after_dot;
+    if n_digits == 0 {
+        return None;
     }
-    if number.is_empty() {
-        return Invalid; // Empty exponent
+
+    // handle scientific format
+    let mut exp_number = 0_i64;
+    if let Some((&c, s_next)) = s.split_first() {
+        if c == b'e' || c == b'E' {
+            s = s_next;
+            // If None, we have no trailing digits after exponent, or an invalid float.
+            exp_number = parse_scientific(&mut s)?;
+            exponent += exp_number;
+        }
     }
-    // At this point, we certainly have a valid string of digits. It may be too long to put into
-    // an `i64`, but if it's that huge, the input is certainly zero or infinity. Since each zero
-    // in the decimal digits only adjusts the exponent by +/- 1, at exp = 10^18 the input would
-    // have to be 17 exabyte (!) of zeros to get even remotely close to being finite.
-    // This is not exactly a use case we need to cater to.
-    while number.first() == Some(&b'0') {
-        number = &number[1..];
+
+    let len = s.offset_from(start) as _;
+
+    // handle uncommon case with many digits
+    if n_digits <= 19 {
+        return Some((Number { exponent, mantissa, negative, many_digits: false }, len));
     }
-    if number.len() >= 18 {
-        return match sign {
-            Sign::Positive => ShortcutToInf,
-            Sign::Negative => ShortcutToZero,
-        };
+
+    n_digits -= 19;
+    let mut many_digits = false;
+    let mut p = start;
+    while let Some((&c, p_next)) = p.split_first() {
+        if c == b'.' || c == b'0' {
+            n_digits -= c.saturating_sub(b'0' - 1) as isize;
+            p = p_next;
+        } else {
+            break;
+        }
+    }
+    if n_digits > 0 {
+        // at this point we have more than 19 significant digits, let's try again
+        many_digits = true;
+        mantissa = 0;
+        let mut s = start;
+        try_parse_19digits(&mut s, &mut mantissa);
+        exponent = if mantissa >= MIN_19DIGIT_INT {
+            // big int
+            int_end.offset_from(s)
+        } else {
+            // The next byte must be present and be '.'. We know this is true
+            // because we had more than 19 digits previously, so we overflowed
+            // a 64-bit integer, but parsing only the integral digits produced
+            // less than 19 digits. That means we must have a decimal point,
+            // and at least 1 fractional digit.
+            s = &s[1..];
+            let before = s;
+            try_parse_19digits(&mut s, &mut mantissa);
+            -s.offset_from(before)
+        } as i64;
+        // add back the explicit part
+        exponent += exp_number;
+    }
+
+    Some((Number { exponent, mantissa, negative, many_digits }, len))
+}
+
+/// Try to parse a non-special floating point number.
+pub fn parse_number(s: &[u8], negative: bool) -> Option<Number> {
+    if let Some((float, rest)) = parse_partial_number(s, negative) {
+        if rest == s.len() {
+            return Some(float);
+        }
     }
-    let abs_exp = num::from_str_unchecked(number);
-    let e = match sign {
-        Sign::Positive => abs_exp as i64,
-        Sign::Negative => -(abs_exp as i64),
-    };
-    Valid(Decimal::new(integral, fractional, e))
+    None
 }
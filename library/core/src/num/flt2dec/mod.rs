@@ -66,14 +66,19 @@
   representation which is at least guaranteed to be shortest. The variant,
   Grisu3, actively detects if the resulting representation is incorrect.
 
-We implement both algorithms with necessary tweaks to suit our requirements.
+- The "Ryū" algorithm by Ulf Adams only handles the shortest mode. It uses
+  precomputed tables of powers of five to directly compute the shortest
+  representation, and is always correct for IEEE 754 inputs.
+
+We implement all of these algorithms with necessary tweaks to suit our requirements.
 In particular, published literatures are short of the actual implementation
 difficulties like how to avoid arithmetic overflows. Each implementation,
-available in `strategy::dragon` and `strategy::grisu` respectively,
+available in `strategy::dragon`, `strategy::grisu` and `strategy::ryu` respectively,
 extensively describes all necessary justifications and many proofs for them.
 (It is still difficult to follow though. You have been warned.)
 
-Both implementations expose two public functions:
+The Dragon and Grisu implementations expose two public functions (Ryū only
+exposes the former):
 
 - `format_shortest(decoded, buf)`, which always needs at least
   `MAX_SIG_DIGITS` digits of buffer. Implements the shortest mode.
@@ -83,7 +88,9 @@
 
 They try to fill the `u8` buffer with digits and returns the number of digits
 written and the exponent `k`. They are total for all finite `f32` and `f64`
-inputs (Grisu internally falls back to Dragon if necessary).
+inputs. Grisu internally falls back to Dragon when it cannot prove its result
+correct; Ryū never needs to for `f32` and `f64` inputs, and only defers to
+Dragon for a `Decoded` outside the range its tables cover.
 
 The rendered digits are formatted into the actual string form with
 four functions:
@@ -133,6 +140,7 @@
 pub mod strategy {
     pub mod dragon;
     pub mod grisu;
+    pub mod ryu;
 }
 
 /// The minimum size of buffer necessary for the shortest mode.
@@ -456,7 +464,7 @@ fn determine_sign(sign: Sign, decoded: &FullDecoded, negative: bool) -> &'static
 ///
 /// `format_shortest` should be the underlying digit-generation function.
 /// It should return the part of the buffer that it initialized.
-/// You probably would want `strategy::grisu::format_shortest` for this.
+/// You probably would want `strategy::ryu::format_shortest` for this.
 ///
 /// `frac_digits` can be less than the number of actual fractional digits in `v`;
 /// it will be ignored and full digits will be printed. It is only used to print
@@ -530,7 +538,7 @@ pub fn to_shortest_str<'a, T, F>(
 ///
 /// `format_shortest` should be the underlying digit-generation function.
 /// It should return the part of the buffer that it initialized.
-/// You probably would want `strategy::grisu::format_shortest` for this.
+/// You probably would want `strategy::ryu::format_shortest` for this.
 ///
 /// The `dec_bounds` is a tuple `(lo, hi)` such that the number is formatted
 /// as decimal only when `10^lo <= V < 10^hi`. Note that this is the *apparent* `V`
@@ -0,0 +1,40 @@
+use super::intrinsics;
+use super::{LaneCount, Mask, Simd, SimdElement, SupportedLaneCount};
+
+macro_rules! lanewise_cmp {
+    ($($(#[$attr:meta])* $name:ident => $intrinsic:ident;)*) => {
+        $(
+            $(#[$attr])*
+            #[unstable(feature = "portable_simd", issue = "none")]
+            #[inline]
+            pub fn $name(self, other: Self) -> Mask<T::Mask, LANES> {
+                // SAFETY: comparing two vectors of `T` yields a vector of `T::Mask` with the
+                // same number of lanes, each of which is all ones or all zeros.
+                unsafe { Mask::from_int_unchecked(intrinsics::$intrinsic(self, other)) }
+            }
+        )*
+    };
+}
+
+impl<T, const LANES: usize> Simd<T, LANES>
+where
+    T: SimdElement + PartialOrd,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    lanewise_cmp! {
+        /// Tests whether each lane of `self` is equal to the matching lane of `other`.
+        lanes_eq => simd_eq;
+        /// Tests whether each lane of `self` is not equal to the matching lane of `other`.
+        lanes_ne => simd_ne;
+        /// Tests whether each lane of `self` is less than the matching lane of `other`.
+        lanes_lt => simd_lt;
+        /// Tests whether each lane of `self` is less than or equal to the matching lane of
+        /// `other`.
+        lanes_le => simd_le;
+        /// Tests whether each lane of `self` is greater than the matching lane of `other`.
+        lanes_gt => simd_gt;
+        /// Tests whether each lane of `self` is greater than or equal to the matching lane
+        /// of `other`.
+        lanes_ge => simd_ge;
+    }
+}
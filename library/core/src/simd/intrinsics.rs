@@ -0,0 +1,78 @@
+//! The platform-independent vector intrinsics that `core::simd` is built on.
+//!
+//! These are type-checked against the vector types they are instantiated with only during
+//! monomorphization, so the safe wrappers in this module tree are responsible for only
+//! ever instantiating them with `#[repr(simd)]` types of matching lane counts.
+
+extern "platform-intrinsic" {
+    /// Lanewise addition; wraps on integer overflow.
+    pub(crate) fn simd_add<T>(x: T, y: T) -> T;
+
+    /// Lanewise subtraction; wraps on integer overflow.
+    pub(crate) fn simd_sub<T>(x: T, y: T) -> T;
+
+    /// Lanewise multiplication; wraps on integer overflow.
+    pub(crate) fn simd_mul<T>(x: T, y: T) -> T;
+
+    /// Lanewise division. Integer division by zero or `MIN / -1` is undefined behavior.
+    pub(crate) fn simd_div<T>(x: T, y: T) -> T;
+
+    /// Lanewise remainder. Integer remainder by zero or `MIN % -1` is undefined behavior.
+    pub(crate) fn simd_rem<T>(x: T, y: T) -> T;
+
+    /// Lanewise shift left. Shifting by the bit width of the element or more is undefined
+    /// behavior.
+    pub(crate) fn simd_shl<T>(x: T, y: T) -> T;
+
+    /// Lanewise shift right, arithmetic for signed and logical for unsigned elements.
+    /// Shifting by the bit width of the element or more is undefined behavior.
+    pub(crate) fn simd_shr<T>(x: T, y: T) -> T;
+
+    pub(crate) fn simd_and<T>(x: T, y: T) -> T;
+    pub(crate) fn simd_or<T>(x: T, y: T) -> T;
+    pub(crate) fn simd_xor<T>(x: T, y: T) -> T;
+
+    /// Lanewise comparisons, returning a vector of integers that are all ones for "true"
+    /// and all zeros for "false".
+    pub(crate) fn simd_eq<T, U>(x: T, y: T) -> U;
+    pub(crate) fn simd_ne<T, U>(x: T, y: T) -> U;
+    pub(crate) fn simd_lt<T, U>(x: T, y: T) -> U;
+    pub(crate) fn simd_le<T, U>(x: T, y: T) -> U;
+    pub(crate) fn simd_gt<T, U>(x: T, y: T) -> U;
+    pub(crate) fn simd_ge<T, U>(x: T, y: T) -> U;
+
+    /// Builds a vector from the lanes of `x` and `y` selected by the constant `idx`, a
+    /// `[u32; N]` where the indices `0..LANES` refer to `x` and `LANES..2 * LANES` to `y`.
+    pub(crate) fn simd_shuffle<T, I, U>(x: T, y: T, idx: I) -> U;
+
+    /// Reads the lanes of `ptr` whose `mask` lane is set, taking the lane of `val` otherwise.
+    pub(crate) fn simd_gather<T, U, V>(val: T, ptr: U, mask: V) -> T;
+
+    /// Writes the lanes of `val` whose `mask` lane is set to the matching lane of `ptr`.
+    pub(crate) fn simd_scatter<T, U, V>(val: T, ptr: U, mask: V);
+
+    /// Picks the lane of `a` where `mask` is set, and the lane of `b` otherwise.
+    pub(crate) fn simd_select<M, T>(mask: M, a: T, b: T) -> T;
+
+    pub(crate) fn simd_fabs<T>(x: T) -> T;
+    pub(crate) fn simd_fmin<T>(x: T, y: T) -> T;
+    pub(crate) fn simd_fmax<T>(x: T, y: T) -> T;
+
+    /// Sums the lanes of `x` in order, starting from `acc`.
+    pub(crate) fn simd_reduce_add_ordered<T, U>(x: T, acc: U) -> U;
+
+    /// Multiplies the lanes of `x` in order, starting from `acc`.
+    pub(crate) fn simd_reduce_mul_ordered<T, U>(x: T, acc: U) -> U;
+
+    pub(crate) fn simd_reduce_min<T, U>(x: T) -> U;
+    pub(crate) fn simd_reduce_max<T, U>(x: T) -> U;
+    pub(crate) fn simd_reduce_and<T, U>(x: T) -> U;
+    pub(crate) fn simd_reduce_or<T, U>(x: T) -> U;
+    pub(crate) fn simd_reduce_xor<T, U>(x: T) -> U;
+
+    /// Returns whether all lanes of the mask `x` are set.
+    pub(crate) fn simd_reduce_all<T>(x: T) -> bool;
+
+    /// Returns whether any lane of the mask `x` is set.
+    pub(crate) fn simd_reduce_any<T>(x: T) -> bool;
+}
@@ -0,0 +1,31 @@
+mod sealed {
+    pub trait Sealed {}
+}
+use sealed::Sealed;
+
+/// A type representing a vector lane count.
+///
+/// Used together with [`SupportedLaneCount`] to restrict [`Simd`](super::Simd) and
+/// [`Mask`](super::Mask) to the lane counts that the backend can lower.
+#[unstable(feature = "portable_simd", issue = "none")]
+#[derive(Debug)]
+pub struct LaneCount<const LANES: usize>;
+
+/// Statically guarantees that a lane count is supported.
+///
+/// This trait is sealed: the supported lane counts are the powers of two from 1 to 64.
+#[unstable(feature = "portable_simd", issue = "none")]
+pub trait SupportedLaneCount: Sealed {}
+
+impl<const LANES: usize> Sealed for LaneCount<LANES> {}
+
+macro_rules! supported_lane_count {
+    ($($lanes:literal)*) => {
+        $(
+            #[unstable(feature = "portable_simd", issue = "none")]
+            impl SupportedLaneCount for LaneCount<$lanes> {}
+        )*
+    };
+}
+
+supported_lane_count! { 1 2 4 8 16 32 64 }
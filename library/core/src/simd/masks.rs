@@ -0,0 +1,374 @@
+use crate::fmt;
+use crate::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+use super::intrinsics;
+use super::{LaneCount, Simd, SimdElement, SupportedLaneCount};
+
+mod sealed {
+    pub trait Sealed {}
+}
+use sealed::Sealed;
+
+/// Marker trait for types that may be used as SIMD mask elements.
+///
+/// A mask element is a signed integer which is `-1` (all bits set) for a selected lane
+/// and `0` for an unselected one. This trait is sealed and cannot be implemented outside
+/// of `core`.
+#[unstable(feature = "portable_simd", issue = "none")]
+pub trait MaskElement: SimdElement<Mask = Self> + PartialEq + Sealed {
+    /// The value of a selected lane.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    const TRUE: Self;
+
+    /// The value of an unselected lane.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    const FALSE: Self;
+}
+
+macro_rules! mask_element {
+    ($($ty:ty)*) => {
+        $(
+            impl Sealed for $ty {}
+
+            #[unstable(feature = "portable_simd", issue = "none")]
+            impl MaskElement for $ty {
+                const TRUE: Self = -1;
+                const FALSE: Self = 0;
+            }
+        )*
+    };
+}
+
+mask_element! { i8 i16 i32 i64 isize }
+
+/// A SIMD vector mask of `LANES` lanes, each of which is either selected or not.
+///
+/// Masks are produced by lanewise comparisons of vectors, and consumed by operations that
+/// treat lanes differently depending on a condition, like [`Mask::select`]. The element type
+/// `T` is the signed integer type with the width of the elements of the compared vectors,
+/// so that a mask has the same layout as the vectors it was computed from.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(portable_simd)]
+/// use core::simd::{f32x4, Mask};
+///
+/// let a = f32x4::from_array([1.0, -2.0, 3.0, -4.0]);
+/// let negative = a.lanes_lt(f32x4::splat(0.0));
+/// assert_eq!(negative.to_array(), [false, true, false, true]);
+/// assert_eq!(negative.select(-a, a).to_array(), [1.0, 2.0, 3.0, 4.0]);
+/// ```
+#[repr(transparent)]
+#[unstable(feature = "portable_simd", issue = "none")]
+pub struct Mask<T, const LANES: usize>(Simd<T, LANES>)
+where
+    T: MaskElement,
+    LaneCount<LANES>: SupportedLaneCount;
+
+impl<T, const LANES: usize> Mask<T, LANES>
+where
+    T: MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    /// Constructs a mask with every lane set to `value`.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    #[inline]
+    pub fn splat(value: bool) -> Self {
+        Self(Simd::splat(if value { T::TRUE } else { T::FALSE }))
+    }
+
+    /// Constructs a mask from an array of booleans.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    #[inline]
+    pub fn from_array(array: [bool; LANES]) -> Self {
+        let mut mask = Self::splat(false);
+        for (lane, &value) in array.iter().enumerate() {
+            mask.set(lane, value);
+        }
+        mask
+    }
+
+    /// Converts the mask into an array of booleans.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    #[inline]
+    pub fn to_array(self) -> [bool; LANES] {
+        let mut array = [false; LANES];
+        for (lane, value) in array.iter_mut().enumerate() {
+            *value = self.test(lane);
+        }
+        array
+    }
+
+    /// Converts a vector of integers into a mask, where `0` is unselected and `-1` is
+    /// selected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any lane is neither `0` nor `-1`.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    #[inline]
+    pub fn from_int(value: Simd<T, LANES>) -> Self {
+        assert!(
+            value.as_array().iter().all(|&lane| lane == T::TRUE || lane == T::FALSE),
+            "all lanes of a mask must be either 0 or -1"
+        );
+        Self(value)
+    }
+
+    /// Converts a vector of integers into a mask, where `0` is unselected and `-1` is
+    /// selected.
+    ///
+    /// # Safety
+    ///
+    /// All lanes must be either `0` or `-1`.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    #[inline]
+    pub unsafe fn from_int_unchecked(value: Simd<T, LANES>) -> Self {
+        Self(value)
+    }
+
+    /// Converts the mask into a vector of integers, where `0` is unselected and `-1` is
+    /// selected.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    #[inline]
+    pub fn to_int(self) -> Simd<T, LANES> {
+        self.0
+    }
+
+    /// Returns whether the lane `lane` is selected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lane` is not less than `LANES`.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    #[inline]
+    pub fn test(&self, lane: usize) -> bool {
+        self.0[lane] == T::TRUE
+    }
+
+    /// Selects or unselects the lane `lane`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lane` is not less than `LANES`.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    #[inline]
+    pub fn set(&mut self, lane: usize, value: bool) {
+        self.0[lane] = if value { T::TRUE } else { T::FALSE };
+    }
+
+    /// Returns whether any lane is selected.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    #[inline]
+    pub fn any(self) -> bool {
+        // SAFETY: the lanes of a mask are either all ones or all zeros.
+        unsafe { intrinsics::simd_reduce_any(self.0) }
+    }
+
+    /// Returns whether all lanes are selected.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    #[inline]
+    pub fn all(self) -> bool {
+        // SAFETY: the lanes of a mask are either all ones or all zeros.
+        unsafe { intrinsics::simd_reduce_all(self.0) }
+    }
+
+    /// Chooses lanes from two vectors: the lane of `true_values` where the mask is
+    /// selected, and the lane of `false_values` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(portable_simd)]
+    /// use core::simd::{i32x4, Mask};
+    ///
+    /// let a = i32x4::from_array([0, 1, 2, 3]);
+    /// let b = i32x4::from_array([4, 5, 6, 7]);
+    /// let mask = Mask::from_array([true, false, false, true]);
+    /// assert_eq!(mask.select(a, b).to_array(), [0, 5, 6, 3]);
+    /// ```
+    #[unstable(feature = "portable_simd", issue = "none")]
+    #[inline]
+    pub fn select<U>(
+        self,
+        true_values: Simd<U, LANES>,
+        false_values: Simd<U, LANES>,
+    ) -> Simd<U, LANES>
+    where
+        U: SimdElement<Mask = T>,
+    {
+        // SAFETY: the mask has as many lanes as the vectors, each of which is all ones
+        // or all zeros.
+        unsafe { intrinsics::simd_select(self.0, true_values, false_values) }
+    }
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> Copy for Mask<T, LANES>
+where
+    T: MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> Clone for Mask<T, LANES>
+where
+    T: MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> Default for Mask<T, LANES>
+where
+    T: MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    /// Returns a mask with no lane selected.
+    #[inline]
+    fn default() -> Self {
+        Self::splat(false)
+    }
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> PartialEq for Mask<T, LANES>
+where
+    T: MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> fmt::Debug for Mask<T, LANES>
+where
+    T: MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries((0..LANES).map(|lane| self.test(lane))).finish()
+    }
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> From<[bool; LANES]> for Mask<T, LANES>
+where
+    T: MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    fn from(array: [bool; LANES]) -> Self {
+        Self::from_array(array)
+    }
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> From<Mask<T, LANES>> for [bool; LANES]
+where
+    T: MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    fn from(mask: Mask<T, LANES>) -> Self {
+        mask.to_array()
+    }
+}
+
+macro_rules! mask_bitop {
+    ($($trait:ident :: $method:ident, $assign_trait:ident :: $assign_method:ident,
+       $intrinsic:ident;)*) => {
+        $(
+            #[unstable(feature = "portable_simd", issue = "none")]
+            impl<T, const LANES: usize> $trait for Mask<T, LANES>
+            where
+                T: MaskElement,
+                LaneCount<LANES>: SupportedLaneCount,
+            {
+                type Output = Self;
+
+                #[inline]
+                fn $method(self, rhs: Self) -> Self {
+                    // SAFETY: bitwise operations on masks produce masks.
+                    Self(unsafe { intrinsics::$intrinsic(self.0, rhs.0) })
+                }
+            }
+
+            #[unstable(feature = "portable_simd", issue = "none")]
+            impl<T, const LANES: usize> $trait<bool> for Mask<T, LANES>
+            where
+                T: MaskElement,
+                LaneCount<LANES>: SupportedLaneCount,
+            {
+                type Output = Self;
+
+                #[inline]
+                fn $method(self, rhs: bool) -> Self {
+                    self.$method(Self::splat(rhs))
+                }
+            }
+
+            #[unstable(feature = "portable_simd", issue = "none")]
+            impl<T, const LANES: usize> $assign_trait for Mask<T, LANES>
+            where
+                T: MaskElement,
+                LaneCount<LANES>: SupportedLaneCount,
+            {
+                #[inline]
+                fn $assign_method(&mut self, rhs: Self) {
+                    *self = self.$method(rhs);
+                }
+            }
+        )*
+    };
+}
+
+mask_bitop! {
+    BitAnd::bitand, BitAndAssign::bitand_assign, simd_and;
+    BitOr::bitor, BitOrAssign::bitor_assign, simd_or;
+    BitXor::bitxor, BitXorAssign::bitxor_assign, simd_xor;
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> Not for Mask<T, LANES>
+where
+    T: MaskElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self {
+        self ^ Self::splat(true)
+    }
+}
+
+macro_rules! mask_aliases {
+    ($($ty:ident: $($alias:ident = $lanes:literal),*;)*) => {
+        $($(
+            doc_comment! {
+                concat!(
+                    "A SIMD mask of ", stringify!($lanes),
+                    " lanes, for vectors of `", stringify!($ty), "`-sized elements."
+                ),
+                #[allow(non_camel_case_types)]
+                #[unstable(feature = "portable_simd", issue = "none")]
+                pub type $alias = Mask<$ty, $lanes>;
+            }
+        )*)*
+    };
+}
+
+mask_aliases! {
+    i8: mask8x2 = 2, mask8x4 = 4, mask8x8 = 8, mask8x16 = 16, mask8x32 = 32, mask8x64 = 64;
+    i16: mask16x2 = 2, mask16x4 = 4, mask16x8 = 8, mask16x16 = 16, mask16x32 = 32;
+    i32: mask32x2 = 2, mask32x4 = 4, mask32x8 = 8, mask32x16 = 16;
+    i64: mask64x2 = 2, mask64x4 = 4, mask64x8 = 8;
+    isize: masksizex2 = 2, masksizex4 = 4, masksizex8 = 8;
+}
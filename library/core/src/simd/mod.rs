@@ -0,0 +1,70 @@
+//! Portable SIMD vector types.
+//!
+//! This module provides [`Simd<T, LANES>`][Simd], a vector of `LANES` elements of type `T`,
+//! together with [`Mask<T, LANES>`][Mask], the result of comparing two vectors lane by lane.
+//! Unlike the intrinsics in [`core::arch`](crate::arch), the operations here are not tied to
+//! a particular instruction set: they lower to LLVM's target-independent vector operations,
+//! which are mapped onto the best available instructions of the target (SSE, AVX, NEON, ...).
+//! On targets without vector registers, LLVM splits the operations into one scalar operation
+//! per lane, so code written against this module runs everywhere.
+//!
+//! Operations on vectors are applied to each lane independently ("lanewise"), unless the name
+//! of the operation says otherwise: the `horizontal_*` reductions combine all lanes of a single
+//! vector, and [`Swizzle`] rearranges lanes.
+//!
+//! Integer arithmetic wraps on overflow, like the `wrapping_*` methods of the integer types.
+//! Integer division and remainder panic if any lane would panic for the scalar operation, and
+//! shifts panic if any lane of the shift amount is not smaller than the number of bits of the
+//! element type.
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(portable_simd)]
+//! use core::simd::{f32x4, u8x16};
+//!
+//! let a = f32x4::from_array([1.0, 2.0, 3.0, 4.0]);
+//! let b = f32x4::splat(10.0);
+//! assert_eq!((a * b).to_array(), [10.0, 20.0, 30.0, 40.0]);
+//! assert_eq!(a.horizontal_sum(), 10.0);
+//!
+//! let bytes = u8x16::from_array(*b"hello, world!!!!");
+//! let commas = bytes.lanes_eq(u8x16::splat(b','));
+//! assert!(commas.any());
+//! assert!(commas.test(5));
+//! ```
+
+macro_rules! doc_comment {
+    ($x:expr, $($tt:tt)*) => {
+        #[doc = $x]
+        $($tt)*
+    };
+}
+
+pub(crate) mod intrinsics;
+
+mod cmp;
+mod lane_count;
+mod masks;
+mod ops;
+mod reduction;
+mod swizzle;
+mod vector;
+
+pub use self::lane_count::{LaneCount, SupportedLaneCount};
+pub use self::masks::*;
+pub use self::swizzle::Swizzle;
+pub use self::vector::*;
+
+/// Whether the target is compiled with vector registers that can hold `NATIVE_BYTE_LANES`
+/// bytes, in which case the byte searches of `core` are faster on `Simd<u8, _>` than on
+/// `usize` words.
+///
+/// On other targets LLVM would split each vector operation into one operation per lane.
+pub(crate) const HAS_NATIVE_VECTORS: bool = cfg!(any(
+    all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"),
+    all(target_arch = "aarch64", target_feature = "neon"),
+));
+
+/// The number of `u8` lanes of the widest vector registers the target is compiled for.
+pub(crate) const NATIVE_BYTE_LANES: usize = if cfg!(target_feature = "avx2") { 32 } else { 16 };
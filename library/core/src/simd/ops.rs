@@ -0,0 +1,222 @@
+use crate::mem;
+use crate::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Not, Rem, RemAssign};
+use crate::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
+use crate::ops::{Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign};
+
+use super::intrinsics;
+use super::{LaneCount, Simd, SupportedLaneCount};
+
+/// Implements a binary operator and its assigning form for `Simd<$ty, LANES>`.
+///
+/// `$check` is run on the operands before the intrinsic, and is where the operations
+/// that would be undefined behavior for some lanes are rejected.
+macro_rules! binary_op {
+    ($ty:ty, $trait:ident :: $method:ident, $assign_trait:ident :: $assign_method:ident,
+     $intrinsic:ident, |$lhs:ident, $rhs:ident| $check:block) => {
+        #[unstable(feature = "portable_simd", issue = "none")]
+        impl<const LANES: usize> $trait for Simd<$ty, LANES>
+        where
+            LaneCount<LANES>: SupportedLaneCount,
+        {
+            type Output = Self;
+
+            #[inline]
+            #[track_caller]
+            fn $method(self, rhs: Self) -> Self {
+                let ($lhs, $rhs) = (self, rhs);
+                $check
+                // SAFETY: the operands are vectors of `$ty`, and `$check` rejected the lanes
+                // for which the operation is undefined.
+                unsafe { intrinsics::$intrinsic($lhs, $rhs) }
+            }
+        }
+
+        #[unstable(feature = "portable_simd", issue = "none")]
+        impl<const LANES: usize> $assign_trait for Simd<$ty, LANES>
+        where
+            LaneCount<LANES>: SupportedLaneCount,
+        {
+            #[inline]
+            #[track_caller]
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = $trait::$method(*self, rhs);
+            }
+        }
+    };
+    ($ty:ty, $trait:ident :: $method:ident, $assign_trait:ident :: $assign_method:ident,
+     $intrinsic:ident) => {
+        binary_op!($ty, $trait::$method, $assign_trait::$assign_method, $intrinsic, |_l, _r| {});
+    };
+}
+
+/// Operators shared by all element types.
+macro_rules! arith_ops {
+    ($($ty:ty)*) => {
+        $(
+            binary_op!($ty, Add::add, AddAssign::add_assign, simd_add);
+            binary_op!($ty, Sub::sub, SubAssign::sub_assign, simd_sub);
+            binary_op!($ty, Mul::mul, MulAssign::mul_assign, simd_mul);
+        )*
+    };
+}
+
+/// Operators for the integer element types.
+macro_rules! int_ops {
+    ($($ty:ty)*) => {
+        $(
+            binary_op!($ty, BitAnd::bitand, BitAndAssign::bitand_assign, simd_and);
+            binary_op!($ty, BitOr::bitor, BitOrAssign::bitor_assign, simd_or);
+            binary_op!($ty, BitXor::bitxor, BitXorAssign::bitxor_assign, simd_xor);
+            binary_op!($ty, Div::div, DivAssign::div_assign, simd_div, |lhs, rhs| {
+                if rhs.lanes_eq(Simd::splat(0)).any() {
+                    panic!("attempt to divide by zero");
+                }
+                if is_overflowing_division(lhs, rhs) {
+                    panic!("attempt to divide with overflow");
+                }
+            });
+            binary_op!($ty, Rem::rem, RemAssign::rem_assign, simd_rem, |lhs, rhs| {
+                if rhs.lanes_eq(Simd::splat(0)).any() {
+                    panic!("attempt to calculate the remainder with a divisor of zero");
+                }
+                if is_overflowing_division(lhs, rhs) {
+                    panic!("attempt to calculate the remainder with overflow");
+                }
+            });
+            binary_op!($ty, Shl::shl, ShlAssign::shl_assign, simd_shl, |_lhs, rhs| {
+                if is_overflowing_shift(rhs) {
+                    panic!("attempt to shift left with overflow");
+                }
+            });
+            binary_op!($ty, Shr::shr, ShrAssign::shr_assign, simd_shr, |_lhs, rhs| {
+                if is_overflowing_shift(rhs) {
+                    panic!("attempt to shift right with overflow");
+                }
+            });
+
+            #[unstable(feature = "portable_simd", issue = "none")]
+            impl<const LANES: usize> Not for Simd<$ty, LANES>
+            where
+                LaneCount<LANES>: SupportedLaneCount,
+            {
+                type Output = Self;
+
+                #[inline]
+                fn not(self) -> Self {
+                    self ^ Self::splat(!0)
+                }
+            }
+        )*
+    };
+}
+
+/// Overflow checks for unsigned element types.
+macro_rules! uint_checks {
+    ($($ty:ty)*) => {
+        $(
+            impl<const LANES: usize> OverflowChecks for Simd<$ty, LANES>
+            where
+                LaneCount<LANES>: SupportedLaneCount,
+            {
+                #[inline]
+                fn is_overflowing_division(_lhs: Self, _rhs: Self) -> bool {
+                    false
+                }
+
+                #[inline]
+                fn is_overflowing_shift(rhs: Self) -> bool {
+                    let bits = (mem::size_of::<$ty>() * 8) as $ty;
+                    rhs.lanes_ge(Simd::splat(bits)).any()
+                }
+            }
+        )*
+    };
+}
+
+/// Overflow checks and negation for signed element types.
+macro_rules! sint_ops {
+    ($($ty:ty)*) => {
+        $(
+            impl<const LANES: usize> OverflowChecks for Simd<$ty, LANES>
+            where
+                LaneCount<LANES>: SupportedLaneCount,
+            {
+                #[inline]
+                fn is_overflowing_division(lhs: Self, rhs: Self) -> bool {
+                    (lhs.lanes_eq(Simd::splat(<$ty>::MIN)) & rhs.lanes_eq(Simd::splat(-1))).any()
+                }
+
+                #[inline]
+                fn is_overflowing_shift(rhs: Self) -> bool {
+                    let bits = (mem::size_of::<$ty>() * 8) as $ty;
+                    (rhs.lanes_lt(Simd::splat(0)) | rhs.lanes_ge(Simd::splat(bits))).any()
+                }
+            }
+
+            /// Negates each lane, wrapping `MIN` to itself.
+            #[unstable(feature = "portable_simd", issue = "none")]
+            impl<const LANES: usize> Neg for Simd<$ty, LANES>
+            where
+                LaneCount<LANES>: SupportedLaneCount,
+            {
+                type Output = Self;
+
+                #[inline]
+                fn neg(self) -> Self {
+                    Self::splat(0) - self
+                }
+            }
+        )*
+    };
+}
+
+/// Operators for the floating point element types.
+macro_rules! float_ops {
+    ($($ty:ty)*) => {
+        $(
+            binary_op!($ty, Div::div, DivAssign::div_assign, simd_div);
+            binary_op!($ty, Rem::rem, RemAssign::rem_assign, simd_rem);
+
+            #[unstable(feature = "portable_simd", issue = "none")]
+            impl<const LANES: usize> Neg for Simd<$ty, LANES>
+            where
+                LaneCount<LANES>: SupportedLaneCount,
+            {
+                type Output = Self;
+
+                #[inline]
+                fn neg(self) -> Self {
+                    // Subtracting from negative zero flips the sign of every lane, zeros
+                    // included, and is what LLVM recognizes as a negation.
+                    Self::splat(-0.0) - self
+                }
+            }
+        )*
+    };
+}
+
+/// The lanes for which integer division, remainder and shifts are undefined.
+trait OverflowChecks: Sized {
+    /// Returns whether any lane divides `MIN` by `-1`.
+    fn is_overflowing_division(lhs: Self, rhs: Self) -> bool;
+
+    /// Returns whether any lane shifts by a negative amount or by at least the number of
+    /// bits of the element type.
+    fn is_overflowing_shift(rhs: Self) -> bool;
+}
+
+#[inline]
+fn is_overflowing_division<V: OverflowChecks>(lhs: V, rhs: V) -> bool {
+    V::is_overflowing_division(lhs, rhs)
+}
+
+#[inline]
+fn is_overflowing_shift<V: OverflowChecks>(rhs: V) -> bool {
+    V::is_overflowing_shift(rhs)
+}
+
+arith_ops! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize f32 f64 }
+int_ops! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+uint_checks! { u8 u16 u32 u64 usize }
+sint_ops! { i8 i16 i32 i64 isize }
+float_ops! { f32 f64 }
@@ -0,0 +1,121 @@
+use super::intrinsics;
+use super::{LaneCount, Simd, SupportedLaneCount};
+
+macro_rules! int_reductions {
+    ($($ty:ty)*) => {
+        $(
+            impl<const LANES: usize> Simd<$ty, LANES>
+            where
+                LaneCount<LANES>: SupportedLaneCount,
+            {
+                /// Returns the sum of the lanes of the vector, wrapping on overflow.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn horizontal_sum(self) -> $ty {
+                    // SAFETY: `self` is a vector of integers.
+                    unsafe { intrinsics::simd_reduce_add_ordered(self, 0) }
+                }
+
+                /// Returns the product of the lanes of the vector, wrapping on overflow.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn horizontal_product(self) -> $ty {
+                    // SAFETY: `self` is a vector of integers.
+                    unsafe { intrinsics::simd_reduce_mul_ordered(self, 1) }
+                }
+
+                /// Returns the bitwise "and" of the lanes of the vector.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn horizontal_and(self) -> $ty {
+                    // SAFETY: `self` is a vector of integers.
+                    unsafe { intrinsics::simd_reduce_and(self) }
+                }
+
+                /// Returns the bitwise "or" of the lanes of the vector.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn horizontal_or(self) -> $ty {
+                    // SAFETY: `self` is a vector of integers.
+                    unsafe { intrinsics::simd_reduce_or(self) }
+                }
+
+                /// Returns the bitwise "xor" of the lanes of the vector.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn horizontal_xor(self) -> $ty {
+                    // SAFETY: `self` is a vector of integers.
+                    unsafe { intrinsics::simd_reduce_xor(self) }
+                }
+
+                /// Returns the maximum lane of the vector.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn horizontal_max(self) -> $ty {
+                    // SAFETY: `self` is a vector of integers.
+                    unsafe { intrinsics::simd_reduce_max(self) }
+                }
+
+                /// Returns the minimum lane of the vector.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn horizontal_min(self) -> $ty {
+                    // SAFETY: `self` is a vector of integers.
+                    unsafe { intrinsics::simd_reduce_min(self) }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! float_reductions {
+    ($($ty:ty)*) => {
+        $(
+            impl<const LANES: usize> Simd<$ty, LANES>
+            where
+                LaneCount<LANES>: SupportedLaneCount,
+            {
+                /// Returns the sum of the lanes of the vector, added in lane order.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn horizontal_sum(self) -> $ty {
+                    // Negative zero is the identity of addition: starting from positive zero
+                    // would turn a sum of negative zeros into positive zero.
+                    // SAFETY: `self` is a vector of floats.
+                    unsafe { intrinsics::simd_reduce_add_ordered(self, -0.0) }
+                }
+
+                /// Returns the product of the lanes of the vector, multiplied in lane order.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn horizontal_product(self) -> $ty {
+                    // SAFETY: `self` is a vector of floats.
+                    unsafe { intrinsics::simd_reduce_mul_ordered(self, 1.0) }
+                }
+
+                /// Returns the maximum lane of the vector.
+                ///
+                /// NaN lanes are ignored, unless all lanes are NaN.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn horizontal_max(self) -> $ty {
+                    // SAFETY: `self` is a vector of floats.
+                    unsafe { intrinsics::simd_reduce_max(self) }
+                }
+
+                /// Returns the minimum lane of the vector.
+                ///
+                /// NaN lanes are ignored, unless all lanes are NaN.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn horizontal_min(self) -> $ty {
+                    // SAFETY: `self` is a vector of floats.
+                    unsafe { intrinsics::simd_reduce_min(self) }
+                }
+            }
+        )*
+    };
+}
+
+int_reductions! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+float_reductions! { f32 f64 }
@@ -0,0 +1,85 @@
+use super::intrinsics;
+use super::{LaneCount, Simd, SimdElement, SupportedLaneCount};
+
+/// Constructs a new vector by selecting lanes of an input vector.
+///
+/// The lanes are selected by the constant `INDEX`: lane `i` of the output is lane
+/// `INDEX[i]` of the input. Every index must be less than `INPUT_LANES`, which is
+/// checked when the swizzle is instantiated.
+///
+/// Swizzles lower to a single shuffle instruction where the target has one.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(portable_simd)]
+/// use core::simd::{f32x4, Simd, Swizzle};
+///
+/// struct Broadcast2;
+/// impl Swizzle<4, 8> for Broadcast2 {
+///     const INDEX: [u32; 8] = [2, 2, 2, 2, 2, 2, 2, 2];
+/// }
+///
+/// let v = f32x4::from_array([0.0, 1.0, 2.0, 3.0]);
+/// assert_eq!(Broadcast2::swizzle(v), Simd::splat(2.0));
+/// ```
+#[unstable(feature = "portable_simd", issue = "none")]
+pub trait Swizzle<const INPUT_LANES: usize, const OUTPUT_LANES: usize> {
+    /// The lane of the input that each lane of the output is taken from.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    const INDEX: [u32; OUTPUT_LANES];
+
+    /// Creates a new vector from the lanes of `vector`.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    #[inline]
+    fn swizzle<T>(vector: Simd<T, INPUT_LANES>) -> Simd<T, OUTPUT_LANES>
+    where
+        T: SimdElement,
+        LaneCount<INPUT_LANES>: SupportedLaneCount,
+        LaneCount<OUTPUT_LANES>: SupportedLaneCount,
+    {
+        // SAFETY: `vector` is a vector, and the index array is a constant whose lanes are
+        // checked against the lane count during codegen.
+        unsafe { intrinsics::simd_shuffle(vector, vector, Self::INDEX) }
+    }
+}
+
+/// Reverses the order of the lanes of a vector.
+struct Reverse;
+
+impl<const LANES: usize> Swizzle<LANES, LANES> for Reverse {
+    const INDEX: [u32; LANES] = reverse_index::<LANES>();
+}
+
+const fn reverse_index<const LANES: usize>() -> [u32; LANES] {
+    let mut index = [0; LANES];
+    let mut i = 0;
+    while i < LANES {
+        index[i] = (LANES - 1 - i) as u32;
+        i += 1;
+    }
+    index
+}
+
+impl<T, const LANES: usize> Simd<T, LANES>
+where
+    T: SimdElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    /// Reverses the order of the lanes of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(portable_simd)]
+    /// use core::simd::u8x4;
+    ///
+    /// let v = u8x4::from_array([1, 2, 3, 4]);
+    /// assert_eq!(v.reverse().to_array(), [4, 3, 2, 1]);
+    /// ```
+    #[unstable(feature = "portable_simd", issue = "none")]
+    #[inline]
+    pub fn reverse(self) -> Self {
+        Reverse::swizzle(self)
+    }
+}
@@ -0,0 +1,501 @@
+use crate::fmt;
+use crate::mem;
+use crate::ops::{Index, IndexMut};
+use crate::slice::SliceIndex;
+
+use super::intrinsics;
+use super::{LaneCount, Mask, MaskElement, SupportedLaneCount};
+
+mod sealed {
+    pub trait Sealed {}
+}
+use sealed::Sealed;
+
+/// Marker trait for types that may be used as SIMD vector elements.
+///
+/// This trait is sealed: it is implemented for the primitive integer and floating point
+/// types, and cannot be implemented outside of `core`.
+#[unstable(feature = "portable_simd", issue = "none")]
+pub trait SimdElement: Sealed + Copy {
+    /// The type of the mask elements produced by comparing vectors of this element type:
+    /// the signed integer type of the same width.
+    type Mask: MaskElement;
+}
+
+macro_rules! simd_element {
+    ($($ty:ty => $mask:ty,)*) => {
+        $(
+            impl Sealed for $ty {}
+
+            #[unstable(feature = "portable_simd", issue = "none")]
+            impl SimdElement for $ty {
+                type Mask = $mask;
+            }
+        )*
+    };
+}
+
+simd_element! {
+    u8 => i8,
+    u16 => i16,
+    u32 => i32,
+    u64 => i64,
+    usize => isize,
+    i8 => i8,
+    i16 => i16,
+    i32 => i32,
+    i64 => i64,
+    isize => isize,
+    f32 => i32,
+    f64 => i64,
+}
+
+/// A SIMD vector of `LANES` elements of type `T`.
+///
+/// `Simd<T, LANES>` has the same layout as `[T; LANES]`, except that it may be more aligned.
+/// Most operations on it are applied to every lane independently and are lowered to the
+/// vector instructions of the target where possible.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(portable_simd)]
+/// use core::simd::Simd;
+///
+/// let a = Simd::from_array([1i32, 2, 3, 4]);
+/// let b = Simd::splat(10);
+/// assert_eq!(a + b, Simd::from_array([11, 12, 13, 14]));
+/// assert_eq!((a * b)[2], 30);
+/// ```
+#[repr(simd)]
+#[unstable(feature = "portable_simd", issue = "none")]
+pub struct Simd<T, const LANES: usize>([T; LANES])
+where
+    T: SimdElement,
+    LaneCount<LANES>: SupportedLaneCount;
+
+impl<T, const LANES: usize> Simd<T, LANES>
+where
+    T: SimdElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    /// The number of lanes in this vector.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    pub const LANES: usize = LANES;
+
+    /// Returns the number of lanes in this vector.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    pub fn lanes(&self) -> usize {
+        LANES
+    }
+
+    /// Constructs a vector with every lane set to `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(portable_simd)]
+    /// use core::simd::u32x4;
+    ///
+    /// assert_eq!(u32x4::splat(8).to_array(), [8, 8, 8, 8]);
+    /// ```
+    #[unstable(feature = "portable_simd", issue = "none")]
+    pub fn splat(value: T) -> Self {
+        Self([value; LANES])
+    }
+
+    /// Constructs a vector from an array, lane `i` taking the value of `array[i]`.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    pub fn from_array(array: [T; LANES]) -> Self {
+        Self(array)
+    }
+
+    /// Converts the vector into an array of its lanes.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    pub fn to_array(self) -> [T; LANES] {
+        *self.as_array()
+    }
+
+    /// Returns a reference to the lanes of the vector as an array.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    pub fn as_array(&self) -> &[T; LANES] {
+        // SAFETY: `Simd<T, LANES>` has the layout of `[T; LANES]`, with at least its alignment.
+        // The lanes are accessed through a pointer cast rather than a projection to the array
+        // field, because codegen treats `Simd` as a single vector value.
+        unsafe { &*(self as *const Self as *const [T; LANES]) }
+    }
+
+    /// Returns a mutable reference to the lanes of the vector as an array.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    pub fn as_mut_array(&mut self) -> &mut [T; LANES] {
+        // SAFETY: see `as_array`.
+        unsafe { &mut *(self as *mut Self as *mut [T; LANES]) }
+    }
+
+    /// Constructs a vector from the first `LANES` elements of `slice`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slice` is shorter than `LANES`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(portable_simd)]
+    /// use core::simd::u32x4;
+    ///
+    /// let source = [1, 2, 3, 4, 5, 6];
+    /// assert_eq!(u32x4::from_slice(&source[2..]).to_array(), [3, 4, 5, 6]);
+    /// ```
+    #[unstable(feature = "portable_simd", issue = "none")]
+    pub fn from_slice(slice: &[T]) -> Self {
+        assert!(slice.len() >= LANES, "slice length must be at least the number of lanes");
+        let mut array = [slice[0]; LANES];
+        array.copy_from_slice(&slice[..LANES]);
+        Self(array)
+    }
+
+    /// Reads the elements of `slice` at the indices in `idxs`, taking the lane of `or` for
+    /// each index that is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(portable_simd)]
+    /// use core::simd::{Simd, usizex4};
+    ///
+    /// let vec = [10, 11, 12, 13, 14, 15, 16, 17, 18];
+    /// let idxs = usizex4::from_array([9, 3, 0, 5]);
+    /// let alt = Simd::from_array([-5, -4, -3, -2]);
+    ///
+    /// let result = Simd::gather_or(&vec, idxs, alt);
+    /// assert_eq!(result.to_array(), [-5, 13, 10, 15]);
+    /// ```
+    #[unstable(feature = "portable_simd", issue = "none")]
+    pub fn gather_or(slice: &[T], idxs: Simd<usize, LANES>, or: Self) -> Self {
+        Self::gather_select(slice, Mask::splat(true), idxs, or)
+    }
+
+    /// Reads the elements of `slice` at the indices in `idxs`, taking the default value for
+    /// each index that is out of bounds.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    pub fn gather_or_default(slice: &[T], idxs: Simd<usize, LANES>) -> Self
+    where
+        T: Default,
+    {
+        Self::gather_or(slice, idxs, Self::splat(T::default()))
+    }
+
+    /// Reads the elements of `slice` at the indices in `idxs` for the lanes enabled in
+    /// `enable`, taking the lane of `or` for disabled lanes and for each index that is
+    /// out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(portable_simd)]
+    /// use core::simd::{Mask, Simd, usizex4};
+    ///
+    /// let vec = [10, 11, 12, 13, 14, 15, 16, 17, 18];
+    /// let idxs = usizex4::from_array([9, 3, 0, 5]);
+    /// let alt = Simd::from_array([-5, -4, -3, -2]);
+    /// let enable = Mask::from_array([true, true, true, false]);
+    ///
+    /// let result = Simd::gather_select(&vec, enable, idxs, alt);
+    /// assert_eq!(result.to_array(), [-5, 13, 10, -2]);
+    /// ```
+    #[unstable(feature = "portable_simd", issue = "none")]
+    pub fn gather_select(
+        slice: &[T],
+        enable: Mask<isize, LANES>,
+        idxs: Simd<usize, LANES>,
+        or: Self,
+    ) -> Self {
+        let enable = enable & idxs.lanes_lt(Simd::splat(slice.len()));
+        let ptrs = Pointers::new(slice.as_ptr(), idxs, <*const T>::wrapping_add);
+        // SAFETY: every enabled lane points to an element of `slice`.
+        unsafe { intrinsics::simd_gather(or, ptrs, enable.to_int()) }
+    }
+
+    /// Writes the lanes of the vector to `slice` at the indices in `idxs`, skipping each
+    /// index that is out of bounds.
+    ///
+    /// If an index appears more than once, only the last lane writing to it is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(portable_simd)]
+    /// use core::simd::{Simd, usizex4};
+    ///
+    /// let mut vec = [10, 11, 12, 13, 14, 15, 16, 17, 18];
+    /// let idxs = usizex4::from_array([9, 3, 0, 0]);
+    /// let vals = Simd::from_array([-27, 82, -41, 124]);
+    ///
+    /// vals.scatter(&mut vec, idxs);
+    /// assert_eq!(vec, [124, 11, 12, 82, 14, 15, 16, 17, 18]);
+    /// ```
+    #[unstable(feature = "portable_simd", issue = "none")]
+    pub fn scatter(self, slice: &mut [T], idxs: Simd<usize, LANES>) {
+        self.scatter_select(slice, Mask::splat(true), idxs)
+    }
+
+    /// Writes the lanes of the vector enabled in `enable` to `slice` at the indices in
+    /// `idxs`, skipping each index that is out of bounds.
+    ///
+    /// If an index appears more than once, only the last enabled lane writing to it is kept.
+    #[unstable(feature = "portable_simd", issue = "none")]
+    pub fn scatter_select(
+        self,
+        slice: &mut [T],
+        enable: Mask<isize, LANES>,
+        idxs: Simd<usize, LANES>,
+    ) {
+        let enable = enable & idxs.lanes_lt(Simd::splat(slice.len()));
+        let ptrs = Pointers::new(slice.as_mut_ptr(), idxs, <*mut T>::wrapping_add);
+        // SAFETY: every enabled lane points to an element of `slice`, which we borrow
+        // mutably. The intrinsic writes lanes in order, so the last lane wins.
+        unsafe { intrinsics::simd_scatter(self, ptrs, enable.to_int()) }
+    }
+}
+
+/// A vector of pointers to the elements of a slice, used by gathers and scatters.
+#[repr(simd)]
+struct Pointers<P, const LANES: usize>([P; LANES]);
+
+impl<P: Copy, const LANES: usize> Pointers<P, LANES> {
+    /// Offsets `base` by each lane of `idxs` using `offset`, which is one of the
+    /// `wrapping_add` methods of the raw pointer types.
+    fn new(base: P, idxs: Simd<usize, LANES>, offset: fn(P, usize) -> P) -> Self
+    where
+        LaneCount<LANES>: SupportedLaneCount,
+    {
+        let mut ptrs = [base; LANES];
+        for (ptr, &idx) in ptrs.iter_mut().zip(idxs.as_array()) {
+            *ptr = offset(base, idx);
+        }
+        Self(ptrs)
+    }
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> Copy for Simd<T, LANES>
+where
+    T: SimdElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> Clone for Simd<T, LANES>
+where
+    T: SimdElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> Default for Simd<T, LANES>
+where
+    T: SimdElement + Default,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::splat(T::default())
+    }
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> PartialEq for Simd<T, LANES>
+where
+    T: SimdElement + PartialEq,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        // SAFETY: comparing two vectors yields a vector of their mask element type.
+        let mask: Simd<T::Mask, LANES> = unsafe { intrinsics::simd_eq(*self, *other) };
+        // SAFETY: the comparison produced only all-ones or all-zeros lanes.
+        unsafe { Mask::from_int_unchecked(mask) }.all()
+    }
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> fmt::Debug for Simd<T, LANES>
+where
+    T: SimdElement + fmt::Debug,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_array(), f)
+    }
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<I, T, const LANES: usize> Index<I> for Simd<T, LANES>
+where
+    I: SliceIndex<[T]>,
+    T: SimdElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        &self.as_array()[index]
+    }
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<I, T, const LANES: usize> IndexMut<I> for Simd<T, LANES>
+where
+    I: SliceIndex<[T]>,
+    T: SimdElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        &mut self.as_mut_array()[index]
+    }
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> AsRef<[T; LANES]> for Simd<T, LANES>
+where
+    T: SimdElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    fn as_ref(&self) -> &[T; LANES] {
+        self.as_array()
+    }
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> AsMut<[T; LANES]> for Simd<T, LANES>
+where
+    T: SimdElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    fn as_mut(&mut self) -> &mut [T; LANES] {
+        self.as_mut_array()
+    }
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> From<[T; LANES]> for Simd<T, LANES>
+where
+    T: SimdElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    fn from(array: [T; LANES]) -> Self {
+        Self::from_array(array)
+    }
+}
+
+#[unstable(feature = "portable_simd", issue = "none")]
+impl<T, const LANES: usize> From<Simd<T, LANES>> for [T; LANES]
+where
+    T: SimdElement,
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    fn from(vector: Simd<T, LANES>) -> Self {
+        vector.to_array()
+    }
+}
+
+macro_rules! float_methods {
+    ($($ty:ty, $bits:ty;)*) => {
+        $(
+            impl<const LANES: usize> Simd<$ty, LANES>
+            where
+                LaneCount<LANES>: SupportedLaneCount,
+            {
+                /// Computes the absolute value of each lane.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn abs(self) -> Self {
+                    // SAFETY: `self` is a vector of floats.
+                    unsafe { intrinsics::simd_fabs(self) }
+                }
+
+                /// Returns the lanewise minimum of `self` and `other`.
+                ///
+                /// If one of the lanes is NaN, the other lane is returned.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn min(self, other: Self) -> Self {
+                    // SAFETY: `self` and `other` are vectors of floats.
+                    unsafe { intrinsics::simd_fmin(self, other) }
+                }
+
+                /// Returns the lanewise maximum of `self` and `other`.
+                ///
+                /// If one of the lanes is NaN, the other lane is returned.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn max(self, other: Self) -> Self {
+                    // SAFETY: `self` and `other` are vectors of floats.
+                    unsafe { intrinsics::simd_fmax(self, other) }
+                }
+
+                /// Returns a mask of the lanes that are NaN.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn is_nan(self) -> Mask<<$ty as SimdElement>::Mask, LANES> {
+                    self.lanes_ne(self)
+                }
+
+                /// Raw transmutation of each lane to its bit pattern.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn to_bits(self) -> Simd<$bits, LANES> {
+                    // SAFETY: the vectors have the same number of lanes of the same size.
+                    unsafe { mem::transmute_copy(&self) }
+                }
+
+                /// Raw transmutation of each lane from its bit pattern.
+                #[unstable(feature = "portable_simd", issue = "none")]
+                #[inline]
+                pub fn from_bits(bits: Simd<$bits, LANES>) -> Self {
+                    // SAFETY: the vectors have the same number of lanes of the same size.
+                    unsafe { mem::transmute_copy(&bits) }
+                }
+            }
+        )*
+    };
+}
+
+float_methods! {
+    f32, u32;
+    f64, u64;
+}
+
+macro_rules! vector_aliases {
+    ($($ty:ident: $($alias:ident = $lanes:literal),*;)*) => {
+        $($(
+            doc_comment! {
+                concat!("A SIMD vector of ", stringify!($lanes), " `", stringify!($ty), "`s."),
+                #[allow(non_camel_case_types)]
+                #[unstable(feature = "portable_simd", issue = "none")]
+                pub type $alias = Simd<$ty, $lanes>;
+            }
+        )*)*
+    };
+}
+
+vector_aliases! {
+    u8: u8x2 = 2, u8x4 = 4, u8x8 = 8, u8x16 = 16, u8x32 = 32, u8x64 = 64;
+    u16: u16x2 = 2, u16x4 = 4, u16x8 = 8, u16x16 = 16, u16x32 = 32;
+    u32: u32x2 = 2, u32x4 = 4, u32x8 = 8, u32x16 = 16;
+    u64: u64x2 = 2, u64x4 = 4, u64x8 = 8;
+    usize: usizex2 = 2, usizex4 = 4, usizex8 = 8;
+    i8: i8x2 = 2, i8x4 = 4, i8x8 = 8, i8x16 = 16, i8x32 = 32, i8x64 = 64;
+    i16: i16x2 = 2, i16x4 = 4, i16x8 = 8, i16x16 = 16, i16x32 = 32;
+    i32: i32x2 = 2, i32x4 = 4, i32x8 = 8, i32x16 = 16;
+    i64: i64x2 = 2, i64x4 = 4, i64x8 = 8;
+    isize: isizex2 = 2, isizex4 = 4, isizex8 = 8;
+    f32: f32x2 = 2, f32x4 = 4, f32x8 = 8, f32x16 = 16;
+    f64: f64x2 = 2, f64x4 = 4, f64x8 = 8;
+}
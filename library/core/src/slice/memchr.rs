@@ -5,6 +5,8 @@
 
 use crate::cmp;
 use crate::mem;
+use crate::ptr;
+use crate::simd::{LaneCount, Simd, SupportedLaneCount, HAS_NATIVE_VECTORS, NATIVE_BYTE_LANES};
 
 const LO_U64: u64 = 0x0101010101010101;
 const HI_U64: u64 = 0x8080808080808080;
@@ -46,6 +48,10 @@ pub fn memchr(x: u8, text: &[u8]) -> Option<usize> {
         return text.iter().position(|elt| *elt == x);
     }
 
+    if HAS_NATIVE_VECTORS {
+        return memchr_simd::<NATIVE_BYTE_LANES>(x, text);
+    }
+
     memchr_general_case(x, text)
 }
 
@@ -90,8 +96,67 @@ fn memchr_general_case(x: u8, text: &[u8]) -> Option<usize> {
     text[offset..].iter().position(|elt| *elt == x).map(|i| offset + i)
 }
 
+/// Returns the first index matching the byte `x` in `text`, comparing `LANES` bytes at a
+/// time.
+///
+/// `memchr` uses this with the native vector width of the target. It is also exposed so
+/// that `std` can instantiate it for wider vectors in functions compiled with additional
+/// target features, selected by runtime CPU feature detection.
+#[inline(always)]
+pub fn memchr_simd<const LANES: usize>(x: u8, text: &[u8]) -> Option<usize>
+where
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    let len = text.len();
+    if len < LANES {
+        return text.iter().position(|elt| *elt == x);
+    }
+
+    let ptr = text.as_ptr();
+    let needle = Simd::<u8, LANES>::splat(x);
+    // SAFETY: callers only pass offsets with `offset + LANES <= len`.
+    let load =
+        |offset: usize| unsafe { ptr::read_unaligned(ptr.add(offset) as *const Simd<u8, LANES>) };
+
+    // Search the body of the text two vectors at a time.
+    let mut offset = 0;
+    while offset + 2 * LANES <= len {
+        let u = load(offset).lanes_eq(needle);
+        let v = load(offset + LANES).lanes_eq(needle);
+        if (u | v).any() {
+            break;
+        }
+        offset += 2 * LANES;
+    }
+
+    // Find the vector containing the match, if the loop above stopped at one.
+    while offset + LANES <= len {
+        if load(offset).lanes_eq(needle).any() {
+            return text[offset..offset + LANES]
+                .iter()
+                .position(|elt| *elt == x)
+                .map(|i| offset + i);
+        }
+        offset += LANES;
+    }
+
+    // Search the remaining bytes with a last vector that overlaps the ones already searched,
+    // which is fine because they are known not to contain `x`.
+    if offset < len {
+        let last = len - LANES;
+        if load(last).lanes_eq(needle).any() {
+            return text[last..].iter().position(|elt| *elt == x).map(|i| last + i);
+        }
+    }
+    None
+}
+
 /// Returns the last index matching the byte `x` in `text`.
 pub fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
+    if HAS_NATIVE_VECTORS {
+        return memrchr_simd::<NATIVE_BYTE_LANES>(x, text);
+    }
+
     // Scan for a single byte value by reading two `usize` words at a time.
     //
     // Split `text` in three parts:
@@ -138,3 +203,52 @@ pub fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
     // Find the byte before the point the body loop stopped.
     text[..offset].iter().rposition(|elt| *elt == x)
 }
+
+/// Returns the last index matching the byte `x` in `text`, comparing `LANES` bytes at a
+/// time.
+///
+/// See `memchr_simd` for how this is used.
+#[inline(always)]
+pub fn memrchr_simd<const LANES: usize>(x: u8, text: &[u8]) -> Option<usize>
+where
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    let len = text.len();
+    if len < LANES {
+        return text.iter().rposition(|elt| *elt == x);
+    }
+
+    let ptr = text.as_ptr();
+    let needle = Simd::<u8, LANES>::splat(x);
+    // SAFETY: callers only pass offsets with `offset + LANES <= len`.
+    let load =
+        |offset: usize| unsafe { ptr::read_unaligned(ptr.add(offset) as *const Simd<u8, LANES>) };
+
+    // Search the body of the text backwards, two vectors at a time. `end` is the index
+    // after the last byte that has not been searched yet.
+    let mut end = len;
+    while end >= 2 * LANES {
+        let u = load(end - 2 * LANES).lanes_eq(needle);
+        let v = load(end - LANES).lanes_eq(needle);
+        if (u | v).any() {
+            break;
+        }
+        end -= 2 * LANES;
+    }
+
+    // Find the vector containing the match, if the loop above stopped at one.
+    while end >= LANES {
+        let start = end - LANES;
+        if load(start).lanes_eq(needle).any() {
+            return text[start..end].iter().rposition(|elt| *elt == x).map(|i| start + i);
+        }
+        end -= LANES;
+    }
+
+    // Search the remaining bytes with a first vector that overlaps the ones already
+    // searched, which is fine because they are known not to contain `x`.
+    if end > 0 && load(0).lanes_eq(needle).any() {
+        return text[..LANES].iter().rposition(|elt| *elt == x);
+    }
+    None
+}
@@ -1,6 +1,9 @@
 //! Operations related to UTF-8 validation.
 
 use crate::mem;
+use crate::ptr;
+use crate::simd::intrinsics::simd_shuffle;
+use crate::simd::{Mask, Simd, HAS_NATIVE_VECTORS, NATIVE_BYTE_LANES};
 
 use super::Utf8Error;
 
@@ -113,7 +116,13 @@ fn contains_nonascii(x: usize) -> bool {
 /// returning `Ok(())` in that case, or, if it is invalid, `Err(err)`.
 #[inline(always)]
 pub(super) fn run_utf8_validation(v: &[u8]) -> Result<(), Utf8Error> {
-    let mut index = 0;
+    let start = if HAS_NATIVE_VECTORS && v.len() >= CHUNK { validate_chunks(v) } else { 0 };
+    run_utf8_validation_from(v, start)
+}
+
+/// Checks the bytes of `v` from `index` on, knowing that `v[..index]` is valid UTF-8.
+#[inline(always)]
+fn run_utf8_validation_from(v: &[u8], mut index: usize) -> Result<(), Utf8Error> {
     let len = v.len();
 
     let usize_bytes = mem::size_of::<usize>();
@@ -229,6 +238,121 @@ macro_rules! next {
     Ok(())
 }
 
+/// The number of bytes validated at a time by `validate_chunks`.
+const CHUNK: usize = NATIVE_BYTE_LANES;
+
+type Chunk = Simd<u8, CHUNK>;
+
+/// Shuffle indices selecting, from the concatenation of two chunks, the `CHUNK` bytes that
+/// end `n` bytes before the end of the concatenation.
+const fn shift_indices(n: usize) -> [u32; CHUNK] {
+    let mut indices = [0; CHUNK];
+    let mut i = 0;
+    while i < CHUNK {
+        indices[i] = (CHUNK - n + i) as u32;
+        i += 1;
+    }
+    indices
+}
+
+const PREV1: [u32; CHUNK] = shift_indices(1);
+const PREV2: [u32; CHUNK] = shift_indices(2);
+const PREV3: [u32; CHUNK] = shift_indices(3);
+
+/// Returns the lanes of `cur` that break the UTF-8 syntax, given the chunk `prev`
+/// preceding it.
+///
+/// Every byte is compared with the one, two and three bytes before it, so that the rules
+/// of the RFC 3629 grammar (see `run_utf8_validation_from`) reduce to lanewise tests:
+///
+/// * a byte must be a continuation byte if and only if the byte before it is a lead byte
+///   of a 2, 3 or 4-byte sequence, the byte two before it is a lead byte of a 3 or 4-byte
+///   sequence, or the byte three before it is a lead byte of a 4-byte sequence;
+/// * `C0`, `C1` and `F5` to `FF` never appear;
+/// * the second byte of a sequence starting with `E0`, `ED`, `F0` or `F4` has a
+///   narrower range, excluding overlong encodings, surrogates and codepoints above
+///   `U+10FFFF`.
+#[inline(always)]
+fn chunk_errors(prev: Chunk, cur: Chunk) -> Mask<i8, CHUNK> {
+    // SAFETY: the indices are constants smaller than `2 * CHUNK`.
+    let (prev1, prev2, prev3): (Chunk, Chunk, Chunk) = unsafe {
+        (
+            simd_shuffle(prev, cur, PREV1),
+            simd_shuffle(prev, cur, PREV2),
+            simd_shuffle(prev, cur, PREV3),
+        )
+    };
+    let splat = Chunk::splat;
+
+    let is_cont = cur.lanes_ge(splat(0x80)) & cur.lanes_lt(splat(0xC0));
+    let must_be_cont =
+        prev1.lanes_ge(splat(0xC0)) | prev2.lanes_ge(splat(0xE0)) | prev3.lanes_ge(splat(0xF0));
+
+    (is_cont ^ must_be_cont)
+        | (cur.lanes_ge(splat(0xC0)) & cur.lanes_lt(splat(0xC2)))
+        | cur.lanes_ge(splat(0xF5))
+        | (prev1.lanes_eq(splat(0xE0)) & cur.lanes_lt(splat(0xA0)))
+        | (prev1.lanes_eq(splat(0xED)) & cur.lanes_ge(splat(0xA0)))
+        | (prev1.lanes_eq(splat(0xF0)) & cur.lanes_lt(splat(0x90)))
+        | (prev1.lanes_eq(splat(0xF4)) & cur.lanes_ge(splat(0x90)))
+}
+
+/// Validates `v` a chunk of `CHUNK` bytes at a time, until it reaches the end of the last
+/// whole chunk or a chunk containing an error.
+///
+/// Returns an index up to which `v` is valid UTF-8, from which `run_utf8_validation_from`
+/// resumes: it checks the incomplete chunk at the end, and finds the exact position of the
+/// error, if any.
+#[inline(never)]
+fn validate_chunks(v: &[u8]) -> usize {
+    let len = v.len();
+    let ptr = v.as_ptr();
+    let mut prev = Chunk::splat(0);
+    let mut prev_is_ascii = true;
+    let mut offset = 0;
+
+    // SAFETY: callers only pass offsets with `offset + CHUNK <= len`, and `read_unaligned`
+    // has no alignment requirement.
+    let load = |offset: usize| unsafe { ptr::read_unaligned(ptr.add(offset) as *const Chunk) };
+
+    while offset + CHUNK <= len {
+        // Skip runs of ASCII quickly, four chunks at a time.
+        if prev_is_ascii {
+            while offset + 4 * CHUNK <= len {
+                let any = load(offset)
+                    | load(offset + CHUNK)
+                    | load(offset + 2 * CHUNK)
+                    | load(offset + 3 * CHUNK);
+                if any.lanes_ge(Chunk::splat(0x80)).any() {
+                    break;
+                }
+                offset += 4 * CHUNK;
+            }
+            if offset + CHUNK > len {
+                break;
+            }
+        }
+
+        let cur = load(offset);
+        let is_ascii = !cur.lanes_ge(Chunk::splat(0x80)).any();
+        // A chunk of ASCII bytes after another one needs no further checks.
+        if !(is_ascii && prev_is_ascii) && chunk_errors(prev, cur).any() {
+            break;
+        }
+        prev = cur;
+        prev_is_ascii = is_ascii;
+        offset += CHUNK;
+    }
+
+    // Step back to the start of the last character before `offset`, which may continue in
+    // the bytes that have not been validated yet. Characters are at most 4 bytes long.
+    let lo = offset.saturating_sub(3);
+    match v[lo..offset].iter().rposition(|&byte| !utf8_is_cont_byte(byte)) {
+        Some(i) => lo + i,
+        None => offset,
+    }
+}
+
 // https://tools.ietf.org/html/rfc3629
 static UTF8_CHAR_WIDTH: [u8; 256] = [
     1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
@@ -0,0 +1,59 @@
+use crate::fmt;
+use crate::marker::PhantomData;
+use crate::pin::Pin;
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// Creates a stream that yields nothing.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(async_stream)]
+/// use core::stream::{self, Stream};
+///
+/// let s = stream::empty::<i32>();
+/// assert_eq!(s.size_hint(), (0, Some(0)));
+/// ```
+#[unstable(feature = "async_stream", issue = "none")]
+pub const fn empty<T>() -> Empty<T> {
+    Empty(PhantomData)
+}
+
+/// A stream that yields nothing.
+///
+/// This `struct` is created by the [`empty()`] function. See its
+/// documentation for more.
+#[must_use = "streams do nothing unless polled"]
+#[unstable(feature = "async_stream", issue = "none")]
+pub struct Empty<T>(PhantomData<fn() -> T>);
+
+#[unstable(feature = "async_stream", issue = "none")]
+impl<T> Unpin for Empty<T> {}
+
+#[unstable(feature = "async_stream", issue = "none")]
+impl<T> Clone for Empty<T> {
+    fn clone(&self) -> Self {
+        Empty(PhantomData)
+    }
+}
+
+#[unstable(feature = "async_stream", issue = "none")]
+impl<T> fmt::Debug for Empty<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Empty")
+    }
+}
+
+#[unstable(feature = "async_stream", issue = "none")]
+impl<T> Stream for Empty<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<T>> {
+        Poll::Ready(None)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(0))
+    }
+}
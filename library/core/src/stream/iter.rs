@@ -0,0 +1,51 @@
+use crate::pin::Pin;
+
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// A stream that was created from an iterator.
+///
+/// This stream is created by the [`iter`] function.
+/// See its documentation for more.
+///
+/// [`iter`]: fn.iter.html
+#[unstable(feature = "async_stream", issue = "none")]
+#[derive(Clone, Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Iter<I> {
+    iter: I,
+}
+
+#[unstable(feature = "async_stream", issue = "none")]
+impl<I> Unpin for Iter<I> {}
+
+/// Converts an iterator into a stream.
+///
+/// The stream is always ready: each poll yields the iterator's next item.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(async_stream)]
+/// use core::stream::{self, Stream};
+///
+/// let s = stream::iter(vec![0, 1, 2, 3]);
+/// assert_eq!(s.size_hint(), (4, Some(4)));
+/// ```
+#[unstable(feature = "async_stream", issue = "none")]
+pub fn iter<I: IntoIterator>(i: I) -> Iter<I::IntoIter> {
+    Iter { iter: i.into_iter() }
+}
+
+#[unstable(feature = "async_stream", issue = "none")]
+impl<I: Iterator> Stream for Iter<I> {
+    type Item = I::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.iter.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
@@ -0,0 +1,120 @@
+#![unstable(feature = "async_stream", issue = "none")]
+
+//! Composable asynchronous iteration.
+//!
+//! If futures are asynchronous values, then streams are asynchronous
+//! iterators. If you've found yourself with an asynchronous collection of some
+//! kind, and needed to perform an operation on the elements of said
+//! collection, you'll quickly run into 'streams'. Streams are heavily used in
+//! idiomatic asynchronous Rust code, so it's worth becoming familiar with them.
+//!
+//! Before explaining more, let's talk about how this module is structured:
+//!
+//! # Organization
+//!
+//! This module is largely organized by type:
+//!
+//! * [Traits] are the core portion: these traits define what kind of streams
+//!   exist and what you can do with them. The methods of these traits are worth
+//!   putting some extra study time into.
+//! * Functions provide some helpful ways to create some basic streams.
+//! * Structs are often the return types of the various methods and functions
+//!   in this module.
+//!
+//! [Traits]: #traits
+//!
+//! From there, we'll dig into the trait, [`Stream`].
+//!
+//! # Stream
+//!
+//! The heart and soul of this module is the [`Stream`] trait. The core of
+//! [`Stream`] looks like this:
+//!
+//! ```
+//! # use core::task::{Context, Poll};
+//! # use core::pin::Pin;
+//! trait Stream {
+//!     type Item;
+//!     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
+//! }
+//! ```
+//!
+//! Like [`Iterator::next`], [`poll_next`] returns `Some(Item)` as long as
+//! there are elements, and once they've all been exhausted, returns `None` to
+//! indicate that iteration is finished. Unlike an iterator, a stream can also
+//! return [`Poll::Pending`] when the next element is not available yet, after
+//! arranging for the task in the [`Context`] to be woken up once it is.
+//!
+//! Individual streams may choose to resume iteration, and so calling
+//! `poll_next` again after `None` may or may not eventually yield `Some(Item)`
+//! again at some point.
+//!
+//! [`poll_next`]: Stream::poll_next
+//! [`Poll::Pending`]: crate::task::Poll::Pending
+//! [`Context`]: crate::task::Context
+//!
+//! # Creating streams
+//!
+//! The functions in this module build streams out of other things: [`iter`]
+//! turns any [`Iterator`] into a stream that is always ready, [`poll_fn`]
+//! wraps a function returning `Poll<Option<T>>`, and [`empty`] and [`once`]
+//! yield no items and a single item respectively.
+//!
+//! ```
+//! #![feature(async_stream)]
+//! use core::pin::Pin;
+//! use core::stream::{self, Stream};
+//! use core::task::{Context, Poll};
+//!
+//! // A stream that counts down from a number, ready on every poll.
+//! struct Countdown(u32);
+//!
+//! impl Stream for Countdown {
+//!     type Item = u32;
+//!
+//!     fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<u32>> {
+//!         if self.0 == 0 {
+//!             Poll::Ready(None)
+//!         } else {
+//!             self.0 -= 1;
+//!             Poll::Ready(Some(self.0 + 1))
+//!         }
+//!     }
+//!
+//!     fn size_hint(&self) -> (usize, Option<usize>) {
+//!         (self.0 as usize, Some(self.0 as usize))
+//!     }
+//! }
+//!
+//! // The same sequence, built from an iterator instead.
+//! let from_iter = stream::iter((1..=3).rev());
+//! assert_eq!(from_iter.size_hint(), Countdown(3).size_hint());
+//! ```
+//!
+//! # Laziness
+//!
+//! Streams are *lazy*. This means that just creating a stream doesn't _do_ a
+//! whole lot. Nothing really happens until you call `poll_next`. This is
+//! sometimes a source of confusion when creating a stream solely for its side
+//! effects. The compiler will warn us about this kind of behavior:
+//!
+//! ```text
+//! warning: unused result that must be used: streams do nothing unless polled
+//! ```
+
+mod empty;
+mod iter;
+mod once;
+mod poll_fn;
+mod stream;
+
+#[unstable(feature = "async_stream", issue = "none")]
+pub use empty::{empty, Empty};
+#[unstable(feature = "async_stream", issue = "none")]
+pub use iter::{iter, Iter};
+#[unstable(feature = "async_stream", issue = "none")]
+pub use once::{once, Once};
+#[unstable(feature = "async_stream", issue = "none")]
+pub use poll_fn::{poll_fn, PollFn};
+#[unstable(feature = "async_stream", issue = "none")]
+pub use stream::Stream;
@@ -0,0 +1,47 @@
+use crate::pin::Pin;
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// Creates a stream that yields an element exactly once.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(async_stream)]
+/// use core::stream::{self, Stream};
+///
+/// let s = stream::once(7);
+/// assert_eq!(s.size_hint(), (1, Some(1)));
+/// ```
+#[unstable(feature = "async_stream", issue = "none")]
+pub fn once<T>(value: T) -> Once<T> {
+    Once { value: Some(value) }
+}
+
+/// A stream that yields an element exactly once.
+///
+/// This `struct` is created by the [`once()`] function. See its
+/// documentation for more.
+#[derive(Clone, Debug)]
+#[must_use = "streams do nothing unless polled"]
+#[unstable(feature = "async_stream", issue = "none")]
+pub struct Once<T> {
+    value: Option<T>,
+}
+
+#[unstable(feature = "async_stream", issue = "none")]
+impl<T> Unpin for Once<T> {}
+
+#[unstable(feature = "async_stream", issue = "none")]
+impl<T> Stream for Once<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<T>> {
+        Poll::Ready(self.value.take())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.value.is_some() as usize;
+        (len, Some(len))
+    }
+}
@@ -0,0 +1,66 @@
+use crate::fmt;
+use crate::pin::Pin;
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// Creates a stream that wraps a function returning `Poll<Option<T>>`.
+///
+/// Polling the stream delegates to the wrapped function.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(async_stream)]
+/// use core::stream::{self, Stream};
+/// use core::task::Poll;
+///
+/// let mut counter = 0;
+/// let s = stream::poll_fn(move |_cx| -> Poll<Option<u32>> {
+///     if counter == 3 {
+///         Poll::Ready(None)
+///     } else {
+///         counter += 1;
+///         Poll::Ready(Some(counter))
+///     }
+/// });
+/// assert_eq!(s.size_hint(), (0, None));
+/// ```
+#[unstable(feature = "async_stream", issue = "none")]
+pub fn poll_fn<T, F>(f: F) -> PollFn<F>
+where
+    F: FnMut(&mut Context<'_>) -> Poll<Option<T>>,
+{
+    PollFn { f }
+}
+
+/// A stream that wraps a function returning `Poll<Option<T>>`.
+///
+/// This `struct` is created by [`poll_fn()`]. See its
+/// documentation for more.
+#[must_use = "streams do nothing unless polled"]
+#[unstable(feature = "async_stream", issue = "none")]
+pub struct PollFn<F> {
+    f: F,
+}
+
+#[unstable(feature = "async_stream", issue = "none")]
+impl<F> Unpin for PollFn<F> {}
+
+#[unstable(feature = "async_stream", issue = "none")]
+impl<F> fmt::Debug for PollFn<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PollFn").finish()
+    }
+}
+
+#[unstable(feature = "async_stream", issue = "none")]
+impl<T, F> Stream for PollFn<F>
+where
+    F: FnMut(&mut Context<'_>) -> Poll<Option<T>>,
+{
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        (&mut self.f)(cx)
+    }
+}
@@ -21,12 +21,32 @@ fn test_pointer_formats_data_pointer() {
 
 #[test]
 fn test_estimated_capacity() {
+    // literal arguments are inlined into the pieces, so use a variable instead.
+    let world = "World";
     assert_eq!(format_args!("").estimated_capacity(), 0);
-    assert_eq!(format_args!("{}", "").estimated_capacity(), 0);
+    assert_eq!(format_args!("{}", world).estimated_capacity(), 0);
     assert_eq!(format_args!("Hello").estimated_capacity(), 5);
-    assert_eq!(format_args!("Hello, {}!", "").estimated_capacity(), 16);
-    assert_eq!(format_args!("{}, hello!", "World").estimated_capacity(), 0);
-    assert_eq!(format_args!("{}. 16-bytes piece", "World").estimated_capacity(), 32);
+    assert_eq!(format_args!("Hello, {}!", world).estimated_capacity(), 16);
+    assert_eq!(format_args!("{}, hello!", world).estimated_capacity(), 0);
+    assert_eq!(format_args!("{}. 16-bytes piece", world).estimated_capacity(), 32);
+    assert_eq!(format_args!("{}, hello!", "World").estimated_capacity(), 13);
+}
+
+#[test]
+fn test_inlined_literal_args() {
+    assert_eq!(format_args!("{}", "").as_str(), Some(""));
+    assert_eq!(format_args!("{}, {}{}", "Hello", "world", '!').as_str(), Some("Hello, world!"));
+    assert_eq!(format_args!("{} {} {}", 42, 255u8, true).as_str(), Some("42 255 true"));
+    assert_eq!(format_args!("{0}{0} {x}", "{}", x = 1).as_str(), Some("{}{} 1"));
+
+    // only literals formatted with a plain `{}` are inlined.
+    let x = 1;
+    assert_eq!(format_args!("{} {}", "a", x).as_str(), None);
+    assert_eq!(format_args!("{:>2}", 1).as_str(), None);
+    assert_eq!(format_args!("{:?}", "a").as_str(), None);
+    assert_eq!(format_args!("{}", 1.5).as_str(), None);
+    assert_eq!(format!("{} {:?} {}", "a", "a", x), "a \"a\" 1");
+    assert_eq!(format!("{1}{0} {1:>3}", 'a', "b"), "ba   b");
 }
 
 #[test]
@@ -102,6 +102,21 @@ fn test_format_int() {
     assert_eq!(format!("{:E}", 10000000001u64), "1.0000000001E10");
 }
 
+#[test]
+fn test_format_int_limits() {
+    // Non-literal arguments, so that these go through `Display` and not through
+    // the literals inlined by `format_args!`.
+    let (min, max) = (i128::MIN, u128::MAX);
+    assert_eq!(format!("{}", min), "-170141183460469231731687303715884105728");
+    assert_eq!(format!("{}", max), "340282366920938463463374607431768211455");
+    assert_eq!(format!("{:41}", min), " -170141183460469231731687303715884105728");
+    assert_eq!(format!("{:+}", max), "+340282366920938463463374607431768211455");
+    let (min, max) = (i8::MIN, i64::MAX);
+    assert_eq!(format!("{}", min), "-128");
+    assert_eq!(format!("{:05}", min), "-0128");
+    assert_eq!(format!("{}", max), "9223372036854775807");
+}
+
 #[test]
 fn test_format_int_exp_limits() {
     assert_eq!(format!("{:e}", i8::MIN), "-1.28e2");
@@ -0,0 +1,62 @@
+use core::hash::{Hash, Hasher, KeyedHasher};
+
+fn hash_with_keys<T: Hash + ?Sized>(key0: u64, key1: u64, x: &T) -> u64 {
+    let mut hasher = KeyedHasher::new_with_keys(key0, key1);
+    x.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_keyed_deterministic() {
+    assert_eq!(hash_with_keys(1, 2, "hello"), hash_with_keys(1, 2, "hello"));
+    assert_eq!(hash_with_keys(0, 0, &17u64), hash_with_keys(0, 0, &17u64));
+
+    let hasher = KeyedHasher::new_with_keys(3, 4);
+    let mut copy = hasher.clone();
+    copy.write_u32(5);
+    assert_ne!(copy.finish(), hasher.finish());
+    assert_eq!(format!("{:?}", hasher), "KeyedHasher { .. }");
+}
+
+#[test]
+fn test_keyed_depends_on_keys() {
+    for x in 0..100u64 {
+        let hash = hash_with_keys(1, 2, &x);
+        assert_ne!(hash, hash_with_keys(2, 2, &x));
+        assert_ne!(hash, hash_with_keys(1, 3, &x));
+    }
+}
+
+#[test]
+fn test_keyed_integers_distinct() {
+    let mut hashes: Vec<u64> = (0..1000u64).map(|x| hash_with_keys(0, 0, &x)).collect();
+    hashes.sort_unstable();
+    hashes.dedup();
+    assert_eq!(hashes.len(), 1000);
+
+    // The low bits, which pick the bucket, should vary for sequential keys too.
+    let mut low: Vec<u64> = (0..1000u64).map(|x| hash_with_keys(5, 6, &x) & 0xff).collect();
+    low.sort_unstable();
+    low.dedup();
+    assert!(low.len() > 200);
+}
+
+#[test]
+fn test_keyed_bytes_distinct() {
+    // Cover every length the short input paths handle, plus multi-block inputs,
+    // and make sure the overlapping loads do not make different inputs collide.
+    let mut hashes = Vec::new();
+    for len in 0..70 {
+        let data: Vec<u8> = (0..len as u8).collect();
+        hashes.push(hash_with_keys(7, 8, &data[..]));
+        for i in 0..len {
+            let mut flipped = data.clone();
+            flipped[i] ^= 0x80;
+            hashes.push(hash_with_keys(7, 8, &flipped[..]));
+        }
+    }
+    let count = hashes.len();
+    hashes.sort_unstable();
+    hashes.dedup();
+    assert_eq!(hashes.len(), count);
+}
@@ -1,3 +1,4 @@
+mod keyed;
 mod sip;
 
 use std::default::Default;
@@ -4,6 +4,7 @@
 #![feature(array_methods)]
 #![feature(array_map)]
 #![feature(array_windows)]
+#![feature(async_stream)]
 #![feature(bool_to_option)]
 #![feature(bound_cloned)]
 #![feature(box_syntax)]
@@ -23,11 +24,14 @@
 #![feature(exact_size_is_empty)]
 #![feature(fixed_size_array)]
 #![feature(flt2dec)]
+#![feature(fmt_as_str)]
 #![feature(fmt_internals)]
 #![feature(hashmap_internals)]
+#![feature(keyed_hasher)]
 #![feature(try_find)]
 #![feature(is_sorted)]
 #![feature(pattern)]
+#![feature(portable_simd)]
 #![feature(raw)]
 #![feature(sort_internals)]
 #![feature(slice_partition_at_index)]
@@ -91,9 +95,11 @@
 mod pin;
 mod ptr;
 mod result;
+mod simd;
 mod slice;
 mod str;
 mod str_lossy;
+mod stream;
 mod task;
 mod time;
 mod tuple;
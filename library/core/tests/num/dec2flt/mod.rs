@@ -1,7 +1,8 @@
 #![allow(overflowing_literals)]
 
+mod float;
+mod lemire;
 mod parse;
-mod rawfp;
 
 // Take a float literal, turn it into a string in various ways (that are all trusted
 // to be correct) and see if those strings are parsed back to the value of the literal.
@@ -149,7 +150,18 @@ fn borderline_overflow() {
     for _ in 0..375 {
         s.push('3');
     }
-    // At the time of this writing, this returns Err(..), but this is a bug that should be fixed.
-    // It makes no sense to enshrine that in a test, the important part is that it doesn't panic.
-    let _ = s.parse::<f64>();
+    assert_eq!(s.parse::<f64>(), Ok(1.0 / 3.0));
+    assert_eq!(s.parse::<f32>(), Ok(1.0 / 3.0));
+}
+
+#[test]
+fn many_digits() {
+    // Halfway between 1.0 and the next float up, which rounds down to even, unless any of the
+    // following digits, no matter how far out, is non-zero.
+    let halfway = "1.00000000000000011102230246251565404236316680908203125";
+    assert_eq!(halfway.parse::<f64>(), Ok(1.0));
+    let above = format!("{}{}1", halfway, "0".repeat(1000));
+    assert_eq!(above.parse::<f64>(), Ok(1.0 + f64::EPSILON));
+    let below = format!("{}{}9", &halfway[..halfway.len() - 1], "4".repeat(1000));
+    assert_eq!(below.parse::<f64>(), Ok(1.0));
 }
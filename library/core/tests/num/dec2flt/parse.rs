@@ -1,17 +1,23 @@
-use core::num::dec2flt::parse::ParseResult::{Invalid, Valid};
-use core::num::dec2flt::parse::{parse_decimal, Decimal};
+use core::num::dec2flt::number::Number;
+use core::num::dec2flt::parse::parse_number;
+use core::num::dec2flt::{dec2flt, pfe_invalid};
+
+fn new_number(e: i64, m: u64) -> Number {
+    Number { exponent: e, mantissa: m, negative: false, many_digits: false }
+}
 
 #[test]
 fn missing_pieces() {
     let permutations = &[".e", "1e", "e4", "e", ".12e", "321.e", "32.12e+", "12.32e-"];
     for &s in permutations {
-        assert_eq!(parse_decimal(s), Invalid);
+        assert_eq!(dec2flt::<f64>(s), Err(pfe_invalid()));
     }
 }
 
 #[test]
 fn invalid_chars() {
     let invalid = "r,?<j";
+    let error = Err(pfe_invalid());
     let valid_strings = &["123", "666.", ".1", "5e1", "7e-3", "0.0e+1"];
     for c in invalid.chars() {
         for s in valid_strings {
@@ -19,23 +25,39 @@ fn invalid_chars() {
                 let mut input = String::new();
                 input.push_str(s);
                 input.insert(i, c);
-                assert!(parse_decimal(&input) == Invalid, "did not reject invalid {:?}", input);
+                assert!(dec2flt::<f64>(&input) == error, "did not reject invalid {:?}", input);
             }
         }
     }
 }
 
+fn parse_positive(s: &[u8]) -> Option<Number> {
+    parse_number(s, false)
+}
+
 #[test]
 fn valid() {
-    assert_eq!(parse_decimal("123.456e789"), Valid(Decimal::new(b"123", b"456", 789)));
-    assert_eq!(parse_decimal("123.456e+789"), Valid(Decimal::new(b"123", b"456", 789)));
-    assert_eq!(parse_decimal("123.456e-789"), Valid(Decimal::new(b"123", b"456", -789)));
-    assert_eq!(parse_decimal(".050"), Valid(Decimal::new(b"", b"050", 0)));
-    assert_eq!(parse_decimal("999"), Valid(Decimal::new(b"999", b"", 0)));
-    assert_eq!(parse_decimal("1.e300"), Valid(Decimal::new(b"1", b"", 300)));
-    assert_eq!(parse_decimal(".1e300"), Valid(Decimal::new(b"", b"1", 300)));
-    assert_eq!(parse_decimal("101e-33"), Valid(Decimal::new(b"101", b"", -33)));
+    assert_eq!(parse_positive(b"123.456e789"), Some(new_number(786, 123456)));
+    assert_eq!(parse_positive(b"123.456e+789"), Some(new_number(786, 123456)));
+    assert_eq!(parse_positive(b"123.456e-789"), Some(new_number(-792, 123456)));
+    assert_eq!(parse_positive(b".050"), Some(new_number(-3, 50)));
+    assert_eq!(parse_positive(b"999"), Some(new_number(0, 999)));
+    assert_eq!(parse_positive(b"1.e300"), Some(new_number(300, 1)));
+    assert_eq!(parse_positive(b".1e300"), Some(new_number(299, 1)));
+    assert_eq!(parse_positive(b"101e-33"), Some(new_number(-33, 101)));
     let zeros = "0".repeat(25);
     let s = format!("1.5e{}", zeros);
-    assert_eq!(parse_decimal(&s), Valid(Decimal::new(b"1", b"5", 0)));
+    assert_eq!(parse_positive(s.as_bytes()), Some(new_number(-1, 15)));
+}
+
+#[test]
+fn many_digits() {
+    let s = format!("1{}.5", "0".repeat(30));
+    let num = parse_positive(s.as_bytes()).unwrap();
+    assert!(num.many_digits);
+    assert_eq!(num.mantissa, 1000000000000000000);
+    assert_eq!(num.exponent, 12);
+    // Leading zeros are not significant digits.
+    let s = format!("0.{}1", "0".repeat(30));
+    assert_eq!(parse_positive(s.as_bytes()), Some(new_number(-31, 1)));
 }
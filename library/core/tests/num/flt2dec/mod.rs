@@ -13,6 +13,7 @@
 mod strategy {
     mod dragon;
     mod grisu;
+    mod ryu;
 }
 mod random;
 
@@ -0,0 +1,64 @@
+use super::super::*;
+use core::num::flt2dec::strategy::ryu::*;
+
+#[test]
+#[cfg_attr(miri, ignore)] // Miri is too slow
+fn test_pow5_tables() {
+    // the entries are `5^i` normalized to `POW5_BITCOUNT` bits, so the most significant
+    // bit is always at the same position (and the inverses are one bit short of that).
+    for (i, &(_, high)) in POW5_SPLIT.iter().enumerate() {
+        assert_eq!(128 - high.leading_zeros() as i32, POW5_BITCOUNT, "POW5_SPLIT[{}]", i);
+    }
+    for (i, &(_, high)) in POW5_INV_SPLIT.iter().enumerate().skip(1) {
+        assert_eq!(128 - high.leading_zeros() as i32, POW5_INV_BITCOUNT, "POW5_INV_SPLIT[{}]", i);
+    }
+}
+
+#[test]
+fn test_log_approximations() {
+    let mut pow5bits_exact = 1;
+    let mut pow5 = 1u128;
+    for e in 0..55 {
+        assert_eq!(pow5bits(e), pow5bits_exact, "pow5bits({})", e);
+        pow5 *= 5;
+        pow5bits_exact = 128 - pow5.leading_zeros() as i32;
+    }
+    assert_eq!(log10_pow2(0), 0);
+    assert_eq!(log10_pow2(3), 0);
+    assert_eq!(log10_pow2(4), 1);
+    assert_eq!(log10_pow2(1074), 323);
+    assert_eq!(log10_pow5(0), 0);
+    assert_eq!(log10_pow5(1), 0);
+    assert_eq!(log10_pow5(2), 1);
+    assert_eq!(log10_pow5(1076), 752);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "emscripten"), ignore)] // issue 42630
+#[test]
+fn shortest_sanity_test() {
+    f64_shortest_sanity_test(format_shortest);
+    f32_shortest_sanity_test(format_shortest);
+    more_shortest_sanity_test(format_shortest);
+}
+
+#[test]
+fn shortest_opt_range_test() {
+    let mut buf = [MaybeUninit::new(0); MAX_SIG_DIGITS];
+    // every finite float is covered by the tables...
+    assert!(format_shortest_opt(&decode_finite(f64::MAX), &mut buf).is_some());
+    assert!(format_shortest_opt(&decode_finite(f64::MIN_POSITIVE), &mut buf).is_some());
+    assert!(format_shortest_opt(&decode_finite(5e-324), &mut buf).is_some());
+    // ...but arbitrary mantissas are not.
+    let d = Decoded { mant: 99_999_999_999_999_999, minus: 1, plus: 1, exp: 0, inclusive: true };
+    assert!(format_shortest_opt(&d, &mut buf).is_none());
+}
+
+#[test]
+fn test_to_shortest_str() {
+    to_shortest_str_test(format_shortest);
+}
+
+#[test]
+fn test_to_shortest_exp_str() {
+    to_shortest_exp_str_test(format_shortest);
+}
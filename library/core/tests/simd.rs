@@ -0,0 +1,178 @@
+use core::simd::*;
+
+#[test]
+fn splat_and_arrays() {
+    let v = u32x4::splat(7);
+    assert_eq!(v.to_array(), [7; 4]);
+    assert_eq!(v.lanes(), 4);
+    assert_eq!(u8x16::LANES, 16);
+
+    let mut v = f64x2::from_array([1.5, -2.5]);
+    assert_eq!(v[1], -2.5);
+    v[0] = 3.0;
+    assert_eq!(<[f64; 2]>::from(v), [3.0, -2.5]);
+    assert_eq!(Simd::from([1u8, 2]), u8x2::from_slice(&[1, 2, 3]));
+    assert_eq!(format!("{:?}", i16x4::from_array([1, -2, 3, -4])), "[1, -2, 3, -4]");
+}
+
+#[test]
+#[should_panic]
+fn from_slice_too_short() {
+    let _ = u16x8::from_slice(&[1, 2, 3]);
+}
+
+#[test]
+fn arithmetic() {
+    let a = i32x4::from_array([1, -2, 3, i32::MAX]);
+    let b = i32x4::from_array([4, 5, -6, 1]);
+    assert_eq!((a + b).to_array(), [5, 3, -3, i32::MIN]);
+    assert_eq!((a - b).to_array(), [-3, -7, 9, i32::MAX - 1]);
+    assert_eq!((a * b).to_array(), [4, -10, -18, i32::MAX]);
+    assert_eq!((b / a).to_array(), [4, -2, -2, 0]);
+    assert_eq!((b % a).to_array(), [0, 1, 0, 1]);
+    assert_eq!((-a).to_array(), [-1, 2, -3, -i32::MAX]);
+    assert_eq!((!a).to_array(), [!1, !-2, !3, !i32::MAX]);
+
+    let mut c = u8x4::from_array([0b1100, 0b1010, 0xff, 0]);
+    c &= u8x4::splat(0b1000);
+    assert_eq!(c.to_array(), [0b1000, 0b1000, 0b1000, 0]);
+    c |= u8x4::from_array([1, 2, 3, 4]);
+    assert_eq!(c.to_array(), [0b1001, 0b1010, 0b1011, 0b100]);
+    c ^= u8x4::splat(0xff);
+    assert_eq!(c.to_array(), [!0b1001, !0b1010, !0b1011, !0b100]);
+    assert_eq!((u8x4::splat(1) << u8x4::from_array([0, 1, 2, 7])).to_array(), [1, 2, 4, 128]);
+    assert_eq!((i8x2::splat(-128) >> i8x2::from_array([1, 7])).to_array(), [-64, -1]);
+
+    let f = f32x4::from_array([1.0, -2.0, 0.0, 8.0]);
+    assert_eq!((f / f32x4::splat(2.0)).to_array(), [0.5, -1.0, 0.0, 4.0]);
+    assert_eq!((f % f32x4::splat(3.0)).to_array(), [1.0, -2.0, 0.0, 2.0]);
+    assert_eq!(f.abs().to_array(), [1.0, 2.0, 0.0, 8.0]);
+    assert!((-f)[2].is_sign_negative());
+}
+
+#[test]
+#[should_panic(expected = "attempt to divide by zero")]
+fn div_by_zero() {
+    let _ = u64x2::splat(1) / u64x2::from_array([1, 0]);
+}
+
+#[test]
+#[should_panic(expected = "attempt to divide with overflow")]
+fn div_overflow() {
+    let _ = i8x4::splat(i8::MIN) / i8x4::from_array([1, 2, -1, 3]);
+}
+
+#[test]
+#[should_panic(expected = "attempt to shift left with overflow")]
+fn shl_overflow() {
+    let _ = u32x2::splat(1) << u32x2::from_array([1, 32]);
+}
+
+#[test]
+#[should_panic(expected = "attempt to shift right with overflow")]
+fn shr_negative() {
+    let _ = i64x2::splat(1) >> i64x2::from_array([-1, 0]);
+}
+
+#[test]
+fn comparisons() {
+    let a = i16x4::from_array([1, 2, 3, 4]);
+    let b = i16x4::from_array([4, 2, 1, 4]);
+    assert_eq!(a.lanes_eq(b).to_array(), [false, true, false, true]);
+    assert_eq!(a.lanes_ne(b).to_array(), [true, false, true, false]);
+    assert_eq!(a.lanes_lt(b).to_array(), [true, false, false, false]);
+    assert_eq!(a.lanes_le(b).to_array(), [true, true, false, true]);
+    assert_eq!(a.lanes_gt(b).to_array(), [false, false, true, false]);
+    assert_eq!(a.lanes_ge(b).to_array(), [false, true, true, true]);
+
+    let f = f64x2::from_array([f64::NAN, 1.0]);
+    assert_eq!(f.is_nan().to_array(), [true, false]);
+    assert_ne!(f, f);
+    assert_eq!(f.min(f64x2::splat(0.0)).to_array(), [0.0, 0.0]);
+    assert_eq!(f.max(f64x2::splat(0.0)).to_array(), [0.0, 1.0]);
+    assert_eq!(f32x2::from_bits(f32x2::splat(1.0).to_bits()), f32x2::splat(1.0));
+}
+
+#[test]
+fn masks() {
+    let mut m = mask32x4::from_array([true, false, true, false]);
+    assert!(m.any());
+    assert!(!m.all());
+    assert!(m.test(2));
+    m.set(1, true);
+    assert_eq!(m.to_array(), [true, true, true, false]);
+    assert_eq!(m.to_int().to_array(), [-1, -1, -1, 0]);
+    assert_eq!((!m).to_array(), [false, false, false, true]);
+    assert_eq!((m & mask32x4::splat(false)), mask32x4::default());
+    assert!((m | !m).all());
+    assert!(!(m ^ m).any());
+    assert_eq!(format!("{:?}", m), "[true, true, true, false]");
+
+    let a = u32x4::from_array([1, 2, 3, 4]);
+    let b = u32x4::splat(0);
+    assert_eq!(m.select(a, b).to_array(), [1, 2, 3, 0]);
+    assert_eq!(Mask::from_int(i8x2::from_array([0, -1])).to_array(), [false, true]);
+}
+
+#[test]
+#[should_panic]
+fn mask_from_invalid_int() {
+    let _ = Mask::from_int(i8x2::from_array([0, 1]));
+}
+
+#[test]
+fn reductions() {
+    let a = u8x8::from_array([1, 2, 3, 4, 5, 6, 7, 200]);
+    assert_eq!(a.horizontal_sum(), 228);
+    assert_eq!(u8x4::splat(128).horizontal_sum(), 0);
+    assert_eq!(u8x4::from_array([1, 2, 3, 4]).horizontal_product(), 24);
+    assert_eq!(a.horizontal_max(), 200);
+    assert_eq!(a.horizontal_min(), 1);
+    assert_eq!(a.horizontal_and(), 0);
+    assert_eq!(a.horizontal_or(), 0b1100_1111);
+    assert_eq!(a.horizontal_xor(), 1 ^ 2 ^ 3 ^ 4 ^ 5 ^ 6 ^ 7 ^ 200);
+    assert_eq!(i32x4::from_array([-1, -2, 3, 4]).horizontal_min(), -2);
+
+    let f = f32x4::from_array([1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(f.horizontal_sum(), 10.0);
+    assert_eq!(f.horizontal_product(), 24.0);
+    assert_eq!(f.horizontal_max(), 4.0);
+    assert_eq!(f.horizontal_min(), 1.0);
+    assert!(f32x2::splat(-0.0).horizontal_sum().is_sign_negative());
+}
+
+#[test]
+fn swizzles() {
+    struct Interleave;
+    impl Swizzle<4, 8> for Interleave {
+        const INDEX: [u32; 8] = [0, 0, 1, 1, 2, 2, 3, 3];
+    }
+
+    let a = u16x4::from_array([1, 2, 3, 4]);
+    assert_eq!(Interleave::swizzle(a).to_array(), [1, 1, 2, 2, 3, 3, 4, 4]);
+    assert_eq!(a.reverse().to_array(), [4, 3, 2, 1]);
+    assert_eq!(f64x2::from_array([1.0, 2.0]).reverse().to_array(), [2.0, 1.0]);
+    assert_eq!(u8x64::splat(1).reverse(), u8x64::splat(1));
+}
+
+#[test]
+fn gather_scatter() {
+    let data = [10, 11, 12, 13, 14, 15, 16, 17, 18];
+    let idxs = usizex4::from_array([9, 3, 0, usize::MAX]);
+    let or = i32x4::splat(-1);
+    assert_eq!(Simd::gather_or(&data, idxs, or).to_array(), [-1, 13, 10, -1]);
+    assert_eq!(Simd::gather_or_default(&data, idxs).to_array(), [0, 13, 10, 0]);
+
+    let enable = masksizex4::from_array([true, false, true, true]);
+    let gathered = Simd::gather_select(&data, enable, usizex4::from_array([0, 1, 2, 3]), or);
+    assert_eq!(gathered.to_array(), [10, -1, 12, 13]);
+
+    let mut out = [0; 5];
+    let vals = i32x4::from_array([1, 2, 3, 4]);
+    vals.scatter(&mut out, usizex4::from_array([4, 0, 4, 7]));
+    assert_eq!(out, [2, 0, 0, 0, 3]);
+
+    let mut out = [0; 4];
+    vals.scatter_select(&mut out, enable, usizex4::from_array([3, 2, 1, 0]));
+    assert_eq!(out, [4, 3, 0, 1]);
+}
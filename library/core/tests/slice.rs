@@ -1717,7 +1717,7 @@ fn select_nth_unstable_past_length() {
 }
 
 pub mod memchr {
-    use core::slice::memchr::{memchr, memrchr};
+    use core::slice::memchr::{memchr, memchr_simd, memrchr, memrchr_simd};
 
     // test fallback implementations on all platforms
     #[test]
@@ -1800,6 +1800,35 @@ fn each_alignment_reversed() {
             assert_eq!(Some(pos - start), memrchr(needle, &data[start..]));
         }
     }
+
+    #[test]
+    fn each_position() {
+        let mut data = [1u8; 200];
+        for pos in 0..data.len() {
+            data[pos] = 2;
+            for start in 0..=pos {
+                let text = &data[start..];
+                assert_eq!(Some(pos - start), memchr(2, text));
+                assert_eq!(Some(pos - start), memrchr(2, text));
+                assert_eq!(Some(pos - start), memchr_simd::<32>(2, text));
+                assert_eq!(Some(pos - start), memrchr_simd::<32>(2, text));
+            }
+            data[pos] = 1;
+        }
+        assert_eq!(None, memchr(2, &data));
+        assert_eq!(None, memrchr(2, &data));
+        assert_eq!(None, memchr_simd::<32>(2, &data));
+        assert_eq!(None, memrchr_simd::<32>(2, &data));
+    }
+
+    #[test]
+    fn first_and_last_of_many() {
+        let data = b"xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxaxxxxxxxxxxxxxxxxxxxxxxaxxxxxxxxxxx";
+        assert_eq!(Some(41), memchr(b'a', data));
+        assert_eq!(Some(64), memrchr(b'a', data));
+        assert_eq!(Some(41), memchr_simd::<16>(b'a', data));
+        assert_eq!(Some(64), memrchr_simd::<16>(b'a', data));
+    }
 }
 
 #[test]
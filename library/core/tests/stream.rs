@@ -0,0 +1,69 @@
+use core::pin::Pin;
+use core::ptr;
+use core::stream::{self, Stream};
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    // SAFETY: the vtable functions ignore the data pointer.
+    unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+}
+
+fn collect<S: Stream + Unpin>(mut s: S) -> Vec<S::Item> {
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut items = Vec::new();
+    loop {
+        match Pin::new(&mut s).poll_next(&mut cx) {
+            Poll::Ready(Some(item)) => items.push(item),
+            Poll::Ready(None) => return items,
+            Poll::Pending => {}
+        }
+    }
+}
+
+#[test]
+fn test_iter() {
+    let s = stream::iter(1..4);
+    assert_eq!(s.size_hint(), (3, Some(3)));
+    assert_eq!(collect(s), [1, 2, 3]);
+}
+
+#[test]
+fn test_empty_and_once() {
+    assert_eq!(collect(stream::empty::<i32>()), []);
+    let s = stream::once(5);
+    assert_eq!(s.size_hint(), (1, Some(1)));
+    assert_eq!(collect(s), [5]);
+}
+
+#[test]
+fn test_poll_fn() {
+    let mut state = 0;
+    let s = stream::poll_fn(move |cx| {
+        state += 1;
+        match state {
+            1 | 3 => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            2 => Poll::Ready(Some("a")),
+            4 => Poll::Ready(Some("b")),
+            _ => Poll::Ready(None),
+        }
+    });
+    assert_eq!(collect(s), ["a", "b"]);
+}
+
+#[test]
+fn test_by_ref_and_pin() {
+    let mut s = stream::iter(vec![1, 2]);
+    assert_eq!(collect(&mut s), [1, 2]);
+    let mut once = stream::once('x');
+    assert_eq!(collect(Pin::new(&mut once)), ['x']);
+    assert_eq!(once.size_hint(), (0, Some(0)));
+}
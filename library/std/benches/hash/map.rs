@@ -1,7 +1,11 @@
 #![cfg(test)]
 
+use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
-use test::Bencher;
+#[allow(deprecated)]
+use std::hash::SipHasher13;
+use std::hash::{BuildHasher, Hasher};
+use test::{black_box, Bencher};
 
 #[bench]
 fn new_drop(b: &mut Bencher) {
@@ -101,3 +105,31 @@ fn get_remove_insert(b: &mut Bencher) {
         k += 1;
     })
 }
+
+#[bench]
+fn hash_u64_default(b: &mut Bencher) {
+    let state = RandomState::new();
+    b.iter(|| {
+        let mut sum = 0u64;
+        for i in 0..1000u64 {
+            let mut hasher = state.build_hasher();
+            hasher.write_u64(black_box(i));
+            sum = sum.wrapping_add(hasher.finish());
+        }
+        sum
+    })
+}
+
+#[bench]
+#[allow(deprecated)]
+fn hash_u64_siphash13(b: &mut Bencher) {
+    b.iter(|| {
+        let mut sum = 0u64;
+        for i in 0..1000u64 {
+            let mut hasher = SipHasher13::new_with_keys(1, 2);
+            hasher.write_u64(black_box(i));
+            sum = sum.wrapping_add(hasher.finish());
+        }
+        sum
+    })
+}
@@ -1,3 +1,4 @@
+#![feature(hashmap_internals)]
 #![feature(test)]
 
 extern crate test;
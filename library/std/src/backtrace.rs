@@ -95,11 +95,14 @@
 // a backtrace or actually symbolizing it.
 
 use crate::backtrace_rs::{self, BytesOrWideString};
+use crate::borrow::Cow;
+use crate::cell::UnsafeCell;
 use crate::env;
 use crate::ffi::c_void;
 use crate::fmt;
+use crate::path::Path;
 use crate::sync::atomic::{AtomicUsize, Ordering::SeqCst};
-use crate::sync::Mutex;
+use crate::sync::Once;
 use crate::sys_common::backtrace::{lock, output_filename};
 use crate::vec::Vec;
 
@@ -132,7 +135,7 @@ pub enum BacktraceStatus {
 enum Inner {
     Unsupported,
     Disabled,
-    Captured(Mutex<Capture>),
+    Captured(LazilyResolvedCapture),
 }
 
 struct Capture {
@@ -146,7 +149,13 @@ fn _assert<T: Send + Sync>() {}
     _assert::<Backtrace>();
 }
 
-struct BacktraceFrame {
+/// A single frame of a backtrace.
+///
+/// Frames are obtained from [`Backtrace::frames`]. A frame corresponds to one
+/// instruction pointer on the stack, which may resolve to several
+/// [`BacktraceSymbol`]s if functions were inlined into each other.
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+pub struct BacktraceFrame {
     frame: RawFrame,
     symbols: Vec<BacktraceSymbol>,
 }
@@ -157,13 +166,29 @@ enum RawFrame {
     Fake,
 }
 
-struct BacktraceSymbol {
+/// A resolved symbol of a [`BacktraceFrame`].
+///
+/// All of the information here is provided on a best-effort basis, see the
+/// [module-level documentation](self) for more on accuracy.
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+pub struct BacktraceSymbol {
     name: Option<Vec<u8>>,
+    addr: Option<usize>,
     filename: Option<BytesOrWide>,
     lineno: Option<u32>,
     colno: Option<u32>,
 }
 
+/// The name of a [`BacktraceSymbol`].
+///
+/// The `Display` implementation prints the demangled name. As with symbols
+/// printed in a backtrace, the alternate flag (`{:#}`) omits the trailing hash
+/// of Rust symbol names.
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+pub struct SymbolName<'a> {
+    name: backtrace_rs::SymbolName<'a>,
+}
+
 enum BytesOrWide {
     Bytes(Vec<u8>),
     Wide(Vec<u16>),
@@ -171,12 +196,11 @@ enum BytesOrWide {
 
 impl fmt::Debug for Backtrace {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut capture = match &self.inner {
+        let capture = match &self.inner {
             Inner::Unsupported => return fmt.write_str("<unsupported>"),
             Inner::Disabled => return fmt.write_str("<disabled>"),
-            Inner::Captured(c) => c.lock().unwrap(),
+            Inner::Captured(c) => c.force(),
         };
-        capture.resolve();
 
         let frames = &capture.frames[capture.actual_start..];
 
@@ -196,6 +220,16 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+impl fmt::Debug for BacktraceFrame {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut dbg = fmt.debug_list();
+        dbg.entries(&self.symbols);
+        dbg.finish()
+    }
+}
+
+#[unstable(feature = "backtrace_frames", issue = "79676")]
 impl fmt::Debug for BacktraceSymbol {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         // FIXME: improve formatting: https://github.com/rust-lang/rust/issues/65280
@@ -222,6 +256,20 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+impl fmt::Display for SymbolName<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.name, fmt)
+    }
+}
+
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+impl fmt::Debug for SymbolName<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.name, fmt)
+    }
+}
+
 impl fmt::Debug for BytesOrWide {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         output_filename(
@@ -331,7 +379,7 @@ fn create(ip: usize) -> Backtrace {
         let inner = if frames.is_empty() {
             Inner::Unsupported
         } else {
-            Inner::Captured(Mutex::new(Capture {
+            Inner::Captured(LazilyResolvedCapture::new(Capture {
                 actual_start: actual_start.unwrap_or(0),
                 frames,
                 resolved: false,
@@ -353,14 +401,145 @@ pub fn status(&self) -> BacktraceStatus {
     }
 }
 
+impl<'a> Backtrace {
+    /// Returns the frames of this backtrace, starting with the caller of
+    /// the function that captured it.
+    ///
+    /// The frames are resolved to symbols on the first call to this method,
+    /// or the first time the backtrace is formatted, whichever happens first.
+    /// Backtraces that were not captured have no frames.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(backtrace, backtrace_frames)]
+    ///
+    /// use std::backtrace::Backtrace;
+    ///
+    /// let backtrace = Backtrace::force_capture();
+    /// for frame in backtrace.frames() {
+    ///     for symbol in frame.symbols() {
+    ///         if let (Some(name), Some(file)) = (symbol.name(), symbol.filename()) {
+    ///             println!("{:#} at {}:{:?}", name, file.display(), symbol.lineno());
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn frames(&'a self) -> &'a [BacktraceFrame] {
+        if let Inner::Captured(c) = &self.inner {
+            let capture = c.force();
+            &capture.frames[capture.actual_start..]
+        } else {
+            &[]
+        }
+    }
+}
+
+impl BacktraceFrame {
+    /// Returns the current instruction pointer of this frame.
+    ///
+    /// This is normally the next instruction to execute in the frame, but not
+    /// all implementations list this with 100% accuracy.
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn ip(&self) -> *mut c_void {
+        self.frame.ip()
+    }
+
+    /// Returns the starting address of the function this frame belongs to,
+    /// if it could be determined while unwinding.
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn symbol_address(&self) -> *mut c_void {
+        self.frame.symbol_address()
+    }
+
+    /// Returns the symbols this frame resolved to.
+    ///
+    /// This is usually a single symbol, but can contain several when
+    /// functions were inlined, with the innermost one first. It is empty if
+    /// the frame could not be resolved, e.g. because of missing debug
+    /// information.
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn symbols(&self) -> &[BacktraceSymbol] {
+        &self.symbols
+    }
+}
+
+impl BacktraceSymbol {
+    /// Returns the name of this symbol, if known.
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn name(&self) -> Option<SymbolName<'_>> {
+        self.name.as_ref().map(|b| SymbolName { name: backtrace_rs::SymbolName::new(b) })
+    }
+
+    /// Returns the starting address of this symbol, if known.
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn addr(&self) -> Option<*mut c_void> {
+        self.addr.map(|addr| addr as *mut c_void)
+    }
+
+    /// Returns the path of the source file this symbol was defined in, if
+    /// known.
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn filename(&self) -> Option<Cow<'_, Path>> {
+        self.filename.as_ref().map(|filename| match filename {
+            #[cfg(unix)]
+            BytesOrWide::Bytes(bytes) => {
+                use crate::os::unix::prelude::*;
+                Path::new(crate::ffi::OsStr::from_bytes(bytes)).into()
+            }
+            #[cfg(not(unix))]
+            BytesOrWide::Bytes(bytes) => {
+                Path::new(crate::str::from_utf8(bytes).unwrap_or("<unknown>")).into()
+            }
+            #[cfg(windows)]
+            BytesOrWide::Wide(wide) => {
+                use crate::os::windows::prelude::*;
+                Cow::Owned(crate::ffi::OsString::from_wide(wide).into())
+            }
+            #[cfg(not(windows))]
+            BytesOrWide::Wide(_wide) => Path::new("<unknown>").into(),
+        })
+    }
+
+    /// Returns the line number within `filename` this symbol corresponds to,
+    /// if known.
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn lineno(&self) -> Option<u32> {
+        self.lineno
+    }
+
+    /// Returns the column number within `filename` this symbol corresponds
+    /// to, if known.
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn colno(&self) -> Option<u32> {
+        self.colno
+    }
+}
+
+impl SymbolName<'_> {
+    /// Returns the raw, possibly mangled, bytes of the symbol name.
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.name.as_bytes()
+    }
+
+    /// Returns the raw, possibly mangled, symbol name if it is valid UTF-8.
+    ///
+    /// Use the `Display` implementation for the demangled version.
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn as_str(&self) -> Option<&str> {
+        self.name.as_str()
+    }
+}
+
 impl fmt::Display for Backtrace {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut capture = match &self.inner {
+        let capture = match &self.inner {
             Inner::Unsupported => return fmt.write_str("unsupported backtrace"),
             Inner::Disabled => return fmt.write_str("disabled backtrace"),
-            Inner::Captured(c) => c.lock().unwrap(),
+            Inner::Captured(c) => c.force(),
         };
-        capture.resolve();
 
         let full = fmt.alternate();
         let (frames, style) = if full {
@@ -404,6 +583,33 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+struct LazilyResolvedCapture {
+    sync: Once,
+    capture: UnsafeCell<Capture>,
+}
+
+impl LazilyResolvedCapture {
+    const fn new(capture: Capture) -> Self {
+        LazilyResolvedCapture { sync: Once::new(), capture: UnsafeCell::new(capture) }
+    }
+
+    fn force(&self) -> &Capture {
+        self.sync.call_once(|| {
+            // SAFETY: This exclusive reference can't overlap with any others
+            // `Once` guarantees callers will block until this closure returns
+            // `Once` also guarantees only a single caller will enter this closure
+            unsafe { &mut *self.capture.get() }.resolve();
+        });
+
+        // SAFETY: This shared reference can't overlap with the exclusive reference above
+        unsafe { &*self.capture.get() }
+    }
+}
+
+// SAFETY: Access to the inner value is synchronized using a thread-safe `Once`
+// So long as `Capture` is `Sync`, `LazilyResolvedCapture` is too
+unsafe impl Sync for LazilyResolvedCapture where Capture: Sync {}
+
 impl Capture {
     fn resolve(&mut self) {
         // If we're already resolved, nothing to do!
@@ -428,6 +634,7 @@ fn resolve(&mut self) {
                 backtrace_rs::resolve_frame_unsynchronized(frame, |symbol| {
                     symbols.push(BacktraceSymbol {
                         name: symbol.name().map(|m| m.as_bytes().to_vec()),
+                        addr: symbol.addr().map(|a| a as usize),
                         filename: symbol.filename_raw().map(|b| match b {
                             BytesOrWideString::Bytes(b) => BytesOrWide::Bytes(b.to_owned()),
                             BytesOrWideString::Wide(b) => BytesOrWide::Wide(b.to_owned()),
@@ -449,4 +656,12 @@ fn ip(&self) -> *mut c_void {
             RawFrame::Fake => 1 as *mut c_void,
         }
     }
+
+    fn symbol_address(&self) -> *mut c_void {
+        match self {
+            RawFrame::Actual(frame) => frame.symbol_address(),
+            #[cfg(test)]
+            RawFrame::Fake => 1 as *mut c_void,
+        }
+    }
 }
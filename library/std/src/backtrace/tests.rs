@@ -3,7 +3,7 @@
 #[test]
 fn test_debug() {
     let backtrace = Backtrace {
-        inner: Inner::Captured(Mutex::new(Capture {
+        inner: Inner::Captured(LazilyResolvedCapture::new(Capture {
             actual_start: 1,
             resolved: true,
             frames: vec![
@@ -11,6 +11,7 @@ fn test_debug() {
                     frame: RawFrame::Fake,
                     symbols: vec![BacktraceSymbol {
                         name: Some(b"std::backtrace::Backtrace::create".to_vec()),
+                        addr: None,
                         filename: Some(BytesOrWide::Bytes(b"rust/backtrace.rs".to_vec())),
                         lineno: Some(100),
                         colno: None,
@@ -20,6 +21,7 @@ fn test_debug() {
                     frame: RawFrame::Fake,
                     symbols: vec![BacktraceSymbol {
                         name: Some(b"__rust_maybe_catch_panic".to_vec()),
+                        addr: None,
                         filename: None,
                         lineno: None,
                         colno: None,
@@ -30,12 +32,14 @@ fn test_debug() {
                     symbols: vec![
                         BacktraceSymbol {
                             name: Some(b"std::rt::lang_start_internal".to_vec()),
+                            addr: None,
                             filename: Some(BytesOrWide::Bytes(b"rust/rt.rs".to_vec())),
                             lineno: Some(300),
                             colno: Some(5),
                         },
                         BacktraceSymbol {
                             name: Some(b"std::rt::lang_start".to_vec()),
+                            addr: None,
                             filename: Some(BytesOrWide::Bytes(b"rust/rt.rs".to_vec())),
                             lineno: Some(400),
                             colno: None,
@@ -55,3 +59,63 @@ fn test_debug() {
 
     assert_eq!(format!("{:#?}", backtrace), expected);
 }
+
+#[test]
+fn test_frames() {
+    let backtrace = Backtrace {
+        inner: Inner::Captured(LazilyResolvedCapture::new(Capture {
+            actual_start: 1,
+            resolved: true,
+            frames: vec![
+                BacktraceFrame {
+                    frame: RawFrame::Fake,
+                    symbols: vec![BacktraceSymbol {
+                        name: Some(b"std::backtrace::Backtrace::create".to_vec()),
+                        addr: None,
+                        filename: Some(BytesOrWide::Bytes(b"rust/backtrace.rs".to_vec())),
+                        lineno: Some(100),
+                        colno: None,
+                    }],
+                },
+                BacktraceFrame {
+                    frame: RawFrame::Fake,
+                    symbols: vec![
+                        BacktraceSymbol {
+                            name: Some(b"std::rt::lang_start_internal".to_vec()),
+                            addr: Some(42),
+                            filename: Some(BytesOrWide::Bytes(b"rust/rt.rs".to_vec())),
+                            lineno: Some(300),
+                            colno: Some(5),
+                        },
+                        BacktraceSymbol {
+                            name: None,
+                            addr: None,
+                            filename: None,
+                            lineno: None,
+                            colno: None,
+                        },
+                    ],
+                },
+            ],
+        })),
+    };
+
+    let frames = backtrace.frames();
+    assert_eq!(frames.len(), 1);
+
+    let symbols = frames[0].symbols();
+    assert_eq!(symbols.len(), 2);
+
+    let name = symbols[0].name().unwrap();
+    assert_eq!(name.as_str(), Some("std::rt::lang_start_internal"));
+    assert_eq!(name.to_string(), "std::rt::lang_start_internal");
+    assert_eq!(symbols[0].addr(), Some(42 as *mut c_void));
+    assert_eq!(symbols[0].filename().as_deref(), Some(Path::new("rust/rt.rs")));
+    assert_eq!(symbols[0].lineno(), Some(300));
+    assert_eq!(symbols[0].colno(), Some(5));
+
+    assert!(symbols[1].name().is_none());
+    assert!(symbols[1].filename().is_none());
+
+    assert!(Backtrace::disabled().frames().is_empty());
+}
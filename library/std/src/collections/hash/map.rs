@@ -9,8 +9,7 @@
 use crate::cell::Cell;
 use crate::collections::TryReserveError;
 use crate::fmt::{self, Debug};
-#[allow(deprecated)]
-use crate::hash::{BuildHasher, Hash, Hasher, SipHasher13};
+use crate::hash::{BuildHasher, Hash, Hasher, KeyedHasher, SipHasher13};
 use crate::iter::{FromIterator, FusedIterator};
 use crate::ops::Index;
 use crate::sys;
@@ -26,12 +25,15 @@
 /// In particular, seeds generated when the system's entropy pool is abnormally
 /// low such as during system boot may be of a lower quality.
 ///
-/// The default hashing algorithm is currently SipHash 1-3, though this is
-/// subject to change at any point in the future. While its performance is very
-/// competitive for medium sized keys, other hashing algorithms will outperform
-/// it for small keys such as integers as well as large keys such as long
-/// strings, though those algorithms will typically *not* protect against
-/// attacks such as HashDoS.
+/// The default hashing algorithm is currently [`KeyedHasher`], though this is
+/// subject to change at any point in the future. It is fast for small keys
+/// such as integers, and uses AES instructions if the standard library itself
+/// was compiled with them enabled (there is no detection at runtime). Unlike
+/// unkeyed hashers such as FxHash, it keeps its resistance to HashDoS as long
+/// as the hash values themselves are not revealed; see its
+/// documentation for the details.
+///
+/// [`KeyedHasher`]: crate::hash::KeyedHasher
 ///
 /// The hashing algorithm can be replaced on a per-`HashMap` basis using the
 /// [`default`], [`with_hasher`], and [`with_capacity_and_hasher`] methods.
@@ -841,6 +843,57 @@ pub fn insert(&mut self, k: K, v: V) -> Option<V> {
         self.base.insert(k, v)
     }
 
+    /// Tries to insert a key-value pair into the map, returning an error
+    /// instead of aborting if the table needs to grow and allocating fails.
+    ///
+    /// On success, the return value is the same as that of [`insert`].
+    /// On failure, the map is left unchanged and the key and value are dropped.
+    ///
+    /// [`insert`]: HashMap::insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// assert_eq!(map.try_insert(37, "a")?, None);
+    /// assert_eq!(map.try_insert(37, "b")?, Some("a"));
+    /// assert_eq!(map[&37], "b");
+    /// # Ok::<(), std::collections::TryReserveError>(())
+    /// ```
+    #[inline]
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_insert(&mut self, k: K, v: V) -> Result<Option<V>, TryReserveError> {
+        let hash = {
+            let mut state = self.base.hasher().build_hasher();
+            k.hash(&mut state);
+            state.finish()
+        };
+        let full = self.base.len() == self.base.capacity();
+        match self.base.raw_entry_mut().from_key_hashed_nocheck(hash, &k) {
+            base::RawEntryMut::Occupied(mut entry) => Ok(Some(entry.insert(v))),
+            // There is room for the new key, so inserting will not grow the table.
+            base::RawEntryMut::Vacant(entry) if !full => {
+                entry.insert_hashed_nocheck(hash, k, v);
+                Ok(None)
+            }
+            base::RawEntryMut::Vacant(_) => {
+                self.try_reserve(1)?;
+                // The key is known to be absent, so the slot is found without
+                // comparing any keys.
+                match self.base.raw_entry_mut().from_hash(hash, |_| false) {
+                    base::RawEntryMut::Vacant(entry) => {
+                        entry.insert_hashed_nocheck(hash, k, v);
+                        Ok(None)
+                    }
+                    base::RawEntryMut::Occupied(_) => unreachable!(),
+                }
+            }
+        }
+    }
+
     /// Removes a key from the map, returning the value at the key if the key
     /// was previously in the map.
     ///
@@ -2780,17 +2833,30 @@ pub fn new() -> RandomState {
         // trap we cache the first set of randomly generated keys per-thread.
         //
         // Later in #36481 it was discovered that exposing a deterministic
-        // iteration order allows a form of DOS attack. To counter that we
-        // increment one of the seeds on every RandomState creation, giving
-        // every corresponding HashMap a different iteration order.
-        thread_local!(static KEYS: Cell<(u64, u64)> = {
-            Cell::new(sys::hashmap_random_keys())
+        // iteration order allows a form of DOS attack. To counter that every
+        // RandomState creation gets a different iteration order from a
+        // per-thread counter.
+        //
+        // The cached keys are never handed out directly. `KeyedHasher` is not
+        // a PRF, so keys that differ in a single known bit (such as successive
+        // counter values) would relate the hashes of different maps to each
+        // other. Instead each map's keys are derived from the cached keys and
+        // the counter with SipHash 1-3, which is.
+        thread_local!(static KEYS: Cell<(u64, u64, u64)> = {
+            let (k0, k1) = sys::hashmap_random_keys();
+            Cell::new((k0, k1, 0))
         });
 
         KEYS.with(|keys| {
-            let (k0, k1) = keys.get();
-            keys.set((k0.wrapping_add(1), k1));
-            RandomState { k0, k1 }
+            let (k0, k1, counter) = keys.get();
+            keys.set((k0, k1, counter.wrapping_add(1)));
+            let derive = |index: u8| {
+                let mut prf = SipHasher13::new_with_keys(k0, k1);
+                prf.write_u64(counter);
+                prf.write_u8(index);
+                prf.finish()
+            };
+            RandomState { k0: derive(0), k1: derive(1) }
         })
     }
 }
@@ -2799,20 +2865,21 @@ pub fn new() -> RandomState {
 impl BuildHasher for RandomState {
     type Hasher = DefaultHasher;
     #[inline]
-    #[allow(deprecated)]
     fn build_hasher(&self) -> DefaultHasher {
-        DefaultHasher(SipHasher13::new_with_keys(self.k0, self.k1))
+        DefaultHasher(KeyedHasher::new_with_keys(self.k0, self.k1))
     }
 }
 
 /// The default [`Hasher`] used by [`RandomState`].
 ///
 /// The internal algorithm is not specified, and so it and its hashes should
-/// not be relied upon over releases.
+/// not be relied upon over releases. It is currently [`KeyedHasher`], whose
+/// documentation describes what it protects against.
+///
+/// [`KeyedHasher`]: crate::hash::KeyedHasher
 #[stable(feature = "hashmap_default_hasher", since = "1.13.0")]
-#[allow(deprecated)]
 #[derive(Clone, Debug)]
-pub struct DefaultHasher(SipHasher13);
+pub struct DefaultHasher(KeyedHasher);
 
 impl DefaultHasher {
     /// Creates a new `DefaultHasher`.
@@ -2821,9 +2888,8 @@ impl DefaultHasher {
     /// `DefaultHasher` instances, but is the same as all other `DefaultHasher`
     /// instances created through `new` or `default`.
     #[stable(feature = "hashmap_default_hasher", since = "1.13.0")]
-    #[allow(deprecated)]
     pub fn new() -> DefaultHasher {
-        DefaultHasher(SipHasher13::new_with_keys(0, 0))
+        DefaultHasher(KeyedHasher::new_with_keys(0, 0))
     }
 }
 
@@ -832,6 +832,18 @@ fn test_try_reserve() {
     }
 }
 
+#[test]
+fn test_try_insert() {
+    let mut map = HashMap::new();
+    for i in 0..100 {
+        assert_eq!(map.try_insert(i, i * 10), Ok(None));
+    }
+    assert_eq!(map.try_insert(7, 0), Ok(Some(70)));
+    assert_eq!(map.len(), 100);
+    assert_eq!(map[&7], 0);
+    assert_eq!(map[&99], 990);
+}
+
 #[test]
 fn test_raw_entry() {
     use super::RawEntryMut::{Occupied, Vacant};
@@ -15,3 +15,19 @@
 #[doc(inline)]
 #[unstable(feature = "into_future", issue = "67644")]
 pub use core::future::IntoFuture;
+
+#[doc(inline)]
+#[unstable(feature = "future_poll_fn", issue = "72302")]
+pub use core::future::{poll_fn, PollFn};
+
+#[doc(inline)]
+#[unstable(feature = "future_join", issue = "none")]
+pub use core::future::join;
+
+#[doc(inline)]
+#[unstable(feature = "future_select", issue = "none")]
+pub use core::future::{select, Select};
+
+#[doc(inline)]
+#[unstable(feature = "future_yield_now", issue = "none")]
+pub use core::future::{yield_now, YieldNow};
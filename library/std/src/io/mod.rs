@@ -271,6 +271,8 @@
 pub use self::cursor::Cursor;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::error::{Error, ErrorKind, Result};
+#[unstable(feature = "read_buf", issue = "78485")]
+pub use self::readbuf::ReadBuf;
 #[unstable(feature = "internal_output_capture", issue = "none")]
 #[doc(no_inline, hidden)]
 pub use self::stdio::set_output_capture;
@@ -289,6 +291,7 @@
 mod error;
 mod impls;
 pub mod prelude;
+mod readbuf;
 mod stdio;
 mod util;
 
@@ -350,8 +353,10 @@ fn append_to_string<F>(buf: &mut String, f: F) -> Result<usize>
 // time is 4,500 times (!) slower than a default reservation size of 32 if the
 // reader has a very small amount of data to return.
 //
-// Because we're extending the buffer with uninitialized data for trusted
-// readers, we need to make sure to truncate that if any of this panics.
+// The spare capacity of the vector is handed to the reader as an uninitialized
+// `ReadBuf`, so readers which implement `read_buf` never have it zeroed. Bytes
+// that a reader initialized but did not fill are remembered across iterations
+// so that they are not initialized a second time.
 fn read_to_end<R: Read + ?Sized>(r: &mut R, buf: &mut Vec<u8>) -> Result<usize> {
     read_to_end_with_reservation(r, buf, |_| 32)
 }
@@ -366,41 +371,48 @@ fn read_to_end_with_reservation<R, F>(
     F: FnMut(&R) -> usize,
 {
     let start_len = buf.len();
-    let mut g = Guard { len: buf.len(), buf };
-    let ret;
+    // Extra initialized bytes from the previous loop iteration
+    let mut initialized = 0;
     loop {
-        if g.len == g.buf.len() {
-            unsafe {
-                // FIXME(danielhenrymantilla): #42788
-                //
-                //   - This creates a (mut) reference to a slice of
-                //     _uninitialized_ integers, which is **undefined behavior**
-                //
-                //   - Only the standard library gets to soundly "ignore" this,
-                //     based on its privileged knowledge of unstable rustc
-                //     internals;
-                g.buf.reserve(reservation_size(r));
-                let capacity = g.buf.capacity();
-                g.buf.set_len(capacity);
-                r.initializer().initialize(&mut g.buf[g.len..]);
-            }
+        if buf.len() == buf.capacity() {
+            buf.reserve(reservation_size(r));
         }
 
-        match r.read(&mut g.buf[g.len..]) {
-            Ok(0) => {
-                ret = Ok(g.len - start_len);
-                break;
-            }
-            Ok(n) => g.len += n,
-            Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
-            Err(e) => {
-                ret = Err(e);
-                break;
-            }
+        let mut read_buf = ReadBuf::uninit(buf.spare_capacity_mut());
+
+        // SAFETY: These bytes were initialized but not filled in the previous loop
+        unsafe {
+            read_buf.assume_init(initialized);
+        }
+
+        let interrupted = match r.read_buf(&mut read_buf) {
+            Ok(()) => false,
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => true,
+            Err(e) => return Err(e),
+        };
+
+        let filled = read_buf.filled_len();
+        initialized = read_buf.initialized_len() - filled;
+
+        if filled == 0 && !interrupted {
+            return Ok(buf.len() - start_len);
+        }
+
+        // SAFETY: ReadBuf's invariants mean this much memory is initialized
+        unsafe {
+            let new_len = buf.len() + filled;
+            buf.set_len(new_len);
         }
     }
+}
 
-    ret
+pub(crate) fn default_read_buf<F>(read: F, buf: &mut ReadBuf<'_>) -> Result<()>
+where
+    F: FnOnce(&mut [u8]) -> Result<usize>,
+{
+    let n = read(buf.initialize_unfilled())?;
+    buf.add_filled(n);
+    Ok(())
 }
 
 pub(crate) fn default_read_vectored<F>(read: F, bufs: &mut [IoSliceMut<'_>]) -> Result<usize>
@@ -590,6 +602,43 @@ fn is_read_vectored(&self) -> bool {
         false
     }
 
+    /// Pull some bytes from this source into the specified buffer.
+    ///
+    /// This is equivalent to the [`read`](Read::read) method, except that it is passed a [`ReadBuf`]
+    /// rather than `[u8]` to allow use with uninitialized buffers. The new data will be appended to
+    /// any existing contents of `buf`.
+    ///
+    /// The default implementation delegates to `read`, initializing the unfilled part of `buf`
+    /// first. Implementations that never read from the buffer they are handed should override
+    /// this method so that callers can skip zeroing it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(read_buf)]
+    ///
+    /// use std::io::{self, ReadBuf};
+    /// use std::io::prelude::*;
+    /// use std::fs::File;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut f = File::open("foo.txt")?;
+    ///     let mut buffer = [MaybeUninit::uninit(); 10];
+    ///     let mut buf = ReadBuf::uninit(&mut buffer);
+    ///
+    ///     // read up to 10 bytes without initializing `buffer` first
+    ///     f.read_buf(&mut buf)?;
+    ///
+    ///     println!("The bytes: {:?}", buf.filled());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "read_buf", issue = "78485")]
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<()> {
+        default_read_buf(|b| self.read(b), buf)
+    }
+
     /// Determines if this `Read`er can work with buffers of uninitialized
     /// memory.
     ///
@@ -2167,6 +2216,18 @@ fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
         self.second.read_vectored(bufs)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<()> {
+        if !self.done_first {
+            let prev_filled = buf.filled_len();
+            self.first.read_buf(buf)?;
+            if buf.filled_len() != prev_filled || buf.remaining() == 0 {
+                return Ok(());
+            }
+            self.done_first = true;
+        }
+        self.second.read_buf(buf)
+    }
+
     unsafe fn initializer(&self) -> Initializer {
         let initializer = self.first.initializer();
         if initializer.should_initialize() { initializer } else { self.second.initializer() }
@@ -2358,6 +2419,53 @@ fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
         Ok(n)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<()> {
+        // Don't call into inner reader at all at EOF because it may still block
+        if self.limit == 0 {
+            return Ok(());
+        }
+
+        let prev_filled = buf.filled_len();
+
+        if self.limit <= buf.remaining() as u64 {
+            // if we just use an as cast to convert, limit may wrap around on a 32 bit target
+            let limit = cmp::min(self.limit, usize::MAX as u64) as usize;
+
+            let extra_init = cmp::min(limit, buf.initialized_len() - buf.filled_len());
+
+            // SAFETY: no uninit data is written to ibuf
+            let ibuf = unsafe { &mut buf.unfilled_mut()[..limit] };
+
+            let mut sliced_buf = ReadBuf::uninit(ibuf);
+
+            // SAFETY: extra_init bytes of ibuf are known to be initialized
+            unsafe {
+                sliced_buf.assume_init(extra_init);
+            }
+
+            self.inner.read_buf(&mut sliced_buf)?;
+
+            let new_init = sliced_buf.initialized_len();
+            let filled = sliced_buf.filled_len();
+
+            // SAFETY: new_init bytes of buf's unfilled buffer have been initialized
+            unsafe {
+                buf.assume_init(new_init);
+            }
+
+            buf.add_filled(filled);
+
+            self.limit -= filled as u64;
+        } else {
+            self.inner.read_buf(buf)?;
+
+            // inner may unfill
+            self.limit -= buf.filled_len().saturating_sub(prev_filled) as u64;
+        }
+
+        Ok(())
+    }
+
     unsafe fn initializer(&self) -> Initializer {
         self.inner.initializer()
     }
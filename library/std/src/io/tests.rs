@@ -1,7 +1,8 @@
 use super::{repeat, Cursor, SeekFrom};
 use crate::cmp::{self, min};
-use crate::io::{self, IoSlice, IoSliceMut};
+use crate::io::{self, IoSlice, IoSliceMut, ReadBuf};
 use crate::io::{BufRead, Read, Seek, Write};
+use crate::mem::MaybeUninit;
 use crate::ops::Deref;
 
 #[test]
@@ -146,6 +147,63 @@ fn read_exact_slice() {
     assert_eq!(c, b"9");
 }
 
+#[test]
+fn read_buf_take() {
+    let mut buf = [MaybeUninit::uninit(); 8];
+    let mut rbuf = ReadBuf::uninit(&mut buf);
+
+    let mut c = Cursor::new(&b"hello world"[..]).take(5);
+    c.read_buf(&mut rbuf).unwrap();
+    assert_eq!(rbuf.filled(), b"hello");
+    assert_eq!(rbuf.initialized_len(), 5);
+    assert_eq!(c.limit(), 0);
+
+    c.read_buf(&mut rbuf).unwrap();
+    assert_eq!(rbuf.filled(), b"hello");
+}
+
+#[test]
+fn read_buf_chain() {
+    let mut buf = [MaybeUninit::uninit(); 8];
+    let mut rbuf = ReadBuf::uninit(&mut buf);
+
+    let mut c = (&b"123"[..]).chain(&b"456789"[..]);
+    c.read_buf(&mut rbuf).unwrap();
+    assert_eq!(rbuf.filled(), b"123");
+    c.read_buf(&mut rbuf).unwrap();
+    assert_eq!(rbuf.filled(), b"12345678");
+    rbuf.clear();
+    c.read_buf(&mut rbuf).unwrap();
+    assert_eq!(rbuf.filled(), b"9");
+}
+
+#[test]
+fn read_to_end_does_not_initialize_for_read_buf() {
+    // A reader that only ever hands out the bytes it has been given, and asserts
+    // that `read_to_end` never asks it to zero the spare capacity of the vector.
+    struct R<'a>(&'a [u8]);
+
+    impl Read for R<'_> {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            panic!("read_to_end should use read_buf")
+        }
+
+        fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+            assert_eq!(buf.filled_len(), 0);
+            let amt = cmp::min(buf.remaining(), self.0.len());
+            buf.append(&self.0[..amt]);
+            self.0 = &self.0[amt..];
+            Ok(())
+        }
+    }
+
+    let data = (0..1000).map(|i| i as u8).collect::<Vec<_>>();
+    let mut v = vec![1, 2, 3];
+    assert_eq!(R(&data).read_to_end(&mut v).unwrap(), 1000);
+    assert_eq!(&v[..3], &[1, 2, 3]);
+    assert_eq!(&v[3..], &data[..]);
+}
+
 #[test]
 fn take_eof() {
     struct R;
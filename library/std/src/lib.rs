@@ -226,6 +226,7 @@
 #![feature(array_error_internals)]
 #![feature(asm)]
 #![feature(associated_type_bounds)]
+#![feature(async_stream)]
 #![feature(atomic_mut_ptr)]
 #![feature(bool_to_option)]
 #![feature(box_syntax)]
@@ -265,6 +266,10 @@
 #![feature(fmt_as_str)]
 #![feature(fn_traits)]
 #![feature(format_args_nl)]
+#![feature(future_join)]
+#![feature(future_poll_fn)]
+#![feature(future_select)]
+#![feature(future_yield_now)]
 #![feature(gen_future)]
 #![feature(generator_trait)]
 #![feature(global_asm)]
@@ -273,6 +278,7 @@
 #![feature(int_error_matching)]
 #![feature(integer_atomics)]
 #![feature(into_future)]
+#![feature(keyed_hasher)]
 #![feature(lang_items)]
 #![feature(link_args)]
 #![feature(linkage)]
@@ -285,6 +291,7 @@
 #![feature(needs_panic_runtime)]
 #![feature(negative_impls)]
 #![feature(never_type)]
+#![feature(new_uninit)]
 #![feature(nll)]
 #![feature(nonnull_slice_from_raw_parts)]
 #![feature(once_cell)]
@@ -293,6 +300,7 @@
 #![feature(panic_info_message)]
 #![feature(panic_internals)]
 #![feature(panic_unwind)]
+#![feature(portable_simd)]
 #![feature(prelude_import)]
 #![feature(ptr_internals)]
 #![feature(raw)]
@@ -327,6 +335,7 @@
 #![cfg_attr(bootstrap, feature(untagged_unions))]
 #![feature(unwind_attributes)]
 #![feature(vec_into_raw_parts)]
+#![feature(vec_spare_capacity)]
 #![feature(wake_trait)]
 // NB: the above list is sorted to minimize merge conflicts.
 #![default_lib_allocator]
@@ -443,6 +452,10 @@
 pub use core::raw;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::result;
+#[unstable(feature = "portable_simd", issue = "none")]
+pub use core::simd;
+#[unstable(feature = "async_stream", issue = "none")]
+pub use core::stream;
 #[stable(feature = "i128", since = "1.26.0")]
 pub use core::u128;
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -482,17 +495,7 @@
 pub mod lazy;
 
 #[stable(feature = "futures_api", since = "1.36.0")]
-pub mod task {
-    //! Types and Traits for working with asynchronous tasks.
-
-    #[doc(inline)]
-    #[stable(feature = "futures_api", since = "1.36.0")]
-    pub use core::task::*;
-
-    #[doc(inline)]
-    #[unstable(feature = "wake_trait", issue = "69912")]
-    pub use alloc::task::*;
-}
+pub mod task;
 
 #[stable(feature = "futures_api", since = "1.36.0")]
 pub mod future;
@@ -105,14 +105,16 @@
 use crate::ffi::OsStr;
 use crate::fmt;
 use crate::fs;
-use crate::io::{self, Initializer, IoSlice, IoSliceMut};
+use crate::io::{self, Initializer, IoSlice, IoSliceMut, ReadBuf};
 use crate::path::Path;
 use crate::str;
-use crate::sys::pipe::{read2, AnonPipe};
+use crate::sys::pipe::{read2, read2_bounded, AnonPipe};
 use crate::sys::process as imp;
 #[unstable(feature = "command_access", issue = "44434")]
 pub use crate::sys_common::process::CommandEnvs;
+use crate::sys_common::process::ReadOutcome;
 use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
+use crate::time::{Duration, Instant};
 
 /// Representation of a running or exited child process.
 ///
@@ -206,6 +208,12 @@ fn as_inner(&self) -> &imp::Process {
     }
 }
 
+impl AsInnerMut<imp::Process> for Child {
+    fn as_inner_mut(&mut self) -> &mut imp::Process {
+        &mut self.handle
+    }
+}
+
 impl FromInner<(imp::Process, imp::StdioPipes)> for Child {
     fn from_inner((handle, io): (imp::Process, imp::StdioPipes)) -> Child {
         Child {
@@ -332,6 +340,10 @@ fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.inner.read_buf(buf)
+    }
+
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.inner.read_vectored(bufs)
     }
@@ -393,6 +405,10 @@ fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.inner.read_buf(buf)
+    }
+
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.inner.read_vectored(bufs)
     }
@@ -880,6 +896,54 @@ pub fn output(&mut self) -> io::Result<Output> {
             .and_then(|p| p.wait_with_output())
     }
 
+    /// Executes the command as a child process like [`output`], but gives up
+    /// on it once `deadline` passes or once it has written more than
+    /// `max_output` bytes to either stdout or stderr.
+    ///
+    /// In either case the child is killed, and whatever output it produced
+    /// up to that point is returned, cut down to `max_output` bytes per
+    /// stream. See [`Child::wait_with_output_limited`] for the details.
+    ///
+    /// Taking a deadline rather than a timeout makes it easy to run a series
+    /// of commands, or commands nested within each other's handling, under a
+    /// single overall time budget.
+    ///
+    /// By default, stdout and stderr are captured (and used to provide the
+    /// resulting output). Stdin is not inherited from the parent and any
+    /// attempt by the child process to read from the stdin stream will result
+    /// in the stream immediately closing.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_output_limits)]
+    /// use std::process::Command;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let deadline = Instant::now() + Duration::from_secs(10);
+    /// let output = Command::new("make")
+    ///                      .output_limited(Some(deadline), Some(1 << 20))
+    ///                      .expect("failed to execute process");
+    ///
+    /// if output.timed_out {
+    ///     println!("the build took too long");
+    /// }
+    /// println!("status: {}", output.output.status);
+    /// ```
+    ///
+    /// [`output`]: Command::output
+    #[unstable(feature = "process_output_limits", issue = "none")]
+    pub fn output_limited(
+        &mut self,
+        deadline: Option<Instant>,
+        max_output: Option<usize>,
+    ) -> io::Result<LimitedOutput> {
+        self.inner
+            .spawn(imp::Stdio::MakePipe, false)
+            .map(Child::from_inner)
+            .and_then(|p| p.wait_with_output_limited(deadline, max_output))
+    }
+
     /// Executes a command as a child process, waiting for it to finish and
     /// collecting its exit status.
     ///
@@ -1100,6 +1164,31 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+/// The output of a process captured with a deadline or a limit on its size.
+///
+/// This is returned in a Result by either the [`output_limited`] method of a
+/// [`Command`], or the [`wait_with_output_limited`] method of a [`Child`]
+/// process.
+///
+/// [`output_limited`]: Command::output_limited
+/// [`wait_with_output_limited`]: Child::wait_with_output_limited
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[unstable(feature = "process_output_limits", issue = "none")]
+pub struct LimitedOutput {
+    /// The exit status of the process and the output that was captured.
+    ///
+    /// If the process was killed because of the deadline or the size limit,
+    /// the output is what it had written up to that point.
+    pub output: Output,
+    /// Whether the deadline passed before the process had exited and closed
+    /// its stdout and stderr, in which case it was killed.
+    pub timed_out: bool,
+    /// Whether the process wrote more than the allowed amount of output to
+    /// stdout or stderr, in which case it was killed and the output was cut
+    /// down to the limit.
+    pub truncated: bool,
+}
+
 /// Describes what to do with a standard I/O stream for a child process when
 /// passed to the [`stdin`], [`stdout`], and [`stderr`] methods of [`Command`].
 ///
@@ -1608,6 +1697,50 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         Ok(self.handle.try_wait()?.map(ExitStatus))
     }
 
+    /// Waits for the child to exit for at most `timeout`, returning its exit
+    /// status if it did.
+    ///
+    /// If the child has already exited, its exit status is returned
+    /// immediately, and if it's still running once the timeout elapses,
+    /// `Ok(None)` is returned. The child is left running in that case, and
+    /// can be waited on again or killed.
+    ///
+    /// Unlike [`wait`], this does not close the stdin handle of the child.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Linux this waits on a pidfd for the child where the kernel supports
+    /// them, and on Windows on the process handle. Elsewhere the child is
+    /// polled with [`try_wait`] at short intervals, so the exit may be noticed
+    /// up to a few milliseconds late.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(child_wait_timeout)]
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// let mut child = Command::new("sleep").arg("5").spawn().unwrap();
+    ///
+    /// match child.wait_timeout(Duration::from_secs(1)) {
+    ///     Ok(Some(status)) => println!("exited with: {}", status),
+    ///     Ok(None) => {
+    ///         println!("still running, killing it");
+    ///         child.kill().unwrap();
+    ///         child.wait().unwrap();
+    ///     }
+    ///     Err(e) => println!("error attempting to wait: {}", e),
+    /// }
+    /// ```
+    ///
+    /// [`wait`]: Child::wait
+    /// [`try_wait`]: Child::try_wait
+    #[unstable(feature = "child_wait_timeout", issue = "none")]
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        Ok(self.handle.wait_timeout(timeout)?.map(ExitStatus))
+    }
+
     /// Simultaneously waits for the child to exit and collect all remaining
     /// output on the stdout/stderr handles, returning an `Output`
     /// instance.
@@ -1664,6 +1797,98 @@ pub fn wait_with_output(mut self) -> io::Result<Output> {
         let status = self.wait()?;
         Ok(Output { status, stdout, stderr })
     }
+
+    /// Waits for the child to exit and collects its output like
+    /// [`wait_with_output`], but gives up once `deadline` passes or once the
+    /// child has written more than `max_output` bytes to either stdout or
+    /// stderr.
+    ///
+    /// Stdout and stderr are read concurrently, and either being closed
+    /// doesn't stop the other from being read. If the deadline passes before
+    /// the child has exited and both have reached EOF, or if either of them
+    /// exceeds `max_output`, the child is killed and waited on, and the
+    /// output it had produced is returned. `timed_out` or `truncated` is set
+    /// on the returned [`LimitedOutput`] accordingly, and output exceeding
+    /// the limit is discarded. Passing `None` lifts either restriction.
+    ///
+    /// Note that only the child itself is killed. Processes it spawned in
+    /// turn may keep running and holding on to its stdout and stderr, but
+    /// they can't make this method block past the deadline.
+    ///
+    /// The stdin handle to the child process, if any, will be closed
+    /// before waiting, as with [`wait_with_output`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_output_limits)]
+    /// use std::process::{Command, Stdio};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let child = Command::new("yes")
+    ///     .stdout(Stdio::piped())
+    ///     .spawn()
+    ///     .expect("failed to execute child");
+    ///
+    /// let deadline = Instant::now() + Duration::from_secs(1);
+    /// let output = child
+    ///     .wait_with_output_limited(Some(deadline), Some(1024))
+    ///     .expect("failed to wait on child");
+    ///
+    /// assert!(output.truncated);
+    /// assert_eq!(output.output.stdout.len(), 1024);
+    /// ```
+    ///
+    /// [`wait_with_output`]: Child::wait_with_output
+    #[unstable(feature = "process_output_limits", issue = "none")]
+    pub fn wait_with_output_limited(
+        mut self,
+        deadline: Option<Instant>,
+        max_output: Option<usize>,
+    ) -> io::Result<LimitedOutput> {
+        drop(self.stdin.take());
+
+        let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+        let outcome = read2_bounded(
+            self.stdout.take().map(|out| out.inner),
+            &mut stdout,
+            self.stderr.take().map(|err| err.inner),
+            &mut stderr,
+            deadline,
+            max_output,
+        )?;
+
+        let mut timed_out = outcome == ReadOutcome::TimedOut;
+        let truncated = outcome == ReadOutcome::LimitReached;
+        let status = if timed_out || truncated {
+            self.kill_and_wait()?
+        } else {
+            // The pipes were closed, but the child may still be running.
+            let timeout = deadline.map(|d| d.saturating_duration_since(Instant::now()));
+            match timeout {
+                None => self.wait()?,
+                Some(timeout) => match self.wait_timeout(timeout)? {
+                    Some(status) => status,
+                    None => {
+                        timed_out = true;
+                        self.kill_and_wait()?
+                    }
+                },
+            }
+        };
+        Ok(LimitedOutput { output: Output { status, stdout, stderr }, timed_out, truncated })
+    }
+
+    // Kills the child unless it has exited already, and reaps it.
+    fn kill_and_wait(&mut self) -> io::Result<ExitStatus> {
+        if let Some(status) = self.try_wait()? {
+            return Ok(status);
+        }
+        // This can only fail if the child exited in the meantime, which the
+        // `wait` below will pick up on.
+        let _ = self.kill();
+        self.wait()
+    }
 }
 
 /// Terminates the current process with the specified exit code.
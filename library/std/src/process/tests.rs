@@ -1,8 +1,9 @@
 use crate::io::prelude::*;
 
-use super::{Command, Output, Stdio};
+use super::{Command, LimitedOutput, Output, Stdio};
 use crate::io::ErrorKind;
 use crate::str;
+use crate::time::{Duration, Instant};
 
 // FIXME(#10380) these tests should not all be ignored on android.
 
@@ -203,6 +204,85 @@ fn test_wait_with_output_once() {
     assert_eq!(stderr, Vec::new());
 }
 
+#[test]
+#[cfg_attr(any(target_os = "vxworks", target_os = "android"), ignore)]
+fn test_wait_timeout() {
+    let mut prog = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(&["/C", "exit 1"]).spawn().unwrap()
+    } else {
+        Command::new("false").spawn().unwrap()
+    };
+    let status = prog.wait_timeout(Duration::from_secs(60)).unwrap().unwrap();
+    assert_eq!(status.code(), Some(1));
+    // Once reaped, the status is still available.
+    assert_eq!(prog.wait_timeout(Duration::from_secs(0)).unwrap(), Some(status));
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks", target_os = "android"), ignore)]
+fn test_wait_timeout_expires() {
+    let mut prog = Command::new("sleep").arg("10").spawn().unwrap();
+    let start = Instant::now();
+    assert_eq!(prog.wait_timeout(Duration::from_millis(100)).unwrap(), None);
+    assert!(start.elapsed() >= Duration::from_millis(100));
+    prog.kill().unwrap();
+    assert!(prog.wait_timeout(Duration::from_secs(60)).unwrap().is_some());
+}
+
+#[test]
+#[cfg_attr(any(target_os = "vxworks", target_os = "android"), ignore)]
+fn test_output_limited() {
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("cmd");
+        cmd.args(&["/C", "echo hello"]);
+        cmd
+    } else {
+        let mut cmd = Command::new("echo");
+        cmd.arg("hello");
+        cmd
+    };
+    let deadline = Instant::now() + Duration::from_secs(60);
+    let LimitedOutput { output, timed_out, truncated } =
+        cmd.output_limited(Some(deadline), Some(1024)).unwrap();
+
+    assert!(!timed_out);
+    assert!(!truncated);
+    assert!(output.status.success());
+    assert_eq!(str::from_utf8(&output.stdout).unwrap().trim(), "hello");
+    assert_eq!(output.stderr, Vec::new());
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks", target_os = "android"), ignore)]
+fn test_output_limited_deadline() {
+    let deadline = Instant::now() + Duration::from_millis(200);
+    let LimitedOutput { output, timed_out, truncated } = Command::new("sh")
+        .args(&["-c", "echo start; echo oops >&2; sleep 10"])
+        .output_limited(Some(deadline), None)
+        .unwrap();
+
+    assert!(timed_out);
+    assert!(!truncated);
+    assert!(!output.status.success());
+    assert_eq!(output.stdout, b"start\n");
+    assert_eq!(output.stderr, b"oops\n");
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks", target_os = "android"), ignore)]
+fn test_output_limited_size() {
+    let LimitedOutput { output, timed_out, truncated } =
+        Command::new("yes").output_limited(None, Some(100)).unwrap();
+
+    assert!(!timed_out);
+    assert!(truncated);
+    assert!(!output.status.success());
+    assert_eq!(output.stdout, b"y\n".repeat(50));
+}
+
 #[cfg(all(unix, not(target_os = "android")))]
 pub fn env_cmd() -> Command {
     Command::new("env")
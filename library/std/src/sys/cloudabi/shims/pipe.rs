@@ -1,5 +1,7 @@
-use crate::io::{self, IoSlice, IoSliceMut};
+use crate::io::{self, IoSlice, IoSliceMut, ReadBuf};
 use crate::sys::Void;
+use crate::sys_common::process::ReadOutcome;
+use crate::time::Instant;
 
 pub struct AnonPipe(Void);
 
@@ -8,6 +10,10 @@ pub fn read(&self, _buf: &mut [u8]) -> io::Result<usize> {
         match self.0 {}
     }
 
+    pub fn read_buf(&self, _buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        match self.0 {}
+    }
+
     pub fn read_vectored(&self, _bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         match self.0 {}
     }
@@ -36,3 +42,17 @@ pub fn diverge(&self) -> ! {
 pub fn read2(p1: AnonPipe, _v1: &mut Vec<u8>, _p2: AnonPipe, _v2: &mut Vec<u8>) -> io::Result<()> {
     match p1.0 {}
 }
+
+pub fn read2_bounded(
+    p1: Option<AnonPipe>,
+    _v1: &mut Vec<u8>,
+    p2: Option<AnonPipe>,
+    _v2: &mut Vec<u8>,
+    _deadline: Option<Instant>,
+    _limit: Option<usize>,
+) -> io::Result<ReadOutcome> {
+    match p1.or(p2) {
+        Some(p) => match p.0 {},
+        None => Ok(ReadOutcome::Finished),
+    }
+}
@@ -5,6 +5,7 @@
 use crate::sys::pipe::AnonPipe;
 use crate::sys::{unsupported, Void};
 use crate::sys_common::process::CommandEnv;
+use crate::time::Duration;
 
 pub use crate::ffi::OsString as EnvKey;
 
@@ -146,4 +147,8 @@ pub fn wait(&mut self) -> io::Result<ExitStatus> {
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         match self.0 {}
     }
+
+    pub fn wait_timeout(&mut self, _timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        match self.0 {}
+    }
 }
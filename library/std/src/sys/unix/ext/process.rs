@@ -119,6 +119,73 @@ fn before_exec<F>(&mut self, f: F) -> &mut process::Command
     fn arg0<S>(&mut self, arg: S) -> &mut process::Command
     where
         S: AsRef<OsStr>;
+
+    /// Sets the process group ID (PGID) of the child process. Equivalent to a
+    /// `setpgid` call in the child process, but may be more efficient.
+    ///
+    /// Process groups determine which processes receive signals.
+    ///
+    /// # Examples
+    ///
+    /// Pressing Ctrl-C in a terminal will send SIGINT to all processes in
+    /// the current foreground process group. By spawning the `sleep`
+    /// subprocess in a new process group, it will not receive SIGINT from the
+    /// terminal.
+    ///
+    /// The parent process could install a signal handler and manage the
+    /// subprocess on its own terms.
+    ///
+    /// A process group ID of 0 will use the process ID as the PGID.
+    ///
+    /// ```no_run
+    /// #![feature(process_group)]
+    /// use std::process::Command;
+    /// use std::os::unix::process::CommandExt;
+    ///
+    /// Command::new("sleep")
+    ///     .arg("10")
+    ///     .process_group(0)
+    ///     .spawn()?
+    ///     .wait()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "process_group", issue = "none")]
+    fn process_group(&mut self, pgroup: i32) -> &mut process::Command;
+
+    /// Sets whether the child process should run in a new session, equivalent
+    /// to a `setsid` call in the child process.
+    ///
+    /// The child becomes the leader of a new session and of a new process
+    /// group within it, and has no controlling terminal. This is mutually
+    /// exclusive with [`process_group`], as a session leader can't be moved
+    /// into another process group; doing both makes the spawn fail.
+    ///
+    /// [`process_group`]: CommandExt::process_group
+    #[unstable(feature = "process_setsid", issue = "none")]
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command;
+
+    /// Sets a resource limit of the child process, equivalent to a
+    /// `setrlimit` call in the child process.
+    ///
+    /// `resource` is one of the platform's `RLIMIT_*` constants, and `soft`
+    /// and `hard` are the soft and hard limits to apply, where
+    /// `RLIM_INFINITY` stands for no limit. Setting the same resource again
+    /// replaces the previous limit. Spawning the process fails with an error
+    /// of kind [`InvalidInput`] if a limit doesn't fit in the platform's
+    /// `rlim_t`, which is only 32 bits wide on some targets.
+    ///
+    /// Limits are applied before the [`uid`] and [`gid`] are changed, so
+    /// raising a hard limit only requires the current process to be
+    /// privileged. Note that unlike the other options here, resource limits
+    /// can't be set up by `posix_spawn` and always make the spawn go through
+    /// `fork` and `exec`.
+    ///
+    /// [`uid`]: CommandExt::uid
+    /// [`gid`]: CommandExt::gid
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    #[unstable(feature = "process_rlimit", issue = "none")]
+    fn rlimit(&mut self, resource: i32, soft: u64, hard: u64) -> &mut process::Command;
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -160,6 +227,21 @@ fn arg0<S>(&mut self, arg: S) -> &mut process::Command
         self.as_inner_mut().set_arg_0(arg.as_ref());
         self
     }
+
+    fn process_group(&mut self, pgroup: i32) -> &mut process::Command {
+        self.as_inner_mut().pgroup(pgroup);
+        self
+    }
+
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command {
+        self.as_inner_mut().setsid(setsid);
+        self
+    }
+
+    fn rlimit(&mut self, resource: i32, soft: u64, hard: u64) -> &mut process::Command {
+        self.as_inner_mut().rlimit(resource, soft, hard);
+        self
+    }
 }
 
 /// Unix-specific extensions to [`process::ExitStatus`].
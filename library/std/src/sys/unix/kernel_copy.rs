@@ -56,6 +56,7 @@
 use crate::net::TcpStream;
 use crate::os::unix::fs::FileTypeExt;
 use crate::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+use crate::os::unix::net::UnixStream;
 use crate::process::{ChildStderr, ChildStdin, ChildStdout};
 use crate::ptr;
 use crate::sync::atomic::{AtomicBool, Ordering};
@@ -99,7 +100,7 @@ fn maybe_fifo(&self) -> bool {
         }
     }
 
-    fn potential_sendfile_source(&self) -> bool {
+    fn potential_sendfile_source(&self, sink: &FdMeta) -> bool {
         match self {
             // procfs erronously shows 0 length on non-empty readable files.
             // and if a file is truly empty then a `read` syscall will determine that and skip the write syscall
@@ -110,10 +111,22 @@ fn potential_sendfile_source(&self) -> bool {
             {
                 true
             }
+            // file to socket is what sendfile was designed for and works on all kernel versions,
+            // so don't trust the reported length here. If nothing gets sent on the first attempt
+            // `sendfile_splice` falls back and the copy loop determines whether the file really is empty.
+            FdMeta::Metadata(meta) if meta.file_type().is_file() => sink.is_socket(),
             _ => false,
         }
     }
 
+    fn is_socket(&self) -> bool {
+        match self {
+            FdMeta::Metadata(meta) => meta.file_type().is_socket(),
+            FdMeta::Socket => true,
+            FdMeta::Pipe | FdMeta::NoneObtained => false,
+        }
+    }
+
     fn copy_file_range_candidate(&self) -> bool {
         match self {
             // copy_file_range will fail on empty procfs files. `read` can determine whether EOF has been reached
@@ -179,7 +192,7 @@ fn copy(self) -> Result<u64> {
             // So we just try and fallback if needed.
             // If current file offsets + write sizes overflow it may also fail, we do not try to fix that and instead
             // fall back to the generic copy loop.
-            if input_meta.potential_sendfile_source() {
+            if input_meta.potential_sendfile_source(&output_meta) {
                 let result = sendfile_splice(SpliceMode::Sendfile, readfd, writefd, max_write);
 
                 match result {
@@ -320,6 +333,34 @@ fn properties(&self) -> CopyParams {
     }
 }
 
+impl CopyRead for UnixStream {
+    fn properties(&self) -> CopyParams {
+        // avoid the stat syscall since we can be fairly sure it's a socket
+        CopyParams(FdMeta::Socket, Some(self.as_raw_fd()))
+    }
+}
+
+impl CopyRead for &UnixStream {
+    fn properties(&self) -> CopyParams {
+        // avoid the stat syscall since we can be fairly sure it's a socket
+        CopyParams(FdMeta::Socket, Some(self.as_raw_fd()))
+    }
+}
+
+impl CopyWrite for UnixStream {
+    fn properties(&self) -> CopyParams {
+        // avoid the stat syscall since we can be fairly sure it's a socket
+        CopyParams(FdMeta::Socket, Some(self.as_raw_fd()))
+    }
+}
+
+impl CopyWrite for &UnixStream {
+    fn properties(&self) -> CopyParams {
+        // avoid the stat syscall since we can be fairly sure it's a socket
+        CopyParams(FdMeta::Socket, Some(self.as_raw_fd()))
+    }
+}
+
 impl CopyWrite for ChildStdin {
     fn properties(&self) -> CopyParams {
         CopyParams(FdMeta::Pipe, Some(self.as_raw_fd()))
@@ -572,6 +613,11 @@ fn splice(
         };
 
         match result {
+            Ok(0) if written == 0 && mode == SpliceMode::Sendfile => {
+                // sendfile may also be attempted on files whose metadata claims they are empty
+                // (e.g. procfs), leave it to the generic copy loop to determine whether they are.
+                return CopyResult::Fallback(0);
+            }
             Ok(0) => break, // EOF
             Ok(ret) => written += ret as u64,
             Err(err) => {
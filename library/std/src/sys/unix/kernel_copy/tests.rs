@@ -58,6 +58,65 @@ fn copy_specialization() -> Result<()> {
     result.and(rm1).and(rm2)
 }
 
+#[test]
+fn copy_specialization_file_to_socket() -> Result<()> {
+    use crate::io::BufReader;
+    use crate::net::{TcpListener, TcpStream};
+
+    let source_path = temp_dir().join("copy-spec-socket.source");
+
+    let result: Result<()> = try {
+        let mut source = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&source_path)?;
+        source.write_all(b"abcdefghiklmnopqr")?;
+        source.seek(SeekFrom::Start(2))?;
+        let mut source = BufReader::with_capacity(4, source.take(12));
+        source.fill_buf()?;
+        assert_eq!(source.buffer(), b"cdef");
+
+        let listener = TcpListener::bind("localhost:0")?;
+        let mut sink = TcpStream::connect(listener.local_addr()?)?;
+        let mut receiver = listener.accept()?.0;
+
+        let copied = crate::io::copy(&mut source, &mut sink)?;
+        assert_eq!(copied, 12);
+        drop(sink);
+
+        let mut received = Vec::new();
+        receiver.read_to_end(&mut received)?;
+        assert_eq!(&received, b"cdefghiklmno");
+    };
+
+    let rm = crate::fs::remove_file(source_path);
+
+    result.and(rm)
+}
+
+#[test]
+fn copy_specialization_unix_stream() -> Result<()> {
+    use crate::os::unix::net::UnixStream;
+
+    let (mut tx, mut source) = UnixStream::pair()?;
+    let (mut sink, mut rx) = UnixStream::pair()?;
+
+    tx.write_all(b"hello world")?;
+    drop(tx);
+
+    let copied = crate::io::copy(&mut (&mut source).take(5), &mut sink)?;
+    assert_eq!(copied, 5);
+    drop(sink);
+
+    let mut received = Vec::new();
+    rx.read_to_end(&mut received)?;
+    assert_eq!(&received, b"hello");
+
+    Ok(())
+}
+
 #[bench]
 fn bench_file_to_file_copy(b: &mut test::Bencher) {
     const BYTES: usize = 128 * 1024;
@@ -1,7 +1,11 @@
-use crate::io::{self, IoSlice, IoSliceMut};
+use crate::cmp;
+use crate::io::{self, IoSlice, IoSliceMut, ReadBuf};
 use crate::mem;
 use crate::sys::fd::FileDesc;
+use crate::sys::time::dur2millis;
 use crate::sys::{cvt, cvt_r};
+use crate::sys_common::process::ReadOutcome;
+use crate::time::Instant;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Anonymous pipes
@@ -43,6 +47,10 @@ pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.0.read(buf)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.0.read_buf(buf)
+    }
+
     pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.0.read_vectored(bufs)
     }
@@ -120,3 +128,88 @@ fn read(fd: &FileDesc, dst: &mut Vec<u8>) -> Result<bool, io::Error> {
         }
     }
 }
+
+/// Like `read2`, but gives up once `deadline` passes or once either pipe has
+/// produced more than `limit` bytes, leaving whatever was read so far in the
+/// buffers. Either pipe may be absent, in which case only the other is read.
+pub fn read2_bounded(
+    p1: Option<AnonPipe>,
+    v1: &mut Vec<u8>,
+    p2: Option<AnonPipe>,
+    v2: &mut Vec<u8>,
+    deadline: Option<Instant>,
+    limit: Option<usize>,
+) -> io::Result<ReadOutcome> {
+    let p1 = p1.map(AnonPipe::into_fd);
+    let p2 = p2.map(AnonPipe::into_fd);
+    let mut fds: [libc::pollfd; 2] = unsafe { mem::zeroed() };
+    for (pollfd, p) in fds.iter_mut().zip(&[&p1, &p2]) {
+        // `poll` ignores entries with a negative descriptor, which is how
+        // absent and finished pipes are taken out of the set.
+        pollfd.fd = match p {
+            Some(fd) => {
+                fd.set_nonblocking(true)?;
+                fd.raw()
+            }
+            None => -1,
+        };
+        pollfd.events = libc::POLLIN;
+    }
+
+    loop {
+        if fds[0].fd < 0 && fds[1].fd < 0 {
+            return Ok(ReadOutcome::Finished);
+        }
+        let timeout = match deadline {
+            None => -1,
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Ok(ReadOutcome::TimedOut);
+                }
+                dur2millis(deadline - now)
+            }
+        };
+        if cvt_r(|| unsafe { libc::poll(fds.as_mut_ptr(), 2, timeout) })? == 0 {
+            continue;
+        }
+
+        for i in 0..2 {
+            let (p, v) = if i == 0 { (&p1, &mut *v1) } else { (&p2, &mut *v2) };
+            let pollfd = &mut fds[i];
+            if pollfd.fd < 0 || pollfd.revents == 0 {
+                continue;
+            }
+            let fd = p.as_ref().unwrap();
+            loop {
+                // Read at most one byte past the limit, which is enough to
+                // tell that it was exceeded.
+                let room =
+                    limit.map_or(usize::MAX, |l| l.saturating_add(1).saturating_sub(v.len()));
+                v.reserve(cmp::min(room, 8 * 1024));
+                let spare = v.spare_capacity_mut();
+                let len = cmp::min(spare.len(), room);
+                let mut buf = ReadBuf::uninit(&mut spare[..len]);
+                match fd.read_buf(&mut buf) {
+                    Ok(()) => {}
+                    Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                    Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(e),
+                }
+                let n = buf.filled_len();
+                if n == 0 {
+                    pollfd.fd = -1;
+                    break;
+                }
+                // SAFETY: `read_buf` initialized the first `n` spare bytes.
+                unsafe { v.set_len(v.len() + n) };
+                if let Some(limit) = limit {
+                    if v.len() > limit {
+                        v.truncate(limit);
+                        return Ok(ReadOutcome::LimitReached);
+                    }
+                }
+            }
+        }
+    }
+}
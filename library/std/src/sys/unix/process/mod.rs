@@ -1,4 +1,6 @@
 pub use self::process_common::{Command, CommandArgs, ExitCode, Stdio, StdioPipes};
+#[cfg(target_os = "linux")]
+pub use self::process_inner::PidFd;
 pub use self::process_inner::{ExitStatus, Process};
 pub use crate::ffi::OsString as EnvKey;
 pub use crate::sys_common::process::CommandEnvs;
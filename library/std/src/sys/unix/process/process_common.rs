@@ -17,7 +17,7 @@
 #[cfg(not(target_os = "fuchsia"))]
 use crate::sys::fs::OpenOptions;
 
-use libc::{c_char, c_int, gid_t, uid_t, EXIT_FAILURE, EXIT_SUCCESS};
+use libc::{c_char, c_int, gid_t, pid_t, uid_t, EXIT_FAILURE, EXIT_SUCCESS};
 
 cfg_if::cfg_if! {
     if #[cfg(target_os = "fuchsia")] {
@@ -85,6 +85,11 @@ pub struct Command {
     cwd: Option<CString>,
     uid: Option<uid_t>,
     gid: Option<gid_t>,
+    pgroup: Option<pid_t>,
+    setsid: bool,
+    rlimits: Vec<(c_int, u64, u64)>,
+    #[cfg(target_os = "linux")]
+    create_pidfd: bool,
     saw_nul: bool,
     closures: Vec<Box<dyn FnMut() -> io::Result<()> + Send + Sync>>,
     stdin: Option<Stdio>,
@@ -146,6 +151,11 @@ pub fn new(program: &OsStr) -> Command {
             cwd: None,
             uid: None,
             gid: None,
+            pgroup: None,
+            setsid: false,
+            rlimits: Vec::new(),
+            #[cfg(target_os = "linux")]
+            create_pidfd: false,
             saw_nul,
             closures: Vec::new(),
             stdin: None,
@@ -183,6 +193,22 @@ pub fn uid(&mut self, id: uid_t) {
     pub fn gid(&mut self, id: gid_t) {
         self.gid = Some(id);
     }
+    pub fn pgroup(&mut self, pgroup: pid_t) {
+        self.pgroup = Some(pgroup);
+    }
+    pub fn setsid(&mut self, setsid: bool) {
+        self.setsid = setsid;
+    }
+    pub fn rlimit(&mut self, resource: c_int, soft: u64, hard: u64) {
+        // Later limits for the same resource replace earlier ones, which keeps
+        // the list free of duplicates when a builder is reused.
+        self.rlimits.retain(|&(r, _, _)| r != resource);
+        self.rlimits.push((resource, soft, hard));
+    }
+    #[cfg(target_os = "linux")]
+    pub fn create_pidfd(&mut self, val: bool) {
+        self.create_pidfd = val;
+    }
 
     pub fn saw_nul(&self) -> bool {
         self.saw_nul
@@ -226,6 +252,27 @@ pub fn get_uid(&self) -> Option<uid_t> {
     pub fn get_gid(&self) -> Option<gid_t> {
         self.gid
     }
+    #[allow(dead_code)]
+    pub fn get_pgroup(&self) -> Option<pid_t> {
+        self.pgroup
+    }
+    #[allow(dead_code)]
+    pub fn get_setsid(&self) -> bool {
+        self.setsid
+    }
+    #[allow(dead_code)]
+    pub fn get_rlimits(&self) -> &[(c_int, u64, u64)] {
+        &self.rlimits
+    }
+    #[cfg(target_os = "linux")]
+    pub fn get_create_pidfd(&self) -> bool {
+        self.create_pidfd
+    }
+    #[cfg(not(target_os = "linux"))]
+    #[allow(dead_code)]
+    pub fn get_create_pidfd(&self) -> bool {
+        false
+    }
 
     pub fn get_closures(&mut self) -> &mut Vec<Box<dyn FnMut() -> io::Result<()> + Send + Sync>> {
         &mut self.closures
@@ -1,9 +1,12 @@
 use super::*;
 
+use crate::convert::TryFrom;
 use crate::ffi::OsStr;
 use crate::mem;
 use crate::ptr;
 use crate::sys::cvt;
+#[cfg(target_os = "linux")]
+use crate::sys_common::IntoInner;
 
 macro_rules! t {
     ($e:expr) => {
@@ -67,3 +70,91 @@ fn test_process_mask() {
         t!(cat.wait());
     }
 }
+
+#[test]
+fn test_process_group() {
+    let mut cmd = Command::new(OsStr::new("sleep"));
+    cmd.arg(OsStr::new("10"));
+    cmd.pgroup(0);
+
+    let (mut child, _pipes) = t!(cmd.spawn(Stdio::Null, false));
+    let pid = child.id() as libc::pid_t;
+    let pgid = unsafe { libc::getpgid(pid) };
+    t!(child.kill());
+    t!(child.wait());
+    assert_eq!(pgid, pid);
+}
+
+#[test]
+fn test_setsid() {
+    let mut cmd = Command::new(OsStr::new("sleep"));
+    cmd.arg(OsStr::new("10"));
+    cmd.setsid(true);
+
+    let (mut child, _pipes) = t!(cmd.spawn(Stdio::Null, false));
+    let pid = child.id() as libc::pid_t;
+    let sid = unsafe { libc::getsid(pid) };
+    t!(child.kill());
+    t!(child.wait());
+    assert_eq!(sid, pid);
+}
+
+#[test]
+fn test_rlimit() {
+    let mut cmd = Command::new(OsStr::new("sh"));
+    cmd.arg(OsStr::new("-c"));
+    cmd.arg(OsStr::new("ulimit -n"));
+    cmd.rlimit(libc::RLIMIT_NOFILE as libc::c_int, 64, 64);
+    cmd.stdout(Stdio::MakePipe);
+
+    let (mut child, mut pipes) = t!(cmd.spawn(Stdio::Null, false));
+    let stdout = pipes.stdout.take().unwrap();
+    let mut out = Vec::new();
+    let mut buf = [0; 16];
+    loop {
+        match t!(stdout.read(&mut buf)) {
+            0 => break,
+            n => out.extend_from_slice(&buf[..n]),
+        }
+    }
+    assert!(t!(child.wait()).success());
+    assert_eq!(out, b"64\n");
+}
+
+#[test]
+fn test_rlimit_out_of_range() {
+    // Only targets with a 32-bit `rlim_t` have limits that don't fit in it.
+    if libc::rlim_t::try_from(u64::MAX).is_ok() {
+        return;
+    }
+    let mut cmd = Command::new(OsStr::new("true"));
+    cmd.rlimit(libc::RLIMIT_NOFILE as libc::c_int, u64::MAX, u64::MAX);
+    let err = cmd.spawn(Stdio::Null, false).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_pidfd() {
+    let mut cmd = Command::new(OsStr::new("sleep"));
+    cmd.arg(OsStr::new("10"));
+    cmd.create_pidfd(true);
+
+    let (mut child, _pipes) = t!(cmd.spawn(Stdio::Null, false));
+    let pidfd = match child.take_pidfd() {
+        Some(pidfd) => pidfd.into_inner(),
+        // Kernels older than 5.3 don't support pidfds.
+        None => {
+            t!(child.kill());
+            t!(child.wait());
+            return;
+        }
+    };
+
+    assert_eq!(t!(pidfd.try_wait()), None);
+    t!(pidfd.kill());
+    let status = t!(pidfd.wait());
+    assert_eq!(status.signal(), Some(libc::SIGKILL));
+    // The child was reaped through the pidfd.
+    assert!(child.wait().is_err());
+}
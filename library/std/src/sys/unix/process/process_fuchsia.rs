@@ -6,6 +6,7 @@
 
 use crate::sys::process::process_common::*;
 use crate::sys::process::zircon::{zx_handle_t, Handle};
+use crate::time::Duration;
 
 use libc::{c_int, size_t};
 
@@ -28,6 +29,13 @@ pub fn spawn(
             ));
         }
 
+        if self.get_pgroup().is_some() || self.get_setsid() || !self.get_rlimits().is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "process groups, sessions and resource limits are not supported on Fuchsia",
+            ));
+        }
+
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
 
         let process_handle = unsafe { self.do_exec(theirs, envp.as_ref())? };
@@ -227,6 +235,10 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         }
         Ok(Some(ExitStatus(proc_info.return_code)))
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        crate::sys_common::process::wait_timeout_by_polling(timeout, || self.try_wait())
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
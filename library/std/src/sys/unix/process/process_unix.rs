@@ -1,10 +1,16 @@
-use crate::convert::TryInto;
+use crate::convert::{TryFrom, TryInto};
 use crate::fmt;
 use crate::io::{self, Error, ErrorKind};
 use crate::ptr;
 use crate::sys;
 use crate::sys::cvt;
 use crate::sys::process::process_common::*;
+use crate::time::Duration;
+#[cfg(target_os = "linux")]
+use crate::time::Instant;
+
+#[cfg(target_os = "linux")]
+use crate::sys::fd::FileDesc;
 
 #[cfg(target_os = "vxworks")]
 use libc::RTP_ID as pid_t;
@@ -29,6 +35,7 @@ pub fn spawn(
         if self.saw_nul() {
             return Err(io::Error::new(ErrorKind::InvalidInput, "nul byte found in provided data"));
         }
+        self.check_rlimits()?;
 
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
 
@@ -46,13 +53,13 @@ pub fn spawn(
         // Note that as soon as we're done with the fork there's no need to hold
         // a lock any more because the parent won't do anything and the child is
         // in its own process.
-        let result = unsafe {
+        let (pid, pidfd) = unsafe {
             let _env_lock = sys::os::env_lock();
-            cvt(libc::fork())?
+            self.do_fork()?
         };
 
         let pid = unsafe {
-            match result {
+            match pid {
                 0 => {
                     drop(input);
                     let Err(err) = self.do_exec(theirs, envp.as_ref());
@@ -78,7 +85,7 @@ pub fn spawn(
             }
         };
 
-        let mut p = Process { pid, status: None };
+        let mut p = Process::new(pid, pidfd);
         drop(output);
         let mut bytes = [0; 8];
 
@@ -111,12 +118,91 @@ pub fn spawn(
         }
     }
 
+    // Forks the current process, returning the child's pid to the parent
+    // along with a pidfd for it if one was requested.
+    #[cfg(not(target_os = "linux"))]
+    unsafe fn do_fork(&mut self) -> io::Result<(pid_t, Option<PidFd>)> {
+        cvt(libc::fork()).map(|pid| (pid, None))
+    }
+
+    // On Linux a pidfd is obtained atomically with the fork through `clone3`
+    // and `CLONE_PIDFD` where the kernel supports it (5.3+). Bypassing libc
+    // for `clone3` means `pthread_atfork` handlers don't run, so it is only
+    // used when a pidfd was actually asked for. If `clone3` is unavailable or
+    // filtered by seccomp we fall back to `fork` followed by `pidfd_open`.
+    // That is still free of races since the child can't be reaped, and its
+    // pid reused, before we've returned it to the caller.
+    #[cfg(target_os = "linux")]
+    unsafe fn do_fork(&mut self) -> io::Result<(pid_t, Option<PidFd>)> {
+        use crate::sync::atomic::{AtomicBool, Ordering};
+
+        static HAS_CLONE3: AtomicBool = AtomicBool::new(true);
+        const CLONE_PIDFD: u64 = 0x00001000;
+
+        #[repr(C)]
+        struct clone_args {
+            flags: u64,
+            pidfd: u64,
+            child_tid: u64,
+            parent_tid: u64,
+            exit_signal: u64,
+            stack: u64,
+            stack_size: u64,
+            tls: u64,
+        }
+
+        if !self.get_create_pidfd() {
+            return cvt(libc::fork()).map(|pid| (pid, None));
+        }
+
+        if HAS_CLONE3.load(Ordering::Relaxed) {
+            let mut pidfd: c_int = -1;
+            let mut args = clone_args {
+                flags: CLONE_PIDFD,
+                pidfd: &mut pidfd as *mut c_int as u64,
+                child_tid: 0,
+                parent_tid: 0,
+                exit_signal: libc::SIGCHLD as u64,
+                stack: 0,
+                stack_size: 0,
+                tls: 0,
+            };
+            let res = cvt(libc::syscall(
+                libc::SYS_clone3,
+                &mut args as *mut clone_args,
+                crate::mem::size_of::<clone_args>(),
+            ));
+            match res {
+                Ok(0) => return Ok((0, None)),
+                Ok(pid) => return Ok((pid as pid_t, Some(PidFd::from_raw(pidfd)))),
+                Err(e) => match e.raw_os_error() {
+                    // Multiple threads may race to store this, which is fine:
+                    // they'll all just have tried the same failing syscall.
+                    Some(libc::ENOSYS) => HAS_CLONE3.store(false, Ordering::Relaxed),
+                    Some(libc::EPERM) => {}
+                    _ => return Err(e),
+                },
+            }
+        }
+
+        let pid = cvt(libc::fork())?;
+        if pid == 0 {
+            return Ok((0, None));
+        }
+        // Failing to create the pidfd isn't fatal, `ChildExt::pidfd` reports
+        // that there is none instead.
+        Ok((pid, PidFd::open(pid).ok()))
+    }
+
     pub fn exec(&mut self, default: Stdio) -> io::Error {
         let envp = self.capture_env();
 
         if self.saw_nul() {
             return io::Error::new(ErrorKind::InvalidInput, "nul byte found in provided data");
         }
+        if let Err(e) = self.check_rlimits() {
+            return e;
+        }
 
         match self.setup_io(default, true) {
             Ok((_, theirs)) => {
@@ -134,6 +220,20 @@ pub fn exec(&mut self, default: Stdio) -> io::Error {
         }
     }
 
+    /// Checks that the resource limits fit in the platform's `rlim_t`, which may be narrower than
+    /// the `u64` they're given as, so that the child doesn't silently apply truncated limits.
+    fn check_rlimits(&self) -> io::Result<()> {
+        for &(_, soft, hard) in self.get_rlimits() {
+            if libc::rlim_t::try_from(soft).is_err() || libc::rlim_t::try_from(hard).is_err() {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidInput,
+                    "resource limit out of range for the platform's `rlim_t`",
+                ));
+            }
+        }
+        Ok(())
+    }
+
     // And at this point we've reached a special time in the life of the
     // child. The child must now be considered hamstrung and unable to
     // do anything other than syscalls really. Consider the following
@@ -181,6 +281,19 @@ unsafe fn do_exec(
             cvt_r(|| libc::dup2(fd, libc::STDERR_FILENO))?;
         }
 
+        if self.get_setsid() {
+            cvt(libc::setsid())?;
+        }
+
+        // Resource limits are applied before privileges are dropped below, as
+        // raising a hard limit requires them.
+        for &(resource, soft, hard) in self.get_rlimits() {
+            // `check_rlimits` made sure that these fit in `rlim_t` before forking.
+            let limit =
+                libc::rlimit { rlim_cur: soft as libc::rlim_t, rlim_max: hard as libc::rlim_t };
+            cvt(libc::setrlimit(resource as _, &limit))?;
+        }
+
         #[cfg(not(target_os = "l4re"))]
         {
             if let Some(u) = self.get_gid() {
@@ -204,6 +317,10 @@ unsafe fn do_exec(
             cvt(libc::chdir(cwd.as_ptr()))?;
         }
 
+        if let Some(pgroup) = self.get_pgroup() {
+            cvt(libc::setpgid(0, pgroup))?;
+        }
+
         // emscripten has no signal support.
         #[cfg(not(target_os = "emscripten"))]
         {
@@ -287,10 +404,27 @@ fn posix_spawn(
             || self.get_uid().is_some()
             || (self.env_saw_path() && !self.program_is_path())
             || !self.get_closures().is_empty()
+            || !self.get_rlimits().is_empty()
         {
             return Ok(None);
         }
 
+        // `POSIX_SPAWN_SETSID` is a glibc 2.26+ extension, for everything else
+        // a new session is set up by hand after forking.
+        if self.get_setsid() {
+            #[cfg(all(target_os = "linux", target_env = "gnu"))]
+            {
+                match sys::os::glibc_version() {
+                    Some(version) if version >= (2, 26) => {}
+                    _ => return Ok(None),
+                }
+            }
+            #[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+            {
+                return Ok(None);
+            }
+        }
+
         // Only glibc 2.24+ posix_spawn() supports returning ENOENT directly.
         #[cfg(all(target_os = "linux", target_env = "gnu"))]
         {
@@ -321,7 +455,7 @@ fn posix_spawn_file_actions_addchdir_np(
             None => None,
         };
 
-        let mut p = Process { pid: 0, status: None };
+        let mut p = Process::new(0, None);
 
         struct PosixSpawnFileActions<'a>(&'a mut MaybeUninit<libc::posix_spawn_file_actions_t>);
 
@@ -383,7 +517,16 @@ fn drop(&mut self) {
             cvt(sigaddset(set.as_mut_ptr(), libc::SIGPIPE))?;
             cvt_nz(libc::posix_spawnattr_setsigdefault(attrs.0.as_mut_ptr(), set.as_ptr()))?;
 
-            let flags = libc::POSIX_SPAWN_SETSIGDEF | libc::POSIX_SPAWN_SETSIGMASK;
+            let mut flags = libc::POSIX_SPAWN_SETSIGDEF | libc::POSIX_SPAWN_SETSIGMASK;
+            if let Some(pgroup) = self.get_pgroup() {
+                flags |= libc::POSIX_SPAWN_SETPGROUP;
+                cvt_nz(libc::posix_spawnattr_setpgroup(attrs.0.as_mut_ptr(), pgroup))?;
+            }
+            #[cfg(all(target_os = "linux", target_env = "gnu"))]
+            if self.get_setsid() {
+                const POSIX_SPAWN_SETSID: c_int = 0x80;
+                flags |= POSIX_SPAWN_SETSID;
+            }
             cvt_nz(libc::posix_spawnattr_setflags(attrs.0.as_mut_ptr(), flags as _))?;
 
             // Make sure we synchronize access to the global `environ` resource
@@ -397,6 +540,13 @@ fn drop(&mut self) {
                 self.get_argv().as_ptr() as *const _,
                 envp as *const _,
             ))?;
+
+            // The child can't have been reaped yet, so its pid can't have
+            // been reused and opening a pidfd for it is race-free.
+            #[cfg(target_os = "linux")]
+            if self.get_create_pidfd() {
+                p = Process::new(p.pid, PidFd::open(p.pid).ok());
+            }
             Ok(Some(p))
         }
     }
@@ -410,9 +560,33 @@ fn drop(&mut self) {
 pub struct Process {
     pid: pid_t,
     status: Option<ExitStatus>,
+    #[cfg(target_os = "linux")]
+    pidfd: Option<crate::os::linux::process::PidFd>,
 }
 
 impl Process {
+    #[cfg(not(target_os = "linux"))]
+    fn new(pid: pid_t, _pidfd: Option<PidFd>) -> Process {
+        Process { pid, status: None }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn new(pid: pid_t, pidfd: Option<PidFd>) -> Process {
+        use crate::sys_common::FromInner;
+        let pidfd = pidfd.map(crate::os::linux::process::PidFd::from_inner);
+        Process { pid, status: None, pidfd }
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn pidfd(&self) -> Option<&crate::os::linux::process::PidFd> {
+        self.pidfd.as_ref()
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn take_pidfd(&mut self) -> Option<crate::os::linux::process::PidFd> {
+        self.pidfd.take()
+    }
+
     pub fn id(&self) -> u32 {
         self.pid as u32
     }
@@ -455,6 +629,52 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        if let Some(status) = self.status {
+            return Ok(Some(status));
+        }
+
+        // A pidfd becomes readable once the process has exited, so where we
+        // can get one we sleep in `poll` for exactly as long as needed. The
+        // child hasn't been reaped yet, so opening one here is race-free.
+        #[cfg(target_os = "linux")]
+        {
+            use crate::sys_common::AsInner;
+
+            let opened = if self.pidfd.is_none() { PidFd::open(self.pid).ok() } else { None };
+            let fd = match (&self.pidfd, &opened) {
+                (Some(pidfd), _) => Some(pidfd.as_inner().fd().raw()),
+                (None, Some(pidfd)) => Some(pidfd.fd().raw()),
+                (None, None) => None,
+            };
+            if let Some(fd) = fd {
+                let deadline = Instant::now().checked_add(timeout);
+                let mut pollfd = libc::pollfd { fd, events: libc::POLLIN, revents: 0 };
+                loop {
+                    let millis = match deadline {
+                        None => -1,
+                        Some(deadline) => {
+                            let now = Instant::now();
+                            if now >= deadline {
+                                return self.try_wait();
+                            }
+                            sys::time::dur2millis(deadline - now)
+                        }
+                    };
+                    let n = cvt(unsafe { libc::poll(&mut pollfd, 1, millis) });
+                    match n {
+                        Ok(0) => {}
+                        Ok(_) => return self.wait().map(Some),
+                        Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                        Err(e) => return Err(e),
+                    }
+                }
+            }
+        }
+
+        crate::sys_common::process::wait_timeout_by_polling(timeout, || self.try_wait())
+    }
 }
 
 /// Unix exit statuses
@@ -483,6 +703,98 @@ pub fn signal(&self) -> Option<i32> {
     }
 }
 
+/// A file descriptor referring to a process, see `pidfd_open(2)`.
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+pub struct PidFd(FileDesc);
+
+// Never constructed on other platforms, it only keeps `Process::new` uniform.
+#[cfg(not(target_os = "linux"))]
+pub enum PidFd {}
+
+#[cfg(target_os = "linux")]
+impl PidFd {
+    // Both `clone3` and `pidfd_open` always set `O_CLOEXEC` on the pidfd.
+    pub fn from_raw(fd: c_int) -> PidFd {
+        PidFd(FileDesc::new(fd))
+    }
+
+    pub fn open(pid: pid_t) -> io::Result<PidFd> {
+        syscall! {
+            fn pidfd_open(pid: pid_t, flags: libc::c_uint) -> c_int
+        }
+        let fd = cvt(unsafe { pidfd_open(pid, 0) })?;
+        Ok(PidFd::from_raw(fd))
+    }
+
+    pub fn fd(&self) -> &FileDesc {
+        &self.0
+    }
+
+    pub fn into_fd(self) -> FileDesc {
+        self.0
+    }
+
+    pub fn kill(&self) -> io::Result<()> {
+        syscall! {
+            fn pidfd_send_signal(
+                pidfd: c_int,
+                sig: c_int,
+                info: *mut libc::siginfo_t,
+                flags: libc::c_uint
+            ) -> c_int
+        }
+        cvt(unsafe { pidfd_send_signal(self.0.raw(), libc::SIGKILL, ptr::null_mut(), 0) }).map(drop)
+    }
+
+    pub fn wait(&self) -> io::Result<ExitStatus> {
+        use crate::sys::cvt_r;
+        let mut siginfo: libc::siginfo_t = unsafe { crate::mem::zeroed() };
+        cvt_r(|| unsafe {
+            libc::waitid(P_PIDFD, self.0.raw() as libc::id_t, &mut siginfo, libc::WEXITED)
+        })?;
+        Ok(ExitStatus::from_waitid_siginfo(&siginfo))
+    }
+
+    pub fn try_wait(&self) -> io::Result<Option<ExitStatus>> {
+        let mut siginfo: libc::siginfo_t = unsafe { crate::mem::zeroed() };
+        cvt(unsafe {
+            libc::waitid(
+                P_PIDFD,
+                self.0.raw() as libc::id_t,
+                &mut siginfo,
+                libc::WEXITED | libc::WNOHANG,
+            )
+        })?;
+        // With `WNOHANG` a child that hasn't exited yet leaves `si_pid` zeroed.
+        if unsafe { siginfo.si_pid() } == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(ExitStatus::from_waitid_siginfo(&siginfo)))
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+const P_PIDFD: libc::idtype_t = 3;
+
+#[cfg(target_os = "linux")]
+impl ExitStatus {
+    // Rebuilds the `waitpid`-style status that the rest of `ExitStatus` works
+    // with from what `waitid` reports.
+    fn from_waitid_siginfo(siginfo: &libc::siginfo_t) -> ExitStatus {
+        let status = unsafe { siginfo.si_status() };
+        match siginfo.si_code {
+            libc::CLD_EXITED => ExitStatus((status & 0xff) << 8),
+            libc::CLD_KILLED => ExitStatus(status),
+            libc::CLD_DUMPED => ExitStatus(status | 0x80),
+            libc::CLD_STOPPED | libc::CLD_TRAPPED => ExitStatus(((status & 0xff) << 8) | 0x7f),
+            libc::CLD_CONTINUED => ExitStatus(0xffff),
+            _ => unreachable!("waitid() should only return the above codes"),
+        }
+    }
+}
+
 /// Converts a raw `c_int` to a type-safe `ExitStatus` by wrapping it without copying.
 impl From<c_int> for ExitStatus {
     fn from(a: c_int) -> ExitStatus {
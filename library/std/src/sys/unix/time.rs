@@ -8,6 +8,14 @@
 
 const NSEC_PER_SEC: u64 = 1_000_000_000;
 
+/// Converts a timeout into the milliseconds that `poll` expects, rounding up
+/// so that the caller never wakes up early and spins on a sub-millisecond
+/// remainder. Timeouts too long for a `c_int` are clamped.
+pub fn dur2millis(dur: Duration) -> libc::c_int {
+    let millis = dur.as_nanos().saturating_add(999_999) / 1_000_000;
+    crate::cmp::min(millis, libc::c_int::MAX as u128) as libc::c_int
+}
+
 #[derive(Copy, Clone)]
 struct Timespec {
     t: libc::timespec,
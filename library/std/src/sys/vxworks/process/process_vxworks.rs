@@ -4,6 +4,7 @@
 use crate::sys::cvt;
 use crate::sys::process::process_common::*;
 use crate::sys_common::thread;
+use crate::time::Duration;
 use libc::RTP_ID;
 use libc::{self, c_char, c_int};
 
@@ -24,6 +25,12 @@ pub fn spawn(
         if self.saw_nul() {
             return Err(io::Error::new(ErrorKind::InvalidInput, "nul byte found in provided data"));
         }
+        if self.get_pgroup().is_some() || self.get_setsid() || !self.get_rlimits().is_empty() {
+            return Err(io::Error::new(
+                ErrorKind::Other,
+                "process groups, sessions and resource limits are not supported on VxWorks",
+            ));
+        }
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
         let mut p = Process { pid: 0, status: None };
 
@@ -167,6 +174,10 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        crate::sys_common::process::wait_timeout_by_polling(timeout, || self.try_wait())
+    }
 }
 
 /// Unix exit statuses
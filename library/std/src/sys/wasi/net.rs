@@ -2,7 +2,7 @@
 
 use crate::convert::TryFrom;
 use crate::fmt;
-use crate::io::{self, IoSlice, IoSliceMut};
+use crate::io::{self, IoSlice, IoSliceMut, ReadBuf};
 use crate::net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr};
 use crate::sys::fd::WasiFd;
 use crate::sys::{unsupported, Void};
@@ -46,6 +46,10 @@ pub fn read(&self, _: &mut [u8]) -> io::Result<usize> {
         unsupported()
     }
 
+    pub fn read_buf(&self, _: &mut ReadBuf<'_>) -> io::Result<()> {
+        unsupported()
+    }
+
     pub fn read_vectored(&self, _: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         unsupported()
     }
@@ -1,7 +1,7 @@
 use crate::os::windows::prelude::*;
 
 use crate::ffi::OsStr;
-use crate::io::{self, IoSlice, IoSliceMut};
+use crate::io::{self, IoSlice, IoSliceMut, ReadBuf};
 use crate::mem;
 use crate::path::Path;
 use crate::ptr;
@@ -12,6 +12,8 @@
 use crate::sys::fs::{File, OpenOptions};
 use crate::sys::handle::Handle;
 use crate::sys::hashmap_random_keys;
+use crate::sys_common::process::ReadOutcome;
+use crate::time::Instant;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Anonymous pipes
@@ -178,6 +180,10 @@ pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.inner.read_buf(buf)
+    }
+
     pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.inner.read_vectored(bufs)
     }
@@ -233,6 +239,74 @@ pub fn read2(p1: AnonPipe, v1: &mut Vec<u8>, p2: AnonPipe, v2: &mut Vec<u8>) ->
     }
 }
 
+/// Like `read2`, but gives up once `deadline` passes or once either pipe has
+/// produced more than `limit` bytes, leaving whatever was read so far in the
+/// buffers. Either pipe may be absent, in which case only the other is read.
+pub fn read2_bounded(
+    p1: Option<AnonPipe>,
+    v1: &mut Vec<u8>,
+    p2: Option<AnonPipe>,
+    v2: &mut Vec<u8>,
+    deadline: Option<Instant>,
+    limit: Option<usize>,
+) -> io::Result<ReadOutcome> {
+    let mut pipes = [
+        p1.map(|p| AsyncPipe::new(p.into_handle(), v1)).transpose()?,
+        p2.map(|p| AsyncPipe::new(p.into_handle(), v2)).transpose()?,
+    ];
+
+    // Same as in `read2`, except that pipes are dropped from the set as they
+    // reach EOF, and the wait is cut short by the deadline. Dropping an
+    // `AsyncPipe` cancels its pending read, so returning early is fine.
+    loop {
+        let mut objs = [ptr::null_mut(); 2];
+        let mut indices = [0; 2];
+        let mut n = 0;
+        for (i, pipe) in pipes.iter().enumerate() {
+            if let Some(pipe) = pipe {
+                objs[n] = pipe.event.raw();
+                indices[n] = i;
+                n += 1;
+            }
+        }
+        if n == 0 {
+            return Ok(ReadOutcome::Finished);
+        }
+
+        let timeout = match deadline {
+            None => c::INFINITE,
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Ok(ReadOutcome::TimedOut);
+                }
+                super::dur2timeout(deadline - now)
+            }
+        };
+        let res =
+            unsafe { c::WaitForMultipleObjects(n as c::DWORD, objs.as_ptr(), c::FALSE, timeout) };
+        if res == c::WAIT_TIMEOUT {
+            continue;
+        }
+        let i = match res.checked_sub(c::WAIT_OBJECT_0) {
+            Some(idx) if (idx as usize) < n => indices[idx as usize],
+            _ => return Err(io::Error::last_os_error()),
+        };
+
+        let pipe = pipes[i].as_mut().unwrap();
+        let open = pipe.result()?;
+        if let Some(limit) = limit {
+            if pipe.dst.len() > limit {
+                pipe.dst.truncate(limit);
+                return Ok(ReadOutcome::LimitReached);
+            }
+        }
+        if !open || !pipe.schedule_read()? {
+            pipes[i] = None;
+        }
+    }
+}
+
 struct AsyncPipe<'a> {
     pipe: Handle,
     event: Handle,
@@ -0,0 +1,55 @@
+//! Byte searches for the platforms without a vectorized libc `memchr`.
+//!
+//! `core::slice::memchr` is vectorized for the target features the standard library is
+//! compiled with, which on x86 is only SSE2. Here we additionally detect at runtime whether
+//! the CPU supports AVX2, and search 32 bytes at a time if it does.
+
+/// Haystacks shorter than this are searched by `core` directly: the vectorized search
+/// reverts to a scalar loop below its vector width anyway, and is not worth the feature
+/// detection for a few vectors.
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(test)))]
+const AVX2_THRESHOLD: usize = 64;
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(test)))]
+fn has_avx2() -> bool {
+    cfg!(target_feature = "avx2") || crate::detect::check_for(crate::detect::Feature::avx2)
+}
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(test)))]
+mod avx2 {
+    use core::slice::memchr::{memchr_simd, memrchr_simd};
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn memchr(x: u8, text: &[u8]) -> Option<usize> {
+        memchr_simd::<32>(x, text)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
+        memrchr_simd::<32>(x, text)
+    }
+}
+
+/// Returns the first index matching the byte `x` in `text`.
+pub fn memchr(x: u8, text: &[u8]) -> Option<usize> {
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(test)))]
+    {
+        if text.len() >= AVX2_THRESHOLD && has_avx2() {
+            // SAFETY: the CPU supports AVX2.
+            return unsafe { avx2::memchr(x, text) };
+        }
+    }
+    core::slice::memchr::memchr(x, text)
+}
+
+/// Returns the last index matching the byte `x` in `text`.
+pub fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(test)))]
+    {
+        if text.len() >= AVX2_THRESHOLD && has_avx2() {
+            // SAFETY: the CPU supports AVX2.
+            return unsafe { avx2::memrchr(x, text) };
+        }
+    }
+    core::slice::memchr::memrchr(x, text)
+}
@@ -1,10 +1,14 @@
 #![allow(dead_code)]
 #![unstable(feature = "process_internals", issue = "none")]
 
+use crate::cmp;
 use crate::collections::BTreeMap;
 use crate::env;
 use crate::ffi::{OsStr, OsString};
+use crate::io;
 use crate::sys::process::EnvKey;
+use crate::thread;
+use crate::time::{Duration, Instant};
 
 // Stores a set of changes to an environment
 #[derive(Clone, Debug)]
@@ -130,3 +134,42 @@ fn is_empty(&self) -> bool {
         self.iter.is_empty()
     }
 }
+
+/// How a bounded read of a child's stdout and stderr ended.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReadOutcome {
+    /// Both pipes reached EOF.
+    Finished,
+    /// The deadline passed before both pipes reached EOF.
+    TimedOut,
+    /// One of the pipes produced more than the allowed amount of output. The
+    /// buffer it was read into has been cut back to the limit.
+    LimitReached,
+}
+
+/// Waits up to `timeout` for a process to exit by repeatedly calling
+/// `try_wait`, sleeping in between.
+///
+/// This is for platforms that have no way to block on a process with a
+/// timeout. The sleeps start short so that quickly exiting processes are
+/// noticed promptly, and back off up to a few milliseconds.
+pub fn wait_timeout_by_polling<T, F>(timeout: Duration, mut try_wait: F) -> io::Result<Option<T>>
+where
+    F: FnMut() -> io::Result<Option<T>>,
+{
+    const MAX_SLEEP: Duration = Duration::from_millis(10);
+
+    let start = Instant::now();
+    let mut sleep = Duration::from_micros(100);
+    loop {
+        if let Some(status) = try_wait()? {
+            return Ok(Some(status));
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            return Ok(None);
+        }
+        thread::sleep(cmp::min(sleep, timeout - elapsed));
+        sleep = cmp::min(sleep * 2, MAX_SLEEP);
+    }
+}
@@ -0,0 +1,77 @@
+//! Types and Traits for working with asynchronous tasks.
+
+#[cfg(test)]
+mod tests;
+
+use crate::future::Future;
+use crate::pin::Pin;
+use crate::sync::Arc;
+use crate::thread::{self, Thread};
+
+#[doc(inline)]
+#[stable(feature = "futures_api", since = "1.36.0")]
+pub use core::task::*;
+
+#[doc(inline)]
+#[unstable(feature = "wake_trait", issue = "69912")]
+pub use alloc::task::*;
+
+/// Runs a future to completion on the current thread.
+///
+/// The future is polled on the calling thread, which is [parked] while the
+/// future is pending and unparked when it is woken. This is a minimal
+/// executor: it runs no other tasks while it waits, so it suits running a
+/// single future to completion from synchronous code, such as at the top of
+/// `main` or in a test. Futures that need the services of a particular async
+/// runtime, like timers or I/O readiness, still need that runtime.
+///
+/// [parked]: crate::thread::park
+///
+/// # Panics
+///
+/// Panics if polling the future panics. Calling `block_on` from within a
+/// future that is itself run by `block_on` does not panic, but blocks the
+/// outer future until the inner one completes.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(block_on)]
+/// use std::task::block_on;
+///
+/// async fn add(a: i32, b: i32) -> i32 {
+///     a + b
+/// }
+///
+/// assert_eq!(block_on(add(2, 3)), 5);
+/// ```
+#[unstable(feature = "block_on", issue = "none")]
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = future;
+    // SAFETY: `future` is shadowed, so it can no longer be moved.
+    let mut future = unsafe { Pin::new_unchecked(&mut future) };
+
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            // A wake-up that came in since the poll left the park token set,
+            // so this returns immediately. Spurious wake-ups just poll again.
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// Wakes the thread running `block_on` by unparking it.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
@@ -0,0 +1,77 @@
+use super::block_on;
+use crate::future::{self, join, select, yield_now};
+use crate::sync::atomic::{AtomicBool, Ordering};
+use crate::sync::Arc;
+use crate::task::Poll;
+use crate::thread;
+use crate::time::Duration;
+
+#[test]
+fn block_on_ready() {
+    assert_eq!(block_on(future::ready(7)), 7);
+    assert_eq!(block_on(async { "hello" }), "hello");
+}
+
+#[test]
+fn block_on_yield_now() {
+    let output = block_on(async {
+        let mut total = 0;
+        for i in 0..100 {
+            yield_now().await;
+            total += i;
+        }
+        total
+    });
+    assert_eq!(output, 4950);
+}
+
+#[test]
+fn block_on_woken_from_other_thread() {
+    let flag = Arc::new(AtomicBool::new(false));
+    let mut spawned = false;
+    let fut = future::poll_fn(|cx| {
+        if flag.load(Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+        if !spawned {
+            spawned = true;
+            let flag = flag.clone();
+            let waker = cx.waker().clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                flag.store(true, Ordering::SeqCst);
+                waker.wake();
+            });
+        }
+        Poll::Pending
+    });
+    block_on(fut);
+    assert!(flag.load(Ordering::SeqCst));
+}
+
+#[test]
+fn join_polls_all() {
+    let output = block_on(async {
+        join!(
+            async {
+                yield_now().await;
+                1
+            },
+            async { 2 },
+            future::ready("three"),
+        )
+        .await
+    });
+    assert_eq!(output, (1, 2, "three"));
+}
+
+#[test]
+fn select_first_ready() {
+    let fast = async { 1 };
+    let slow = async {
+        yield_now().await;
+        2
+    };
+    assert_eq!(block_on(select(slow, fast)), 1);
+    assert_eq!(block_on(select(future::pending(), future::ready(3))), 3);
+}
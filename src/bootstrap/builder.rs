@@ -420,6 +420,7 @@ macro_rules! describe {
                 test::Miri,
                 test::Clippy,
                 test::CompiletestTest,
+                test::ApiDiff,
                 test::RustdocJSStd,
                 test::RustdocJSNotStd,
                 test::RustdocTheme,
@@ -502,6 +502,42 @@ fn run(self, builder: &Builder<'_>) {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ApiDiff {
+    host: TargetSelection,
+}
+
+impl Step for ApiDiff {
+    type Output = ();
+    const ONLY_HOSTS: bool = true;
+
+    fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
+        run.path("src/tools/api-diff")
+    }
+
+    fn make_run(run: RunConfig<'_>) {
+        run.builder.ensure(ApiDiff { host: run.target });
+    }
+
+    /// Runs `cargo test` for api-diff.
+    fn run(self, builder: &Builder<'_>) {
+        let host = self.host;
+        let compiler = builder.compiler(0, host);
+        let cargo = tool::prepare_tool_cargo(
+            builder,
+            compiler,
+            Mode::ToolBootstrap,
+            host,
+            "test",
+            "src/tools/api-diff",
+            SourceType::InTree,
+            &[],
+        );
+
+        try_run(builder, &mut cargo.into());
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Clippy {
     stage: u32,
@@ -105,6 +105,14 @@ impl<'a, 'tcx> BlanketImplFinder<'a, 'tcx> {
                     .provided_trait_methods(trait_def_id)
                     .map(|meth| meth.ident.to_string())
                     .collect();
+                let mut items = self
+                    .cx
+                    .tcx
+                    .associated_items(impl_def_id)
+                    .in_definition_order()
+                    .collect::<Vec<_>>()
+                    .clean(self.cx);
+                resolve_assoc_types(self.cx, impl_def_id, ty, param_env, &mut items);
 
                 impls.push(Item {
                     source: self.cx.tcx.def_span(impl_def_id).clean(self.cx),
@@ -126,13 +134,7 @@ impl<'a, 'tcx> BlanketImplFinder<'a, 'tcx> {
                         // the post-inference `trait_ref`, as it's more accurate.
                         trait_: Some(trait_ref.clean(self.cx).get_trait_type().unwrap()),
                         for_: ty.clean(self.cx),
-                        items: self
-                            .cx
-                            .tcx
-                            .associated_items(impl_def_id)
-                            .in_definition_order()
-                            .collect::<Vec<_>>()
-                            .clean(self.cx),
+                        items,
                         polarity: None,
                         synthetic: false,
                         blanket_impl: Some(trait_ref.self_ty().clean(self.cx)),
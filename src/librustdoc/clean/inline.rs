@@ -397,7 +397,7 @@ fn merge_attrs(
     }
 
     let predicates = tcx.explicit_predicates_of(did);
-    let (trait_items, generics) = match impl_item {
+    let (mut trait_items, generics) = match impl_item {
         Some((_, generics, items)) => (
             items.iter().map(|item| tcx.hir().impl_item(item.id).clean(cx)).collect::<Vec<_>>(),
             generics.clean(cx),
@@ -416,6 +416,9 @@ fn merge_attrs(
             clean::enter_impl_trait(cx, || (tcx.generics_of(did), predicates).clean(cx)),
         ),
     };
+    if associated_trait.is_some() {
+        super::resolve_assoc_types(cx, did, tcx.type_of(did), tcx.param_env(did), &mut trait_items);
+    }
     let polarity = tcx.impl_polarity(did);
     let trait_ = associated_trait.clean(cx).map(|bound| match bound {
         clean::GenericBound::TraitBound(polyt, _) => polyt.trait_,
@@ -2066,8 +2066,13 @@ impl Clean<Vec<Item>> for doctree::Impl<'_> {
     fn clean(&self, cx: &DocContext<'_>) -> Vec<Item> {
         let mut ret = Vec::new();
         let trait_ = self.trait_.clean(cx);
-        let items = self.items.iter().map(|ii| ii.clean(cx)).collect::<Vec<_>>();
+        let mut items = self.items.iter().map(|ii| ii.clean(cx)).collect::<Vec<_>>();
         let def_id = cx.tcx.hir().local_def_id(self.id);
+        if trait_.is_some() {
+            let impl_def_id = def_id.to_def_id();
+            let (self_ty, param_env) = (cx.tcx.type_of(impl_def_id), cx.tcx.param_env(impl_def_id));
+            resolve_assoc_types(cx, impl_def_id, self_ty, param_env, &mut items);
+        }
 
         // If this impl block is an implementation of the Deref trait, then we
         // need to try inlining the target's inherent impl blocks as well.
@@ -331,6 +331,21 @@ impl ItemKind {
         }
     }
 
+    /// Some items contain others such as structs (for their fields) and Enums
+    /// (for their variants). This method returns those contained items.
+    crate fn inner_items(&self) -> slice::Iter<'_, Item> {
+        match self {
+            StructItem(s) => s.fields.iter(),
+            UnionItem(u) => u.fields.iter(),
+            VariantItem(Variant { kind: VariantKind::Struct(v) }) => v.fields.iter(),
+            EnumItem(e) => e.variants.iter(),
+            TraitItem(t) => t.items.iter(),
+            ImplItem(i) => i.items.iter(),
+            StrippedItem(kind) => kind.inner_items(),
+            _ => [].iter(),
+        }
+    }
+
     crate fn as_assoc_kind(&self) -> Option<AssocKind> {
         match *self {
             ItemKind::AssocConstItem(..) => Some(AssocKind::Const),
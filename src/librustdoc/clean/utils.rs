@@ -14,10 +14,15 @@
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_infer::infer::TyCtxtInferExt;
+use rustc_infer::traits;
 use rustc_middle::mir::interpret::ConstValue;
-use rustc_middle::ty::subst::{GenericArgKind, SubstsRef};
-use rustc_middle::ty::{self, DefIdTree, Ty};
+use rustc_middle::traits::query::NoSolution;
+use rustc_middle::ty::subst::{GenericArgKind, Subst, SubstsRef};
+use rustc_middle::ty::{self, DefIdTree, Ty, TypeFoldable};
 use rustc_span::symbol::{kw, sym, Symbol};
+use rustc_span::DUMMY_SP;
+use rustc_trait_selection::traits::query::normalize::AtExt;
 use std::mem;
 
 crate fn krate(mut cx: &mut DocContext<'_>) -> Crate {
@@ -576,6 +581,56 @@ fn print_const_with_custom_print_scalar(cx: &DocContext<'_>, ct: &'tcx ty::Const
         .chain(BlanketImplFinder::new(cx).get_blanket_impls(ty, param_env_def_id))
 }
 
+/// Replaces the associated types of a trait impl with what they resolve to when the impl is used
+/// for `self_ty`. This is what lets a page say `type Item = u32` where the impl says
+/// `type Item = <I as Iterator>::Item`, be it a blanket impl or one whose associated types are
+/// written as projections.
+///
+/// Types that still depend on generic parameters which `self_ty` doesn't pin down are kept as they
+/// were written.
+crate fn resolve_assoc_types(
+    cx: &DocContext<'tcx>,
+    impl_def_id: DefId,
+    self_ty: Ty<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    items: &mut [Item],
+) {
+    let tcx = cx.tcx;
+    tcx.infer_ctxt().enter(|infcx| {
+        let impl_substs = infcx.fresh_substs_for_item(DUMMY_SP, impl_def_id);
+        let impl_self_ty = tcx.type_of(impl_def_id).subst(tcx, impl_substs);
+        let cause = traits::ObligationCause::dummy();
+        if infcx.at(&cause, param_env).eq(impl_self_ty, self_ty).is_err() {
+            return;
+        }
+
+        for item in items {
+            let typedef = match item.kind {
+                ItemKind::TypedefItem(ref mut typedef, true) => typedef,
+                _ => continue,
+            };
+            let declared = tcx.type_of(item.def_id);
+            if !declared.has_projections() && !declared.has_param_types_or_consts() {
+                continue;
+            }
+            let ty = infcx.resolve_vars_if_possible(declared.subst(tcx, impl_substs));
+            if ty.needs_infer() {
+                continue;
+            }
+            let resolved = match infcx.at(&cause, param_env).normalize(ty) {
+                Ok(normalized) => infcx.resolve_vars_if_possible(normalized.value),
+                Err(NoSolution) => continue,
+            };
+            let resolved = tcx.erase_regions(resolved);
+            if !resolved.needs_infer() && resolved != tcx.erase_regions(declared) {
+                debug!("resolve_assoc_types: {:?} resolves to {:?}", declared, resolved);
+                typedef.type_ = resolved.clean(cx);
+                typedef.item_type = None;
+            }
+        }
+    })
+}
+
 crate fn register_res(cx: &DocContext<'_>, res: Res) -> DefId {
     debug!("register_res({:?})", res);
 
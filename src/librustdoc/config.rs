@@ -27,12 +27,14 @@
 use crate::html::static_files;
 use crate::opts;
 use crate::passes::{self, Condition, DefaultPassOption};
+use crate::scrape_examples::{CallData, ScrapeExamplesOptions};
 use crate::theme;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 crate enum OutputFormat {
     Json,
     Html,
+    Lcov,
 }
 
 impl OutputFormat {
@@ -51,6 +53,7 @@ fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value {
             "json" => Ok(OutputFormat::Json),
             "html" => Ok(OutputFormat::Html),
+            "lcov" => Ok(OutputFormat::Lcov),
             _ => Err(format!("unknown output format `{}`", value)),
         }
     }
@@ -109,6 +112,9 @@ fn try_from(value: &str) -> Result<Self, Self::Error> {
     /// Optional path to persist the doctest executables to, defaults to a
     /// temporary directory if not set.
     crate persist_doctests: Option<PathBuf>,
+    /// Whether to compile doctests that don't need a crate of their own into a single test
+    /// harness crate, instead of compiling each one on its own.
+    crate merge_doctests: bool,
     /// Runtool to run doctests with
     crate runtool: Option<String>,
     /// Arguments to pass to the runtool
@@ -148,6 +154,12 @@ fn try_from(value: &str) -> Result<Self, Self::Error> {
     /// If this option is set to `true`, rustdoc will only run checks and not generate
     /// documentation.
     crate run_check: bool,
+    /// If set, rustdoc only collects the calls the crate makes to other crates' functions, and
+    /// writes them out so that they can be shown as examples in those crates' documentation.
+    crate scrape_examples_options: Option<ScrapeExamplesOptions>,
+    /// Files of calls collected by scraping other crates, to show as examples on the pages of
+    /// the functions they call.
+    crate with_examples: Vec<PathBuf>,
 }
 
 impl fmt::Debug for Options {
@@ -179,6 +191,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             .field("should_test", &self.should_test)
             .field("test_args", &self.test_args)
             .field("persist_doctests", &self.persist_doctests)
+            .field("merge_doctests", &self.merge_doctests)
             .field("default_passes", &self.default_passes)
             .field("manual_passes", &self.manual_passes)
             .field("display_warnings", &self.display_warnings)
@@ -189,6 +202,8 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             .field("runtool_args", &self.runtool_args)
             .field("enable-per-target-ignores", &self.enable_per_target_ignores)
             .field("run_check", &self.run_check)
+            .field("scrape_examples_options", &self.scrape_examples_options)
+            .field("with_examples", &self.with_examples)
             .finish()
     }
 }
@@ -253,6 +268,9 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     /// If false, the `select` element to have search filtering by crates on rendered docs
     /// won't be generated.
     crate generate_search_filter: bool,
+    /// Whether to build an index of the words in the docs, so that the search can find items by
+    /// what their docs say.
+    crate full_text_search: bool,
     /// Document items that have lower than `pub` visibility.
     crate document_private: bool,
     /// Document items that have `doc(hidden)`.
@@ -272,6 +290,8 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     crate deref_mut_trait_did: Option<DefId>,
     crate owned_box_did: Option<DefId>,
     crate output_format: Option<OutputFormat>,
+    /// Usage examples of the crate's functions found in other crates.
+    crate call_locations: FxHashMap<DefId, Vec<CallData>>,
 }
 
 impl Options {
@@ -547,12 +567,18 @@ fn println_condition(condition: Condition) {
                         diag.struct_err("json output format isn't supported for doc generation")
                             .emit();
                         return Err(1);
-                    } else if !o.is_json() && show_coverage {
+                    } else if o == OutputFormat::Html && show_coverage {
                         diag.struct_err(
                             "html output format isn't supported for the --show-coverage option",
                         )
                         .emit();
                         return Err(1);
+                    } else if o == OutputFormat::Lcov && !show_coverage {
+                        diag.struct_err(
+                            "lcov output format is only supported with the --show-coverage option",
+                        )
+                        .emit();
+                        return Err(1);
                     }
                     Some(o)
                 }
@@ -578,7 +604,9 @@ fn println_condition(condition: Condition) {
         let enable_index_page = matches.opt_present("enable-index-page") || index_page.is_some();
         let static_root_path = matches.opt_str("static-root-path");
         let generate_search_filter = !matches.opt_present("disable-per-crate-search");
+        let full_text_search = matches.opt_present("full-text-search");
         let persist_doctests = matches.opt_str("persist-doctests").map(PathBuf::from);
+        let merge_doctests = matches.opt_present("merge-doctests");
         let test_builder = matches.opt_str("test-builder").map(PathBuf::from);
         let codegen_options_strs = matches.opt_strs("C");
         let debugging_opts_strs = matches.opt_strs("Z");
@@ -590,6 +618,9 @@ fn println_condition(condition: Condition) {
         let document_private = matches.opt_present("document-private-items");
         let document_hidden = matches.opt_present("document-hidden-items");
         let run_check = matches.opt_present("check");
+        let scrape_examples_options = ScrapeExamplesOptions::new(matches, &diag)?;
+        let with_examples =
+            matches.opt_strs("with-examples").into_iter().map(PathBuf::from).collect();
 
         let (lint_opts, describe_lints, lint_cap) = get_cmd_lint_options(matches, error_format);
 
@@ -620,11 +651,14 @@ fn println_condition(condition: Condition) {
             show_coverage,
             crate_version,
             persist_doctests,
+            merge_doctests,
             runtool,
             runtool_args,
             enable_per_target_ignores,
             test_builder,
             run_check,
+            scrape_examples_options,
+            with_examples,
             render_options: RenderOptions {
                 output,
                 external_html,
@@ -644,6 +678,7 @@ fn println_condition(condition: Condition) {
                 markdown_css,
                 markdown_playground_url,
                 generate_search_filter,
+                full_text_search,
                 document_private,
                 document_hidden,
                 unstable_features: rustc_feature::UnstableFeatures::from_environment(
@@ -28,6 +28,7 @@
 
 use std::cell::RefCell;
 use std::mem;
+use std::path::PathBuf;
 use std::rc::Rc;
 
 use crate::clean;
@@ -35,6 +36,7 @@
 use crate::config::{Options as RustdocOptions, RenderOptions};
 use crate::config::{OutputFormat, RenderInfo};
 use crate::passes::{self, Condition::*, ConditionalPass};
+use crate::scrape_examples;
 
 crate use rustc_session::config::{DebuggingOptions, Input, Options};
 
@@ -305,6 +307,8 @@ pub(crate) fn init_lints<F>(
         display_warnings,
         render_options,
         output_format,
+        scrape_examples_options,
+        with_examples,
         ..
     } = options;
 
@@ -471,14 +475,19 @@ pub(crate) fn init_lints<F>(
 
             let (krate, render_info, opts) = sess.time("run_global_ctxt", || {
                 global_ctxt.enter(|tcx| {
-                    run_global_ctxt(
+                    let result = run_global_ctxt(
                         tcx,
                         resolver,
                         default_passes,
                         manual_passes,
                         render_options,
                         output_format,
-                    )
+                        &with_examples,
+                    );
+                    if let Some(ref options) = scrape_examples_options {
+                        tcx.sess.time("scrape_examples", || scrape_examples::run(tcx, options));
+                    }
+                    result
                 })
             });
             (krate, render_info, opts, Lrc::clone(sess))
@@ -493,6 +502,7 @@ fn run_global_ctxt(
     mut manual_passes: Vec<String>,
     render_options: RenderOptions,
     output_format: Option<OutputFormat>,
+    with_examples: &[PathBuf],
 ) -> (clean::Crate, RenderInfo, RenderOptions) {
     // Certain queries assume that some checks were run elsewhere
     // (see https://github.com/rust-lang/rust/pull/73566#issuecomment-656954425),
@@ -536,6 +546,7 @@ fn run_global_ctxt(
     let mut renderinfo = RenderInfo::default();
     renderinfo.access_levels = access_levels;
     renderinfo.output_format = output_format;
+    renderinfo.call_locations = scrape_examples::load_call_locations(tcx, with_examples);
 
     let mut ctxt = DocContext {
         tcx,
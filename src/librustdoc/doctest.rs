@@ -20,9 +20,10 @@
 use std::env;
 use std::io::{self, Write};
 use std::panic;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{self, Command, Stdio};
 use std::str;
+use std::sync::{Arc, Mutex};
 
 use crate::clean::Attributes;
 use crate::config::Options;
@@ -253,61 +254,16 @@ fn run_test(
 
     let output_file = outdir.path().join("rust_out");
 
-    let rustc_binary = options
-        .test_builder
-        .as_deref()
-        .unwrap_or_else(|| rustc_interface::util::rustc_path().expect("found rustc"));
-    let mut compiler = Command::new(&rustc_binary);
-    compiler.arg("--crate-type").arg("bin");
-    for cfg in &options.cfgs {
-        compiler.arg("--cfg").arg(&cfg);
-    }
-    if let Some(sysroot) = options.maybe_sysroot {
-        compiler.arg("--sysroot").arg(sysroot);
-    }
-    compiler.arg("--edition").arg(&edition.to_string());
+    let mut compiler = compiler_command(&options, edition, &target, supports_color);
     compiler.env("UNSTABLE_RUSTDOC_TEST_PATH", path);
     compiler.env("UNSTABLE_RUSTDOC_TEST_LINE", format!("{}", line as isize - line_offset as isize));
     compiler.arg("-o").arg(&output_file);
     if as_test_harness {
         compiler.arg("--test");
     }
-    for lib_str in &options.lib_strs {
-        compiler.arg("-L").arg(&lib_str);
-    }
-    for extern_str in &options.extern_strs {
-        compiler.arg("--extern").arg(&extern_str);
-    }
-    compiler.arg("-Ccodegen-units=1");
-    for codegen_options_str in &options.codegen_options_strs {
-        compiler.arg("-C").arg(&codegen_options_str);
-    }
-    for debugging_option_str in &options.debugging_opts_strs {
-        compiler.arg("-Z").arg(&debugging_option_str);
-    }
     if no_run && !compile_fail {
         compiler.arg("--emit=metadata");
     }
-    compiler.arg("--target").arg(match target {
-        TargetTriple::TargetTriple(s) => s,
-        TargetTriple::TargetPath(path) => {
-            path.to_str().expect("target path must be valid unicode").to_string()
-        }
-    });
-    if let ErrorOutputType::HumanReadable(kind) = options.error_format {
-        let (_, color_config) = kind.unzip();
-        match color_config {
-            ColorConfig::Never => {
-                compiler.arg("--color").arg("never");
-            }
-            ColorConfig::Always => {
-                compiler.arg("--color").arg("always");
-            }
-            ColorConfig::Auto => {
-                compiler.arg("--color").arg(if supports_color { "always" } else { "never" });
-            }
-        }
-    }
 
     compiler.arg("-");
     compiler.stdin(Stdio::piped());
@@ -355,16 +311,7 @@ fn drop(&mut self) {
     }
 
     // Run the code!
-    let mut cmd;
-
-    if let Some(tool) = runtool {
-        cmd = Command::new(tool);
-        cmd.args(runtool_args);
-        cmd.arg(output_file);
-    } else {
-        cmd = Command::new(output_file);
-    }
-
+    let mut cmd = binary_command(&output_file, runtool, runtool_args);
     match cmd.output() {
         Err(e) => return Err(TestFailure::ExecutionError(e)),
         Ok(out) => {
@@ -379,6 +326,75 @@ fn drop(&mut self) {
     Ok(())
 }
 
+/// Creates the `rustc` command that doctests are compiled with, without its input and output.
+fn compiler_command(
+    options: &Options,
+    edition: Edition,
+    target: &TargetTriple,
+    supports_color: bool,
+) -> Command {
+    let rustc_binary = options
+        .test_builder
+        .as_deref()
+        .unwrap_or_else(|| rustc_interface::util::rustc_path().expect("found rustc"));
+    let mut compiler = Command::new(&rustc_binary);
+    compiler.arg("--crate-type").arg("bin");
+    for cfg in &options.cfgs {
+        compiler.arg("--cfg").arg(&cfg);
+    }
+    if let Some(ref sysroot) = options.maybe_sysroot {
+        compiler.arg("--sysroot").arg(sysroot);
+    }
+    compiler.arg("--edition").arg(&edition.to_string());
+    for lib_str in &options.lib_strs {
+        compiler.arg("-L").arg(&lib_str);
+    }
+    for extern_str in &options.extern_strs {
+        compiler.arg("--extern").arg(&extern_str);
+    }
+    compiler.arg("-Ccodegen-units=1");
+    for codegen_options_str in &options.codegen_options_strs {
+        compiler.arg("-C").arg(&codegen_options_str);
+    }
+    for debugging_option_str in &options.debugging_opts_strs {
+        compiler.arg("-Z").arg(&debugging_option_str);
+    }
+    compiler.arg("--target").arg(match *target {
+        TargetTriple::TargetTriple(ref s) => s.as_str(),
+        TargetTriple::TargetPath(ref path) => {
+            path.to_str().expect("target path must be valid unicode")
+        }
+    });
+    if let ErrorOutputType::HumanReadable(kind) = options.error_format {
+        let (_, color_config) = kind.unzip();
+        match color_config {
+            ColorConfig::Never => {
+                compiler.arg("--color").arg("never");
+            }
+            ColorConfig::Always => {
+                compiler.arg("--color").arg("always");
+            }
+            ColorConfig::Auto => {
+                compiler.arg("--color").arg(if supports_color { "always" } else { "never" });
+            }
+        }
+    }
+    compiler
+}
+
+/// Creates the command that runs a compiled doctest, through the runtool if there is one.
+fn binary_command(binary: &Path, runtool: Option<String>, runtool_args: Vec<String>) -> Command {
+    match runtool {
+        Some(tool) => {
+            let mut cmd = Command::new(tool);
+            cmd.args(runtool_args);
+            cmd.arg(binary);
+            cmd
+        }
+        None => Command::new(binary),
+    }
+}
+
 /// Transforms a test into code that can be compiled into a Rust binary, and returns the number of
 /// lines before the test code begins as well as if the output stream supports colors or not.
 crate fn make_test(
@@ -392,7 +408,6 @@ fn drop(&mut self) {
     let everything_else = everything_else.trim();
     let mut line_offset = 0;
     let mut prog = String::new();
-    let mut supports_color = false;
 
     if opts.attrs.is_empty() && !opts.display_warnings {
         // If there aren't any attributes supplied by #![doc(test(attr(...)))], then allow some
@@ -416,6 +431,62 @@ fn drop(&mut self) {
 
     // Uses librustc_ast to parse the doctest and find if there's a main fn and the extern
     // crate already is included.
+    let (already_has_main, already_has_extern_crate, supports_color) =
+        match parse_source(s, crates + everything_else, cratename, edition) {
+            Ok(parsed) => parsed,
+            Err(ErrorReported) => {
+                // If the parser panicked due to a fatal error, pass the test code through
+                // unchanged. The error will be reported during compilation.
+                return (s.to_owned(), 0, false);
+            }
+        };
+
+    // Don't inject `extern crate std` because it's already injected by the
+    // compiler.
+    if !already_has_extern_crate && !opts.no_crate_inject && cratename != Some("std") {
+        if let Some(cratename) = cratename {
+            // Make sure its actually used if not included.
+            if s.contains(cratename) {
+                prog.push_str(&format!("extern crate {};\n", cratename));
+                line_offset += 1;
+            }
+        }
+    }
+
+    // FIXME: This code cannot yet handle no_std test cases yet
+    if dont_insert_main || already_has_main || prog.contains("![no_std]") {
+        prog.push_str(everything_else);
+    } else {
+        prog.push_str(&wrap_in_main(everything_else));
+        line_offset += 1;
+    }
+
+    debug!("final doctest:\n{}", prog);
+
+    (prog, line_offset, supports_color)
+}
+
+/// Wraps the code of a doctest in a `main` function. The code can end with `Ok(())` to use `?`.
+fn wrap_in_main(code: &str) -> String {
+    let returns_result = code.trim_end().ends_with("(())");
+    let (main_pre, main_post) = if returns_result {
+        ("fn main() { fn _inner() -> Result<(), impl core::fmt::Debug> {", "}\n_inner().unwrap() }")
+    } else {
+        ("fn main() {\n", "\n}")
+    };
+    format!("{}{}{}", main_pre, code, main_post)
+}
+
+/// Parses the items of a doctest to find whether it has a `main` function and already declares
+/// the crate being tested as an `extern crate`, and returns those along with whether the output
+/// stream supports colors. `s` is the whole doctest, and `source` the part of it to parse.
+fn parse_source(
+    s: &str,
+    source: String,
+    cratename: Option<&str>,
+    edition: Edition,
+) -> Result<(bool, bool, bool), ErrorReported> {
+    let mut supports_color = false;
     let result = rustc_driver::catch_fatal_errors(|| {
         rustc_span::with_session_globals(edition, || {
             use rustc_errors::emitter::{Emitter, EmitterWriter};
@@ -425,10 +496,10 @@ fn drop(&mut self) {
             use rustc_span::source_map::FilePathMapping;
 
             let filename = FileName::anon_source_code(s);
-            let source = crates + everything_else;
 
-            // Any errors in parsing should also appear when the doctest is compiled for real, so just
-            // send all the errors that librustc_ast emits directly into a `Sink` instead of stderr.
+            // Any errors in parsing should also appear when the doctest is compiled for real, so
+            // just send all the errors that librustc_ast emits directly into a `Sink` instead of
+            // stderr.
             let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
             supports_color =
                 EmitterWriter::stderr(ColorConfig::Auto, None, false, false, Some(80), false)
@@ -501,14 +572,7 @@ fn drop(&mut self) {
             (found_main, found_extern_crate, found_macro)
         })
     });
-    let (already_has_main, already_has_extern_crate, found_macro) = match result {
-        Ok(result) => result,
-        Err(ErrorReported) => {
-            // If the parser panicked due to a fatal error, pass the test code through unchanged.
-            // The error will be reported during compilation.
-            return (s.to_owned(), 0, false);
-        }
-    };
+    let (already_has_main, already_has_extern_crate, found_macro) = result?;
 
     // If a doctest's `fn main` is being masked by a wrapper macro, the parsing loop above won't
     // see it. In that case, run the old text-based scan to see if they at least have a main
@@ -525,38 +589,134 @@ fn drop(&mut self) {
         already_has_main
     };
 
-    // Don't inject `extern crate std` because it's already injected by the
-    // compiler.
-    if !already_has_extern_crate && !opts.no_crate_inject && cratename != Some("std") {
-        if let Some(cratename) = cratename {
-            // Make sure its actually used if not included.
-            if s.contains(cratename) {
-                prog.push_str(&format!("extern crate {};\n", cratename));
-                line_offset += 1;
-            }
+    Ok((already_has_main, already_has_extern_crate, supports_color))
+}
+
+/// Turns a doctest into a module of a merged doctest crate, whose `__run` test function runs the
+/// doctest. Returns `None` if the doctest needs a crate of its own, because it has crate
+/// attributes, `extern crate` items or its own `main` function.
+crate fn make_merged_test(
+    s: &str,
+    index: usize,
+    cratename: Option<&str>,
+    should_panic: bool,
+    edition: Edition,
+) -> Option<String> {
+    let (crate_attrs, everything_else, crates) = partition_source(s);
+    if crate_attrs.lines().any(|line| line.trim_start().starts_with("#![")) || !crates.is_empty() {
+        return None;
+    }
+    let everything_else = everything_else.trim();
+    let (has_main, _, _) = parse_source(s, everything_else.to_string(), cratename, edition).ok()?;
+    if has_main {
+        return None;
+    }
+
+    let mut module = format!("mod __doctest_{} {{\n", index);
+    module.push_str(&wrap_in_main(everything_else));
+    module.push_str("\n#[test]\n");
+    if should_panic {
+        module.push_str("#[should_panic]\n");
+    }
+    module.push_str("fn __run() {\n    main()\n}\n}\n");
+    Some(module)
+}
+
+/// Doctests of one edition that are compiled together into a single test harness crate, with a
+/// module for each doctest.
+struct MergedDoctests {
+    /// The modules of the doctests, in the order they were added.
+    modules: Vec<String>,
+    /// Whether any of the doctests mentions the crate being tested, which is then declared as an
+    /// `extern crate` at the root of the merged crate.
+    uses_crate: bool,
+    outdir: DirState,
+    /// The test harness built from the merged crate, once one of its doctests has run. It holds
+    /// `None` if the merged crate didn't compile, in which case every doctest is compiled on its
+    /// own, so that errors are reported for the doctests that cause them.
+    binary: Option<Option<PathBuf>>,
+}
+
+impl MergedDoctests {
+    /// Returns the test harness built from the merged crate, compiling it the first time.
+    fn binary(
+        &mut self,
+        cratename: &str,
+        opts: &TestOptions,
+        options: &Options,
+        edition: Edition,
+    ) -> Option<PathBuf> {
+        if self.binary.is_none() {
+            self.binary = Some(self.compile(cratename, opts, options, edition));
         }
+        self.binary.clone().unwrap()
     }
 
-    // FIXME: This code cannot yet handle no_std test cases yet
-    if dont_insert_main || already_has_main || prog.contains("![no_std]") {
-        prog.push_str(everything_else);
-    } else {
-        let returns_result = everything_else.trim_end().ends_with("(())");
-        let (main_pre, main_post) = if returns_result {
-            (
-                "fn main() { fn _inner() -> Result<(), impl core::fmt::Debug> {",
-                "}\n_inner().unwrap() }",
-            )
+    fn compile(
+        &self,
+        cratename: &str,
+        opts: &TestOptions,
+        options: &Options,
+        edition: Edition,
+    ) -> Option<PathBuf> {
+        let mut source = String::new();
+        if opts.attrs.is_empty() && !opts.display_warnings {
+            source.push_str("#![allow(unused)]\n");
+        }
+        for attr in &opts.attrs {
+            source.push_str(&format!("#![{}]\n", attr));
+        }
+        if self.uses_crate && !opts.no_crate_inject && cratename != "std" {
+            source.push_str(&format!("extern crate {};\n", cratename));
+        }
+        for module in &self.modules {
+            source.push_str(module);
+        }
+
+        let output_file = self.outdir.path().join("rust_out");
+        // The output is only shown by the doctests if they end up being compiled on their own.
+        let mut compiler = compiler_command(options, edition, &options.target, false);
+        compiler.arg("--test").arg("-o").arg(&output_file).arg("-");
+        compiler.stdin(Stdio::piped());
+        compiler.stderr(Stdio::piped());
+
+        let mut child = compiler.spawn().expect("Failed to spawn rustc process");
+        {
+            let stdin = child.stdin.as_mut().expect("Failed to open stdin");
+            stdin.write_all(source.as_bytes()).expect("could write out test sources");
+        }
+        let output = child.wait_with_output().expect("Failed to read stdout");
+        if output.status.success() {
+            Some(output_file)
         } else {
-            ("fn main() {\n", "\n}")
-        };
-        prog.extend([main_pre, everything_else, main_post].iter().cloned());
-        line_offset += 1;
+            debug!(
+                "merged doctests failed to compile:\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+            None
+        }
     }
+}
 
-    debug!("final doctest:\n{}", prog);
+/// Runs the doctest with the given module index from the test harness of a merged crate.
+fn run_merged_test(
+    binary: &Path,
+    index: usize,
+    no_run: bool,
+    runtool: Option<String>,
+    runtool_args: Vec<String>,
+) -> Result<(), TestFailure> {
+    if no_run {
+        return Ok(());
+    }
 
-    (prog, line_offset, supports_color)
+    let mut cmd = binary_command(binary, runtool, runtool_args);
+    cmd.arg("--exact").arg(format!("__doctest_{}::__run", index)).arg("--nocapture");
+    match cmd.output() {
+        Err(e) => Err(TestFailure::ExecutionError(e)),
+        Ok(out) if !out.status.success() => Err(TestFailure::ExecutionFailure(out)),
+        Ok(_) => Ok(()),
+    }
 }
 
 // FIXME(aburka): use a real parser to deal with multiline attributes
@@ -671,6 +831,8 @@ fn register_header(&mut self, _name: &str, _level: u32) {}
     source_map: Option<Lrc<SourceMap>>,
     filename: Option<PathBuf>,
     visited_tests: HashMap<(String, usize), usize>,
+    /// The crates that compatible doctests are merged into, with `--merge-doctests`.
+    merged: HashMap<Edition, Arc<Mutex<MergedDoctests>>>,
 }
 
 impl Collector {
@@ -695,6 +857,7 @@ impl Collector {
             source_map,
             filename,
             visited_tests: HashMap::new(),
+            merged: HashMap::new(),
         }
     }
 
@@ -727,6 +890,58 @@ fn get_filename(&self) -> FileName {
             FileName::Custom("input".to_owned())
         }
     }
+
+    /// Adds a doctest to the merged crate of its edition, unless it has to be compiled on its
+    /// own. Returns the merged crate along with the index of the doctest's module in it.
+    fn merge_test(
+        &mut self,
+        test: &str,
+        config: &LangString,
+        edition: Edition,
+    ) -> Option<(Arc<Mutex<MergedDoctests>>, usize)> {
+        // Ignored doctests aren't compiled, and may not compile at all. Before the 2018 edition,
+        // paths are relative to the current module, so doctests can't be moved into one.
+        if config.ignore != Ignore::None
+            || config.compile_fail
+            || config.test_harness
+            || edition < Edition::Edition2018
+        {
+            return None;
+        }
+
+        let index = self.merged.get(&edition).map_or(0, |m| m.lock().unwrap().modules.len());
+        let module =
+            make_merged_test(test, index, Some(&self.cratename), config.should_panic, edition)?;
+
+        let persist_doctests = self.options.persist_doctests.clone();
+        let merged = self.merged.entry(edition).or_insert_with(|| {
+            let outdir = if let Some(mut path) = persist_doctests {
+                path.push(format!("merged_doctests_{}", edition));
+                std::fs::create_dir_all(&path)
+                    .expect("Couldn't create directory for doctest executables");
+                DirState::Perm(path)
+            } else {
+                DirState::Temp(
+                    TempFileBuilder::new()
+                        .prefix("rustdoctest")
+                        .tempdir()
+                        .expect("rustdoc needs a tempdir"),
+                )
+            };
+            Arc::new(Mutex::new(MergedDoctests {
+                modules: Vec::new(),
+                uses_crate: false,
+                outdir,
+                binary: None,
+            }))
+        });
+        {
+            let mut merged = merged.lock().unwrap();
+            merged.modules.push(module);
+            merged.uses_crate |= test.contains(&self.cratename);
+        }
+        Some((merged.clone(), index))
+    }
 }
 
 impl Tester for Collector {
@@ -784,6 +999,12 @@ fn add_test(&mut self, test: String, config: LangString, line: usize) {
             )
         };
 
+        let merged = if self.options.merge_doctests {
+            self.merge_test(&test, &config, edition)
+        } else {
+            None
+        };
+
         debug!("creating test {}: {}", name, test);
         self.tests.push(testing::TestDescAndFn {
             desc: testing::TestDesc {
@@ -799,24 +1020,32 @@ fn add_test(&mut self, test: String, config: LangString, line: usize) {
                 test_type: testing::TestType::DocTest,
             },
             testfn: testing::DynTestFn(box move || {
-                let res = run_test(
-                    &test,
-                    &cratename,
-                    line,
-                    options,
-                    config.should_panic,
-                    config.no_run,
-                    config.test_harness,
-                    runtool,
-                    runtool_args,
-                    target,
-                    config.compile_fail,
-                    config.error_codes,
-                    &opts,
-                    edition,
-                    outdir,
-                    path,
-                );
+                let binary = merged.as_ref().and_then(|(merged, _)| {
+                    merged.lock().unwrap().binary(&cratename, &opts, &options, edition)
+                });
+                let res = match (binary, merged) {
+                    (Some(binary), Some((_, index))) => {
+                        run_merged_test(&binary, index, config.no_run, runtool, runtool_args)
+                    }
+                    _ => run_test(
+                        &test,
+                        &cratename,
+                        line,
+                        options,
+                        config.should_panic,
+                        config.no_run,
+                        config.test_harness,
+                        runtool,
+                        runtool_args,
+                        target,
+                        config.compile_fail,
+                        config.error_codes,
+                        &opts,
+                        edition,
+                        outdir,
+                        path,
+                    ),
+                };
 
                 if let Err(err) = res {
                     match err {
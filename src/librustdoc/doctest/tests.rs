@@ -1,4 +1,4 @@
-use super::{make_test, TestOptions};
+use super::{make_merged_test, make_test, TestOptions};
 use rustc_span::edition::DEFAULT_EDITION;
 
 #[test]
@@ -277,3 +277,59 @@ fn main() {}
     let (output, len, _) = make_test(input, Some("my_crate"), false, &opts, DEFAULT_EDITION);
     assert_eq!((output, len), (expected, 1));
 }
+
+#[test]
+fn make_merged_test_basic() {
+    // A merged doctest becomes a module with a test function that calls its `main`.
+    let input = "use asdf::qwop;
+assert_eq!(2+2, 4);";
+    let expected = "mod __doctest_3 {
+fn main() {
+use asdf::qwop;
+assert_eq!(2+2, 4);
+}
+#[test]
+fn __run() {
+    main()
+}
+}
+";
+    let output = make_merged_test(input, 3, Some("asdf"), false, DEFAULT_EDITION);
+    assert_eq!(output.as_deref(), Some(expected));
+}
+
+#[test]
+fn make_merged_test_should_panic() {
+    let input = "panic!();";
+    let expected = "mod __doctest_0 {
+fn main() {
+panic!();
+}
+#[test]
+#[should_panic]
+fn __run() {
+    main()
+}
+}
+";
+    let output = make_merged_test(input, 0, None, true, DEFAULT_EDITION);
+    assert_eq!(output.as_deref(), Some(expected));
+}
+
+#[test]
+fn make_merged_test_needs_own_crate() {
+    // Doctests with crate attributes, `extern crate` items or a `main` function of their own
+    // can't be merged.
+    let inputs = [
+        "#![feature(sick_rad)]
+assert_eq!(2+2, 4);",
+        "extern crate asdf;
+assert_eq!(2+2, 4);",
+        "fn main() {
+    assert_eq!(2+2, 4);
+}",
+    ];
+    for input in &inputs {
+        assert_eq!(make_merged_test(input, 0, Some("asdf"), false, DEFAULT_EDITION), None);
+    }
+}
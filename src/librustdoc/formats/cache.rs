@@ -15,8 +15,11 @@
 use crate::formats::item_type::ItemType;
 use crate::formats::Impl;
 use crate::html::render::cache::{extern_location, get_index_search_type, ExternalLocation};
+use crate::html::render::cache::{get_index_signature, SignatureParent};
+use crate::html::render::full_text::doc_text;
 use crate::html::render::IndexItem;
 use crate::html::render::{plain_text_summary, shorten};
+use crate::scrape_examples::CallData;
 
 thread_local!(crate static CACHE_KEY: RefCell<Arc<Cache>> = Default::default());
 
@@ -91,10 +94,16 @@
     /// This is stored in `Cache` so it doesn't need to be passed through all rustdoc functions.
     crate document_private: bool,
 
+    /// Whether to keep the text of the docs of the items in the search index, to build the
+    /// full-text search index from.
+    crate full_text_search: bool,
+
     // Private fields only used when initially crawling a crate to build a cache
     stack: Vec<String>,
     parent_stack: Vec<DefId>,
     parent_is_trait_impl: bool,
+    /// The impl or trait whose items are being crawled, for the signatures in the search index.
+    signature_parent: Option<SignatureParent>,
     stripped_mod: bool,
     masked_crates: FxHashSet<CrateNum>,
 
@@ -108,7 +117,7 @@
     // then the fully qualified name of the structure isn't presented in `paths`
     // yet when its implementation methods are being indexed. Caches such methods
     // and their parent id here and indexes them at the end of crate parsing.
-    crate orphan_impl_items: Vec<(DefId, clean::Item)>,
+    crate orphan_impl_items: Vec<(DefId, clean::Item, Option<SignatureParent>)>,
 
     // Similarly to `orphan_impl_items`, sometimes trait impls are picked up
     // even though the trait itself is not exported. This can happen if a trait
@@ -122,12 +131,17 @@
     /// Aliases added through `#[doc(alias = "...")]`. Since a few items can have the same alias,
     /// we need the alias element to have an array of items.
     crate aliases: BTreeMap<String, Vec<usize>>,
+
+    /// Usage examples of the crate's functions that were scraped from other crates, keyed by
+    /// the function they call.
+    crate call_locations: FxHashMap<DefId, Vec<CallData>>,
 }
 
 impl Cache {
     crate fn from_krate(
         render_info: RenderInfo,
         document_private: bool,
+        full_text_search: bool,
         extern_html_root_urls: &BTreeMap<String, String>,
         dst: &Path,
         mut krate: clean::Crate,
@@ -141,6 +155,7 @@ impl Cache {
             deref_trait_did,
             deref_mut_trait_did,
             owned_box_did,
+            call_locations,
             ..
         } = render_info;
 
@@ -155,11 +170,13 @@ impl Cache {
             access_levels,
             crate_version: krate.version.take(),
             document_private,
+            full_text_search,
             traits: krate.external_traits.replace(Default::default()),
             deref_trait_did,
             deref_mut_trait_did,
             owned_box_did,
             masked_crates: mem::take(&mut krate.masked_crates),
+            call_locations,
             ..Cache::default()
         };
 
@@ -314,9 +331,15 @@ fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
                             name: s.to_string(),
                             path: path.join("::"),
                             desc: shorten(plain_text_summary(item.doc_value())),
+                            text: if self.full_text_search {
+                                doc_text(&item)
+                            } else {
+                                String::new()
+                            },
                             parent,
                             parent_idx: None,
                             search_type: get_index_search_type(&item),
+                            signature: get_index_signature(&item, self.signature_parent.as_ref()),
                         });
 
                         for alias in item.attrs.get_doc_aliases() {
@@ -330,7 +353,11 @@ fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
                 (Some(parent), None) if is_inherent_impl_item => {
                     // We have a parent, but we don't know where they're
                     // defined yet. Wait for later to index this item.
-                    self.orphan_impl_items.push((parent, item.clone()));
+                    self.orphan_impl_items.push((
+                        parent,
+                        item.clone(),
+                        self.signature_parent.clone(),
+                    ));
                 }
                 _ => {}
             }
@@ -382,6 +409,16 @@ fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
             _ => {}
         }
 
+        let new_signature_parent = match item.kind {
+            clean::ImplItem(ref i) => Some(SignatureParent::from_impl(i)),
+            clean::TraitItem(ref t) => {
+                Some(SignatureParent::from_trait(item.name.as_deref().unwrap_or_default(), t))
+            }
+            _ => None,
+        };
+        let orig_signature_parent = new_signature_parent
+            .map(|parent| mem::replace(&mut self.signature_parent, Some(parent)));
+
         // Maintain the parent stack
         let orig_parent_is_trait_impl = self.parent_is_trait_impl;
         let parent_pushed = match item.kind {
@@ -479,6 +516,9 @@ fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
         }
         self.stripped_mod = orig_stripped_mod;
         self.parent_is_trait_impl = orig_parent_is_trait_impl;
+        if let Some(orig_signature_parent) = orig_signature_parent {
+            self.signature_parent = orig_signature_parent;
+        }
         ret
     }
 }
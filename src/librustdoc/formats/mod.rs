@@ -13,7 +13,7 @@
 /// impl.
 crate enum AssocItemRender<'a> {
     All,
-    DerefFor { trait_: &'a clean::Type, type_: &'a clean::Type, deref_mut_: bool },
+    DerefFor { trait_: &'a clean::Type, type_: &'a clean::Type, deref_mut_: bool, id: &'a str },
 }
 
 /// For different handling of associated items from the Deref target of a type rather than the type
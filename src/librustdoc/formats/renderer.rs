@@ -53,6 +53,7 @@ fn mod_item_in(
     let (krate, mut cache) = Cache::from_krate(
         render_info.clone(),
         options.document_private,
+        options.full_text_search,
         &options.extern_html_root_urls,
         &options.output,
         krate,
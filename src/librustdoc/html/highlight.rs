@@ -40,6 +40,34 @@
     out
 }
 
+/// Highlights `src` with line numbers next to it, counting from `first_line`. The numbers of
+/// the lines at the (zero-based) indices in `highlighted_lines` are highlighted.
+crate fn render_with_line_numbers(
+    src: String,
+    first_line: usize,
+    highlighted_lines: &[usize],
+) -> String {
+    let mut out = String::with_capacity(src.len());
+    let numbers: Vec<String> = (0..src.lines().count())
+        .map(|i| {
+            if highlighted_lines.contains(&i) {
+                format!("<span class=\"line-highlighted\">{}</span>", first_line + i)
+            } else {
+                format!("<span>{}</span>", first_line + i)
+            }
+        })
+        .collect();
+    write!(
+        out,
+        "<div class=\"example-wrap\"><pre class=\"line-number\">{}</pre><pre class=\"rust\">\n",
+        numbers.join("\n")
+    )
+    .unwrap();
+    write_code(&mut out, &src);
+    write_footer(&mut out, None);
+    out
+}
+
 fn write_header(out: &mut String, class: Option<&str>) {
     write!(out, "<div class=\"example-wrap\"><pre class=\"rust {}\">\n", class.unwrap_or_default())
         .unwrap()
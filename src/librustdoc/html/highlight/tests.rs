@@ -1,4 +1,4 @@
-use super::write_code;
+use super::{render_with_line_numbers, write_code};
 use expect_test::expect_file;
 
 const STYLE: &str = r#"
@@ -33,3 +33,14 @@ fn test_dos_backline() {
     write_code(&mut html, src);
     expect_file!["fixtures/dos_line.html"].assert_eq(&html);
 }
+
+#[test]
+fn test_line_numbers() {
+    let src = "foo();\nbar();\n".to_string();
+    let html = render_with_line_numbers(src, 10, &[1]);
+    assert!(html.starts_with(
+        "<div class=\"example-wrap\"><pre class=\"line-number\"><span>10</span>\n\
+         <span class=\"line-highlighted\">11</span></pre><pre class=\"rust\">\n"
+    ));
+    assert!(html.ends_with("</pre></div>\n"));
+}
@@ -1065,6 +1065,38 @@ impl MarkdownSummaryLine<'_> {
     s
 }
 
+/// Renders all of the provided markdown as plain text, for the full-text search index.
+///
+/// - Headings, links, and formatting are stripped.
+/// - Inline code is rendered as-is.
+/// - HTML and code blocks are ignored.
+crate fn plain_text(md: &str) -> String {
+    let mut s = String::with_capacity(md.len());
+    let mut in_code_block = false;
+
+    for event in Parser::new_ext(md, Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES) {
+        match &event {
+            Event::Text(text) if !in_code_block => s.push_str(text),
+            Event::Code(code) => s.push_str(code),
+            Event::Start(Tag::CodeBlock(..)) => in_code_block = true,
+            Event::End(Tag::CodeBlock(..)) => {
+                in_code_block = false;
+                s.push(' ');
+            }
+            // keep the words of separate blocks apart, but not those around inline formatting
+            Event::End(Tag::Emphasis)
+            | Event::End(Tag::Strong)
+            | Event::End(Tag::Strikethrough)
+            | Event::End(Tag::Link(..))
+            | Event::End(Tag::Image(..)) => (),
+            Event::End(_) | Event::HardBreak | Event::SoftBreak => s.push(' '),
+            _ => (),
+        }
+    }
+
+    s
+}
+
 crate fn markdown_links(md: &str) -> Vec<(String, Option<Range<usize>>)> {
     if md.is_empty() {
         return vec![];
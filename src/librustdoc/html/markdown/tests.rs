@@ -1,4 +1,4 @@
-use super::plain_text_summary;
+use super::{plain_text, plain_text_summary};
 use super::{ErrorCodes, IdMap, Ignore, LangString, Markdown, MarkdownHtml};
 use rustc_span::edition::{Edition, DEFAULT_EDITION};
 use std::cell::RefCell;
@@ -226,6 +226,23 @@ fn t(input: &str, expect: &str) {
     t("<div>hello</div>", "");
 }
 
+#[test]
+fn test_plain_text() {
+    fn t(input: &str, expect: &str) {
+        let output = plain_text(input);
+        assert_eq!(output, expect, "original: {}", input);
+    }
+
+    t("hello [Rust](https://www.rust-lang.org) :)", "hello Rust :) ");
+    t("**bold**ed", "bolded ");
+    t("code `let x = i32;` ...", "code let x = i32; ... ");
+    t(
+        "# header\n\nfirst paragraph\n\nsecond paragraph",
+        "header first paragraph second paragraph ",
+    );
+    t("before\n\n```\nfn main() {}\n```\n\nafter", "before  after ");
+}
+
 #[test]
 fn test_markdown_html_escape() {
     fn t(input: &str, expect: &str) {
@@ -1,7 +1,9 @@
 use std::collections::BTreeMap;
+use std::iter;
 use std::path::Path;
 
 use rustc_data_structures::fx::FxHashMap;
+use rustc_hir as hir;
 use rustc_span::symbol::sym;
 use serde::Serialize;
 
@@ -9,8 +11,10 @@
 use crate::clean::{self, AttributesExt};
 use crate::formats::cache::Cache;
 use crate::formats::item_type::ItemType;
+use crate::html::render::full_text::{build_full_text_index, doc_text};
 use crate::html::render::{plain_text_summary, shorten};
 use crate::html::render::{Generic, IndexItem, IndexItemFunctionType, RenderType, TypeWithKind};
+use crate::html::render::{IndexFnSignature, SearchType};
 
 /// Indicates where an external crate can be found.
 crate enum ExternalLocation {
@@ -61,27 +65,36 @@
         .unwrap_or(Unknown) // Well, at least we tried.
 }
 
-/// Builds the search index from the collected metadata
-crate fn build_index(krate: &clean::Crate, cache: &mut Cache) -> String {
+/// Builds the search index from the collected metadata, along with the full-text index if
+/// `--full-text-search` was passed.
+crate fn build_index(krate: &clean::Crate, cache: &mut Cache) -> (String, Option<String>) {
     let mut defid_to_pathid = FxHashMap::default();
     let mut crate_items = Vec::with_capacity(cache.search_index.len());
     let mut crate_paths = vec![];
 
-    let Cache { ref mut search_index, ref orphan_impl_items, ref paths, ref mut aliases, .. } =
-        *cache;
+    let Cache {
+        ref mut search_index,
+        ref orphan_impl_items,
+        ref paths,
+        ref mut aliases,
+        full_text_search,
+        ..
+    } = *cache;
 
     // Attach all orphan items to the type's definition if the type
     // has since been learned.
-    for &(did, ref item) in orphan_impl_items {
+    for &(did, ref item, ref signature_parent) in orphan_impl_items {
         if let Some(&(ref fqp, _)) = paths.get(&did) {
             search_index.push(IndexItem {
                 ty: item.type_(),
                 name: item.name.clone().unwrap(),
                 path: fqp[..fqp.len() - 1].join("::"),
                 desc: shorten(plain_text_summary(item.doc_value())),
+                text: if full_text_search { doc_text(item) } else { String::new() },
                 parent: Some(did),
                 parent_idx: None,
                 search_type: get_index_search_type(&item),
+                signature: get_index_signature(&item, signature_parent.as_ref()),
             });
             for alias in item.attrs.get_doc_aliases() {
                 aliases
@@ -130,6 +143,14 @@
         .map(|module| shorten(plain_text_summary(module.doc_value())))
         .unwrap_or_default();
 
+    let full_text_index = if full_text_search {
+        let crate_text = krate.module.as_ref().map(doc_text).unwrap_or_default();
+        let docs = iter::once(&*crate_text).chain(crate_items.iter().map(|item| &*item.text));
+        Some(build_full_text_index(&krate.name, docs))
+    } else {
+        None
+    };
+
     #[derive(Serialize)]
     struct CrateData<'a> {
         doc: String,
@@ -143,10 +164,14 @@ struct CrateData<'a> {
         #[serde(rename = "a")]
         #[serde(skip_serializing_if = "BTreeMap::is_empty")]
         aliases: &'a BTreeMap<String, Vec<usize>>,
+        // Whether the crate has a full-text index for the search to load.
+        #[serde(rename = "f")]
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        full_text: bool,
     }
 
     // Collect the index into a string
-    format!(
+    let index = format!(
         r#""{}":{}"#,
         krate.name,
         serde_json::to_string(&CrateData {
@@ -154,6 +179,7 @@ struct CrateData<'a> {
             items: crate_items,
             paths: crate_paths,
             aliases,
+            full_text: full_text_index.is_some(),
         })
         .expect("failed serde conversion")
         // All these `replace` calls are because we have to go through JS string for JSON content.
@@ -161,7 +187,8 @@ struct CrateData<'a> {
         .replace("'", r"\'")
         // We need to escape double quotes for the JSON.
         .replace("\\\"", "\\\\\"")
-    )
+    );
+    (index, full_text_index)
 }
 
 crate fn get_index_search_type(item: &clean::Item) -> Option<IndexItemFunctionType> {
@@ -231,3 +258,225 @@ fn get_generics(clean_type: &clean::Type) -> Option<Vec<Generic>> {
         if r.is_empty() { None } else { Some(r) }
     })
 }
+
+/// The impl or trait that the methods being indexed belong to, which gives the meaning of
+/// `Self` and of the generic parameters the methods don't declare themselves.
+#[derive(Clone, Debug)]
+crate struct SignatureParent {
+    /// The type an impl is for.
+    self_ty: Option<clean::Type>,
+    /// The name of a trait, which bounds `Self` in the trait's own methods.
+    trait_name: Option<String>,
+    generics: clean::Generics,
+}
+
+impl SignatureParent {
+    crate fn from_impl(i: &clean::Impl) -> SignatureParent {
+        SignatureParent {
+            self_ty: Some(i.for_.clone()),
+            trait_name: None,
+            generics: i.generics.clone(),
+        }
+    }
+
+    crate fn from_trait(name: &str, t: &clean::Trait) -> SignatureParent {
+        SignatureParent {
+            self_ty: None,
+            trait_name: Some(name.to_ascii_lowercase()),
+            generics: t.generics.clone(),
+        }
+    }
+}
+
+/// Builds the full signature of a function for type-based search. Unlike
+/// `get_index_search_type`, this keeps every type of the signature, along with the generic
+/// parameters and their bounds.
+crate fn get_index_signature(
+    item: &clean::Item,
+    parent: Option<&SignatureParent>,
+) -> Option<IndexFnSignature> {
+    let f = match item.kind {
+        clean::FunctionItem(ref f) | clean::MethodItem(ref f, _) | clean::TyMethodItem(ref f) => f,
+        _ => return None,
+    };
+
+    let mut builder = SignatureBuilder {
+        generics: vec![&f.generics],
+        parent,
+        params: Vec::new(),
+        bounds: Vec::new(),
+    };
+    if let Some(parent) = parent {
+        builder.generics.push(&parent.generics);
+    }
+
+    let inputs = f.decl.inputs.values.iter().filter_map(|arg| builder.ty(&arg.type_)).collect();
+    let output = match f.header.asyncness {
+        hir::IsAsync::Async => f.decl.sugared_async_return_type(),
+        hir::IsAsync::NotAsync => f.decl.output.clone(),
+    };
+    let output = match output {
+        clean::FnRetTy::Return(ref ty) => builder.ty(ty),
+        clean::FnRetTy::DefaultReturn => None,
+    };
+    Some(IndexFnSignature { inputs, output, bounds: builder.bounds })
+}
+
+struct SignatureBuilder<'a> {
+    /// The generics of the function, followed by those of its parent.
+    generics: Vec<&'a clean::Generics>,
+    parent: Option<&'a SignatureParent>,
+    /// The names of the generic parameters found so far, in the order they were first used.
+    /// `impl Trait` types and the like are parameters without a name.
+    params: Vec<Option<String>>,
+    bounds: Vec<Vec<SearchType>>,
+}
+
+impl SignatureBuilder<'_> {
+    fn ty(&mut self, ty: &clean::Type) -> Option<SearchType> {
+        fn named(name: &str, generics: Vec<SearchType>) -> Option<SearchType> {
+            Some(SearchType::Path { name: name.to_string(), generics })
+        }
+
+        match *ty {
+            clean::ResolvedPath { ref path, is_generic: true, .. } => {
+                let name = path.segments.iter().map(|s| &*s.name).collect::<Vec<_>>().join("::");
+                Some(self.param(&name))
+            }
+            clean::ResolvedPath { ref path, .. } => {
+                let segment = path.segments.last()?;
+                let generics = self.generic_args(&segment.args);
+                named(&segment.name.to_ascii_lowercase(), generics)
+            }
+            clean::Generic(ref name) => {
+                match self.parent.and_then(|parent| parent.self_ty.as_ref()) {
+                    Some(self_ty) if name == "Self" => self.ty(self_ty),
+                    _ => Some(self.param(name)),
+                }
+            }
+            clean::Primitive(p) => named(p.as_str(), Vec::new()),
+            clean::BareFunction(ref f) => {
+                let mut generics: Vec<_> =
+                    f.decl.inputs.values.iter().filter_map(|arg| self.ty(&arg.type_)).collect();
+                if let clean::FnRetTy::Return(ref output) = f.decl.output {
+                    generics.extend(self.ty(output));
+                }
+                named("fn", generics)
+            }
+            clean::Tuple(ref types) if types.is_empty() => named("unit", Vec::new()),
+            clean::Tuple(ref types) => {
+                let generics = types.iter().filter_map(|ty| self.ty(ty)).collect();
+                named("tuple", generics)
+            }
+            clean::Slice(ref ty) => {
+                let generics = self.ty(ty).into_iter().collect();
+                named("slice", generics)
+            }
+            clean::Array(ref ty, _) => {
+                let generics = self.ty(ty).into_iter().collect();
+                named("array", generics)
+            }
+            clean::Never => named("never", Vec::new()),
+            // References and pointers are left out, so that searching for `str` finds
+            // functions taking `&str` too.
+            clean::RawPointer(_, ref ty) | clean::BorrowedRef { type_: ref ty, .. } => self.ty(ty),
+            clean::QPath { ref name, ref self_type, .. } => match **self_type {
+                clean::Generic(ref param) => Some(self.param(&format!("{}::{}", param, name))),
+                _ => Some(SearchType::Param(self.new_param(None, Vec::new()))),
+            },
+            clean::ImplTrait(ref bounds) => {
+                let bounds = self.convert_bounds(bounds);
+                Some(SearchType::Param(self.new_param(None, bounds)))
+            }
+            clean::Infer => None,
+        }
+    }
+
+    fn generic_args(&mut self, args: &clean::GenericArgs) -> Vec<SearchType> {
+        let mut generics = Vec::new();
+        match *args {
+            clean::GenericArgs::AngleBracketed { ref args, ref bindings } => {
+                for arg in args {
+                    if let clean::GenericArg::Type(ref ty) = *arg {
+                        generics.extend(self.ty(ty));
+                    }
+                }
+                // Associated type bindings, like the `Item = T` of `Iterator<Item = T>`, are
+                // treated like generic arguments.
+                for binding in bindings {
+                    match binding.kind {
+                        clean::TypeBindingKind::Equality { ref ty } => generics.extend(self.ty(ty)),
+                        clean::TypeBindingKind::Constraint { ref bounds } => {
+                            let bounds = self.convert_bounds(bounds);
+                            generics.push(SearchType::Param(self.new_param(None, bounds)));
+                        }
+                    }
+                }
+            }
+            clean::GenericArgs::Parenthesized { ref inputs, ref output } => {
+                generics.extend(inputs.iter().filter_map(|ty| self.ty(ty)));
+                if let Some(ref output) = *output {
+                    generics.extend(self.ty(output));
+                }
+            }
+        }
+        generics
+    }
+
+    fn convert_bounds(&mut self, bounds: &[clean::GenericBound]) -> Vec<SearchType> {
+        let mut converted = Vec::new();
+        for bound in bounds {
+            // `?Sized` doesn't tell anything about what the parameter can be.
+            if let clean::GenericBound::TraitBound(ref poly, hir::TraitBoundModifier::None) = *bound
+            {
+                converted.extend(self.ty(&poly.trait_));
+            }
+        }
+        converted
+    }
+
+    /// Returns the named generic parameter, looking up its bounds the first time it's used.
+    fn param(&mut self, name: &str) -> SearchType {
+        if let Some(idx) = self.params.iter().position(|p| p.as_deref() == Some(name)) {
+            return SearchType::Param(idx);
+        }
+        // The parameter is added before its bounds are converted, since they can refer to it.
+        let idx = self.new_param(Some(name.to_string()), Vec::new());
+
+        let mut bounds = Vec::new();
+        for generics in self.generics.clone() {
+            for param in &generics.params {
+                if let clean::GenericParamDefKind::Type { bounds: ref b, .. } = param.kind {
+                    if param.name == name {
+                        bounds.extend(self.convert_bounds(b));
+                    }
+                }
+            }
+            for predicate in &generics.where_predicates {
+                if let clean::WherePredicate::BoundPredicate {
+                    ty: clean::Generic(ref ty),
+                    bounds: ref b,
+                } = *predicate
+                {
+                    if ty == name {
+                        bounds.extend(self.convert_bounds(b));
+                    }
+                }
+            }
+        }
+        if name == "Self" {
+            if let Some(trait_name) = self.parent.and_then(|parent| parent.trait_name.as_ref()) {
+                bounds.push(SearchType::Path { name: trait_name.clone(), generics: Vec::new() });
+            }
+        }
+        self.bounds[idx] = bounds;
+        SearchType::Param(idx)
+    }
+
+    /// Adds a generic parameter, and returns its index.
+    fn new_param(&mut self, name: Option<String>, bounds: Vec<SearchType>) -> usize {
+        self.params.push(name);
+        self.bounds.push(bounds);
+        self.params.len() - 1
+    }
+}
@@ -0,0 +1,106 @@
+//! The full-text search index, which lets the search find items by the words in their docs rather
+//! than only by their names.
+//!
+//! The index of each crate is written to its own file, `full-text-index.js` in the crate's
+//! directory, which the search in `main.js` loads the first time it's used. For every word, the
+//! index lists the items whose docs contain it and how many times, which the search uses to rank
+//! the items with BM25.
+
+use std::collections::BTreeMap;
+
+use rustc_data_structures::fx::FxHashMap;
+use serde::Serialize;
+
+use crate::clean;
+use crate::html::markdown;
+
+/// Words that are in the docs of so many items that they aren't worth indexing.
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "if", "in", "into", "is", "it",
+    "its", "of", "on", "or", "that", "the", "this", "to", "was", "which", "will", "with",
+];
+
+/// Splits some text into the words of the index, which are lowercased and have plurals turned
+/// into the singular. This has to give the same words as `fullTextWords` in `main.js`, which is
+/// used to split up the queries.
+crate fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| c.is_whitespace() || c.is_ascii_punctuation())
+        .map(str::to_lowercase)
+        .filter(|word| word.chars().count() >= 2 && !STOP_WORDS.contains(&word.as_str()))
+        .map(stem)
+}
+
+fn stem(mut word: String) -> String {
+    let len = word.chars().count();
+    if len > 4 && (word.ends_with("ies") || word.ends_with("ied")) {
+        word.truncate(word.len() - 3);
+        word.push('y');
+    } else if len > 3
+        && word.ends_with('s')
+        && !word.ends_with("ss")
+        && !word.ends_with("us")
+        && !word.ends_with("is")
+    {
+        word.pop();
+    }
+    word
+}
+
+/// The plain text of the docs of an item, which are what its words in the index come from.
+crate fn doc_text(item: &clean::Item) -> String {
+    item.collapsed_doc_value().map(|doc| markdown::plain_text(&doc)).unwrap_or_default()
+}
+
+#[derive(Serialize)]
+struct FullTextIndex {
+    /// The number of words in the docs of each item.
+    #[serde(rename = "l")]
+    lengths: Vec<usize>,
+    /// The items that have each word in their docs, as pairs of the distance from the previous
+    /// item in the list and the number of times the item has the word.
+    #[serde(rename = "t")]
+    terms: BTreeMap<String, Vec<usize>>,
+}
+
+/// Builds the full-text index of a crate, as a script which hands it over to the search.
+///
+/// `docs` are the plain text of the docs of the crate itself, followed by those of its items in
+/// the order they have in the search index.
+crate fn build_full_text_index<'a>(
+    crate_name: &str,
+    docs: impl Iterator<Item = &'a str>,
+) -> String {
+    let mut lengths = Vec::new();
+    let mut postings: BTreeMap<String, Vec<(usize, usize)>> = BTreeMap::new();
+    for (item, doc) in docs.enumerate() {
+        let mut counts = FxHashMap::default();
+        for word in words(doc) {
+            *counts.entry(word).or_insert(0) += 1;
+        }
+        lengths.push(counts.values().sum());
+        for (word, count) in counts {
+            postings.entry(word).or_default().push((item, count));
+        }
+    }
+
+    let terms = postings
+        .into_iter()
+        .map(|(word, items)| {
+            let mut last = 0;
+            let mut encoded = Vec::with_capacity(items.len() * 2);
+            for (item, count) in items {
+                encoded.push(item - last);
+                encoded.push(count);
+                last = item;
+            }
+            (word, encoded)
+        })
+        .collect();
+
+    let index = serde_json::to_string(&FullTextIndex { lengths, terms })
+        .expect("failed serde conversion")
+        // These `replace` calls are because the JSON goes through a JS string.
+        .replace(r"\", r"\\")
+        .replace("'", r"\'");
+    format!("registerFullTextIndex(\"{}\", JSON.parse('{}'));\n", crate_name, index)
+}
@@ -26,6 +26,7 @@
 //! both occur before the crate is rendered.
 
 crate mod cache;
+crate mod full_text;
 
 #[cfg(test)]
 mod tests;
@@ -206,9 +207,13 @@ impl SharedContext {
     crate name: String,
     crate path: String,
     crate desc: String,
+    /// The plain text of the docs, for the full-text search index. This is empty unless
+    /// `--full-text-search` was passed.
+    crate text: String,
     crate parent: Option<DefId>,
     crate parent_idx: Option<usize>,
     crate search_type: Option<IndexItemFunctionType>,
+    crate signature: Option<IndexFnSignature>,
 }
 
 impl Serialize for IndexItem {
@@ -223,7 +228,15 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
             self.name
         );
 
-        (self.ty, &self.name, &self.path, &self.desc, self.parent_idx, &self.search_type)
+        (
+            self.ty,
+            &self.name,
+            &self.path,
+            &self.desc,
+            self.parent_idx,
+            &self.search_type,
+            &self.signature,
+        )
             .serialize(serializer)
     }
 }
@@ -314,6 +327,58 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     }
 }
 
+/// A type in the full signature of a function, which type-based searches are unified with.
+#[derive(Debug, Clone)]
+crate enum SearchType {
+    /// A named type, like `Vec<T>` or `usize`. Built-in type constructors use the names of
+    /// their primitive pages, like `slice` for `[T]` or `tuple` for `(A, B)`.
+    Path { name: String, generics: Vec<SearchType> },
+    /// A generic parameter, given by its position in the `bounds` of the signature.
+    Param(usize),
+}
+
+impl Serialize for SearchType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            SearchType::Path { ref name, ref generics } if generics.is_empty() => {
+                name.serialize(serializer)
+            }
+            SearchType::Path { ref name, ref generics } => (name, generics).serialize(serializer),
+            SearchType::Param(idx) => idx.serialize(serializer),
+        }
+    }
+}
+
+/// The full signature of a function in the search index, with its generic parameters and
+/// the traits they're bounded by.
+#[derive(Debug)]
+crate struct IndexFnSignature {
+    crate inputs: Vec<SearchType>,
+    crate output: Option<SearchType>,
+    crate bounds: Vec<Vec<SearchType>>,
+}
+
+impl Serialize for IndexFnSignature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Trailing empty fields are left out to keep the index small.
+        let mut seq = serializer.serialize_seq(None)?;
+        seq.serialize_element(&self.inputs)?;
+        if self.output.is_some() || !self.bounds.is_empty() {
+            seq.serialize_element(&self.output)?;
+        }
+        if !self.bounds.is_empty() {
+            seq.serialize_element(&self.bounds)?;
+        }
+        seq.end()
+    }
+}
+
 #[derive(Debug)]
 crate struct TypeWithKind {
     ty: RenderType,
@@ -486,9 +551,10 @@ fn init(
         let dst = output;
         scx.ensure_dir(&dst)?;
         krate = sources::render(&dst, &mut scx, krate)?;
+        sources::render_examples(&dst, &mut scx, &cache.call_locations);
 
         // Build our search index
-        let index = build_index(&krate, cache);
+        let (index, full_text_index) = build_index(&krate, cache);
 
         let cache = Arc::new(cache);
         let mut cx = Context {
@@ -505,7 +571,7 @@ fn init(
 
         // Write shared runs within a flock; disable thread dispatching of IO temporarily.
         Arc::get_mut(&mut cx.shared).unwrap().fs.set_sync_only(true);
-        write_shared(&cx, &krate, index, &md_opts, &cache)?;
+        write_shared(&cx, &krate, index, full_text_index, &md_opts, &cache)?;
         Arc::get_mut(&mut cx.shared).unwrap().fs.set_sync_only(false);
         Ok((cx, krate))
     }
@@ -681,6 +747,7 @@ fn write_shared(
     cx: &Context,
     krate: &clean::Crate,
     search_index: String,
+    full_text_index: Option<String>,
     options: &RenderOptions,
     cache: &Cache,
 ) -> Result<(), Error> {
@@ -1040,6 +1107,12 @@ fn to_json_string(&self) -> String {
         v.push_str("\\\n}');\naddSearchOptions(searchIndex);initSearch(searchIndex);");
         cx.shared.fs.write(&dst, &v)?;
     }
+    if let Some(full_text_index) = full_text_index {
+        let dir = cx.dst.join(&krate.name);
+        cx.shared.ensure_dir(&dir)?;
+        let dst = dir.join(&format!("full-text-index{}.js", cx.shared.resource_suffix));
+        cx.shared.fs.write(&dst, full_text_index.as_bytes())?;
+    }
     if options.enable_index_page {
         if let Some(index_page) = options.index_page.clone() {
             let mut md_opts = options.clone();
@@ -1848,6 +1921,52 @@ fn document(w: &mut Buffer, cx: &Context, item: &clean::Item, parent: Option<&cl
     document_full(w, item, cx, "", false);
 }
 
+/// Renders the calls to a function that were scraped from other crates, like the crate's
+/// examples and tests.
+fn render_call_locations(w: &mut Buffer, cx: &Context, item: &clean::Item) {
+    let cache = cache();
+    let call_locations = match cache.call_locations.get(&item.def_id) {
+        Some(call_locations) if !call_locations.is_empty() => call_locations,
+        _ => return,
+    };
+    write!(w, "<div class=\"scraped-examples\"><h4>Examples found in repository</h4>");
+    for data in call_locations {
+        // The source page only exists if the file could be read when rendering sources.
+        let href = cx
+            .shared
+            .local_sources
+            .get(&data.file)
+            .map(|href| format!("{}src/{}/{}", cx.root_path(), data.krate, href));
+        for location in &data.locations {
+            let lines = location.snippet.lines().count();
+            write!(
+                w,
+                "<div class=\"scraped-example\"><div class=\"scraped-example-title\">{}",
+                Escape(&data.display_name)
+            );
+            if let Some(ref href) = href {
+                write!(
+                    w,
+                    " <a class=\"srclink\" href=\"{}#{}-{}\">[src]</a>",
+                    href,
+                    location.first_line,
+                    location.first_line + lines.saturating_sub(1)
+                );
+            }
+            write!(
+                w,
+                "</div>{}</div>",
+                highlight::render_with_line_numbers(
+                    location.snippet.clone(),
+                    location.first_line,
+                    &location.call_lines()
+                )
+            );
+        }
+    }
+    write!(w, "</div>");
+}
+
 /// Render md_text as markdown.
 fn render_markdown(
     w: &mut Buffer,
@@ -2434,7 +2553,8 @@ fn item_function(w: &mut Buffer, cx: &Context, it: &clean::Item, f: &clean::Func
             .print(),
         spotlight = spotlight_decl(&f.decl),
     );
-    document(w, cx, it, None)
+    document(w, cx, it, None);
+    render_call_locations(w, cx, it);
 }
 
 fn render_implementor(
@@ -2640,6 +2760,7 @@ fn trait_item(w: &mut Buffer, cx: &Context, m: &clean::Item, t: &clean::Item, ca
         write_srclink(cx, m, w, cache);
         write!(w, "</h3>");
         document(w, cx, m, Some(t));
+        render_call_locations(w, cx, m);
     }
 
     if !types.is_empty() {
@@ -3407,6 +3528,23 @@ fn render_assoc_items(
     it: DefId,
     what: AssocItemRender<'_>,
     cache: &Cache,
+) {
+    let mut derefs = FxHashSet::default();
+    derefs.insert(it);
+    render_assoc_items_inner(w, cx, containing_item, it, what, &mut derefs, cache)
+}
+
+/// Renders the associated items of `it`, following its `Deref` impl to the methods of the target
+/// and on through the target's own `Deref` impl. `derefs` holds the types whose methods are
+/// already on the page, which keeps cycles of `Deref` impls from being followed forever.
+fn render_assoc_items_inner(
+    w: &mut Buffer,
+    cx: &Context,
+    containing_item: &clean::Item,
+    it: DefId,
+    what: AssocItemRender<'_>,
+    derefs: &mut FxHashSet<DefId>,
+    cache: &Cache,
 ) {
     info!(
         "Documenting associated items of {}",
@@ -3428,15 +3566,16 @@ fn render_assoc_items(
                 );
                 RenderMode::Normal
             }
-            AssocItemRender::DerefFor { trait_, type_, deref_mut_ } => {
+            AssocItemRender::DerefFor { trait_, type_, deref_mut_, id } => {
                 write!(
                     w,
-                    "<h2 id=\"deref-methods\" class=\"small-section-header\">\
+                    "<h2 id=\"{id}\" class=\"small-section-header\">\
                          Methods from {}&lt;Target = {}&gt;\
-                         <a href=\"#deref-methods\" class=\"anchor\"></a>\
+                         <a href=\"#{id}\" class=\"anchor\"></a>\
                      </h2>",
                     trait_.print(),
-                    type_.print()
+                    type_.print(),
+                    id = id
                 );
                 RenderMode::ForDeref { mut_: deref_mut_ }
             }
@@ -3459,18 +3598,26 @@ fn render_assoc_items(
             );
         }
     }
-    if let AssocItemRender::DerefFor { .. } = what {
-        return;
-    }
     if !traits.is_empty() {
         let deref_impl =
             traits.iter().find(|t| t.inner_impl().trait_.def_id() == cache.deref_trait_did);
         if let Some(impl_) = deref_impl {
-            let has_deref_mut =
-                traits.iter().any(|t| t.inner_impl().trait_.def_id() == cache.deref_mut_trait_did);
-            render_deref_methods(w, cx, impl_, containing_item, has_deref_mut, cache);
+            // Methods taking `&mut self` can only be reached through a chain of `DerefMut` impls.
+            let chain_deref_mut = match what {
+                AssocItemRender::All => true,
+                AssocItemRender::DerefFor { deref_mut_, .. } => deref_mut_,
+            };
+            let has_deref_mut = chain_deref_mut
+                && traits
+                    .iter()
+                    .any(|t| t.inner_impl().trait_.def_id() == cache.deref_mut_trait_did);
+            render_deref_methods(w, cx, impl_, containing_item, has_deref_mut, derefs, cache);
         }
-
+    }
+    if let AssocItemRender::DerefFor { .. } = what {
+        return;
+    }
+    if !traits.is_empty() {
         let (synthetic, concrete): (Vec<&&Impl>, Vec<&&Impl>) =
             traits.iter().partition(|t| t.inner_impl().synthetic);
         let (blanket_impl, concrete): (Vec<&&Impl>, _) =
@@ -3524,34 +3671,51 @@ fn render_deref_methods(
     impl_: &Impl,
     container_item: &clean::Item,
     deref_mut: bool,
+    derefs: &mut FxHashSet<DefId>,
     cache: &Cache,
 ) {
     let deref_type = impl_.inner_impl().trait_.as_ref().unwrap();
-    let (target, real_target) = impl_
-        .inner_impl()
-        .items
-        .iter()
-        .find_map(|item| match item.kind {
-            clean::TypedefItem(ref t, true) => Some(match *t {
-                clean::Typedef { item_type: Some(ref type_), .. } => (type_, &t.type_),
-                _ => (&t.type_, &t.type_),
-            }),
-            _ => None,
-        })
-        .expect("Expected associated type binding");
-    let what =
-        AssocItemRender::DerefFor { trait_: deref_type, type_: real_target, deref_mut_: deref_mut };
-    if let Some(did) = target.def_id() {
-        render_assoc_items(w, cx, container_item, did, what, cache);
-    } else {
-        if let Some(prim) = target.primitive_type() {
-            if let Some(&did) = cache.primitive_locations.get(&prim) {
-                render_assoc_items(w, cx, container_item, did, what, cache);
-            }
+    let (target, real_target) = deref_target(impl_).expect("Expected associated type binding");
+    let did = target.def_id().or_else(|| {
+        target.primitive_type().and_then(|prim| cache.primitive_locations.get(&prim).cloned())
+    });
+    if let Some(did) = did {
+        // Only the containing type has been visited when this is the first `Deref` target.
+        let id = deref_methods_id(real_target, derefs.len() == 1);
+        if derefs.insert(did) {
+            let what = AssocItemRender::DerefFor {
+                trait_: deref_type,
+                type_: real_target,
+                deref_mut_: deref_mut,
+                id: &id,
+            };
+            render_assoc_items_inner(w, cx, container_item, did, what, derefs, cache);
         }
     }
 }
 
+/// Returns the type that a `Deref` impl has as its `Target`, once as the type itself and once as
+/// it was written, which differ when the target is a type alias.
+fn deref_target(impl_: &Impl) -> Option<(&clean::Type, &clean::Type)> {
+    impl_.inner_impl().items.iter().find_map(|item| match item.kind {
+        clean::TypedefItem(ref t, true) => Some(match *t {
+            clean::Typedef { item_type: Some(ref type_), .. } => (type_, &t.type_),
+            _ => (&t.type_, &t.type_),
+        }),
+        _ => None,
+    })
+}
+
+/// The id of the section with the methods of a `Deref` target. The first target of the chain keeps
+/// the plain `deref-methods` id, and those further down are told apart by their type.
+fn deref_methods_id(real_target: &clean::Type, first: bool) -> String {
+    if first {
+        "deref-methods".to_owned()
+    } else {
+        format!("deref-methods-{}", small_url_encode(&format!("{:#}", real_target.print())))
+    }
+}
+
 fn should_render_item(item: &clean::Item, deref_mut_: bool) -> bool {
     let self_type_opt = match item.kind {
         clean::MethodItem(ref method, _) => method.decl.self_type(),
@@ -3814,6 +3978,7 @@ fn doc_impl_item(
                     document_stability(w, cx, item, is_hidden, Some(parent));
                     if show_def_docs {
                         document_full(w, item, cx, "", is_hidden);
+                        render_call_locations(w, cx, item);
                     }
                 }
             } else {
@@ -4182,53 +4347,9 @@ fn sidebar_assoc_items(it: &clean::Item) -> String {
                 .filter(|i| i.inner_impl().trait_.is_some())
                 .find(|i| i.inner_impl().trait_.def_id() == c.deref_trait_did)
             {
-                if let Some((target, real_target)) =
-                    impl_.inner_impl().items.iter().find_map(|item| match item.kind {
-                        clean::TypedefItem(ref t, true) => Some(match *t {
-                            clean::Typedef { item_type: Some(ref type_), .. } => (type_, &t.type_),
-                            _ => (&t.type_, &t.type_),
-                        }),
-                        _ => None,
-                    })
-                {
-                    let deref_mut = v
-                        .iter()
-                        .filter(|i| i.inner_impl().trait_.is_some())
-                        .any(|i| i.inner_impl().trait_.def_id() == c.deref_mut_trait_did);
-                    let inner_impl = target
-                        .def_id()
-                        .or(target
-                            .primitive_type()
-                            .and_then(|prim| c.primitive_locations.get(&prim).cloned()))
-                        .and_then(|did| c.impls.get(&did));
-                    if let Some(impls) = inner_impl {
-                        out.push_str("<a class=\"sidebar-title\" href=\"#deref-methods\">");
-                        out.push_str(&format!(
-                            "Methods from {}&lt;Target={}&gt;",
-                            Escape(&format!(
-                                "{:#}",
-                                impl_.inner_impl().trait_.as_ref().unwrap().print()
-                            )),
-                            Escape(&format!("{:#}", real_target.print()))
-                        ));
-                        out.push_str("</a>");
-                        let mut ret = impls
-                            .iter()
-                            .filter(|i| i.inner_impl().trait_.is_none())
-                            .flat_map(|i| {
-                                get_methods(i.inner_impl(), true, &mut used_links, deref_mut)
-                            })
-                            .collect::<Vec<_>>();
-                        // We want links' order to be reproducible so we don't use unstable sort.
-                        ret.sort();
-                        if !ret.is_empty() {
-                            out.push_str(&format!(
-                                "<div class=\"sidebar-links\">{}</div>",
-                                ret.join("")
-                            ));
-                        }
-                    }
-                }
+                let mut derefs = FxHashSet::default();
+                derefs.insert(it.def_id);
+                sidebar_deref_methods(&mut out, impl_, v, &mut used_links, &mut derefs, true);
             }
             let format_impls = |impls: Vec<&Impl>| {
                 let mut links = FxHashSet::default();
@@ -4296,6 +4417,67 @@ fn sidebar_assoc_items(it: &clean::Item) -> String {
     out
 }
 
+/// Adds the methods of the target of a `Deref` impl to the sidebar, and then those of the targets
+/// further down the chain, in the same order as `render_deref_methods` puts them on the page.
+fn sidebar_deref_methods(
+    out: &mut String,
+    impl_: &Impl,
+    v: &[Impl],
+    used_links: &mut FxHashSet<String>,
+    derefs: &mut FxHashSet<DefId>,
+    deref_mut: bool,
+) {
+    let c = cache();
+    let (target, real_target) = match deref_target(impl_) {
+        Some(target) => target,
+        None => return,
+    };
+    let deref_mut = deref_mut
+        && v.iter()
+            .filter(|i| i.inner_impl().trait_.is_some())
+            .any(|i| i.inner_impl().trait_.def_id() == c.deref_mut_trait_did);
+    let did = target.def_id().or_else(|| {
+        target.primitive_type().and_then(|prim| c.primitive_locations.get(&prim).cloned())
+    });
+    // Only the containing type has been visited when this is the first `Deref` target.
+    let first = derefs.len() == 1;
+    let impls = match did.filter(|&did| derefs.insert(did)).and_then(|did| c.impls.get(&did)) {
+        Some(impls) => impls,
+        None => return,
+    };
+
+    if impls.iter().any(|i| i.inner_impl().trait_.is_none()) {
+        out.push_str(&format!(
+            "<a class=\"sidebar-title\" href=\"#{}\">",
+            deref_methods_id(real_target, first)
+        ));
+        out.push_str(&format!(
+            "Methods from {}&lt;Target={}&gt;",
+            Escape(&format!("{:#}", impl_.inner_impl().trait_.as_ref().unwrap().print())),
+            Escape(&format!("{:#}", real_target.print()))
+        ));
+        out.push_str("</a>");
+        let mut ret = impls
+            .iter()
+            .filter(|i| i.inner_impl().trait_.is_none())
+            .flat_map(|i| get_methods(i.inner_impl(), true, used_links, deref_mut))
+            .collect::<Vec<_>>();
+        // We want links' order to be reproducible so we don't use unstable sort.
+        ret.sort();
+        if !ret.is_empty() {
+            out.push_str(&format!("<div class=\"sidebar-links\">{}</div>", ret.join("")));
+        }
+    }
+
+    if let Some(next) = impls
+        .iter()
+        .filter(|i| i.inner_impl().trait_.is_some())
+        .find(|i| i.inner_impl().trait_.def_id() == c.deref_trait_did)
+    {
+        sidebar_deref_methods(out, next, impls, used_links, derefs, deref_mut);
+    }
+}
+
 fn sidebar_struct(buf: &mut Buffer, it: &clean::Item, s: &clean::Struct) {
     let mut sidebar = String::new();
     let fields = get_struct_fields_name(&s.fields);
@@ -38,3 +38,20 @@ fn test_name_sorting() {
     sorted.sort_by(|&l, r| compare_names(l, r));
     assert_eq!(names, sorted);
 }
+
+#[test]
+fn test_full_text_words() {
+    let text = "Retries the request with an exponential back-off, like `retry_after`.";
+    let words: Vec<_> = full_text::words(text).collect();
+    assert_eq!(words, ["retry", "request", "exponential", "back", "off", "like", "retry", "after"]);
+}
+
+#[test]
+fn test_full_text_index() {
+    let docs = ["Retries requests.", "", "Sends a request, then retries it."];
+    assert_eq!(
+        full_text::build_full_text_index("foo", docs.iter().copied()),
+        "registerFullTextIndex(\"foo\", JSON.parse('{\"l\":[2,0,4],\"t\":{\"request\":[0,1,2,1],\
+         \"retry\":[0,1,2,1],\"send\":[2,1],\"then\":[2,1]}}'));\n"
+    );
+}
@@ -6,8 +6,11 @@
 use crate::html::highlight;
 use crate::html::layout;
 use crate::html::render::{SharedContext, BASIC_KEYWORDS};
-use rustc_hir::def_id::LOCAL_CRATE;
+use crate::scrape_examples::CallData;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
 use rustc_span::source_map::FileName;
+use rustc_span::RealFileName;
 use std::ffi::OsStr;
 use std::fs;
 use std::path::{Component, Path, PathBuf};
@@ -20,16 +23,45 @@
     info!("emitting source files");
     let dst = dst.join("src").join(&krate.name);
     scx.ensure_dir(&dst)?;
-    let mut folder = SourceCollector { dst, scx };
+    let src_root = scx.src_root.clone();
+    let mut folder = SourceCollector { dst, src_root, scx };
     Ok(folder.fold_crate(krate))
 }
 
+/// Renders the files that scraped examples were found in, so that the examples can link to
+/// them. Files that can't be read are skipped, and their examples aren't linked.
+crate fn render_examples(
+    dst: &Path,
+    scx: &mut SharedContext,
+    call_locations: &FxHashMap<DefId, Vec<CallData>>,
+) {
+    if !scx.include_sources {
+        return;
+    }
+    info!("emitting source files of scraped examples");
+    for data in call_locations.values().flatten() {
+        let dst = dst.join("src").join(&data.krate);
+        let src_root = data.src_root.clone();
+        let mut collector = SourceCollector { dst, src_root, scx };
+        let filename = FileName::Real(RealFileName::Named(data.file.clone()));
+        if let Err(e) = collector.emit_source(&filename) {
+            println!(
+                "warning: failed to render the source of scraped examples `{}`: {}",
+                data.display_name, e
+            );
+        }
+    }
+}
+
 /// Helper struct to render all source code to HTML pages
 struct SourceCollector<'a> {
     scx: &'a mut SharedContext,
 
     /// Root destination to place all HTML output into
     dst: PathBuf,
+
+    /// The directory that the paths of the source pages are made relative to.
+    src_root: PathBuf,
 }
 
 impl<'a> DocFolder for SourceCollector<'a> {
@@ -92,7 +124,7 @@ fn emit_source(&mut self, filename: &FileName) -> Result<(), Error> {
         let mut cur = self.dst.clone();
         let mut root_path = String::from("../../");
         let mut href = String::new();
-        clean_path(&self.scx.src_root, &p, false, |component| {
+        clean_path(&self.src_root, &p, false, |component| {
             cur.push(component);
             root_path.push_str("../");
             href.push_str(&component.to_string_lossy());
@@ -1,5 +1,5 @@
 // From rust:
-/* global ALIASES, currentCrate, rootPath */
+/* global ALIASES, currentCrate, resourcesSuffix, rootPath */
 
 // Local js definitions:
 /* global addClass, getCurrentValue, hasClass */
@@ -631,6 +631,16 @@ function defocusSearchBar() {
         var NO_TYPE_FILTER = -1;
         var currentResults, index, searchIndex;
         var ALIASES = {};
+        // The crates that have a full-text index, with where their items start in `searchIndex`
+        // and the index itself once it has been loaded.
+        var FULL_TEXT = {};
+        var fullTextTimeout = null;
+        // Has to be the same as `STOP_WORDS` in `html/render/full_text.rs`.
+        var FULL_TEXT_STOP_WORDS = [
+            "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "if", "in", "into",
+            "is", "it", "its", "of", "on", "or", "that", "the", "this", "to", "was", "which",
+            "will", "with",
+        ];
         var params = getQueryStringParams();
 
         // Populate search bar with query string search term when provided,
@@ -1056,6 +1066,324 @@ function defocusSearchBar() {
                 return false;
             }
 
+            // Parses a query like `Vec<T>, usize -> Option<T>` into its inputs and output, or
+            // returns null if it isn't a valid signature. The inputs are null if they're left
+            // empty or are `*`, and so is the output; both can't be missing.
+            //
+            // Each type is either `{name, generics}`, `{variable}` for a type variable (written
+            // as a single capital letter, like `T` or `U2`), or `{any: true}` for `_` and `*`.
+            function parseSignatureQuery(raw) {
+                var tokens = raw.match(/->|::|'?\w+|[^\s\w]/g) || [];
+                var pos = 0;
+
+                function eat(token) {
+                    if (tokens[pos] === token) {
+                        pos += 1;
+                        return true;
+                    }
+                    return false;
+                }
+
+                function parseList(end) {
+                    var types = [];
+                    while (pos < tokens.length && tokens[pos] !== end) {
+                        if (tokens[pos].charAt(0) === "'") {
+                            // lifetimes don't matter for the search
+                            pos += 1;
+                        } else {
+                            // associated type bindings, like `Item = T`, are kept as generics
+                            if (tokens[pos + 1] === "=") {
+                                pos += 2;
+                            }
+                            var type = parseType();
+                            if (type === null) {
+                                return null;
+                            }
+                            types.push(type);
+                        }
+                        if (!eat(",")) {
+                            break;
+                        }
+                    }
+                    return types;
+                }
+
+                function parseType() {
+                    var token = tokens[pos++];
+                    var generics;
+                    if (token === undefined) {
+                        return null;
+                    } else if (token === "&") {
+                        if (tokens[pos] !== undefined && tokens[pos].charAt(0) === "'") {
+                            pos += 1;
+                        }
+                        eat("mut");
+                        return parseType();
+                    } else if (token === "*") {
+                        // `*const T` and `*mut T` are raw pointers, `*` alone matches anything
+                        return eat("const") || eat("mut") ? parseType() : {any: true};
+                    } else if (token === "_") {
+                        return {any: true};
+                    } else if (token === "!") {
+                        return {name: "never", generics: []};
+                    } else if (token === "[") {
+                        generics = [parseType()];
+                        var name = "slice";
+                        if (eat(";")) {
+                            name = "array";
+                            while (pos < tokens.length && tokens[pos] !== "]") {
+                                pos += 1;
+                            }
+                        }
+                        if (generics[0] === null || !eat("]")) {
+                            return null;
+                        }
+                        return {name: name, generics: generics};
+                    } else if (token === "(") {
+                        generics = parseList(")");
+                        if (generics === null || !eat(")")) {
+                            return null;
+                        } else if (generics.length === 0) {
+                            return {name: "unit", generics: []};
+                        } else if (generics.length === 1) {
+                            return generics[0];
+                        }
+                        return {name: "tuple", generics: generics};
+                    } else if (token === "impl" || token === "dyn") {
+                        return parseType();
+                    } else if (!/^\w+$/.test(token)) {
+                        return null;
+                    }
+
+                    // only the last segment of a path is indexed
+                    while (eat("::")) {
+                        token = tokens[pos++];
+                        if (token === undefined || !/^\w+$/.test(token)) {
+                            return null;
+                        }
+                    }
+                    generics = [];
+                    if (eat("<")) {
+                        generics = parseList(">");
+                        if (generics === null || !eat(">")) {
+                            return null;
+                        }
+                    } else if (eat("(")) {
+                        // `fn(A) -> B` and `Fn(A) -> B`
+                        generics = parseList(")");
+                        if (generics === null || !eat(")")) {
+                            return null;
+                        }
+                        if (eat("->")) {
+                            var output = parseType();
+                            if (output === null) {
+                                return null;
+                            }
+                            generics.push(output);
+                        }
+                    }
+                    if (generics.length === 0 && /^[A-Z][0-9]*$/.test(token)) {
+                        return {variable: token};
+                    }
+                    return {name: token.toLowerCase(), generics: generics};
+                }
+
+                function parseSide(sideTokens) {
+                    if (sideTokens.length === 0 || sideTokens.join("") === "*") {
+                        return null;
+                    }
+                    tokens = sideTokens;
+                    pos = 0;
+                    var types = parseList(undefined);
+                    if (types === null || pos !== tokens.length) {
+                        return undefined;
+                    }
+                    return types;
+                }
+
+                // The inputs end at the first `->` that isn't nested in a type, like the one in
+                // `Box<dyn Fn(u8) -> u8>`, and doesn't give the output of a function type, like
+                // the one in `impl Fn(u8) -> u8`. If there's none, the first `->` is used.
+                var brackets = [];
+                var arrow = -1;
+                var firstArrow = -1;
+                for (var x = 0; x < tokens.length && arrow === -1; ++x) {
+                    if (tokens[x] === "<" || tokens[x] === "(" || tokens[x] === "[") {
+                        // whether the bracket starts the arguments of a function type
+                        brackets.push(
+                            tokens[x] === "(" && x > 0 && /^\w+$/.test(tokens[x - 1]));
+                    } else if (tokens[x] === ">" || tokens[x] === ")" || tokens[x] === "]") {
+                        var fnArgs = brackets.pop();
+                        if (fnArgs === true && brackets.length === 0 && tokens[x + 1] === "->") {
+                            // skip the output of the function type
+                            x += 1;
+                        }
+                    } else if (tokens[x] === "->" && brackets.length === 0) {
+                        arrow = x;
+                    }
+                    if (tokens[x] === "->" && firstArrow === -1) {
+                        firstArrow = x;
+                    }
+                }
+                if (arrow === -1) {
+                    arrow = firstArrow;
+                }
+                if (arrow === -1) {
+                    return null;
+                }
+                var allTokens = tokens;
+                var inputs = parseSide(allTokens.slice(0, arrow));
+                var output = parseSide(allTokens.slice(arrow + 1));
+                if (inputs === undefined || output === undefined ||
+                    (inputs === null && output === null) ||
+                    (output !== null && output.length !== 1))
+                {
+                    return null;
+                }
+                return {inputs: inputs, output: output === null ? null : output[0]};
+            }
+
+            // Unifies a parsed signature query with the signature of a function from the index,
+            // and returns how far apart they are, or -1 if they don't match.
+            //
+            // In the index, a type is either a name, `[name, generics]`, or the number of a
+            // generic parameter of the function, whose bounds are in `signature[2]`. A generic
+            // parameter matches any type of the query, but less closely when none of its bounds
+            // is named like that type. Type variables of the query have to match the same type
+            // everywhere they're used.
+            function unifySignature(signatureQuery, signature) {
+                var bounds = signature[2] || [];
+
+                function copyEnv(env) {
+                    var copy = {vars: {}, params: {}};
+                    var key;
+                    for (key in env.vars) {
+                        if (hasOwnProperty(env.vars, key)) {
+                            copy.vars[key] = env.vars[key];
+                        }
+                    }
+                    for (key in env.params) {
+                        if (hasOwnProperty(env.params, key)) {
+                            copy.params[key] = env.params[key];
+                        }
+                    }
+                    return copy;
+                }
+
+                function unifyType(queryType, type, env) {
+                    var key;
+                    if (queryType.any === true) {
+                        return 0;
+                    }
+                    if (typeof type === "number") {
+                        key = queryType.variable !== undefined ?
+                            "$" + queryType.variable : JSON.stringify(queryType);
+                        if (env.params[type] !== undefined) {
+                            return env.params[type] === key ? 0 : -1;
+                        }
+                        env.params[type] = key;
+                        if (queryType.variable !== undefined) {
+                            if (env.vars[queryType.variable] !== undefined) {
+                                return -1;
+                            }
+                            env.vars[queryType.variable] = "#" + type;
+                            return 0;
+                        }
+                        var paramBounds = bounds[type] || [];
+                        if (paramBounds.length === 0) {
+                            return 1;
+                        }
+                        for (var b = 0; b < paramBounds.length; ++b) {
+                            var bound = paramBounds[b];
+                            if ((typeof bound === "string" ? bound : bound[0]) === queryType.name) {
+                                return 0;
+                            }
+                        }
+                        return 2;
+                    }
+                    if (queryType.variable !== undefined) {
+                        // a type variable can stand for a concrete type too, but it's a less
+                        // precise match than a generic parameter
+                        key = JSON.stringify(type);
+                        if (env.vars[queryType.variable] === undefined) {
+                            env.vars[queryType.variable] = key;
+                            return 1;
+                        }
+                        return env.vars[queryType.variable] === key ? 0 : -1;
+                    }
+                    var name = typeof type === "string" ? type : type[0];
+                    var generics = typeof type === "string" ? [] : type[1];
+                    if (name !== queryType.name || queryType.generics.length > generics.length) {
+                        return -1;
+                    }
+                    var total = 0;
+                    for (var g = 0; g < queryType.generics.length; ++g) {
+                        var cost = unifyType(queryType.generics[g], generics[g], env);
+                        if (cost < 0) {
+                            return -1;
+                        }
+                        total += cost;
+                    }
+                    return total;
+                }
+
+                // Tries every way to pair the query inputs up with distinct inputs of the
+                // function, since they can be given in any order.
+                function unifyInputs(inputs, start, used, env) {
+                    if (start === signatureQuery.inputs.length) {
+                        return 0;
+                    }
+                    var best = -1;
+                    for (var x = 0; x < inputs.length; ++x) {
+                        if (used[x] === true) {
+                            continue;
+                        }
+                        var newEnv = copyEnv(env);
+                        var cost = unifyType(signatureQuery.inputs[start], inputs[x], newEnv);
+                        if (cost < 0) {
+                            continue;
+                        }
+                        used[x] = true;
+                        var rest = unifyInputs(inputs, start + 1, used, newEnv);
+                        used[x] = false;
+                        if (rest >= 0 && (best < 0 || cost + rest < best)) {
+                            best = cost + rest;
+                        }
+                    }
+                    return best;
+                }
+
+                var env = {vars: {}, params: {}};
+                var total = 0;
+                if (signatureQuery.output !== null) {
+                    if (signature[1] === undefined || signature[1] === null) {
+                        if (signatureQuery.output.name !== "unit" &&
+                            signatureQuery.output.any !== true)
+                        {
+                            return -1;
+                        }
+                    } else {
+                        total = unifyType(signatureQuery.output, signature[1], env);
+                        if (total < 0) {
+                            return -1;
+                        }
+                    }
+                }
+                if (signatureQuery.inputs !== null) {
+                    var inputs = signature[0];
+                    if (signatureQuery.inputs.length > inputs.length) {
+                        return -1;
+                    }
+                    var cost = unifyInputs(inputs, 0, [], env);
+                    if (cost < 0) {
+                        return -1;
+                    }
+                    // every input the query doesn't mention makes the match less close
+                    total += cost + inputs.length - signatureQuery.inputs.length;
+                }
+                return total;
+            }
+
             function generateId(ty) {
                 if (ty.parent && ty.parent.name) {
                     return itemTypes[ty.ty] + ty.path + ty.parent.name + ty.name;
@@ -1177,70 +1505,53 @@ function defocusSearchBar() {
                 query.inputs = [val];
                 query.output = val;
                 query.search = val;
-            // searching by type
+            // searching by type signature
             } else if (val.search("->") > -1) {
-                var trimmer = function(s) { return s.trim(); };
-                var parts = val.split("->").map(trimmer);
-                var input = parts[0];
-                // sort inputs so that order does not matter
-                var inputs = input.split(",").map(trimmer).sort();
-                for (i = 0; i < inputs.length; ++i) {
-                    inputs[i] = extractGenerics(inputs[i]);
-                }
-                var output = extractGenerics(parts[1]);
-
-                for (i = 0; i < nSearchWords; ++i) {
-                    if (filterCrates !== undefined && searchIndex[i].crate !== filterCrates) {
-                        continue;
-                    }
-                    var type = searchIndex[i].type;
+                var signatureQuery = parseSignatureQuery(query.query);
+                for (i = 0; signatureQuery !== null && i < nSearchWords; ++i) {
                     ty = searchIndex[i];
-                    if (!type) {
+                    if (!ty.signature ||
+                        (filterCrates !== undefined && ty.crate !== filterCrates) ||
+                        typePassesFilter(typeFilter, ty.ty) === false)
+                    {
                         continue;
                     }
                     fullId = generateId(ty);
 
-                    returned = checkReturned(ty, output, true, NO_TYPE_FILTER);
-                    if (output.name === "*" || returned === true) {
-                        in_args = false;
-                        var is_module = false;
-
-                        if (input === "*") {
-                            is_module = true;
-                        } else {
-                            var allFound = true;
-                            for (var it = 0; allFound === true && it < inputs.length; it++) {
-                                allFound = checkType(type, inputs[it], true);
-                            }
-                            in_args = allFound;
-                        }
-                        if (in_args === true) {
+                    var cost = unifySignature(signatureQuery, ty.signature);
+                    if (cost >= 0) {
+                        results[fullId] = {
+                            id: i,
+                            index: -1,
+                            lev: cost,
+                            dontValidate: true,
+                        };
+                    }
+                    if (signatureQuery.inputs !== null) {
+                        cost = unifySignature({inputs: signatureQuery.inputs, output: null},
+                                              ty.signature);
+                        if (cost >= 0) {
                             results_in_args[fullId] = {
                                 id: i,
                                 index: -1,
+                                lev: cost,
                                 dontValidate: true,
                             };
                         }
-                        if (returned === true) {
+                    }
+                    if (signatureQuery.output !== null) {
+                        cost = unifySignature({inputs: null, output: signatureQuery.output},
+                                              ty.signature);
+                        if (cost >= 0) {
                             results_returned[fullId] = {
                                 id: i,
                                 index: -1,
-                                dontValidate: true,
-                            };
-                        }
-                        if (is_module === true) {
-                            results[fullId] = {
-                                id: i,
-                                index: -1,
+                                lev: cost,
                                 dontValidate: true,
                             };
                         }
                     }
                 }
-                query.inputs = inputs.map(function(input) {
-                    return input.name;
-                });
-                query.output = output.name;
             } else {
                 query.inputs = [val];
                 query.output = val;
@@ -1392,6 +1703,163 @@ function defocusSearchBar() {
             return true;
         }
 
+        /**
+         * Splits some text into the words of the full-text index, in the same way as
+         * `full_text::words` in rustdoc does.
+         * @param  {string} text
+         * @return {[string]}
+         */
+        function fullTextWords(text) {
+            var words = [];
+            var parts = text.toLowerCase().split(/[\s!-\/:-@\[-\x60\x7b-~]+/);
+            for (var i = 0; i < parts.length; ++i) {
+                var word = parts[i];
+                var len = word.length;
+                if (len < 2 || FULL_TEXT_STOP_WORDS.indexOf(word) !== -1) {
+                    continue;
+                }
+                if (len > 4 && (word.endsWith("ies") || word.endsWith("ied"))) {
+                    word = word.slice(0, len - 3) + "y";
+                } else if (len > 3 && word.endsWith("s") && !word.endsWith("ss") &&
+                           !word.endsWith("us") && !word.endsWith("is")) {
+                    word = word.slice(0, len - 1);
+                }
+                words.push(word);
+            }
+            return words;
+        }
+
+        /**
+         * Looks for the words of the query in the docs of the items of the crates whose
+         * full-text index has been loaded. The items that contain the most of the words come
+         * first, and are then ranked with BM25. The last word also matches the words it is the
+         * start of, since it may not have been typed in full yet.
+         * @param  {Object} query
+         * @param  {string} filterCrates [Crate to search in if defined]
+         * @return {[Object]}
+         */
+        function execFullTextQuery(query, filterCrates) {
+            var K1 = 1.2, B = 0.75;
+            var words = fullTextWords(query.query);
+            var matches = {};
+
+            function addMatches(shard, term, wordIndex, weight) {
+                var postings = shard.index.t[term];
+                var lengths = shard.index.l;
+                var count = postings.length / 2;
+                var idf = Math.log(1 + (lengths.length - count + 0.5) / (count + 0.5));
+                var item = 0;
+                for (var i = 0; i < postings.length; i += 2) {
+                    item += postings[i];
+                    var tf = postings[i + 1];
+                    var id = shard.offset + item;
+                    var match = matches[id];
+                    if (match === undefined) {
+                        match = matches[id] = {id: id, words: [], score: 0};
+                    }
+                    if (match.words.indexOf(wordIndex) === -1) {
+                        match.words.push(wordIndex);
+                    }
+                    match.score += weight * idf * tf * (K1 + 1) /
+                        (tf + K1 * (1 - B + B * lengths[item] / shard.averageLength));
+                }
+            }
+
+            for (var crate in FULL_TEXT) {
+                if (!hasOwnProperty(FULL_TEXT, crate) || FULL_TEXT[crate].index === null ||
+                    (filterCrates !== undefined && crate !== filterCrates)) {
+                    continue;
+                }
+                var shard = FULL_TEXT[crate];
+                for (var w = 0; w < words.length; ++w) {
+                    var word = words[w];
+                    if (hasOwnProperty(shard.index.t, word)) {
+                        addMatches(shard, word, w, 1);
+                    }
+                    if (w === words.length - 1) {
+                        // `terms` is sorted, so the words starting with this one are together.
+                        var terms = shard.terms;
+                        var lo = 0, hi = terms.length;
+                        while (lo < hi) {
+                            var mid = (lo + hi) >> 1;
+                            if (terms[mid] <= word) {
+                                lo = mid + 1;
+                            } else {
+                                hi = mid;
+                            }
+                        }
+                        for (; lo < terms.length && terms[lo].startsWith(word); ++lo) {
+                            addMatches(shard, terms[lo], w, 0.5);
+                        }
+                    }
+                }
+            }
+
+            var sorted = [];
+            for (var id in matches) {
+                if (hasOwnProperty(matches, id)) {
+                    sorted.push(matches[id]);
+                }
+            }
+            sorted.sort(function(a, b) {
+                if (a.words.length !== b.words.length) {
+                    return b.words.length - a.words.length;
+                }
+                return b.score - a.score || a.id - b.id;
+            });
+
+            var out = [];
+            for (var i = 0; i < sorted.length && out.length < MAX_RESULTS; ++i) {
+                var obj = searchIndex[sorted[i].id];
+                var res = buildHrefAndPath(obj);
+                obj.displayPath = pathSplitter(res[0]);
+                obj.fullPath = obj.displayPath + obj.name + "|" + obj.ty;
+                obj.href = res[1];
+                out.push(obj);
+            }
+            return out;
+        }
+
+        /**
+         * Starts loading the full-text indexes of the crates that are searched. Each index calls
+         * `registerFullTextIndex` once it has loaded.
+         * @param  {string} filterCrates [Crate to search in if defined]
+         */
+        function loadFullTextIndexes(filterCrates) {
+            for (var crate in FULL_TEXT) {
+                if (!hasOwnProperty(FULL_TEXT, crate) || FULL_TEXT[crate].requested ||
+                    (filterCrates !== undefined && crate !== filterCrates)) {
+                    continue;
+                }
+                FULL_TEXT[crate].requested = true;
+                var script = document.createElement("script");
+                script.src = rootPath + crate + "/full-text-index" + resourcesSuffix + ".js";
+                document.head.appendChild(script);
+            }
+        }
+
+        window.registerFullTextIndex = function(crate, fullTextIndex) {
+            var shard = FULL_TEXT[crate];
+            if (shard === undefined) {
+                return;
+            }
+            var totalLength = 0;
+            for (var i = 0; i < fullTextIndex.l.length; ++i) {
+                totalLength += fullTextIndex.l[i];
+            }
+            shard.averageLength = Math.max(1, totalLength / fullTextIndex.l.length);
+            shard.terms = Object.keys(fullTextIndex.t).sort();
+            shard.index = fullTextIndex;
+            // Redo the search with the new results, once the other indexes that were requested
+            // at the same time have loaded as well.
+            clearTimeout(fullTextTimeout);
+            fullTextTimeout = setTimeout(function() {
+                if (!hasClass(getSearchElement(), "hidden")) {
+                    search(undefined, true);
+                }
+            }, 100);
+        };
+
         function getQuery(raw) {
             var matches, type, query;
             query = raw;
@@ -1457,7 +1925,7 @@ function defocusSearchBar() {
             search_input.onkeydown = function(e) {
                 // "actives" references the currently highlighted item in each search tab.
                 // Each array in "actives" represents a tab.
-                var actives = [[], [], []];
+                var actives = [[], [], [], []];
                 // "current" is used to know which tab we're looking into.
                 var current = 0;
                 onEachLazy(document.getElementById("results").childNodes, function(e) {
@@ -1496,10 +1964,11 @@ function defocusSearchBar() {
                             actives[currentTab][0].getElementsByTagName("a")[0].href;
                     }
                 } else if (e.which === 9) { // tab
+                    var nbTabs = document.getElementById("titles").childNodes.length;
                     if (e.shiftKey) {
-                        printTab(currentTab > 0 ? currentTab - 1 : 2);
+                        printTab(currentTab > 0 ? currentTab - 1 : nbTabs - 1);
                     } else {
-                        printTab(currentTab > 1 ? 0 : currentTab + 1);
+                        printTab(currentTab < nbTabs - 1 ? currentTab + 1 : 0);
                     }
                     e.preventDefault();
                 } else if (e.which === 16) { // shift
@@ -1664,6 +2133,13 @@ function defocusSearchBar() {
             var ret_others = addTab(results.others, query);
             var ret_in_args = addTab(results.in_args, query, false);
             var ret_returned = addTab(results.returned, query, false);
+            // The tab for the full-text search is only shown if there is an index to search.
+            var ret_in_docs = ["", 0];
+            var docs_header = "";
+            if (Object.keys(FULL_TEXT).length > 0) {
+                ret_in_docs = addTab(results.in_docs, query, false);
+                docs_header = makeTabHeader(3, "In Docs", ret_in_docs[1]);
+            }
 
             var output = "<h1>Results for " + escape(query.query) +
                 (query.type ? " (type: " + escape(query.type) + ")" : "") + "</h1>" +
@@ -1671,8 +2147,9 @@ function defocusSearchBar() {
                 makeTabHeader(0, "In Names", ret_others[1]) +
                 makeTabHeader(1, "In Parameters", ret_in_args[1]) +
                 makeTabHeader(2, "In Return Types", ret_returned[1]) +
+                docs_header +
                 "</div><div id=\"results\">" +
-                ret_others[0] + ret_in_args[0] + ret_returned[0] + "</div>";
+                ret_others[0] + ret_in_args[0] + ret_returned[0] + ret_in_docs[0] + "</div>";
 
             search.innerHTML = output;
             showSearchResults(search);
@@ -1690,7 +2167,10 @@ function defocusSearchBar() {
             elems[0].onclick = function() { printTab(0); };
             elems[1].onclick = function() { printTab(1); };
             elems[2].onclick = function() { printTab(2); };
-            printTab(currentTab);
+            if (elems.length > 3) {
+                elems[3].onclick = function() { printTab(3); };
+            }
+            printTab(currentTab < elems.length ? currentTab : 0);
         }
 
         function execSearch(query, searchWords, filterCrates) {
@@ -1734,7 +2214,9 @@ function defocusSearchBar() {
                 return ret;
             }
 
-            var queries = query.raw.split(",");
+            var inDocs = execFullTextQuery(query, filterCrates);
+            // Commas separate the inputs of a signature rather than queries.
+            var queries = query.raw.search("->") > -1 ? [query.raw] : query.raw.split(",");
             var results = {
                 "in_args": [],
                 "returned": [],
@@ -1756,12 +2238,14 @@ function defocusSearchBar() {
                     "in_args": mergeArrays(results.in_args),
                     "returned": mergeArrays(results.returned),
                     "others": mergeArrays(results.others),
+                    "in_docs": inDocs,
                 };
             }
             return {
                 "in_args": results.in_args[0],
                 "returned": results.returned[0],
                 "others": results.others[0],
+                "in_docs": inDocs,
             };
         }
 
@@ -1806,6 +2290,7 @@ function defocusSearchBar() {
             }
 
             var filterCrates = getFilterCrates();
+            loadFullTextIndexes(filterCrates);
             showResults(execSearch(query, index, filterCrates));
         }
 
@@ -1820,6 +2305,10 @@ function defocusSearchBar() {
 
                 var crateSize = 0;
 
+                if (rawSearchIndex[crate].f) {
+                    FULL_TEXT[crate] = {offset: currentIndex, index: null, requested: false};
+                }
+
                 searchWords.push(crate);
                 searchIndex.push({
                     crate: crate,
@@ -1835,8 +2324,9 @@ function defocusSearchBar() {
                 //              (String) name,
                 //              (String) full path or empty string for previous path,
                 //              (String) description,
-                //              (Number | null) the parent path index to `paths`]
-                //              (Object | null) the type of the function (if any)
+                //              (Number | null) the parent path index to `paths`,
+                //              (Object | null) the type of the function (if any),
+                //              (Array | null) the full signature of the function (if any)]
                 var items = rawSearchIndex[crate].i;
                 // an array of [(Number) item type,
                 //              (String) name]
@@ -1873,6 +2363,7 @@ function defocusSearchBar() {
                         desc: rawRow[3],
                         parent: paths[rawRow[4]],
                         type: rawRow[5],
+                        signature: rawRow[6],
                     };
                     searchIndex.push(row);
                     if (typeof row.name === "string") {
@@ -2720,7 +3211,7 @@ function defocusSearchBar() {
 
     // In the search display, allows to switch between tabs.
     function printTab(nb) {
-        if (nb === 0 || nb === 1 || nb === 2) {
+        if (nb >= 0 && nb <= 3) {
             currentTab = nb;
         }
         var nb_copy = nb;
@@ -2886,8 +3377,9 @@ function defocusSearchBar() {
             "Accepted kinds are: <code>fn</code>, <code>mod</code>, <code>struct</code>, \
              <code>enum</code>, <code>trait</code>, <code>type</code>, <code>macro</code>, \
              and <code>const</code>.",
-            "Search functions by type signature (e.g., <code>vec -&gt; usize</code> or \
-             <code>* -&gt; vec</code>)",
+            "Search functions by type signature (e.g., <code>&amp;str, usize -&gt; String</code> \
+             or <code>Vec&lt;T&gt; -&gt; Option&lt;T&gt;</code>), where single capital letters \
+             like <code>T</code> stand for any type",
             "Search multiple things at once by splitting your query with comma (e.g., \
              <code>str,u8</code> or <code>String,struct:Vec,test</code>)",
             "You can look for items with an exact name by putting double quotes around \
@@ -0,0 +1,557 @@
+//! These from impls are used to create the JSON types which get serialized. They're very close to
+//! the `clean` types but with some fields removed or stringified to simplify the output and not
+//! expose unstable compiler internals.
+
+use rustc_hir::def_id::{DefId, CRATE_DEF_INDEX};
+use rustc_hir::Mutability;
+use rustdoc_json_types::*;
+
+use crate::clean;
+use crate::doctree;
+use crate::formats::item_type::ItemType;
+
+impl From<clean::Item> for Item {
+    fn from(item: clean::Item) -> Self {
+        let item_type = ItemType::from(&item);
+        let clean::Item {
+            source,
+            name,
+            attrs,
+            kind,
+            visibility,
+            def_id,
+            stability: _,
+            deprecation,
+        } = item;
+        Item {
+            id: from_def_id(def_id),
+            crate_id: def_id.krate.as_u32(),
+            name,
+            source: source.into(),
+            visibility: visibility.into(),
+            docs: attrs.collapsed_doc_value().unwrap_or_default(),
+            links: attrs
+                .links
+                .into_iter()
+                .filter_map(|clean::ItemLink { link, did, .. }| {
+                    did.map(|did| (link, from_def_id(did)))
+                })
+                .collect(),
+            attrs: attrs
+                .other_attrs
+                .iter()
+                .map(rustc_ast_pretty::pprust::attribute_to_string)
+                .collect(),
+            deprecation: deprecation.map(Into::into),
+            kind: item_type.into(),
+            inner: kind.into(),
+        }
+    }
+}
+
+impl From<clean::Span> for Option<Span> {
+    fn from(span: clean::Span) -> Self {
+        let clean::Span { loline, locol, hiline, hicol, .. } = span;
+        match span.filename {
+            rustc_span::FileName::Real(name) => Some(Span {
+                filename: name.into_local_path(),
+                begin: (loline, locol),
+                end: (hiline, hicol),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl From<clean::Deprecation> for Deprecation {
+    fn from(deprecation: clean::Deprecation) -> Self {
+        let clean::Deprecation { since, note, is_since_rustc_version: _ } = deprecation;
+        Deprecation { since, note }
+    }
+}
+
+impl From<clean::Visibility> for Visibility {
+    fn from(v: clean::Visibility) -> Self {
+        use clean::Visibility::*;
+        match v {
+            Public => Visibility::Public,
+            Inherited => Visibility::Default,
+            Restricted(did, _) if did.index == CRATE_DEF_INDEX => Visibility::Crate,
+            Restricted(did, path) => Visibility::Restricted {
+                parent: from_def_id(did),
+                path: path.to_string_no_crate_verbose(),
+            },
+        }
+    }
+}
+
+impl From<clean::GenericArgs> for GenericArgs {
+    fn from(args: clean::GenericArgs) -> Self {
+        use clean::GenericArgs::*;
+        match args {
+            AngleBracketed { args, bindings } => GenericArgs::AngleBracketed {
+                args: args.into_iter().map(Into::into).collect(),
+                bindings: bindings.into_iter().map(Into::into).collect(),
+            },
+            Parenthesized { inputs, output } => GenericArgs::Parenthesized {
+                inputs: inputs.into_iter().map(Into::into).collect(),
+                output: output.map(Into::into),
+            },
+        }
+    }
+}
+
+impl From<clean::GenericArg> for GenericArg {
+    fn from(arg: clean::GenericArg) -> Self {
+        use clean::GenericArg::*;
+        match arg {
+            Lifetime(l) => GenericArg::Lifetime(l.0),
+            Type(t) => GenericArg::Type(t.into()),
+            Const(c) => GenericArg::Const(c.into()),
+        }
+    }
+}
+
+impl From<clean::Constant> for Constant {
+    fn from(constant: clean::Constant) -> Self {
+        let clean::Constant { type_, expr, value, is_literal } = constant;
+        Constant { type_: type_.into(), expr, value, is_literal }
+    }
+}
+
+impl From<clean::TypeBinding> for TypeBinding {
+    fn from(binding: clean::TypeBinding) -> Self {
+        TypeBinding { name: binding.name, binding: binding.kind.into() }
+    }
+}
+
+impl From<clean::TypeBindingKind> for TypeBindingKind {
+    fn from(kind: clean::TypeBindingKind) -> Self {
+        use clean::TypeBindingKind::*;
+        match kind {
+            Equality { ty } => TypeBindingKind::Equality(ty.into()),
+            Constraint { bounds } => {
+                TypeBindingKind::Constraint(bounds.into_iter().map(Into::into).collect())
+            }
+        }
+    }
+}
+
+crate fn from_def_id(did: DefId) -> Id {
+    Id(format!("{}:{}", did.krate.as_u32(), did.index.as_u32()))
+}
+
+impl From<clean::ItemKind> for ItemEnum {
+    fn from(item: clean::ItemKind) -> Self {
+        use clean::ItemKind::*;
+        match item {
+            ModuleItem(m) => ItemEnum::ModuleItem(m.into()),
+            ExternCrateItem(c, a) => ItemEnum::ExternCrateItem { name: c, rename: a },
+            ImportItem(i) => ItemEnum::ImportItem(i.into()),
+            StructItem(s) => ItemEnum::StructItem(s.into()),
+            UnionItem(u) => ItemEnum::UnionItem(u.into()),
+            StructFieldItem(f) => ItemEnum::StructFieldItem(f.into()),
+            EnumItem(e) => ItemEnum::EnumItem(e.into()),
+            VariantItem(v) => ItemEnum::VariantItem(v.into()),
+            FunctionItem(f) => ItemEnum::FunctionItem(f.into()),
+            ForeignFunctionItem(f) => ItemEnum::FunctionItem(f.into()),
+            TraitItem(t) => ItemEnum::TraitItem(t.into()),
+            TraitAliasItem(t) => ItemEnum::TraitAliasItem(t.into()),
+            MethodItem(m, _) => ItemEnum::MethodItem(from_function_with_body(m, true)),
+            TyMethodItem(m) => ItemEnum::MethodItem(from_function_with_body(m, false)),
+            ImplItem(i) => ItemEnum::ImplItem(i.into()),
+            StaticItem(s) => ItemEnum::StaticItem(s.into()),
+            ForeignStaticItem(s) => ItemEnum::StaticItem(s.into()),
+            ForeignTypeItem => ItemEnum::ForeignTypeItem,
+            TypedefItem(t, _) => ItemEnum::TypedefItem(t.into()),
+            OpaqueTyItem(t) => ItemEnum::OpaqueTyItem(t.into()),
+            ConstantItem(c) => ItemEnum::ConstantItem(c.into()),
+            MacroItem(m) => ItemEnum::MacroItem(m.source),
+            ProcMacroItem(m) => ItemEnum::ProcMacroItem(m.into()),
+            PrimitiveItem(p) => ItemEnum::PrimitiveItem(p.as_str().to_string()),
+            KeywordItem(k) => ItemEnum::KeywordItem(k),
+            AssocConstItem(t, s) => ItemEnum::AssocConstItem { type_: t.into(), default: s },
+            AssocTypeItem(g, t) => ItemEnum::AssocTypeItem {
+                bounds: g.into_iter().map(Into::into).collect(),
+                default: t.map(Into::into),
+            },
+            StrippedItem(inner) => (*inner).into(),
+        }
+    }
+}
+
+impl From<clean::Module> for Module {
+    fn from(module: clean::Module) -> Self {
+        Module { is_crate: module.is_crate, items: ids(module.items) }
+    }
+}
+
+impl From<clean::Struct> for Struct {
+    fn from(struct_: clean::Struct) -> Self {
+        let clean::Struct { struct_type, generics, fields, fields_stripped } = struct_;
+        Struct {
+            struct_type: struct_type.into(),
+            generics: generics.into(),
+            fields_stripped,
+            fields: ids(fields),
+            impls: Vec::new(), // Added in JsonRenderer::item
+        }
+    }
+}
+
+impl From<clean::Union> for Union {
+    fn from(union_: clean::Union) -> Self {
+        let clean::Union { struct_type: _, generics, fields, fields_stripped } = union_;
+        Union {
+            generics: generics.into(),
+            fields_stripped,
+            fields: ids(fields),
+            impls: Vec::new(), // Added in JsonRenderer::item
+        }
+    }
+}
+
+impl From<doctree::StructType> for StructType {
+    fn from(struct_type: doctree::StructType) -> Self {
+        use doctree::StructType::*;
+        match struct_type {
+            Plain => StructType::Plain,
+            Tuple => StructType::Tuple,
+            Unit => StructType::Unit,
+        }
+    }
+}
+
+fn from_header(header: rustc_hir::FnHeader) -> Header {
+    Header {
+        const_: header.constness == rustc_hir::Constness::Const,
+        unsafe_: header.unsafety == rustc_hir::Unsafety::Unsafe,
+        async_: header.asyncness == rustc_hir::IsAsync::Async,
+        abi: header.abi.name().to_string(),
+    }
+}
+
+impl From<clean::Function> for Function {
+    fn from(function: clean::Function) -> Self {
+        let clean::Function { decl, generics, header, all_types: _, ret_types: _ } = function;
+        Function { decl: decl.into(), generics: generics.into(), header: from_header(header) }
+    }
+}
+
+impl From<clean::Generics> for Generics {
+    fn from(generics: clean::Generics) -> Self {
+        Generics {
+            params: generics.params.into_iter().map(Into::into).collect(),
+            where_predicates: generics.where_predicates.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<clean::GenericParamDef> for GenericParamDef {
+    fn from(generic_param: clean::GenericParamDef) -> Self {
+        GenericParamDef { name: generic_param.name, kind: generic_param.kind.into() }
+    }
+}
+
+impl From<clean::GenericParamDefKind> for GenericParamDefKind {
+    fn from(kind: clean::GenericParamDefKind) -> Self {
+        use clean::GenericParamDefKind::*;
+        match kind {
+            Lifetime => GenericParamDefKind::Lifetime,
+            Type { did: _, bounds, default, synthetic: _ } => GenericParamDefKind::Type {
+                bounds: bounds.into_iter().map(Into::into).collect(),
+                default: default.map(Into::into),
+            },
+            Const { did: _, ty } => GenericParamDefKind::Const(ty.into()),
+        }
+    }
+}
+
+impl From<clean::WherePredicate> for WherePredicate {
+    fn from(predicate: clean::WherePredicate) -> Self {
+        use clean::WherePredicate::*;
+        match predicate {
+            BoundPredicate { ty, bounds } => WherePredicate::BoundPredicate {
+                ty: ty.into(),
+                bounds: bounds.into_iter().map(Into::into).collect(),
+            },
+            RegionPredicate { lifetime, bounds } => WherePredicate::RegionPredicate {
+                lifetime: lifetime.0,
+                bounds: bounds.into_iter().map(Into::into).collect(),
+            },
+            EqPredicate { lhs, rhs } => {
+                WherePredicate::EqPredicate { lhs: lhs.into(), rhs: rhs.into() }
+            }
+        }
+    }
+}
+
+impl From<clean::GenericBound> for GenericBound {
+    fn from(bound: clean::GenericBound) -> Self {
+        use clean::GenericBound::*;
+        match bound {
+            TraitBound(clean::PolyTrait { trait_, generic_params }, modifier) => {
+                GenericBound::TraitBound {
+                    trait_: trait_.into(),
+                    generic_params: generic_params.into_iter().map(Into::into).collect(),
+                    modifier: from_trait_bound_modifier(modifier),
+                }
+            }
+            Outlives(lifetime) => GenericBound::Outlives(lifetime.0),
+        }
+    }
+}
+
+fn from_trait_bound_modifier(modifier: rustc_hir::TraitBoundModifier) -> TraitBoundModifier {
+    use rustc_hir::TraitBoundModifier::*;
+    match modifier {
+        None => TraitBoundModifier::None,
+        Maybe => TraitBoundModifier::Maybe,
+        MaybeConst => TraitBoundModifier::MaybeConst,
+    }
+}
+
+/// Joins the segments of a path back into its source form, like `std::vec::Vec`. Generic
+/// arguments are left out, they are recorded separately.
+fn path_to_string(path: &clean::Path) -> String {
+    let segments: Vec<&str> = path.segments.iter().map(|s| s.name.as_str()).collect();
+    let joined = segments.join("::");
+    if path.global { format!("::{}", joined) } else { joined }
+}
+
+impl From<clean::Type> for Type {
+    fn from(ty: clean::Type) -> Self {
+        use clean::Type::*;
+        match ty {
+            ResolvedPath { path, param_names, did, is_generic: _ } => Type::ResolvedPath {
+                name: path_to_string(&path),
+                id: from_def_id(did),
+                args: path.segments.last().map(|seg| Box::new(seg.args.clone().into())),
+                param_names: param_names
+                    .map(|v| v.into_iter().map(Into::into).collect())
+                    .unwrap_or_default(),
+            },
+            Generic(s) => Type::Generic(s),
+            Primitive(p) => Type::Primitive(p.as_str().to_string()),
+            BareFunction(f) => Type::FunctionPointer(Box::new((*f).into())),
+            Tuple(t) => Type::Tuple(t.into_iter().map(Into::into).collect()),
+            Slice(t) => Type::Slice(Box::new((*t).into())),
+            Array(t, s) => Type::Array { type_: Box::new((*t).into()), len: s },
+            ImplTrait(g) => Type::ImplTrait(g.into_iter().map(Into::into).collect()),
+            Never => Type::Never,
+            Infer => Type::Infer,
+            RawPointer(mutability, type_) => Type::RawPointer {
+                mutable: mutability == Mutability::Mut,
+                type_: Box::new((*type_).into()),
+            },
+            BorrowedRef { lifetime, mutability, type_ } => Type::BorrowedRef {
+                lifetime: lifetime.map(|l| l.0),
+                mutable: mutability == Mutability::Mut,
+                type_: Box::new((*type_).into()),
+            },
+            QPath { name, self_type, trait_ } => Type::QualifiedPath {
+                name,
+                self_type: Box::new((*self_type).into()),
+                trait_: Box::new((*trait_).into()),
+            },
+        }
+    }
+}
+
+impl From<clean::BareFunctionDecl> for FunctionPointer {
+    fn from(bare_decl: clean::BareFunctionDecl) -> Self {
+        let clean::BareFunctionDecl { unsafety, generic_params, decl, abi } = bare_decl;
+        FunctionPointer {
+            is_unsafe: unsafety == rustc_hir::Unsafety::Unsafe,
+            generic_params: generic_params.into_iter().map(Into::into).collect(),
+            decl: decl.into(),
+            abi: abi.name().to_string(),
+        }
+    }
+}
+
+impl From<clean::FnDecl> for FnDecl {
+    fn from(decl: clean::FnDecl) -> Self {
+        let clean::FnDecl { inputs, output, c_variadic, attrs: _ } = decl;
+        FnDecl {
+            inputs: inputs.values.into_iter().map(|arg| (arg.name, arg.type_.into())).collect(),
+            output: match output {
+                clean::FnRetTy::Return(t) => Some(t.into()),
+                clean::FnRetTy::DefaultReturn => None,
+            },
+            c_variadic,
+        }
+    }
+}
+
+impl From<clean::Trait> for Trait {
+    fn from(trait_: clean::Trait) -> Self {
+        let clean::Trait { unsafety, items, generics, bounds, is_spotlight: _, is_auto } = trait_;
+        Trait {
+            is_auto,
+            is_unsafe: unsafety == rustc_hir::Unsafety::Unsafe,
+            items: ids(items),
+            generics: generics.into(),
+            bounds: bounds.into_iter().map(Into::into).collect(),
+            implementors: Vec::new(), // Added in JsonRenderer::item
+        }
+    }
+}
+
+impl From<clean::Impl> for Impl {
+    fn from(impl_: clean::Impl) -> Self {
+        let clean::Impl {
+            unsafety,
+            generics,
+            provided_trait_methods,
+            trait_,
+            for_,
+            items,
+            polarity,
+            synthetic,
+            blanket_impl,
+        } = impl_;
+        let mut provided_trait_methods: Vec<String> = provided_trait_methods.into_iter().collect();
+        // Keep the output stable, the set is not ordered.
+        provided_trait_methods.sort_unstable();
+        Impl {
+            is_unsafe: unsafety == rustc_hir::Unsafety::Unsafe,
+            generics: generics.into(),
+            provided_trait_methods,
+            trait_: trait_.map(Into::into),
+            for_: for_.into(),
+            items: ids(items),
+            negative: polarity == Some(clean::ImplPolarity::Negative),
+            synthetic,
+            blanket_impl: blanket_impl.map(Into::into),
+        }
+    }
+}
+
+fn from_function_with_body(function: clean::Function, has_body: bool) -> Method {
+    let clean::Function { header, decl, generics, all_types: _, ret_types: _ } = function;
+    Method { decl: decl.into(), generics: generics.into(), header: from_header(header), has_body }
+}
+
+impl From<clean::Enum> for Enum {
+    fn from(enum_: clean::Enum) -> Self {
+        let clean::Enum { variants, generics, variants_stripped } = enum_;
+        Enum {
+            generics: generics.into(),
+            variants_stripped,
+            variants: ids(variants),
+            impls: Vec::new(), // Added in JsonRenderer::item
+        }
+    }
+}
+
+impl From<clean::Variant> for Variant {
+    fn from(variant: clean::Variant) -> Self {
+        use clean::VariantKind::*;
+        match variant.kind {
+            CLike => Variant::Plain,
+            Tuple(t) => Variant::Tuple(t.into_iter().map(Into::into).collect()),
+            Struct(s) => Variant::Struct(ids(s.fields)),
+        }
+    }
+}
+
+impl From<clean::Import> for Import {
+    fn from(import: clean::Import) -> Self {
+        use clean::ImportKind::*;
+        match import.kind {
+            Simple(s) => Import {
+                source: path_to_string(&import.source.path),
+                name: s,
+                id: import.source.did.map(from_def_id),
+                glob: false,
+            },
+            Glob => Import {
+                source: path_to_string(&import.source.path),
+                name: import.source.path.last_name().to_string(),
+                id: import.source.did.map(from_def_id),
+                glob: true,
+            },
+        }
+    }
+}
+
+impl From<clean::ProcMacro> for ProcMacro {
+    fn from(mac: clean::ProcMacro) -> Self {
+        ProcMacro { kind: from_macro_kind(mac.kind), helpers: mac.helpers }
+    }
+}
+
+fn from_macro_kind(kind: rustc_span::hygiene::MacroKind) -> MacroKind {
+    use rustc_span::hygiene::MacroKind::*;
+    match kind {
+        Bang => MacroKind::Bang,
+        Attr => MacroKind::Attr,
+        Derive => MacroKind::Derive,
+    }
+}
+
+impl From<clean::Typedef> for Typedef {
+    fn from(typedef: clean::Typedef) -> Self {
+        let clean::Typedef { type_, generics, item_type: _ } = typedef;
+        Typedef { type_: type_.into(), generics: generics.into() }
+    }
+}
+
+impl From<clean::OpaqueTy> for OpaqueTy {
+    fn from(opaque: clean::OpaqueTy) -> Self {
+        OpaqueTy {
+            bounds: opaque.bounds.into_iter().map(Into::into).collect(),
+            generics: opaque.generics.into(),
+        }
+    }
+}
+
+impl From<clean::Static> for Static {
+    fn from(stat: clean::Static) -> Self {
+        Static {
+            type_: stat.type_.into(),
+            mutable: stat.mutability == Mutability::Mut,
+            expr: stat.expr,
+        }
+    }
+}
+
+impl From<ItemType> for ItemKind {
+    fn from(kind: ItemType) -> Self {
+        use ItemType::*;
+        match kind {
+            Module => ItemKind::Module,
+            ExternCrate => ItemKind::ExternCrate,
+            Import => ItemKind::Import,
+            Struct => ItemKind::Struct,
+            Union => ItemKind::Union,
+            Enum => ItemKind::Enum,
+            Function => ItemKind::Function,
+            Typedef => ItemKind::Typedef,
+            OpaqueTy => ItemKind::OpaqueTy,
+            Static => ItemKind::Static,
+            Constant => ItemKind::Constant,
+            Trait => ItemKind::Trait,
+            Impl => ItemKind::Impl,
+            TyMethod | Method => ItemKind::Method,
+            StructField => ItemKind::StructField,
+            Variant => ItemKind::Variant,
+            Macro => ItemKind::Macro,
+            Primitive => ItemKind::Primitive,
+            AssocConst => ItemKind::AssocConst,
+            AssocType => ItemKind::AssocType,
+            ForeignType => ItemKind::ForeignType,
+            Keyword => ItemKind::Keyword,
+            TraitAlias => ItemKind::TraitAlias,
+            ProcAttribute => ItemKind::ProcAttribute,
+            ProcDerive => ItemKind::ProcDerive,
+        }
+    }
+}
+
+/// The IDs of the items that are kept in the output. Stripped items are left out, since they
+/// are not written to the index either.
+fn ids(items: impl IntoIterator<Item = clean::Item>) -> Vec<Id> {
+    items.into_iter().filter(|x| !x.is_stripped()).map(|i| from_def_id(i.def_id)).collect()
+}
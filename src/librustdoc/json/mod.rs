@@ -1,47 +1,245 @@
+//! Rustdoc's JSON backend
+//!
+//! This module contains the logic for rendering a crate as JSON rather than the normal static HTML
+//! output. See [the RFC](https://github.com/rust-lang/rfcs/pull/2963) and the `rustdoc-json-types`
+//! crate docs for usage and details.
+
+mod conversions;
+
+#[cfg(test)]
+mod tests;
+
+use std::cell::RefCell;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_span::def_id::DefId;
+use rustc_span::edition::Edition;
+
+use rustdoc_json_types as types;
+
 use crate::clean;
 use crate::config::{RenderInfo, RenderOptions};
+use crate::docfs::PathError;
 use crate::error::Error;
 use crate::formats::cache::Cache;
 use crate::formats::FormatRenderer;
-
-use rustc_span::edition::Edition;
+use crate::html::render::cache::ExternalLocation;
+use crate::json::conversions::from_def_id;
 
 #[derive(Clone)]
-crate struct JsonRenderer {}
+crate struct JsonRenderer {
+    /// A mapping of IDs that contains all local items for this crate which gets output as a top
+    /// level field of the JSON blob.
+    index: Rc<RefCell<FxHashMap<types::Id, types::Item>>>,
+    /// The directory where the blob will be written to.
+    out_path: PathBuf,
+}
+
+impl JsonRenderer {
+    fn get_trait_implementors(&mut self, id: DefId, cache: &Cache) -> Vec<types::Id> {
+        cache
+            .implementors
+            .get(&id)
+            .map(|implementors| {
+                implementors
+                    .iter()
+                    .map(|i| {
+                        let item = &i.impl_item;
+                        self.item(item.clone(), cache).unwrap();
+                        from_def_id(item.def_id)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn get_impls(&mut self, id: DefId, cache: &Cache) -> Vec<types::Id> {
+        cache
+            .impls
+            .get(&id)
+            .map(|impls| {
+                impls
+                    .iter()
+                    .filter_map(|i| {
+                        let item = &i.impl_item;
+                        if item.def_id.is_local() {
+                            self.item(item.clone(), cache).unwrap();
+                            Some(from_def_id(item.def_id))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// External traits are not part of the crate being documented, but impls in it refer to
+    /// them, so their items are synthesized from the cache.
+    fn get_trait_items(&mut self, cache: &Cache) -> Vec<(types::Id, types::Item)> {
+        cache
+            .traits
+            .iter()
+            .filter_map(|(&id, trait_item)| {
+                // only need to synthesize items for external traits
+                if !id.is_local() {
+                    trait_item.items.clone().into_iter().for_each(|i| self.item(i, cache).unwrap());
+                    Some((
+                        from_def_id(id),
+                        types::Item {
+                            id: from_def_id(id),
+                            crate_id: id.krate.as_u32(),
+                            name: cache
+                                .paths
+                                .get(&id)
+                                .or_else(|| cache.external_paths.get(&id))
+                                .and_then(|(path, _)| path.last().cloned()),
+                            source: None,
+                            visibility: types::Visibility::Public,
+                            docs: Default::default(),
+                            links: Default::default(),
+                            attrs: Default::default(),
+                            deprecation: Default::default(),
+                            kind: types::ItemKind::Trait,
+                            inner: types::ItemEnum::TraitItem(trait_item.clone().into()),
+                        },
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
 
 impl FormatRenderer for JsonRenderer {
     fn init(
-        _krate: clean::Crate,
-        _options: RenderOptions,
+        krate: clean::Crate,
+        options: RenderOptions,
         _render_info: RenderInfo,
         _edition: Edition,
         _cache: &mut Cache,
     ) -> Result<(Self, clean::Crate), Error> {
-        unimplemented!()
+        debug!("Initializing json renderer");
+        Ok((
+            JsonRenderer {
+                index: Rc::new(RefCell::new(FxHashMap::default())),
+                out_path: options.output,
+            },
+            krate,
+        ))
     }
 
-    fn item(&mut self, _item: clean::Item, _cache: &Cache) -> Result<(), Error> {
-        unimplemented!()
+    /// Inserts an item into the index. This should be used rather than directly calling insert on
+    /// the hashmap because certain items (traits and types) need to have their mappings for trait
+    /// implementations filled out before they're inserted.
+    fn item(&mut self, item: clean::Item, cache: &Cache) -> Result<(), Error> {
+        // Stripped items are not part of the documented API, and nothing refers to them.
+        if item.is_stripped() {
+            return Ok(());
+        }
+
+        // Flatten items that recursively store other items
+        item.kind.inner_items().for_each(|i| self.item(i.clone(), cache).unwrap());
+
+        let id = item.def_id;
+        let mut new_item: types::Item = item.into();
+        match new_item.inner {
+            types::ItemEnum::TraitItem(ref mut t) => {
+                t.implementors = self.get_trait_implementors(id, cache)
+            }
+            types::ItemEnum::StructItem(ref mut s) => s.impls = self.get_impls(id, cache),
+            types::ItemEnum::UnionItem(ref mut u) => u.impls = self.get_impls(id, cache),
+            types::ItemEnum::EnumItem(ref mut e) => e.impls = self.get_impls(id, cache),
+            _ => {}
+        }
+
+        self.index.borrow_mut().insert(from_def_id(id), new_item);
+        Ok(())
     }
 
     fn mod_item_in(
         &mut self,
-        _item: &clean::Item,
-        _item_name: &str,
-        _cache: &Cache,
+        item: &clean::Item,
+        _module_name: &str,
+        cache: &Cache,
     ) -> Result<(), Error> {
-        unimplemented!()
+        use clean::ItemKind::*;
+        if let ModuleItem(m) = &item.kind {
+            for item in &m.items {
+                match &item.kind {
+                    // These don't have names so they don't get added to the output by default
+                    ImportItem(_) | ExternCrateItem(_, _) | ImplItem(_) => {
+                        self.item(item.clone(), cache)?
+                    }
+                    _ => {}
+                }
+            }
+        }
+        self.item(item.clone(), cache)
     }
 
     fn mod_item_out(&mut self, _item_name: &str) -> Result<(), Error> {
-        unimplemented!()
+        Ok(())
     }
 
-    fn after_krate(&mut self, _krate: &clean::Crate, _cache: &Cache) -> Result<(), Error> {
-        unimplemented!()
+    fn after_krate(&mut self, krate: &clean::Crate, cache: &Cache) -> Result<(), Error> {
+        debug!("Done with crate");
+        let mut index = (*self.index).clone().into_inner();
+        index.extend(self.get_trait_items(cache));
+        let output = types::Crate {
+            root: types::Id(String::from("0:0")),
+            crate_version: cache.crate_version.clone(),
+            includes_private: cache.document_private,
+            index: index.into_iter().collect(),
+            paths: cache
+                .paths
+                .iter()
+                .chain(cache.external_paths.iter())
+                .map(|(&k, (path, kind))| {
+                    (
+                        from_def_id(k),
+                        types::ItemSummary {
+                            crate_id: k.krate.as_u32(),
+                            path: path.clone(),
+                            kind: (*kind).into(),
+                        },
+                    )
+                })
+                .collect(),
+            external_crates: cache
+                .extern_locations
+                .iter()
+                .map(|(k, v)| {
+                    (
+                        k.as_u32(),
+                        types::ExternalCrate {
+                            name: v.0.clone(),
+                            html_root_url: match &v.2 {
+                                ExternalLocation::Remote(s) => Some(s.clone()),
+                                _ => None,
+                            },
+                        },
+                    )
+                })
+                .collect(),
+            format_version: types::FORMAT_VERSION,
+        };
+
+        try_err!(fs::create_dir_all(&self.out_path), &self.out_path);
+        let mut p = self.out_path.clone();
+        p.push(&krate.name);
+        p.set_extension("json");
+        let file = try_err!(File::create(&p), &p);
+        try_err!(serde_json::ser::to_writer(BufWriter::new(file), &output), &p);
+        Ok(())
     }
 
     fn after_run(&mut self, _diag: &rustc_errors::Handler) -> Result<(), Error> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,70 @@
+use rustc_hir::def_id::{CrateNum, DefId, DefIndex};
+use rustdoc_json_types::*;
+
+use super::conversions::from_def_id;
+
+#[test]
+fn id_from_def_id() {
+    let did = DefId { krate: CrateNum::from_u32(2), index: DefIndex::from_u32(17) };
+    assert_eq!(from_def_id(did), Id(String::from("2:17")));
+}
+
+#[test]
+fn types_are_tagged() {
+    let ty = Type::BorrowedRef {
+        lifetime: Some(String::from("'a")),
+        mutable: true,
+        type_: Box::new(Type::Primitive(String::from("str"))),
+    };
+    assert_eq!(
+        serde_json::to_string(&ty).unwrap(),
+        concat!(
+            r#"{"kind":"borrowed_ref","inner":{"lifetime":"'a","mutable":true,"#,
+            r#""type":{"kind":"primitive","inner":"str"}}}"#,
+        )
+    );
+}
+
+#[test]
+fn variants_are_tagged() {
+    let variant = Variant::Struct(vec![Id(String::from("0:3"))]);
+    assert_eq!(
+        serde_json::to_string(&variant).unwrap(),
+        r#"{"variant_kind":"struct","variant_inner":["0:3"]}"#
+    );
+}
+
+#[test]
+fn visibility_is_snake_case() {
+    assert_eq!(serde_json::to_string(&Visibility::Crate).unwrap(), r#""crate""#);
+    let restricted =
+        Visibility::Restricted { parent: Id(String::from("0:1")), path: String::from("::foo") };
+    assert_eq!(
+        serde_json::to_string(&restricted).unwrap(),
+        r#"{"restricted":{"parent":"0:1","path":"::foo"}}"#
+    );
+}
+
+#[test]
+fn untagged_items_round_trip() {
+    let import = ItemEnum::ImportItem(Import {
+        source: String::from("foo::Bar"),
+        name: String::from("Bar"),
+        id: None,
+        glob: false,
+    });
+    let json = serde_json::to_string(&import).unwrap();
+    assert!(matches!(serde_json::from_str(&json).unwrap(), ItemEnum::ImportItem(_)));
+
+    let method = ItemEnum::MethodItem(Method {
+        decl: FnDecl { inputs: vec![], output: None, c_variadic: false },
+        generics: Generics::default(),
+        header: Header { const_: false, unsafe_: false, async_: false, abi: String::from("Rust") },
+        has_body: false,
+    });
+    let json = serde_json::to_string(&method).unwrap();
+    assert!(matches!(
+        serde_json::from_str(&json).unwrap(),
+        ItemEnum::MethodItem(Method { has_body: false, .. })
+    ));
+}
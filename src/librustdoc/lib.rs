@@ -81,6 +81,7 @@
 mod json;
 mod markdown;
 mod passes;
+mod scrape_examples;
 mod theme;
 mod visit_ast;
 mod visit_lib;
@@ -378,6 +379,13 @@ fn opts() -> Vec<RustcOptGroup> {
                 "disables generating the crate selector on the search box",
             )
         }),
+        unstable("full-text-search", |o| {
+            o.optflag(
+                "",
+                "full-text-search",
+                "build an index of the words in the docs for the search to look through",
+            )
+        }),
         unstable("persist-doctests", |o| {
             o.optopt(
                 "",
@@ -386,6 +394,13 @@ fn opts() -> Vec<RustcOptGroup> {
                 "PATH",
             )
         }),
+        unstable("merge-doctests", |o| {
+            o.optflag(
+                "",
+                "merge-doctests",
+                "compile doctests that can share a crate into a single test harness",
+            )
+        }),
         unstable("show-coverage", |o| {
             o.optflag(
                 "",
@@ -424,6 +439,32 @@ fn opts() -> Vec<RustcOptGroup> {
             )
         }),
         unstable("check", |o| o.optflag("", "check", "Run rustdoc checks")),
+        unstable("scrape-examples-output-path", |o| {
+            o.optopt(
+                "",
+                "scrape-examples-output-path",
+                "collect calls to the target crates and write them to this file \
+                 instead of generating documentation",
+                "PATH",
+            )
+        }),
+        unstable("scrape-examples-target-crate", |o| {
+            o.optmulti(
+                "",
+                "scrape-examples-target-crate",
+                "crate whose functions calls should be collected for",
+                "CRATE",
+            )
+        }),
+        unstable("with-examples", |o| {
+            o.optmulti(
+                "",
+                "with-examples",
+                "file of calls collected with --scrape-examples-output-path, to show as \
+                 examples on the pages of the functions being called",
+                "PATH",
+            )
+        }),
     ]
 }
 
@@ -517,6 +558,7 @@ fn main_options(options: config::Options) -> MainResult {
     let diag_opts = (options.error_format, options.edition, options.debugging_opts.clone());
     let show_coverage = options.show_coverage;
     let run_check = options.run_check;
+    let scraping_examples = options.scrape_examples_options.is_some();
 
     // First, parse the crate and extract all relevant information.
     info!("starting to run rustc");
@@ -545,6 +587,10 @@ fn main_options(options: config::Options) -> MainResult {
     } else if run_check {
         // Since we're in "check" mode, no need to generate anything beyond this point.
         return Ok(());
+    } else if scraping_examples {
+        // The calls were written out while the compiler was still running, and the
+        // documentation of this crate isn't needed.
+        return Ok(());
     }
 
     info!("going to format");
@@ -557,5 +603,8 @@ fn main_options(options: config::Options) -> MainResult {
         Some(config::OutputFormat::Json) => sess.time("render_json", || {
             run_renderer::<json::JsonRenderer>(krate, renderopts, renderinfo, &diag, edition)
         }),
+        Some(config::OutputFormat::Lcov) => {
+            unreachable!("lcov output is only produced by --show-coverage")
+        }
     }
 }
@@ -1,11 +1,14 @@
 use crate::clean;
+use crate::config::OutputFormat;
 use crate::core::DocContext;
 use crate::fold::{self, DocFolder};
 use crate::html::markdown::{find_testable_code, ErrorCodes};
 use crate::passes::doc_test_lints::{should_have_doc_example, Tests};
 use crate::passes::Pass;
+use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_lint::builtin::MISSING_DOCS;
 use rustc_middle::lint::LintSource;
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_session::lint;
 use rustc_span::symbol::sym;
 use rustc_span::FileName;
@@ -99,8 +102,43 @@ fn add_assign(&mut self, rhs: Self) {
     }
 }
 
+/// An item that was counted, and where it is in its file.
+#[derive(Serialize, Debug)]
+struct ItemCoverage {
+    /// The full path of the item, starting with the crate name.
+    name: String,
+    kind: &'static str,
+    /// The line and column the item starts at. Lines count from one, columns from zero.
+    begin: (usize, usize),
+    end: (usize, usize),
+    has_docs: bool,
+    /// Whether the item counts as undocumented if it has no docs, which is the case unless
+    /// `missing_docs` was explicitly allowed on it.
+    needs_docs: bool,
+    has_example: bool,
+    needs_example: bool,
+}
+
+impl ItemCoverage {
+    fn counts_docs(&self) -> bool {
+        self.has_docs || self.needs_docs
+    }
+
+    fn counts_example(&self) -> bool {
+        self.has_example || self.needs_example
+    }
+}
+
+/// The counts for a file, along with the items that make them up.
+#[derive(Default, Serialize, Debug)]
+struct FileCoverage {
+    #[serde(flatten)]
+    count: ItemCount,
+    items: Vec<ItemCoverage>,
+}
+
 struct CoverageCalculator<'a, 'b> {
-    items: BTreeMap<FileName, ItemCount>,
+    items: BTreeMap<FileName, FileCoverage>,
     ctx: &'a DocContext<'b>,
 }
 
@@ -119,22 +157,79 @@ fn new(ctx: &'a DocContext<'b>) -> CoverageCalculator<'a, 'b> {
         CoverageCalculator { items: Default::default(), ctx }
     }
 
+    fn item_path(&self, i: &clean::Item) -> String {
+        let krate = self.ctx.tcx.crate_name(LOCAL_CRATE);
+        let path = with_no_trimmed_paths(|| self.ctx.tcx.def_path_str(i.def_id));
+        if path.is_empty() { krate.to_string() } else { format!("{}::{}", krate, path) }
+    }
+
     fn to_json(&self) -> String {
         serde_json::to_string(
             &self
                 .items
                 .iter()
                 .map(|(k, v)| (k.to_string(), v))
-                .collect::<BTreeMap<String, &ItemCount>>(),
+                .collect::<BTreeMap<String, &FileCoverage>>(),
         )
         .expect("failed to convert JSON data to string")
     }
 
-    fn print_results(&self) {
+    /// Prints the results as an LCOV tracefile, so that the tools which annotate source code
+    /// with test coverage can show which items are missing docs.
+    ///
+    /// Every item that counts towards the documented total is a function, which is hit if it has
+    /// docs. A line is hit if all the items that start on it are documented. Items that count
+    /// towards the examples total are branches, which are taken if the item has an example.
+    fn print_lcov(&self) {
+        for (file, coverage) in &self.items {
+            let documented: Vec<_> = coverage.items.iter().filter(|i| i.counts_docs()).collect();
+            let examples: Vec<_> = coverage.items.iter().filter(|i| i.counts_example()).collect();
+            let mut lines = BTreeMap::new();
+            for item in &documented {
+                *lines.entry(item.begin.0).or_insert(true) &= item.has_docs;
+            }
+
+            println!("TN:");
+            println!("SF:{}", file);
+            for item in &documented {
+                println!("FN:{},{}", item.begin.0, item.name);
+            }
+            for item in &documented {
+                println!("FNDA:{},{}", item.has_docs as u8, item.name);
+            }
+            println!("FNF:{}", documented.len());
+            println!("FNH:{}", documented.iter().filter(|i| i.has_docs).count());
+            for (branch, item) in examples.iter().enumerate() {
+                println!("BRDA:{},0,{},{}", item.begin.0, branch, item.has_example as u8);
+            }
+            println!("BRF:{}", examples.len());
+            println!("BRH:{}", examples.iter().filter(|i| i.has_example).count());
+            for (line, &hit) in &lines {
+                println!("DA:{},{}", line, hit as u8);
+            }
+            println!("LF:{}", lines.len());
+            println!("LH:{}", lines.values().filter(|&&hit| hit).count());
+            println!("end_of_record");
+        }
+    }
+
+    fn print_results(&mut self) {
+        // items are folded grouped by their kind, so put them back in the order of the source
+        for coverage in self.items.values_mut() {
+            coverage.items.sort_by(|a, b| (a.begin, &a.name).cmp(&(b.begin, &b.name)));
+        }
+
         let output_format = self.ctx.renderinfo.borrow().output_format;
-        if output_format.map(|o| o.is_json()).unwrap_or_else(|| false) {
-            println!("{}", self.to_json());
-            return;
+        match output_format {
+            Some(OutputFormat::Json) => {
+                println!("{}", self.to_json());
+                return;
+            }
+            Some(OutputFormat::Lcov) => {
+                self.print_lcov();
+                return;
+            }
+            Some(OutputFormat::Html) | None => {}
         }
         let mut total = ItemCount::default();
 
@@ -161,7 +256,8 @@ fn print_table_record(
         );
         print_table_line();
 
-        for (file, &count) in &self.items {
+        for (file, coverage) in &self.items {
+            let count = coverage.count;
             if let Some(percentage) = count.percentage() {
                 print_table_record(
                     &limit_filename_len(file.to_string()),
@@ -258,13 +354,29 @@ fn fold_item(&mut self, i: clean::Item) -> Option<clean::Item> {
                 // unless the user had an explicit `allow`
                 let should_have_docs =
                     level != lint::Level::Allow || matches!(source, LintSource::Default);
+                let should_have_doc_example = should_have_doc_example(self.ctx, &i);
                 debug!("counting {:?} {:?} in {}", i.type_(), i.name, i.source.filename);
-                self.items.entry(i.source.filename.clone()).or_default().count_item(
+                let name = self.item_path(&i);
+                let file = self.items.entry(i.source.filename.clone()).or_default();
+                file.count.count_item(
                     has_docs,
                     has_doc_example,
-                    should_have_doc_example(self.ctx, &i),
+                    should_have_doc_example,
                     should_have_docs,
                 );
+                let item = ItemCoverage {
+                    name,
+                    kind: i.type_().as_str(),
+                    begin: (i.source.loline, i.source.locol),
+                    end: (i.source.hiline, i.source.hicol),
+                    has_docs,
+                    needs_docs: should_have_docs,
+                    has_example: has_doc_example,
+                    needs_example: should_have_doc_example,
+                };
+                if item.counts_docs() || item.counts_example() {
+                    file.items.push(item);
+                }
             }
         }
 
@@ -3,7 +3,7 @@
 use crate::core::DocContext;
 use crate::fold::DocFolder;
 
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_hir::def_id::{DefId, LOCAL_CRATE};
 use rustc_span::symbol::sym;
 
@@ -51,12 +51,27 @@
         }
     }
 
+    // `tcx.crates()` doesn't include the local crate, and `tcx.all_trait_implementations`
+    // doesn't work with it anyway, so pull them from the HIR map instead
+    let mut local_items = Vec::new();
+    for &trait_did in cx.tcx.all_traits(LOCAL_CRATE).iter() {
+        for &impl_node in cx.tcx.hir().trait_impls(trait_did) {
+            let impl_did = cx.tcx.hir().local_def_id(impl_node);
+            cx.tcx.sess.time("build_local_trait_impl", || {
+                inline::build_impl(cx, None, impl_did.to_def_id(), None, &mut local_items);
+            });
+        }
+    }
+
     let mut cleaner = BadImplStripper { prims, items: crate_items };
 
-    // scan through included items ahead of time to splice in Deref targets to the "valid" sets
-    for it in &new_items {
+    // scan through included items ahead of time to splice in Deref targets to the "valid" sets,
+    // following the `Deref` impls of the targets themselves so that the whole chain is kept
+    let mut deref_targets = FxHashMap::default();
+    let mut kept_targets = Vec::new();
+    for it in new_items.iter().chain(&local_items) {
         if let ImplItem(Impl { ref for_, ref trait_, ref items, .. }) = it.kind {
-            if cleaner.keep_item(for_) && trait_.def_id() == cx.tcx.lang_items().deref_trait() {
+            if trait_.def_id() == cx.tcx.lang_items().deref_trait() {
                 let target = items
                     .iter()
                     .find_map(|item| match item.kind {
@@ -65,14 +80,33 @@
                     })
                     .expect("Deref impl without Target type");
 
-                if let Some(prim) = target.primitive_type() {
-                    cleaner.prims.insert(prim);
-                } else if let Some(did) = target.def_id() {
-                    cleaner.items.insert(did);
+                if let Some(did) = for_.def_id() {
+                    deref_targets.insert(did, target);
+                }
+                if cleaner.keep_item(for_) {
+                    kept_targets.push(target);
                 }
             }
         }
     }
+    for mut target in kept_targets {
+        loop {
+            if let Some(prim) = target.primitive_type() {
+                cleaner.prims.insert(prim);
+                break;
+            }
+            match target.def_id() {
+                // If the target was already valid, the rest of its chain is either spliced in by
+                // its own entry in `kept_targets` or by an earlier pass of this loop. This also
+                // stops the loop on `Deref` cycles.
+                Some(did) if cleaner.items.insert(did) => match deref_targets.get(&did) {
+                    Some(&next) => target = next,
+                    None => break,
+                },
+                _ => break,
+            }
+        }
+    }
 
     new_items.retain(|it| {
         if let ImplItem(Impl { ref for_, ref trait_, ref blanket_impl, .. }) = it.kind {
@@ -84,21 +118,11 @@
         }
     });
 
-    // `tcx.crates()` doesn't include the local crate, and `tcx.all_trait_implementations`
-    // doesn't work with it anyway, so pull them from the HIR map instead
-    for &trait_did in cx.tcx.all_traits(LOCAL_CRATE).iter() {
-        for &impl_node in cx.tcx.hir().trait_impls(trait_did) {
-            let impl_did = cx.tcx.hir().local_def_id(impl_node);
-            cx.tcx.sess.time("build_local_trait_impl", || {
-                inline::build_impl(cx, None, impl_did.to_def_id(), None, &mut new_items);
-            });
-        }
-    }
-
     if let Some(ref mut it) = krate.module {
         if let ModuleItem(Module { ref mut items, .. }) = it.kind {
             items.extend(synth.impls);
             items.extend(new_items);
+            items.extend(local_items);
         } else {
             panic!("collect-trait-impls can't run");
         }
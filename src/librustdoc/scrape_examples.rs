@@ -0,0 +1,326 @@
+//! Finds the calls that other crates, like a crate's examples and tests, make to the functions
+//! of the crate being documented, so that they can be shown as usage examples on its pages.
+//!
+//! Scraping is done by running rustdoc on each of the calling crates with
+//! `--scrape-examples-output-path`, which writes the calls it finds to a file instead of
+//! generating documentation. The files are then given to the documentation run of the target
+//! crate with `--with-examples`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
+use rustc_hir::itemlikevisit::ItemLikeVisitor;
+use rustc_hir::{self as hir, HirId};
+use rustc_middle::hir::map::Map;
+use rustc_middle::ty::{self, TyCtxt, TypeckResults};
+use rustc_session::getopts;
+use rustc_span::source_map::FileName;
+use rustc_span::Span;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug)]
+crate struct ScrapeExamplesOptions {
+    /// The file to write the calls that were found to.
+    crate output_path: PathBuf,
+    /// The crates whose functions the calls are looked for.
+    crate target_crates: Vec<String>,
+}
+
+impl ScrapeExamplesOptions {
+    crate fn new(
+        matches: &getopts::Matches,
+        diag: &rustc_errors::Handler,
+    ) -> Result<Option<Self>, i32> {
+        let output_path = matches.opt_str("scrape-examples-output-path");
+        let target_crates = matches.opt_strs("scrape-examples-target-crate");
+        match (output_path, target_crates.is_empty()) {
+            (Some(output_path), false) => Ok(Some(ScrapeExamplesOptions {
+                output_path: PathBuf::from(output_path),
+                target_crates,
+            })),
+            (Some(_), true) => {
+                diag.struct_err(
+                    "must use --scrape-examples-target-crate with --scrape-examples-output-path",
+                )
+                .emit();
+                Err(1)
+            }
+            (None, false) => {
+                diag.struct_err(
+                    "must use --scrape-examples-output-path with --scrape-examples-target-crate",
+                )
+                .emit();
+                Err(1)
+            }
+            (None, true) => Ok(None),
+        }
+    }
+}
+
+/// Calls to a function that were found in a single file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+crate struct CallData {
+    /// The name of the crate the file belongs to.
+    crate krate: String,
+    /// The file the calls are in.
+    crate file: PathBuf,
+    /// The directory of the root module of `krate`, which source pages are laid out relative to.
+    crate src_root: PathBuf,
+    /// The name of the file as it was given to the compiler, to show above the examples.
+    crate display_name: String,
+    crate locations: Vec<CallLocation>,
+}
+
+/// The calls to a function that were found in a single item, like the `main` of an example.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+crate struct CallLocation {
+    /// The source code of the item.
+    crate snippet: String,
+    /// The line of the file that `snippet` starts on, counting from one.
+    crate first_line: usize,
+    /// The byte ranges of the calls in `snippet`.
+    crate calls: Vec<(usize, usize)>,
+}
+
+impl CallLocation {
+    /// The lines of the snippet that calls are on, counting from zero.
+    crate fn call_lines(&self) -> Vec<usize> {
+        let mut lines: Vec<usize> =
+            self.calls.iter().map(|&(lo, _)| self.snippet[..lo].matches('\n').count()).collect();
+        lines.sort_unstable();
+        lines.dedup();
+        lines
+    }
+}
+
+/// The calls found by scraping, keyed by the `def_path_key` of the function being called.
+crate type AllCallLocations = FxHashMap<String, Vec<CallData>>;
+
+/// A name for a function that is the same in every crate that can refer to it, so that it
+/// can be matched up between the crate calling the function and the one documenting it.
+fn def_path_key(tcx: TyCtxt<'_>, def_id: DefId) -> String {
+    format!("{}{}", tcx.crate_name(def_id.krate), tcx.def_path(def_id).to_string_no_crate_verbose())
+}
+
+struct FindCalls<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    options: &'a ScrapeExamplesOptions,
+    src_root: PathBuf,
+    calls: AllCallLocations,
+    /// The span of the item being visited, which is used as the snippet for the calls in it.
+    item_span: Span,
+    maybe_typeck_results: Option<&'tcx TypeckResults<'tcx>>,
+}
+
+impl<'a, 'tcx> FindCalls<'a, 'tcx> {
+    fn is_target(&self, def_id: DefId) -> bool {
+        let krate = self.tcx.crate_name(def_id.krate);
+        self.options.target_crates.iter().any(|name| *name == *krate.as_str())
+    }
+
+    fn add_call(&mut self, def_id: DefId, call_span: Span) {
+        if call_span.from_expansion() || !self.item_span.contains(call_span) {
+            return;
+        }
+        let source_map = self.tcx.sess.source_map();
+        let start = source_map.lookup_char_pos(self.item_span.lo());
+        let file = match start.file.name {
+            FileName::Real(ref name) => name.local_path().to_path_buf(),
+            _ => return,
+        };
+        let snippet = match source_map.span_to_snippet(self.item_span) {
+            Ok(snippet) => snippet,
+            Err(_) => return,
+        };
+        let lo = (call_span.lo() - self.item_span.lo()).0 as usize;
+        let hi = (call_span.hi() - self.item_span.lo()).0 as usize;
+
+        let key = def_path_key(self.tcx, def_id);
+        let file_calls = self.calls.entry(key).or_default();
+        let data = match file_calls.iter().position(|data| data.file == file) {
+            Some(i) => &mut file_calls[i],
+            None => {
+                file_calls.push(CallData {
+                    krate: self.tcx.crate_name(LOCAL_CRATE).to_string(),
+                    file,
+                    src_root: self.src_root.clone(),
+                    display_name: start.file.name.to_string(),
+                    locations: Vec::new(),
+                });
+                file_calls.last_mut().unwrap()
+            }
+        };
+        match data.locations.iter_mut().find(|loc| loc.first_line == start.line) {
+            Some(location) => location.calls.push((lo, hi)),
+            None => data.locations.push(CallLocation {
+                snippet,
+                first_line: start.line,
+                calls: vec![(lo, hi)],
+            }),
+        }
+    }
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for FindCalls<'a, 'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::OnlyBodies(self.tcx.hir())
+    }
+
+    fn visit_nested_body(&mut self, id: hir::BodyId) {
+        let old = self.maybe_typeck_results.replace(self.tcx.typeck_body(id));
+        let body = self.tcx.hir().body(id);
+        self.visit_body(body);
+        self.maybe_typeck_results = old;
+    }
+
+    fn visit_item(&mut self, item: &'tcx hir::Item<'tcx>) {
+        self.item_span = item.span;
+        intravisit::walk_item(self, item);
+    }
+
+    fn visit_trait_item(&mut self, item: &'tcx hir::TraitItem<'tcx>) {
+        self.item_span = item.span;
+        intravisit::walk_trait_item(self, item);
+    }
+
+    fn visit_impl_item(&mut self, item: &'tcx hir::ImplItem<'tcx>) {
+        self.item_span = item.span;
+        intravisit::walk_impl_item(self, item);
+    }
+
+    fn visit_expr(&mut self, ex: &'tcx hir::Expr<'tcx>) {
+        intravisit::walk_expr(self, ex);
+
+        let typeck_results = match self.maybe_typeck_results {
+            Some(typeck_results) => typeck_results,
+            None => return,
+        };
+        let (def_id, span) = match ex.kind {
+            hir::ExprKind::Call(callee, _) => {
+                match typeck_results.node_type(callee.hir_id).kind() {
+                    ty::FnDef(def_id, _) => (*def_id, callee.span),
+                    _ => return,
+                }
+            }
+            hir::ExprKind::MethodCall(_, span, _, _) => {
+                match typeck_results.type_dependent_def_id(ex.hir_id) {
+                    Some(def_id) => (def_id, span),
+                    None => return,
+                }
+            }
+            _ => return,
+        };
+        if self.is_target(def_id) {
+            self.add_call(def_id, span);
+        }
+    }
+}
+
+/// Finds the calls the local crate makes to the target crates, and writes them to the output
+/// path as JSON.
+crate fn run(tcx: TyCtxt<'_>, options: &ScrapeExamplesOptions) {
+    let src_root = tcx
+        .sess
+        .local_crate_source_file
+        .as_ref()
+        .and_then(|p| p.parent())
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let mut finder = FindCalls {
+        tcx,
+        options,
+        src_root,
+        calls: FxHashMap::default(),
+        item_span: rustc_span::DUMMY_SP,
+        maybe_typeck_results: None,
+    };
+    tcx.hir().krate().visit_all_item_likes(&mut finder.as_deep_visitor());
+
+    let result = serde_json::to_string(&finder.calls)
+        .map_err(|e| e.to_string())
+        .and_then(|json| fs::write(&options.output_path, json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        tcx.sess.err(&format!(
+            "failed to write scraped examples to `{}`: {}",
+            options.output_path.display(),
+            e
+        ));
+    }
+    tcx.sess.abort_if_errors();
+}
+
+/// Reads the files given with `--with-examples`, and matches the calls in them up with the
+/// functions of the local crate.
+crate fn load_call_locations(
+    tcx: TyCtxt<'_>,
+    with_examples: &[PathBuf],
+) -> FxHashMap<DefId, Vec<CallData>> {
+    let mut all_calls = AllCallLocations::default();
+    for path in with_examples {
+        let calls = fs::read_to_string(path).map_err(|e| e.to_string()).and_then(|contents| {
+            serde_json::from_str::<AllCallLocations>(&contents).map_err(|e| e.to_string())
+        });
+        match calls {
+            Ok(calls) => {
+                for (key, data) in calls {
+                    all_calls.entry(key).or_default().extend(data);
+                }
+            }
+            Err(e) => {
+                tcx.sess.err(&format!(
+                    "failed to load scraped examples from `{}`: {}",
+                    path.display(),
+                    e
+                ));
+            }
+        }
+    }
+    if all_calls.is_empty() {
+        return FxHashMap::default();
+    }
+
+    let mut collector = LocalFns { tcx, all_calls, call_locations: FxHashMap::default() };
+    tcx.hir().krate().visit_all_item_likes(&mut collector);
+    collector.call_locations
+}
+
+/// Picks the calls to the functions and methods of the local crate out of `all_calls`.
+struct LocalFns<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    all_calls: AllCallLocations,
+    call_locations: FxHashMap<DefId, Vec<CallData>>,
+}
+
+impl<'tcx> LocalFns<'tcx> {
+    fn add(&mut self, hir_id: HirId) {
+        let def_id = self.tcx.hir().local_def_id(hir_id).to_def_id();
+        if let Some(calls) = self.all_calls.remove(&def_path_key(self.tcx, def_id)) {
+            self.call_locations.insert(def_id, calls);
+        }
+    }
+}
+
+impl<'hir, 'tcx> ItemLikeVisitor<'hir> for LocalFns<'tcx> {
+    fn visit_item(&mut self, item: &'hir hir::Item<'hir>) {
+        if let hir::ItemKind::Fn(..) = item.kind {
+            self.add(item.hir_id);
+        }
+    }
+
+    fn visit_trait_item(&mut self, item: &'hir hir::TraitItem<'hir>) {
+        if let hir::TraitItemKind::Fn(..) = item.kind {
+            self.add(item.hir_id);
+        }
+    }
+
+    fn visit_impl_item(&mut self, item: &'hir hir::ImplItem<'hir>) {
+        if let hir::ImplItemKind::Fn(..) = item.kind {
+            self.add(item.hir_id);
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub trait Greet {
+    fn greet(&self) -> String;
+}
+
+pub struct Name(pub String);
@@ -0,0 +1,20 @@
+extern crate dep;
+
+use dep::{Greet, Name};
+
+/// Greets someone by name.
+pub struct Greeter {
+    pub name: Name,
+}
+
+impl Greeter {
+    pub fn new(name: Name) -> Self {
+        Greeter { name }
+    }
+}
+
+impl Greet for Greeter {
+    fn greet(&self) -> String {
+        format!("Hello, {}!", (self.name).0)
+    }
+}
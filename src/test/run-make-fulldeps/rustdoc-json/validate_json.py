@@ -0,0 +1,52 @@
+#!/usr/bin/env python
+
+import sys
+import json
+
+with open(sys.argv[1]) as f:
+    krate = json.load(f)
+index = krate["index"]
+paths = krate["paths"]
+
+
+def item(id):
+    assert id in index, "{} is not in the index".format(id)
+    return index[id]
+
+
+def external_path(ty):
+    """Checks that a type refers to an item of another crate, and returns its path."""
+    assert ty["kind"] == "resolved_path"
+    id = ty["inner"]["id"]
+    summary = paths[id]
+    assert summary["crate_id"] != 0
+    assert krate["external_crates"][str(summary["crate_id"])]["name"] == "dep"
+    return summary["path"]
+
+
+root = item(krate["root"])
+assert root["kind"] == "module" and root["inner"]["is_crate"]
+greeters = [item(id) for id in root["inner"]["items"] if item(id)["name"] == "Greeter"]
+assert len(greeters) == 1
+greeter = greeters[0]
+assert greeter["kind"] == "struct"
+assert greeter["docs"].strip() == "Greets someone by name."
+assert paths[greeter["id"]]["path"] == ["krate", "Greeter"]
+
+fields = [item(id) for id in greeter["inner"]["fields"]]
+assert [field["name"] for field in fields] == ["name"]
+assert external_path(fields[0]["inner"]) == ["dep", "Name"]
+
+impls = [item(id)["inner"] for id in greeter["inner"]["impls"]]
+inherent = [impl for impl in impls if impl["trait"] is None]
+assert len(inherent) == 1
+assert [item(id)["name"] for id in inherent[0]["items"]] == ["new"]
+assert item(inherent[0]["items"][0])["kind"] == "method"
+
+# Leave out the auto trait impls that rustdoc synthesizes.
+trait_impls = [impl for impl in impls if impl["trait"] is not None and not impl["synthetic"]]
+assert len(trait_impls) == 1
+assert trait_impls[0]["for"]["inner"]["id"] == greeter["id"]
+assert external_path(trait_impls[0]["trait"]) == ["dep", "Greet"]
+# Traits of other crates that local impls refer to are included in the index.
+assert item(trait_impls[0]["trait"]["inner"]["id"])["kind"] == "trait"
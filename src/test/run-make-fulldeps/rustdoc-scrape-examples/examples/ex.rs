@@ -0,0 +1,17 @@
+// The line numbers of this file are checked in `src/lib.rs`.
+
+use foobar::{ok, Thing};
+
+struct Helper;
+
+impl Helper {
+    fn run(&self) {
+        let thing = Thing::new();
+        thing.frob();
+    }
+}
+
+fn main() {
+    ok();
+    Helper.run();
+}
@@ -0,0 +1,28 @@
+// @has src/ex/ex.rs.html
+
+// @has foobar/fn.ok.html '//div[@class="scraped-examples"]/h4' 'Examples found in repository'
+// @count - '//div[@class="scraped-example"]' 1
+// @has - '//div[@class="scraped-example-title"]' 'examples/ex.rs'
+// @has - '//div[@class="scraped-example-title"]/a[@class="srclink"]/@href' \
+//     '../src/ex/ex.rs.html#14-17'
+// @count - '//span[@class="line-highlighted"]' 1
+// @has - '//span[@class="line-highlighted"]' '15'
+pub fn ok() {}
+
+// Both calls are made from a method in an impl block of the example.
+// @has foobar/struct.Thing.html '//div[@class="scraped-examples"]/h4' \
+//     'Examples found in repository'
+// @count - '//div[@class="scraped-example"]' 2
+// @has - '//div[@class="scraped-example-title"]/a[@class="srclink"]/@href' \
+//     '../src/ex/ex.rs.html#8-11'
+// @has - '//span[@class="line-highlighted"]' '9'
+// @has - '//span[@class="line-highlighted"]' '10'
+pub struct Thing;
+
+impl Thing {
+    pub fn new() -> Thing {
+        Thing
+    }
+
+    pub fn frob(&self) {}
+}
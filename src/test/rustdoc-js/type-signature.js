@@ -0,0 +1,43 @@
+const QUERY = [
+    'Vec<T> -> Option<T>',
+    'usize, &str -> String',
+    'impl Display, usize -> String',
+    'T -> Wrapper<T>',
+    'Wrapper<T> -> T',
+];
+
+const EXPECTED = [
+    {
+        'others': [
+            { 'path': 'type_signature', 'name': 'first' },
+        ],
+    },
+    {
+        // the order of the inputs doesn't matter, and a generic parameter whose bounds
+        // don't name the type ranks lower
+        'others': [
+            { 'path': 'type_signature', 'name': 'repeat' },
+            { 'path': 'type_signature', 'name': 'describe' },
+        ],
+    },
+    {
+        'others': [
+            { 'path': 'type_signature', 'name': 'describe' },
+        ],
+        'returned': [
+            { 'path': 'type_signature', 'name': 'repeat' },
+            { 'path': 'type_signature', 'name': 'describe' },
+        ],
+    },
+    {
+        'others': [
+            { 'path': 'type_signature::Wrapper', 'name': 'new' },
+        ],
+    },
+    {
+        'others': [
+            { 'path': 'type_signature::Wrapper', 'name': 'into_inner' },
+            { 'path': 'type_signature::Wrapper', 'name': 'new' },
+        ],
+    },
+];
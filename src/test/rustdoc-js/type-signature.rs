@@ -0,0 +1,29 @@
+use std::fmt::Display;
+
+pub struct Wrapper<T>(T);
+
+impl<T> Wrapper<T> {
+    pub fn new(value: T) -> Wrapper<T> {
+        Wrapper(value)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+pub fn first<T>(items: Vec<T>) -> Option<T> {
+    items.into_iter().next()
+}
+
+pub fn repeat(s: &str, times: usize) -> String {
+    s.repeat(times)
+}
+
+pub fn describe<D: Display>(value: D, width: usize) -> String {
+    format!("{:width$}", value, width = width)
+}
+
+pub fn count(s: &str) -> usize {
+    s.len()
+}
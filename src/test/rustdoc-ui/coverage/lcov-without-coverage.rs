@@ -0,0 +1,4 @@
+// compile-flags:-Z unstable-options --output-format lcov
+
+/// Foo
+pub struct Xo;
@@ -0,0 +1,22 @@
+// check-pass
+// compile-flags:-Z unstable-options --output-format lcov --show-coverage
+
+//! The crate root.
+
+/// Documented, with an example.
+///
+/// ```
+/// lcov::documented();
+/// ```
+pub fn documented() {}
+
+pub fn undocumented() {}
+
+/// Documented, without an example.
+pub struct Unit;
+
+/// The variants of this enum aren't documented.
+pub enum Letters { A, B }
+
+#[allow(missing_docs)]
+pub struct Allowed;
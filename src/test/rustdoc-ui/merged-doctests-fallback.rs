@@ -0,0 +1,23 @@
+// Doctests that compile on their own but conflict with each other make the merged crate fail to
+// compile, and are then compiled on their own like the rest of the doctests.
+
+// edition:2018
+// compile-flags:--test --test-args=--test-threads=1 -Z unstable-options --merge-doctests
+// normalize-stdout-test: "src/test/rustdoc-ui" -> "$$DIR"
+// check-pass
+
+/// ```
+/// #[no_mangle]
+/// pub extern "C" fn hook() {}
+///
+/// assert_eq!(module_path!(), "rust_out");
+/// ```
+pub struct First;
+
+/// ```
+/// #[no_mangle]
+/// pub extern "C" fn hook() {}
+///
+/// assert_eq!(module_path!(), "rust_out");
+/// ```
+pub struct Second;
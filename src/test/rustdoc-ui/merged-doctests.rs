@@ -0,0 +1,39 @@
+// edition:2018
+// compile-flags:--test --test-args=--test-threads=1 -Z unstable-options --merge-doctests
+// normalize-stdout-test: "src/test/rustdoc-ui" -> "$$DIR"
+// check-pass
+
+/// ```
+/// assert_eq!(1 + 1, 2);
+/// ```
+pub fn add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+/// ```should_panic
+/// panic!("oh no");
+/// ```
+pub fn fail() {
+    panic!("failed");
+}
+
+/// ```no_run
+/// loop {}
+/// ```
+pub fn never() {}
+
+/// Needs a crate of its own, since it has its own `main`.
+///
+/// ```
+/// fn main() {
+///     assert_eq!(2 + 2, 4);
+/// }
+/// ```
+pub struct OwnMain;
+
+/// Runs from its module in the merged crate.
+///
+/// ```
+/// assert!(module_path!().starts_with("rust_out::__doctest_"));
+/// ```
+pub struct Merged;
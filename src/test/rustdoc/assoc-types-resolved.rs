@@ -0,0 +1,61 @@
+// Associated types of trait impls are shown as what they resolve to for the implementing type,
+// rather than as the projections or generic parameters they're written as.
+
+#![crate_name = "foo"]
+
+pub trait Shape {
+    type Unit;
+}
+
+pub trait Measure {
+    type Output;
+}
+
+pub struct Meters;
+
+// @has foo/struct.Square.html
+// @has - '//*[@id="trait-implementations-list"]//h4[@class="type"]/code' 'type Unit = Meters'
+// @has - '//*[@id="trait-implementations-list"]//h4[@class="type"]/code' 'type Output = Meters'
+// @!has - '//*[@id="trait-implementations-list"]//h4[@class="type"]/code' \
+//      'type Output = <Square as Shape>::Unit'
+pub struct Square;
+
+impl Shape for Square {
+    type Unit = Meters;
+}
+
+impl Measure for Square {
+    type Output = <Square as Shape>::Unit;
+}
+
+// @has foo/trait.Measure.html
+// @has - '//*[@id="implementors-list"]//h4[@class="type"]/code' 'type Output = Meters'
+
+// @has foo/struct.Counter.html
+// @has - '//*[@id="blanket-implementations-list"]//h4[@class="type"]/code' 'type Item = u32'
+// @has - '//*[@id="blanket-implementations-list"]//h4[@class="type"]/code' \
+//      'type IntoIter = Counter'
+// @!has - '//*[@id="blanket-implementations-list"]//h4[@class="type"]/code' \
+//      'type Item = <I as Iterator>::Item'
+pub struct Counter;
+
+impl Iterator for Counter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        None
+    }
+}
+
+// Types that depend on the implementing type's own parameters resolve as far as they can.
+// @has foo/struct.Wrapper.html
+// @has - '//*[@id="blanket-implementations-list"]//h4[@class="type"]/code' 'type Item = T'
+pub struct Wrapper<T>(T);
+
+impl<T> Iterator for Wrapper<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        None
+    }
+}
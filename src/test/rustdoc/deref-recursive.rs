@@ -0,0 +1,73 @@
+// Show all methods reachable via Deref impls, recursing through multiple dereferencing levels
+// and stopping at cycles.
+
+#![crate_name = "foo"]
+
+use std::ops::{Deref, DerefMut};
+
+// @has 'foo/struct.Bar.html'
+// @has '-' '//*[@id="deref-methods"]' 'Methods from Deref<Target = Baz>'
+// @has '-' '//*[@class="impl-items"]//*[@id="method.baz"]' 'pub fn baz(&self)'
+// @has '-' '//*[@id="deref-methods-Qux"]' 'Methods from Deref<Target = Qux>'
+// @has '-' '//*[@class="impl-items"]//*[@id="method.qux"]' 'pub fn qux(&self)'
+// @!has '-' '//*[@class="impl-items"]//*[@id="method.qux_mut"]' 'pub fn qux_mut(&mut self)'
+// @has '-' '//*[@class="sidebar-title"][@href="#deref-methods"]' 'Methods from Deref<Target=Baz>'
+// @has '-' '//*[@class="sidebar-title"][@href="#deref-methods-Qux"]' \
+//      'Methods from Deref<Target=Qux>'
+// @has '-' '//*[@class="sidebar-links"]/a[@href="#method.baz"]' 'baz'
+// @has '-' '//*[@class="sidebar-links"]/a[@href="#method.qux"]' 'qux'
+// @!has '-' '//*[@class="sidebar-links"]/a[@href="#method.qux_mut"]' 'qux_mut'
+pub struct Bar(Baz);
+
+impl Deref for Bar {
+    type Target = Baz;
+    fn deref(&self) -> &Baz { &self.0 }
+}
+
+pub struct Baz(Qux);
+
+impl Baz {
+    pub fn baz(&self) {}
+}
+
+impl Deref for Baz {
+    type Target = Qux;
+    fn deref(&self) -> &Qux { &self.0 }
+}
+
+impl DerefMut for Baz {
+    fn deref_mut(&mut self) -> &mut Qux { &mut self.0 }
+}
+
+pub struct Qux;
+
+impl Qux {
+    pub fn qux(&self) {}
+    pub fn qux_mut(&mut self) {}
+}
+
+// @has 'foo/struct.Ping.html'
+// @has '-' '//*[@id="deref-methods"]' 'Methods from Deref<Target = Pong>'
+// @has '-' '//*[@class="impl-items"]//*[@id="method.pong"]' 'pub fn pong(&self)'
+// @!has '-' '//*[@id="deref-methods-Ping"]' 'Methods from Deref<Target = Ping>'
+pub struct Ping;
+
+impl Ping {
+    pub fn ping(&self) {}
+}
+
+impl Deref for Ping {
+    type Target = Pong;
+    fn deref(&self) -> &Pong { &Pong }
+}
+
+pub struct Pong;
+
+impl Pong {
+    pub fn pong(&self) {}
+}
+
+impl Deref for Pong {
+    type Target = Ping;
+    fn deref(&self) -> &Ping { &Ping }
+}
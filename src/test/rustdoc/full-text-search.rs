@@ -0,0 +1,23 @@
+// compile-flags: -Z unstable-options --full-text-search
+
+#![crate_name = "foo"]
+
+//! Utilities for talking to flaky services.
+
+// @has search-index.js '"f":true'
+// @has foo/full-text-index.js 'registerFullTextIndex("foo", JSON.parse('
+// @has - '"flaky":[0,1]'
+// @has - '"backoff":['
+// @has - '"retry":['
+// @!has - '"the":'
+// @!has - '"zebra":'
+
+/// Sends a request, and retries it with exponential backoff if it fails.
+///
+/// ```
+/// let zebra = 1;
+/// ```
+pub fn send() {}
+
+/// Retries happen at most this many times.
+pub const MAX_RETRIES: u32 = 3;
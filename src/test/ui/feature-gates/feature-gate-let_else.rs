@@ -0,0 +1,6 @@
+fn main() {
+    let Some(x) = Some(1) else { //~ ERROR `let...else` statements are unstable
+        return;
+    };
+    assert_eq!(x, 1);
+}
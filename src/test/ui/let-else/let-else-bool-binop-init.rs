@@ -0,0 +1,8 @@
+// run-rustfix
+
+#![feature(let_else)]
+
+fn main() {
+    let true = true && false else { return }; //~ ERROR a lazy boolean expression
+    let true = true || false else { return }; //~ ERROR a lazy boolean expression
+}
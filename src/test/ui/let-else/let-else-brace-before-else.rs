@@ -0,0 +1,22 @@
+// run-rustfix
+
+#![feature(let_else)]
+
+fn main() {
+    let Some(1) = { Some(1) } else { //~ ERROR right curly brace
+        return;
+    };
+    let Some(1) = loop { break Some(1) } else { //~ ERROR right curly brace
+        return;
+    };
+    let 2 = match 1 { n => n + 1 } else { //~ ERROR right curly brace
+        return;
+    };
+    let Some(1) = unsafe { unsafe_fn() } else { //~ ERROR right curly brace
+        return;
+    };
+}
+
+unsafe fn unsafe_fn<T>() -> T {
+    unimplemented!();
+}
@@ -0,0 +1,21 @@
+// run-pass
+// The initializer of a `let...else` with a type annotation is coerced to that type, as with a
+// plain `let`.
+
+#![feature(let_else)]
+
+fn first(v: &Vec<u32>) -> u32 {
+    let [first, ..]: &[u32] = v else { return 0 };
+    *first
+}
+
+fn main() {
+    assert_eq!(first(&vec![3, 4]), 3);
+    assert_eq!(first(&vec![]), 0);
+
+    let Some(bytes): Option<&[u8]> = Some(&[1, 2, 3]) else { panic!() };
+    assert_eq!(bytes, [1, 2, 3]);
+
+    let [_, rest @ ..]: &[u8] = &[1, 2, 3] else { panic!() };
+    assert_eq!(rest, [2, 3]);
+}
@@ -0,0 +1,42 @@
+// run-pass
+// Checks when the temporaries of the initializer of a `let...else` are dropped: like for a
+// plain `let`, at the end of the statement, so they're still alive while the `else` block runs.
+
+#![feature(let_else)]
+
+use std::cell::RefCell;
+
+struct Guard<'a>(&'static str, &'a RefCell<Vec<&'static str>>);
+
+impl Guard<'_> {
+    fn get(&self, value: Option<u32>) -> Option<u32> {
+        value
+    }
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        self.1.borrow_mut().push(self.0);
+    }
+}
+
+fn run(log: &RefCell<Vec<&'static str>>, value: Option<u32>) {
+    let _a = Guard("a", log);
+    let Some(x) = Guard("temp", log).get(value) else {
+        log.borrow_mut().push("else");
+        return;
+    };
+    let _b = Guard("b", log);
+    log.borrow_mut().push("rest");
+    assert_eq!(x, 1);
+}
+
+fn main() {
+    let log = RefCell::new(Vec::new());
+    run(&log, Some(1));
+    assert_eq!(*log.borrow(), ["temp", "rest", "b", "a"]);
+
+    let log = RefCell::new(Vec::new());
+    run(&log, None);
+    assert_eq!(*log.borrow(), ["else", "temp", "a"]);
+}
@@ -0,0 +1,17 @@
+#![feature(let_else)]
+
+fn main() {
+    let opt = Some(String::new());
+    let Some(s) = opt else {
+        // Nothing is moved out of `opt` when the pattern doesn't match.
+        drop(opt);
+        return;
+    };
+    drop(s);
+    drop(opt); //~ ERROR use of partially moved value: `opt`
+
+    let mut v = vec![1];
+    let [first, ..] = &v[..] else { return };
+    v.push(2); //~ ERROR cannot borrow `v` as mutable because it is also borrowed as immutable
+    drop(first);
+}
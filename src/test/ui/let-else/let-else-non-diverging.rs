@@ -0,0 +1,11 @@
+#![feature(let_else)]
+
+fn main() {
+    let Some(x) = Some(1) else {
+        Some(2) //~ ERROR mismatched types
+    };
+    let Some(y) = Some(1) else { //~ ERROR mismatched types
+        let _ = x;
+    };
+    let Some(_) = Some(x + y) else { panic!() };
+}
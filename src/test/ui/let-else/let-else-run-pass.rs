@@ -0,0 +1,65 @@
+// run-pass
+
+#![feature(let_else)]
+
+enum Shape {
+    Circle(f64),
+    Rect { w: f64, h: f64 },
+}
+
+fn radius(shape: &Shape) -> Option<f64> {
+    let Shape::Circle(r) = shape else { return None };
+    Some(*r)
+}
+
+fn area(shape: Shape) -> f64 {
+    let Shape::Rect { w, h }: Shape = shape else {
+        panic!("not a rectangle");
+    };
+    w * h
+}
+
+fn first_even(v: &[u32]) -> u32 {
+    for &x in v {
+        let 0 = x % 2 else { continue };
+        return x;
+    }
+    0
+}
+
+fn parse_pair(s: &str) -> Result<(u32, u32), String> {
+    let [a, b] = s.split(',').collect::<Vec<_>>()[..] else {
+        return Err(format!("expected two numbers in {:?}", s));
+    };
+    let (Ok(a), Ok(b)) = (a.trim().parse(), b.trim().parse()) else {
+        return Err(format!("invalid numbers in {:?}", s));
+    };
+    Ok((a, b))
+}
+
+fn main() {
+    assert_eq!(radius(&Shape::Circle(2.0)), Some(2.0));
+    assert_eq!(radius(&Shape::Rect { w: 1.0, h: 2.0 }), None);
+    assert_eq!(area(Shape::Rect { w: 3.0, h: 2.0 }), 6.0);
+    assert_eq!(first_even(&[1, 3, 4, 6]), 4);
+    assert_eq!(parse_pair("1, 2"), Ok((1, 2)));
+    assert!(parse_pair("1").is_err());
+    assert!(parse_pair("1, x").is_err());
+
+    // The statements after the `let...else` see its bindings, including in nested blocks.
+    let x = loop {
+        let Some(x) = Some(5) else { break 0 };
+        let y = {
+            let Some(y) = Some(x + 1) else { unreachable!() };
+            y
+        };
+        break x + y;
+    };
+    assert_eq!(x, 11);
+
+    // Bindings by reference work as with `let`.
+    let mut v = Some(vec![1]);
+    let Some(ref mut inner) = v else { return };
+    inner.push(2);
+    assert_eq!(v, Some(vec![1, 2]));
+}
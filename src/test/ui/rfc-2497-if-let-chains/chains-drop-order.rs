@@ -0,0 +1,52 @@
+// run-pass
+// Checks when the temporaries of the operands of a `let` chain are dropped: those of the
+// scrutinee of a `let` live until the end of the `then` block, those of any other operand are
+// dropped right after it's evaluated, and all of them are dropped before the `else` block runs.
+
+#![feature(let_chains)]
+
+use std::cell::RefCell;
+
+struct Guard<'a>(&'static str, &'a RefCell<Vec<&'static str>>);
+
+impl Guard<'_> {
+    fn get(&self, value: Option<u32>) -> Option<u32> {
+        value
+    }
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        self.1.borrow_mut().push(self.0);
+    }
+}
+
+fn run(a: Option<u32>, b: bool, c: Option<u32>) -> Vec<&'static str> {
+    let log = RefCell::new(Vec::new());
+    if let Some(_) = Guard("a", &log).get(a)
+        && Guard("b", &log).get(Some(b as u32)) == Some(1)
+        && let Some(_) = Guard("c", &log).get(c)
+    {
+        log.borrow_mut().push("then");
+    } else {
+        log.borrow_mut().push("else");
+    }
+    log.into_inner()
+}
+
+fn run_while(mut n: u32) -> Vec<&'static str> {
+    let log = RefCell::new(Vec::new());
+    while let Some(_) = Guard("a", &log).get(Some(n)) && n > 0 {
+        log.borrow_mut().push("body");
+        n -= 1;
+    }
+    log.into_inner()
+}
+
+fn main() {
+    assert_eq!(run(Some(1), true, Some(1)), ["b", "then", "c", "a"]);
+    assert_eq!(run(Some(1), true, None), ["b", "c", "a", "else"]);
+    assert_eq!(run(Some(1), false, Some(1)), ["b", "a", "else"]);
+    assert_eq!(run(None, true, Some(1)), ["a", "else"]);
+    assert_eq!(run_while(1), ["body", "a", "a"]);
+}
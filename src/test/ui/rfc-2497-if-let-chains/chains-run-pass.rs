@@ -0,0 +1,75 @@
+// run-pass
+
+#![feature(label_break_value)]
+#![feature(let_chains)]
+
+fn classify(a: Option<i32>, b: Result<i32, ()>) -> &'static str {
+    if let Some(x) = a && x > 3 && let Ok(y) = b && y == x * 2 {
+        "both"
+    } else if let Some(x) = a && (x < 0) {
+        "negative"
+    } else if let Ok(_) = b && a.is_none() {
+        "only b"
+    } else {
+        "neither"
+    }
+}
+
+fn main() {
+    assert_eq!(classify(Some(4), Ok(8)), "both");
+    assert_eq!(classify(Some(4), Ok(7)), "neither");
+    assert_eq!(classify(Some(2), Ok(4)), "neither");
+    assert_eq!(classify(Some(-1), Err(())), "negative");
+    assert_eq!(classify(None, Ok(1)), "only b");
+
+    // The bindings of a `let` are in scope for the rest of the chain, and can be shadowed there.
+    let v = Some(Some(3));
+    let r = if let Some(x) = v && let Some(x) = x && x == 3 { x } else { 0 };
+    assert_eq!(r, 3);
+
+    // Irrefutable patterns are fine in a chain.
+    if let Some(x) = Some(1) && let y = x + 1 && y == 2 {
+    } else {
+        panic!();
+    }
+
+    // `while` chains stop at the first operand that doesn't hold.
+    let mut stack = vec![1, 2, 3, 10, 4];
+    let mut seen = Vec::new();
+    while let Some(x) = stack.pop() && x < 5 {
+        seen.push(x);
+    }
+    assert_eq!(seen, [4]);
+    assert_eq!(stack, [1, 2, 3]);
+
+    let mut it = vec![Some(1), Some(2), None, Some(3)].into_iter();
+    let mut sum = 0;
+    'outer: while let Some(x) = it.next() && let Some(x) = x {
+        if x == 2 {
+            continue 'outer;
+        }
+        sum += x;
+    }
+    assert_eq!(sum, 1);
+
+    // Unlabeled `break` and `continue` in the blocks of an `if` chain are those of the loop.
+    let mut odd = Vec::new();
+    for i in 0..10 {
+        if let Some(x) = Some(i) && x % 2 == 0 {
+            continue;
+        } else if i > 6 {
+            break;
+        }
+        odd.push(i);
+    }
+    assert_eq!(odd, [1, 3, 5]);
+
+    // An `if` chain can be in a labeled block.
+    let x = 'block: {
+        if let Some(x) = Some(5) && x > 1 {
+            break 'block x;
+        }
+        0
+    };
+    assert_eq!(x, 5);
+}
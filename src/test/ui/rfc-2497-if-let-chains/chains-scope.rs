@@ -0,0 +1,14 @@
+#![feature(let_chains)]
+
+fn main() {
+    let opt = Some(1);
+    if y > 0 && let Some(y) = opt { //~ ERROR cannot find value `y` in this scope
+        let _ = y;
+    } else {
+        let _ = y; //~ ERROR cannot find value `y` in this scope
+    }
+    while let Some(x) = opt && x > 0 {
+        break;
+    }
+    let _ = x; //~ ERROR cannot find value `x` in this scope
+}
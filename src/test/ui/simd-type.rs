@@ -4,7 +4,14 @@
 #[repr(simd)]
 struct empty; //~ ERROR SIMD vector cannot be empty
 
+#[repr(simd)]
+struct empty2([f32; 0]); //~ ERROR SIMD vector cannot be empty
+
 #[repr(simd)]
 struct i64f64(i64, f64); //~ ERROR SIMD vector should be homogeneous
 
+#[repr(simd)]
+struct f32x2x2([f32; 2], [f32; 2]);
+//~^ ERROR SIMD vector with an array field should have exactly one field
+
 fn main() {}
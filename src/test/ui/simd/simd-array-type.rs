@@ -0,0 +1,43 @@
+// run-pass
+#![allow(dead_code)]
+
+// pretty-expanded FIXME #23616
+
+#![feature(repr_simd, platform_intrinsics, const_generics)]
+#![allow(incomplete_features)]
+
+use std::mem;
+
+#[repr(simd)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct S([i32; 4]);
+
+#[repr(simd)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct T<const N: usize>([i32; N]);
+
+extern "platform-intrinsic" {
+    fn simd_add<T>(x: T, y: T) -> T;
+    fn simd_extract<T, E>(x: T, idx: u32) -> E;
+    fn simd_shuffle<T, I, U>(x: T, y: T, idx: I) -> U;
+}
+
+const REVERSE: [u32; 4] = [3, 2, 1, 0];
+
+pub fn main() {
+    assert_eq!(mem::size_of::<S>(), 16);
+    assert_eq!(mem::size_of::<T<8>>(), 32);
+
+    let s = S([1, 2, 3, 4]);
+    let t = T::<4>([10, 20, 30, 40]);
+    unsafe {
+        assert_eq!(simd_add(s, s), S([2, 4, 6, 8]));
+        assert_eq!(simd_add(t, t), T([20, 40, 60, 80]));
+        assert_eq!(simd_extract::<_, i32>(t, 2), 30);
+
+        let r: S = simd_shuffle(s, s, REVERSE);
+        assert_eq!(r, S([4, 3, 2, 1]));
+        let w: T<8> = simd_shuffle(t, t, [0u32, 4, 1, 5, 2, 6, 3, 7]);
+        assert_eq!(w, T([10, 10, 20, 20, 30, 30, 40, 40]));
+    }
+}
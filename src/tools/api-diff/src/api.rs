@@ -0,0 +1,85 @@
+//! Finds the public items of a crate by walking its modules from the root, following re-exports
+//! the way a user of the crate would reach them.
+
+use std::collections::BTreeMap;
+
+use rustdoc_json_types::*;
+
+/// What a public path of a crate refers to.
+pub(crate) enum Entry<'a> {
+    /// An item that is described in the crate's JSON.
+    Item(&'a Item),
+    /// A re-export of something the JSON has no details about, like an item of another crate.
+    Reexport(&'a Import),
+}
+
+/// Returns all the public paths of a crate, like `mycrate::io::Reader`. An item that is
+/// re-exported is listed once for each path it can be reached through.
+pub(crate) fn public_items(krate: &Crate) -> BTreeMap<String, Entry<'_>> {
+    let mut collector = Collector { krate, items: BTreeMap::new(), modules: Vec::new() };
+    if let Some(root) = krate.index.get(&krate.root) {
+        collector.add(root.name.clone().unwrap_or_default(), root);
+    }
+    collector.items
+}
+
+pub(crate) fn is_public(item: &Item) -> bool {
+    matches!(item.visibility, Visibility::Public)
+}
+
+struct Collector<'a> {
+    krate: &'a Crate,
+    items: BTreeMap<String, Entry<'a>>,
+    /// The modules being walked, to avoid looping forever when a module re-exports one of its
+    /// parents.
+    modules: Vec<&'a Id>,
+}
+
+impl<'a> Collector<'a> {
+    fn add(&mut self, path: String, item: &'a Item) {
+        if let ItemEnum::ModuleItem(module) = &item.inner {
+            self.module(&path, &item.id, module);
+        }
+        self.items.insert(path, Entry::Item(item));
+    }
+
+    fn module(&mut self, path: &str, id: &'a Id, module: &'a Module) {
+        if self.modules.contains(&id) {
+            return;
+        }
+        self.modules.push(id);
+        let krate = self.krate;
+        for item in module.items.iter().filter_map(|id| krate.index.get(id)) {
+            if !is_public(item) {
+                continue;
+            }
+            match &item.inner {
+                ItemEnum::ImportItem(import) => self.import(path, import),
+                // Impls are compared as part of the types they are for.
+                ItemEnum::ImplItem(_) | ItemEnum::ExternCrateItem { .. } => {}
+                _ => {
+                    if let Some(name) = &item.name {
+                        self.add(format!("{}::{}", path, name), item);
+                    }
+                }
+            }
+        }
+        self.modules.pop();
+    }
+
+    fn import(&mut self, path: &str, import: &'a Import) {
+        let target = import.id.as_ref().and_then(|id| self.krate.index.get(id));
+        match target {
+            Some(item) if import.glob => {
+                if let ItemEnum::ModuleItem(module) = &item.inner {
+                    self.module(path, &item.id, module);
+                }
+            }
+            Some(item) => self.add(format!("{}::{}", path, import.name), item),
+            None => {
+                let name = if import.glob { "*" } else { &import.name };
+                self.items.insert(format!("{}::{}", path, name), Entry::Reexport(import));
+            }
+        }
+    }
+}
@@ -0,0 +1,775 @@
+//! Compares the public API of two versions of a crate, as described by rustdoc's JSON output
+//! (`rustdoc --output-format json`), and reports which changes need a new major or minor
+//! version under [RFC 1105].
+//!
+//! [RFC 1105]: https://github.com/rust-lang/rfcs/blob/master/text/1105-api-evolution.md
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+use rustdoc_json_types::*;
+
+use crate::api::Entry;
+use crate::print::Printer;
+
+mod api;
+mod print;
+
+#[cfg(test)]
+mod tests;
+
+/// How much a change can affect the users of a crate, from least to most.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Doesn't change the public API.
+    Patch,
+    /// Adds to the public API without breaking existing users.
+    Minor,
+    /// Can break existing users of the crate.
+    Major,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Patch => "patch",
+            Severity::Minor => "minor",
+            Severity::Major => "major",
+        })
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "patch" => Ok(Severity::Patch),
+            "minor" => Ok(Severity::Minor),
+            "major" => Ok(Severity::Major),
+            _ => Err(format!("unknown version bump `{}`, expected patch, minor or major", s)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Change {
+    pub severity: Severity,
+    /// The public path of the item that changed.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: `{}`: {}", self.severity, self.path, self.message)
+    }
+}
+
+/// Lists the changes made to the public API of a crate between two versions of it.
+pub fn compare(old: &Crate, new: &Crate) -> Vec<Change> {
+    let mut cx = Comparison { old, new, changes: Vec::new() };
+    cx.crates();
+    cx.changes
+}
+
+/// The version bump needed to publish the given changes.
+pub fn required_bump(changes: &[Change]) -> Severity {
+    changes.iter().map(|c| c.severity).max().unwrap_or(Severity::Patch)
+}
+
+struct Comparison<'a> {
+    old: &'a Crate,
+    new: &'a Crate,
+    changes: Vec<Change>,
+}
+
+/// The parts of a function or method that matter to its callers.
+struct Callable<'b> {
+    decl: &'b FnDecl,
+    header: &'b Header,
+    /// The generics of the impl or trait a method is in. Only used to print the signature,
+    /// since they are compared along with the impl or trait.
+    parent: &'b Generics,
+    generics: &'b Generics,
+}
+
+impl<'b> Callable<'b> {
+    fn function(f: &'b Function, no_generics: &'b Generics) -> Self {
+        Callable { decl: &f.decl, header: &f.header, parent: no_generics, generics: &f.generics }
+    }
+
+    fn method(m: &'b Method, parent: &'b Generics) -> Self {
+        Callable { decl: &m.decl, header: &m.header, parent, generics: &m.generics }
+    }
+}
+
+impl<'a> Comparison<'a> {
+    fn push(&mut self, severity: Severity, path: &str, message: String) {
+        self.changes.push(Change { severity, path: path.to_string(), message });
+    }
+
+    fn crates(&mut self) {
+        let old_items = api::public_items(self.old);
+        let new_items = api::public_items(self.new);
+
+        // When a whole module is added or removed, only the module itself is reported.
+        let parent_exists = |path: &str, items: &BTreeMap<String, Entry<'_>>| match path.rfind("::")
+        {
+            Some(i) => items.contains_key(&path[..i]),
+            None => true,
+        };
+
+        for (path, old_entry) in &old_items {
+            match new_items.get(path) {
+                Some(new_entry) => self.entry(path, old_entry, new_entry),
+                None if parent_exists(path, &new_items) => {
+                    self.push(Severity::Major, path, format!("{} was removed", describe(old_entry)))
+                }
+                None => {}
+            }
+        }
+        for (path, new_entry) in &new_items {
+            if !old_items.contains_key(path) && parent_exists(path, &old_items) {
+                self.push(Severity::Minor, path, format!("{} was added", describe(new_entry)));
+            }
+        }
+    }
+
+    fn entry(&mut self, path: &str, old: &Entry<'a>, new: &Entry<'a>) {
+        // Re-exports of other crates can't be looked into, so only their presence is compared.
+        let (old, new) = match (old, new) {
+            (Entry::Item(old), Entry::Item(new)) => (*old, *new),
+            _ => return,
+        };
+        let (old_kind, new_kind) = (kind_name(&old.kind), kind_name(&new.kind));
+        if old_kind != new_kind {
+            let message = format!("changed from {} to {}", article(old_kind), article(new_kind));
+            return self.push(Severity::Major, path, message);
+        }
+        if old.deprecation.is_none() && new.deprecation.is_some() {
+            self.push(Severity::Minor, path, String::from("was deprecated"));
+        }
+
+        match (&old.inner, &new.inner) {
+            (ItemEnum::FunctionItem(o), ItemEnum::FunctionItem(n)) => {
+                let no_generics = Generics::default();
+                let (o, n) =
+                    (Callable::function(o, &no_generics), Callable::function(n, &no_generics));
+                self.callable(path, o, n)
+            }
+            (ItemEnum::StructItem(o), ItemEnum::StructItem(n)) => {
+                let (old_type, new_type) =
+                    (struct_type_name(&o.struct_type), struct_type_name(&n.struct_type));
+                if old_type != new_type {
+                    let message =
+                        format!("changed from a {} struct to a {} struct", old_type, new_type);
+                    self.push(Severity::Major, path, message);
+                }
+                self.non_exhaustive(path, old, new);
+                self.generics(path, &o.generics, &n.generics);
+                self.fields(
+                    path,
+                    (old, &o.fields, o.fields_stripped),
+                    (new, &n.fields, n.fields_stripped),
+                );
+                self.impls(path, &o.impls, &n.impls);
+            }
+            (ItemEnum::UnionItem(o), ItemEnum::UnionItem(n)) => {
+                self.generics(path, &o.generics, &n.generics);
+                self.fields(
+                    path,
+                    (old, &o.fields, o.fields_stripped),
+                    (new, &n.fields, n.fields_stripped),
+                );
+                self.impls(path, &o.impls, &n.impls);
+            }
+            (ItemEnum::EnumItem(o), ItemEnum::EnumItem(n)) => {
+                self.non_exhaustive(path, old, new);
+                self.generics(path, &o.generics, &n.generics);
+                self.variants(path, (old, &o.variants), &n.variants);
+                self.impls(path, &o.impls, &n.impls);
+            }
+            (ItemEnum::TraitItem(o), ItemEnum::TraitItem(n)) => self.trait_(path, o, n),
+            (ItemEnum::TypedefItem(o), ItemEnum::TypedefItem(n)) => {
+                self.generics(path, &o.generics, &n.generics);
+                self.ty(path, "type", (&o.type_, &o.generics), (&n.type_, &n.generics));
+            }
+            (ItemEnum::ConstantItem(o), ItemEnum::ConstantItem(n)) => {
+                let no_generics = Generics::default();
+                self.ty(path, "type", (&o.type_, &no_generics), (&n.type_, &no_generics));
+            }
+            (ItemEnum::StaticItem(o), ItemEnum::StaticItem(n)) => {
+                let no_generics = Generics::default();
+                self.ty(path, "type", (&o.type_, &no_generics), (&n.type_, &no_generics));
+                if o.mutable != n.mutable {
+                    let message = if n.mutable { "became mutable" } else { "is no longer mutable" };
+                    self.push(Severity::Major, path, String::from(message));
+                }
+            }
+            (ItemEnum::TraitAliasItem(o), ItemEnum::TraitAliasItem(n)) => {
+                self.generics(path, &o.generics, &n.generics);
+                self.supertraits(path, (&o.params, &o.generics), (&n.params, &n.generics));
+            }
+            (ItemEnum::ProcMacroItem(o), ItemEnum::ProcMacroItem(n)) => {
+                for helper in o.helpers.iter().filter(|h| !n.helpers.contains(h)) {
+                    let message = format!("helper attribute `{}` was removed", helper);
+                    self.push(Severity::Major, path, message);
+                }
+                for helper in n.helpers.iter().filter(|h| !o.helpers.contains(h)) {
+                    let message = format!("helper attribute `{}` was added", helper);
+                    self.push(Severity::Minor, path, message);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn callable(&mut self, path: &str, old: Callable<'_>, new: Callable<'_>) {
+        let (o, n) = (old.header, new.header);
+        if o.unsafe_ != n.unsafe_ {
+            if n.unsafe_ {
+                self.push(Severity::Major, path, String::from("became unsafe"));
+            } else {
+                self.push(Severity::Minor, path, String::from("is no longer unsafe"));
+            }
+        }
+        if o.const_ != n.const_ {
+            if n.const_ {
+                self.push(Severity::Minor, path, String::from("became a `const fn`"));
+            } else {
+                self.push(Severity::Major, path, String::from("is no longer a `const fn`"));
+            }
+        }
+        if o.async_ != n.async_ {
+            let message = if n.async_ { "became async" } else { "is no longer async" };
+            self.push(Severity::Major, path, String::from(message));
+        }
+        if o.abi != n.abi {
+            let message = format!("ABI changed from `{}` to `{}`", o.abi, n.abi);
+            self.push(Severity::Major, path, message);
+        }
+
+        let (old_printer, new_printer) =
+            self.printers(&merge(old.parent, old.generics), &merge(new.parent, new.generics));
+        let old_sig = old_printer.signature(old.decl);
+        let new_sig = new_printer.signature(new.decl);
+        if old_sig != new_sig {
+            let message = format!("signature changed from `{}` to `{}`", old_sig, new_sig);
+            self.push(Severity::Major, path, message);
+        }
+        self.generic_params(path, old.generics, new.generics);
+        self.bounds(path, (old.parent, old.generics), (new.parent, new.generics));
+    }
+
+    /// Compares generic parameters and the bounds on them. Requiring more from the users of an
+    /// item is a breaking change, while requiring less is not.
+    fn generics(&mut self, path: &str, old: &Generics, new: &Generics) {
+        let no_generics = Generics::default();
+        self.generic_params(path, old, new);
+        self.bounds(path, (&no_generics, old), (&no_generics, new));
+    }
+
+    fn generic_params(&mut self, path: &str, old: &Generics, new: &Generics) {
+        let old_params: Vec<_> = old.params.iter().filter(|p| !is_lifetime(p)).collect();
+        let new_params: Vec<_> = new.params.iter().filter(|p| !is_lifetime(p)).collect();
+        for param in new_params.iter().skip(old_params.len()) {
+            if has_default(param) {
+                let message =
+                    format!("generic parameter `{}` with a default was added", param.name);
+                self.push(Severity::Minor, path, message);
+            } else {
+                let message = format!("generic parameter `{}` was added", param.name);
+                self.push(Severity::Major, path, message);
+            }
+        }
+        for param in old_params.iter().skip(new_params.len()) {
+            let message = format!("generic parameter `{}` was removed", param.name);
+            self.push(Severity::Major, path, message);
+        }
+    }
+
+    /// Compares the bounds of some generics, which can refer to the parameters of their parent.
+    fn bounds(
+        &mut self,
+        path: &str,
+        (old_parent, old): (&Generics, &Generics),
+        (new_parent, new): (&Generics, &Generics),
+    ) {
+        let (old_printer, new_printer) =
+            self.printers(&merge(old_parent, old), &merge(new_parent, new));
+        let old_bounds = old_printer.bounds_by_subject(old);
+        let new_bounds = new_printer.bounds_by_subject(new);
+        let subjects: BTreeSet<&String> = old_bounds.keys().chain(new_bounds.keys()).collect();
+        let empty = BTreeSet::new();
+        for subject in subjects {
+            let old = old_bounds.get(subject).unwrap_or(&empty);
+            let new = new_bounds.get(subject).unwrap_or(&empty);
+            // `?Sized` and other relaxed bounds work the other way around.
+            for bound in new.difference(old) {
+                let severity =
+                    if bound.starts_with('?') { Severity::Minor } else { Severity::Major };
+                let message = format!("bound `{}: {}` was added", subject, bound);
+                self.push(severity, path, message);
+            }
+            for bound in old.difference(new) {
+                let severity =
+                    if bound.starts_with('?') { Severity::Major } else { Severity::Minor };
+                let message = format!("bound `{}: {}` was removed", subject, bound);
+                self.push(severity, path, message);
+            }
+        }
+    }
+
+    fn ty(&mut self, path: &str, what: &str, old: (&Type, &Generics), new: (&Type, &Generics)) {
+        let (old_printer, new_printer) = self.printers(old.1, new.1);
+        let (old_ty, new_ty) = (old_printer.ty(old.0), new_printer.ty(new.0));
+        if old_ty != new_ty {
+            let message = format!("{} changed from `{}` to `{}`", what, old_ty, new_ty);
+            self.push(Severity::Major, path, message);
+        }
+    }
+
+    fn non_exhaustive(&mut self, path: &str, old: &Item, new: &Item) {
+        match (is_non_exhaustive(old), is_non_exhaustive(new)) {
+            (false, true) => {
+                self.push(Severity::Major, path, String::from("became `#[non_exhaustive]`"))
+            }
+            (true, false) => {
+                let message = String::from("is no longer `#[non_exhaustive]`");
+                self.push(Severity::Minor, path, message)
+            }
+            _ => {}
+        }
+    }
+
+    /// Compares the public fields of a struct or union. Users outside the crate can only build
+    /// one with a literal if all of its fields are public.
+    fn fields(
+        &mut self,
+        path: &str,
+        (old_item, old_ids, old_stripped): (&Item, &[Id], bool),
+        (new_item, new_ids, new_stripped): (&Item, &[Id], bool),
+    ) {
+        let (old_fields, old_private) = fields(self.old, old_ids);
+        let (new_fields, new_private) = fields(self.new, new_ids);
+        let constructible = !old_stripped && !old_private && !is_non_exhaustive(old_item);
+        let (old_printer, new_printer) =
+            self.printers(generics_of(old_item), generics_of(new_item));
+
+        for (name, old_ty) in &old_fields {
+            let field_path = format!("{}::{}", path, name);
+            match new_fields.get(name) {
+                Some(new_ty) => {
+                    let (old_ty, new_ty) = (old_printer.ty(old_ty), new_printer.ty(new_ty));
+                    if old_ty != new_ty {
+                        let message = format!("type changed from `{}` to `{}`", old_ty, new_ty);
+                        self.push(Severity::Major, &field_path, message);
+                    }
+                }
+                None => self.push(Severity::Major, &field_path, String::from("field was removed")),
+            }
+        }
+        for name in new_fields.keys().filter(|name| !old_fields.contains_key(*name)) {
+            let field_path = format!("{}::{}", path, name);
+            if constructible {
+                let message = String::from("field was added to a struct with no private fields");
+                self.push(Severity::Major, &field_path, message);
+            } else {
+                self.push(Severity::Minor, &field_path, String::from("field was added"));
+            }
+        }
+        if constructible && (new_stripped || new_private) {
+            let message = String::from("private fields were added, so it can no longer be built");
+            self.push(Severity::Major, path, message);
+        }
+    }
+
+    fn variants(&mut self, path: &str, (old_item, old_ids): (&Item, &[Id]), new_ids: &[Id]) {
+        let old_variants = variants(self.old, old_ids);
+        let new_variants = variants(self.new, new_ids);
+        let old_printer = Printer::new(self.old);
+        let new_printer = Printer::new(self.new);
+
+        for (name, old_variant) in &old_variants {
+            let variant_path = format!("{}::{}", path, name);
+            match new_variants.get(name) {
+                Some(new_variant) => {
+                    let old_shape = old_printer.variant(old_variant);
+                    let new_shape = new_printer.variant(new_variant);
+                    if old_shape != new_shape {
+                        let message = format!(
+                            "changed from `{}{}` to `{}{}`",
+                            name, old_shape, name, new_shape
+                        );
+                        self.push(Severity::Major, &variant_path, message);
+                    }
+                }
+                None => {
+                    self.push(Severity::Major, &variant_path, String::from("variant was removed"))
+                }
+            }
+        }
+        for name in new_variants.keys().filter(|name| !old_variants.contains_key(*name)) {
+            let variant_path = format!("{}::{}", path, name);
+            if is_non_exhaustive(old_item) {
+                self.push(Severity::Minor, &variant_path, String::from("variant was added"));
+            } else {
+                let message = String::from("variant was added to an exhaustive enum");
+                self.push(Severity::Major, &variant_path, message);
+            }
+        }
+    }
+
+    fn trait_(&mut self, path: &str, old: &Trait, new: &Trait) {
+        if old.is_unsafe != new.is_unsafe {
+            let message = if new.is_unsafe { "became unsafe" } else { "is no longer unsafe" };
+            self.push(Severity::Major, path, String::from(message));
+        }
+        if old.is_auto != new.is_auto {
+            let message = if new.is_auto { "became an auto trait" } else { "is no longer auto" };
+            self.push(Severity::Major, path, String::from(message));
+        }
+        self.generics(path, &old.generics, &new.generics);
+        self.supertraits(path, (&old.bounds, &old.generics), (&new.bounds, &new.generics));
+
+        let old_items: BTreeMap<_, _> = named_items(self.old, &old.items, |_| true).collect();
+        let new_items: BTreeMap<_, _> = named_items(self.new, &new.items, |_| true).collect();
+        for (name, old_item) in &old_items {
+            let item_path = format!("{}::{}", path, name);
+            match new_items.get(name) {
+                Some(new_item) => self.trait_item(&item_path, (old_item, old), (new_item, new)),
+                None => {
+                    let message = format!("{} was removed", kind_name(&old_item.kind));
+                    self.push(Severity::Major, &item_path, message);
+                }
+            }
+        }
+        for (name, new_item) in new_items.iter().filter(|(name, _)| !old_items.contains_key(*name))
+        {
+            let item_path = format!("{}::{}", path, name);
+            if is_required(new_item) {
+                let message = format!("required {} was added", kind_name(&new_item.kind));
+                self.push(Severity::Major, &item_path, message);
+            } else {
+                let message = format!("{} with a default was added", kind_name(&new_item.kind));
+                self.push(Severity::Minor, &item_path, message);
+            }
+        }
+    }
+
+    fn trait_item(&mut self, path: &str, old: (&Item, &Trait), new: (&Item, &Trait)) {
+        let ((old_item, old_trait), (new_item, new_trait)) = (old, new);
+        match (is_required(old_item), is_required(new_item)) {
+            (false, true) => {
+                self.push(Severity::Major, path, String::from("no longer has a default"))
+            }
+            (true, false) => self.push(Severity::Minor, path, String::from("now has a default")),
+            _ => {}
+        }
+        match (&old_item.inner, &new_item.inner) {
+            (ItemEnum::MethodItem(o), ItemEnum::MethodItem(n)) => self.callable(
+                path,
+                Callable::method(o, &old_trait.generics),
+                Callable::method(n, &new_trait.generics),
+            ),
+            (
+                ItemEnum::AssocConstItem { type_: old_ty, .. },
+                ItemEnum::AssocConstItem { type_: new_ty, .. },
+            ) => {
+                self.ty(path, "type", (old_ty, &old_trait.generics), (new_ty, &new_trait.generics))
+            }
+            (
+                ItemEnum::AssocTypeItem { bounds: old_bounds, .. },
+                ItemEnum::AssocTypeItem { bounds: new_bounds, .. },
+            ) => {
+                // Implementations have to satisfy the bounds, and users rely on them, so any
+                // change breaks someone.
+                let (old_printer, new_printer) =
+                    self.printers(&old_trait.generics, &new_trait.generics);
+                let old_bounds: BTreeSet<_> =
+                    old_bounds.iter().map(|b| old_printer.bound(b)).collect();
+                let new_bounds: BTreeSet<_> =
+                    new_bounds.iter().map(|b| new_printer.bound(b)).collect();
+                for bound in new_bounds.difference(&old_bounds) {
+                    self.push(Severity::Major, path, format!("bound `{}` was added", bound));
+                }
+                for bound in old_bounds.difference(&new_bounds) {
+                    self.push(Severity::Major, path, format!("bound `{}` was removed", bound));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Supertraits are both a promise to users and a requirement for implementations, so
+    /// adding and removing them are both breaking.
+    fn supertraits(
+        &mut self,
+        path: &str,
+        old: (&[GenericBound], &Generics),
+        new: (&[GenericBound], &Generics),
+    ) {
+        let (old_printer, new_printer) = self.printers(old.1, new.1);
+        let old_bounds: BTreeSet<_> = old.0.iter().map(|b| old_printer.bound(b)).collect();
+        let new_bounds: BTreeSet<_> = new.0.iter().map(|b| new_printer.bound(b)).collect();
+        for bound in new_bounds.difference(&old_bounds) {
+            self.push(Severity::Major, path, format!("supertrait `{}` was added", bound));
+        }
+        for bound in old_bounds.difference(&new_bounds) {
+            self.push(Severity::Major, path, format!("supertrait `{}` was removed", bound));
+        }
+    }
+
+    /// Compares the trait implementations and inherent methods of a type.
+    fn impls(&mut self, path: &str, old_ids: &[Id], new_ids: &[Id]) {
+        let old_impls = impls(self.old, old_ids);
+        let new_impls = impls(self.new, new_ids);
+
+        let mut matched = BTreeSet::new();
+        for (header, old_impl) in &old_impls.traits {
+            // The new version of the impl may name its generic parameters differently.
+            let new = new_impls.traits.get_key_value(header).or_else(|| {
+                new_impls.traits.iter().find(|(new_header, new_impl)| {
+                    let (old_printer, _) = self.printers(&old_impl.generics, &new_impl.generics);
+                    impl_header(&old_printer, old_impl) == **new_header
+                })
+            });
+            match new {
+                Some((new_header, new_impl)) => {
+                    matched.insert(new_header);
+                    let impl_path = format!("{} ({})", path, new_header);
+                    self.generics(&impl_path, &old_impl.generics, &new_impl.generics);
+                }
+                None => self.push(Severity::Major, path, format!("`{}` was removed", header)),
+            }
+        }
+        for header in new_impls.traits.keys().filter(|h| !matched.contains(h)) {
+            self.push(Severity::Minor, path, format!("`{}` was added", header));
+        }
+
+        for (name, (old_impl, old_item)) in &old_impls.inherent {
+            let item_path = format!("{}::{}", path, name);
+            let (new_impl, new_item) = match new_impls.inherent.get(name) {
+                Some(new) => new,
+                None => {
+                    let message = format!("{} was removed", kind_name(&old_item.kind));
+                    self.push(Severity::Major, &item_path, message);
+                    continue;
+                }
+            };
+            let no_generics = Generics::default();
+            self.bounds(
+                &item_path,
+                (&no_generics, &old_impl.generics),
+                (&no_generics, &new_impl.generics),
+            );
+            match (&old_item.inner, &new_item.inner) {
+                (ItemEnum::MethodItem(o), ItemEnum::MethodItem(n)) => self.callable(
+                    &item_path,
+                    Callable::method(o, &old_impl.generics),
+                    Callable::method(n, &new_impl.generics),
+                ),
+                (
+                    ItemEnum::AssocConstItem { type_: old_ty, .. },
+                    ItemEnum::AssocConstItem { type_: new_ty, .. },
+                ) => self.ty(
+                    &item_path,
+                    "type",
+                    (old_ty, &old_impl.generics),
+                    (new_ty, &new_impl.generics),
+                ),
+                _ => {
+                    let message = format!(
+                        "changed from {} to {}",
+                        article(kind_name(&old_item.kind)),
+                        article(kind_name(&new_item.kind))
+                    );
+                    self.push(Severity::Major, &item_path, message);
+                }
+            }
+        }
+        for (name, (_, new_item)) in &new_impls.inherent {
+            if !old_impls.inherent.contains_key(name) {
+                let message = format!("{} was added", kind_name(&new_item.kind));
+                self.push(Severity::Minor, &format!("{}::{}", path, name), message);
+            }
+        }
+    }
+
+    /// Returns printers for the old and new version of an item, where the old one uses the
+    /// generic parameter names of the new one.
+    fn printers(&self, old: &Generics, new: &Generics) -> (Printer<'a>, Printer<'a>) {
+        (Printer::with_renames(self.old, print::renames(old, new)), Printer::new(self.new))
+    }
+}
+
+/// The impls of a type, keyed so they can be matched up between versions.
+struct Impls<'a> {
+    /// Trait impls, keyed by their header like `impl Clone for Foo<T>`.
+    traits: BTreeMap<String, &'a Impl>,
+    /// The public items of inherent impls, by name.
+    inherent: BTreeMap<&'a str, (&'a Impl, &'a Item)>,
+}
+
+fn impls<'a>(krate: &'a Crate, ids: &[Id]) -> Impls<'a> {
+    let printer = Printer::new(krate);
+    let mut impls = Impls { traits: BTreeMap::new(), inherent: BTreeMap::new() };
+    for impl_ in ids.iter().filter_map(|id| krate.index.get(id)) {
+        let impl_ = match &impl_.inner {
+            ItemEnum::ImplItem(impl_) => impl_,
+            _ => continue,
+        };
+        match &impl_.trait_ {
+            Some(_) => {
+                impls.traits.insert(impl_header(&printer, impl_), impl_);
+            }
+            None => {
+                for (name, item) in named_items(krate, &impl_.items, api::is_public) {
+                    impls.inherent.insert(name, (impl_, item));
+                }
+            }
+        }
+    }
+    impls
+}
+
+/// Prints the header of a trait impl, like `impl Clone for Foo<T>`.
+fn impl_header(printer: &Printer<'_>, impl_: &Impl) -> String {
+    let trait_ = impl_.trait_.as_ref().map(|t| printer.ty(t)).unwrap_or_default();
+    let negative = if impl_.negative { "!" } else { "" };
+    format!("impl {}{} for {}", negative, trait_, printer.ty(&impl_.for_))
+}
+
+/// Returns the public fields by name, and whether there are any private ones.
+fn fields<'a>(krate: &'a Crate, ids: &[Id]) -> (BTreeMap<&'a str, &'a Type>, bool) {
+    let mut private = false;
+    let mut fields = BTreeMap::new();
+    for (name, field) in named_items(krate, ids, |_| true) {
+        match &field.inner {
+            ItemEnum::StructFieldItem(ty) if api::is_public(field) => {
+                fields.insert(name, ty);
+            }
+            _ => private = true,
+        }
+    }
+    (fields, private)
+}
+
+fn variants<'a>(krate: &'a Crate, ids: &[Id]) -> BTreeMap<&'a str, &'a Variant> {
+    named_items(krate, ids, |_| true)
+        .filter_map(|(name, item)| match &item.inner {
+            ItemEnum::VariantItem(variant) => Some((name, variant)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn named_items<'a, 'i>(
+    krate: &'a Crate,
+    ids: &'i [Id],
+    filter: impl Fn(&Item) -> bool + 'i,
+) -> impl Iterator<Item = (&'a str, &'a Item)> + 'i
+where
+    'a: 'i,
+{
+    ids.iter()
+        .filter_map(move |id| krate.index.get(id))
+        .filter(move |item| filter(item))
+        .filter_map(|item| Some((item.name.as_deref()?, item)))
+}
+
+/// Whether implementations of a trait have to provide this item.
+fn is_required(item: &Item) -> bool {
+    match &item.inner {
+        ItemEnum::MethodItem(method) => !method.has_body,
+        ItemEnum::AssocConstItem { default, .. } => default.is_none(),
+        ItemEnum::AssocTypeItem { default, .. } => default.is_none(),
+        _ => false,
+    }
+}
+
+fn is_non_exhaustive(item: &Item) -> bool {
+    item.attrs.iter().any(|attr| attr.contains("non_exhaustive"))
+}
+
+fn is_lifetime(param: &GenericParamDef) -> bool {
+    matches!(param.kind, GenericParamDefKind::Lifetime)
+}
+
+fn has_default(param: &GenericParamDef) -> bool {
+    matches!(param.kind, GenericParamDefKind::Type { default: Some(_), .. })
+}
+
+fn generics_of(item: &Item) -> &Generics {
+    match &item.inner {
+        ItemEnum::StructItem(s) => &s.generics,
+        ItemEnum::UnionItem(u) => &u.generics,
+        ItemEnum::EnumItem(e) => &e.generics,
+        _ => unreachable!("only structs, unions and enums have fields"),
+    }
+}
+
+/// Combines the generics of an impl or trait with the ones of a method in it.
+fn merge(parent: &Generics, own: &Generics) -> Generics {
+    Generics {
+        params: parent.params.iter().chain(&own.params).cloned().collect(),
+        where_predicates: parent
+            .where_predicates
+            .iter()
+            .chain(&own.where_predicates)
+            .cloned()
+            .collect(),
+    }
+}
+
+fn describe(entry: &Entry<'_>) -> String {
+    match entry {
+        Entry::Item(item) => kind_name(&item.kind).to_string(),
+        Entry::Reexport(import) => format!("re-export of `{}`", import.source),
+    }
+}
+
+fn kind_name(kind: &ItemKind) -> &'static str {
+    match kind {
+        ItemKind::Module => "module",
+        ItemKind::ExternCrate => "extern crate",
+        ItemKind::Import => "import",
+        ItemKind::Struct => "struct",
+        ItemKind::StructField => "field",
+        ItemKind::Union => "union",
+        ItemKind::Enum => "enum",
+        ItemKind::Variant => "variant",
+        ItemKind::Function => "function",
+        ItemKind::Typedef => "type alias",
+        ItemKind::OpaqueTy => "opaque type",
+        ItemKind::Constant => "constant",
+        ItemKind::Trait => "trait",
+        ItemKind::TraitAlias => "trait alias",
+        ItemKind::Method => "method",
+        ItemKind::Impl => "impl",
+        ItemKind::Static => "static",
+        ItemKind::ForeignType => "foreign type",
+        ItemKind::Macro => "macro",
+        ItemKind::ProcAttribute => "attribute macro",
+        ItemKind::ProcDerive => "derive macro",
+        ItemKind::AssocConst => "associated constant",
+        ItemKind::AssocType => "associated type",
+        ItemKind::Primitive => "primitive",
+        ItemKind::Keyword => "keyword",
+    }
+}
+
+fn article(kind: &str) -> String {
+    let article = if kind.starts_with(|c| "aeiou".contains(c)) { "an" } else { "a" };
+    format!("{} {}", article, kind)
+}
+
+fn struct_type_name(struct_type: &StructType) -> &'static str {
+    match struct_type {
+        StructType::Plain => "plain",
+        StructType::Tuple => "tuple",
+        StructType::Unit => "unit",
+    }
+}
@@ -0,0 +1,78 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use api_diff::Severity;
+use rustdoc_json_types::{Crate, FORMAT_VERSION};
+
+const USAGE: &str = "usage: api-diff <old.json> <new.json> [--bump patch|minor|major]";
+
+fn main() {
+    match doit() {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            println!("error: {}", e);
+            std::process::exit(2);
+        }
+    }
+}
+
+/// Returns whether the changes fit in the version bump given with `--bump`, if any.
+fn doit() -> Result<bool, Box<dyn Error>> {
+    let mut args = std::env::args().skip(1);
+    let mut paths = Vec::new();
+    let mut bump = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bump" => {
+                bump = match args.next() {
+                    Some(s) => Some(s.parse::<Severity>()?),
+                    None => return Err("--bump requires a value".into()),
+                };
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(true);
+            }
+            s if s.starts_with('-') => return Err(format!("unexpected argument `{}`", s).into()),
+            s => paths.push(s.to_string()),
+        }
+    }
+    if paths.len() != 2 {
+        return Err(USAGE.into());
+    }
+    let old = load(Path::new(&paths[0]))?;
+    let new = load(Path::new(&paths[1]))?;
+
+    let changes = api_diff::compare(&old, &new);
+    for change in &changes {
+        println!("{}", change);
+    }
+    let required = api_diff::required_bump(&changes);
+    println!("required version bump: {}", required);
+    match bump {
+        Some(bump) if bump < required => {
+            println!("error: these changes need a {} version bump, not a {} one", required, bump);
+            Ok(false)
+        }
+        _ => Ok(true),
+    }
+}
+
+fn load(path: &Path) -> Result<Crate, Box<dyn Error>> {
+    let file = File::open(path).map_err(|e| format!("failed to open {}: {}", path.display(), e))?;
+    let krate: Crate = serde_json::from_reader(BufReader::new(file))
+        .map_err(|e| format!("failed to parse {}: {}", path.display(), e))?;
+    if krate.format_version != FORMAT_VERSION {
+        return Err(format!(
+            "{} uses version {} of the JSON format, but only version {} is supported",
+            path.display(),
+            krate.format_version,
+            FORMAT_VERSION
+        )
+        .into());
+    }
+    Ok(krate)
+}
@@ -0,0 +1,230 @@
+//! Prints types, bounds and signatures back into source-like strings, which is how they are
+//! compared between two versions of a crate.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use rustdoc_json_types::*;
+
+pub(crate) struct Printer<'a> {
+    krate: &'a Crate,
+    /// Generic parameters to print under another name. The old version of an item is printed
+    /// with the parameter names of the new one, so that renaming a parameter isn't a change.
+    renames: HashMap<String, String>,
+}
+
+impl<'a> Printer<'a> {
+    pub(crate) fn new(krate: &'a Crate) -> Self {
+        Printer { krate, renames: HashMap::new() }
+    }
+
+    pub(crate) fn with_renames(krate: &'a Crate, renames: HashMap<String, String>) -> Self {
+        Printer { krate, renames }
+    }
+
+    pub(crate) fn ty(&self, ty: &Type) -> String {
+        match ty {
+            Type::ResolvedPath { name, id, args, param_names } => {
+                let mut path = self.path(name, id);
+                if let Some(args) = args {
+                    path.push_str(&self.generic_args(args));
+                }
+                if param_names.is_empty() {
+                    path
+                } else {
+                    format!("dyn {} + {}", path, self.bounds(param_names))
+                }
+            }
+            Type::Generic(name) => self.generic(name),
+            Type::Primitive(name) => name.clone(),
+            Type::FunctionPointer(f) => self.fn_pointer(f),
+            Type::Tuple(types) if types.len() == 1 => format!("({},)", self.ty(&types[0])),
+            Type::Tuple(types) => format!("({})", self.types(types)),
+            Type::Slice(ty) => format!("[{}]", self.ty(ty)),
+            Type::Array { type_, len } => format!("[{}; {}]", self.ty(type_), len),
+            Type::ImplTrait(bounds) => format!("impl {}", self.bounds(bounds)),
+            Type::Never => String::from("!"),
+            Type::Infer => String::from("_"),
+            Type::RawPointer { mutable, type_ } => {
+                format!("*{} {}", if *mutable { "mut" } else { "const" }, self.ty(type_))
+            }
+            Type::BorrowedRef { lifetime, mutable, type_ } => format!(
+                "&{}{}{}",
+                lifetime.as_ref().map(|l| format!("{} ", self.generic(l))).unwrap_or_default(),
+                if *mutable { "mut " } else { "" },
+                self.ty(type_)
+            ),
+            Type::QualifiedPath { name, self_type, trait_ } => {
+                format!("<{} as {}>::{}", self.ty(self_type), self.ty(trait_), name)
+            }
+        }
+    }
+
+    pub(crate) fn bound(&self, bound: &GenericBound) -> String {
+        match bound {
+            GenericBound::TraitBound { trait_, generic_params, modifier } => {
+                let modifier = match modifier {
+                    TraitBoundModifier::None => "",
+                    TraitBoundModifier::Maybe => "?",
+                    TraitBoundModifier::MaybeConst => "?const ",
+                };
+                format!("{}{}{}", self.for_lifetimes(generic_params), modifier, self.ty(trait_))
+            }
+            GenericBound::Outlives(lifetime) => self.generic(lifetime),
+        }
+    }
+
+    /// Prints the argument and return types of a function. Argument names are left out, since
+    /// changing them doesn't affect callers.
+    pub(crate) fn signature(&self, decl: &FnDecl) -> String {
+        let mut inputs: Vec<String> = decl.inputs.iter().map(|(_, ty)| self.ty(ty)).collect();
+        if decl.c_variadic {
+            inputs.push(String::from("..."));
+        }
+        format!("fn({}){}", inputs.join(", "), self.output(&decl.output))
+    }
+
+    /// Prints the shape of an enum variant, like `(u8, String)` or `{ x: u8 }`.
+    pub(crate) fn variant(&self, variant: &Variant) -> String {
+        match variant {
+            Variant::Plain => String::new(),
+            Variant::Tuple(types) => format!("({})", self.types(types)),
+            Variant::Struct(fields) => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .filter_map(|id| self.krate.index.get(id))
+                    .filter_map(|field| match (&field.name, &field.inner) {
+                        (Some(name), ItemEnum::StructFieldItem(ty)) => {
+                            Some(format!("{}: {}", name, self.ty(ty)))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                format!(" {{ {} }}", fields.join(", "))
+            }
+        }
+    }
+
+    /// Collects the bounds that some generics put on each parameter or type. The bounds written
+    /// inline on a parameter and those from `where` clauses end up in the same set.
+    pub(crate) fn bounds_by_subject(
+        &self,
+        generics: &Generics,
+    ) -> BTreeMap<String, BTreeSet<String>> {
+        let mut subjects: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for param in &generics.params {
+            if let GenericParamDefKind::Type { bounds, .. } = &param.kind {
+                subjects
+                    .entry(self.generic(&param.name))
+                    .or_default()
+                    .extend(bounds.iter().map(|b| self.bound(b)));
+            }
+        }
+        for predicate in &generics.where_predicates {
+            match predicate {
+                WherePredicate::BoundPredicate { ty, bounds } => subjects
+                    .entry(self.ty(ty))
+                    .or_default()
+                    .extend(bounds.iter().map(|b| self.bound(b))),
+                WherePredicate::RegionPredicate { lifetime, bounds } => subjects
+                    .entry(self.generic(lifetime))
+                    .or_default()
+                    .extend(bounds.iter().map(|b| self.bound(b))),
+                WherePredicate::EqPredicate { lhs, rhs } => {
+                    subjects.entry(self.ty(lhs)).or_default().insert(format!("= {}", self.ty(rhs)));
+                }
+            }
+        }
+        subjects
+    }
+
+    /// Items of the crate are printed with their full path, since the same type can be written
+    /// in many ways in the source. Other types are printed as they were written.
+    fn path(&self, name: &str, id: &Id) -> String {
+        match self.krate.paths.get(id) {
+            Some(summary) => summary.path.join("::"),
+            None => name.to_string(),
+        }
+    }
+
+    fn generic(&self, name: &str) -> String {
+        self.renames.get(name).cloned().unwrap_or_else(|| name.to_string())
+    }
+
+    fn generic_args(&self, args: &GenericArgs) -> String {
+        match args {
+            GenericArgs::AngleBracketed { args, bindings } => {
+                let mut printed: Vec<String> = args
+                    .iter()
+                    .map(|arg| match arg {
+                        GenericArg::Lifetime(lifetime) => self.generic(lifetime),
+                        GenericArg::Type(ty) => self.ty(ty),
+                        GenericArg::Const(c) => c.expr.clone(),
+                    })
+                    .collect();
+                printed.extend(bindings.iter().map(|binding| match &binding.binding {
+                    TypeBindingKind::Equality(ty) => format!("{} = {}", binding.name, self.ty(ty)),
+                    TypeBindingKind::Constraint(bounds) => {
+                        format!("{}: {}", binding.name, self.bounds(bounds))
+                    }
+                }));
+                if printed.is_empty() {
+                    String::new()
+                } else {
+                    format!("<{}>", printed.join(", "))
+                }
+            }
+            GenericArgs::Parenthesized { inputs, output } => {
+                format!("({}){}", self.types(inputs), self.output(output))
+            }
+        }
+    }
+
+    fn fn_pointer(&self, f: &FunctionPointer) -> String {
+        format!(
+            "{}{}{}fn({}){}",
+            self.for_lifetimes(&f.generic_params),
+            if f.is_unsafe { "unsafe " } else { "" },
+            if f.abi == "Rust" { String::new() } else { format!("extern \"{}\" ", f.abi) },
+            self.types(f.decl.inputs.iter().map(|(_, ty)| ty)),
+            self.output(&f.decl.output)
+        )
+    }
+
+    fn for_lifetimes(&self, params: &[GenericParamDef]) -> String {
+        if params.is_empty() {
+            return String::new();
+        }
+        let names: Vec<String> = params.iter().map(|p| self.generic(&p.name)).collect();
+        format!("for<{}> ", names.join(", "))
+    }
+
+    fn output(&self, output: &Option<Type>) -> String {
+        output.as_ref().map(|ty| format!(" -> {}", self.ty(ty))).unwrap_or_default()
+    }
+
+    fn types<'t>(&self, types: impl IntoIterator<Item = &'t Type>) -> String {
+        types.into_iter().map(|ty| self.ty(ty)).collect::<Vec<_>>().join(", ")
+    }
+
+    fn bounds(&self, bounds: &[GenericBound]) -> String {
+        bounds.iter().map(|b| self.bound(b)).collect::<Vec<_>>().join(" + ")
+    }
+}
+
+/// Pairs up the generic parameters of two versions of an item by position, and returns the
+/// renames that turn the old parameter names into the new ones.
+pub(crate) fn renames(old: &Generics, new: &Generics) -> HashMap<String, String> {
+    fn names(generics: &Generics, lifetimes: bool) -> impl Iterator<Item = &String> {
+        generics
+            .params
+            .iter()
+            .filter(move |p| matches!(p.kind, GenericParamDefKind::Lifetime) == lifetimes)
+            .map(|p| &p.name)
+    }
+    names(old, true)
+        .zip(names(new, true))
+        .chain(names(old, false).zip(names(new, false)))
+        .filter(|(old, new)| old != new)
+        .map(|(old, new)| (old.clone(), new.clone()))
+        .collect()
+}
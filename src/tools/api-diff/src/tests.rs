@@ -0,0 +1,399 @@
+use super::*;
+
+use std::collections::HashMap;
+
+/// Builds up a crate named `krate` in the shape rustdoc's JSON output has.
+struct Builder {
+    krate: Crate,
+    next_id: u32,
+}
+
+impl Builder {
+    fn new() -> Self {
+        let mut builder = Builder {
+            krate: Crate {
+                root: Id(String::from("0:0")),
+                crate_version: None,
+                includes_private: false,
+                index: HashMap::new(),
+                paths: HashMap::new(),
+                external_crates: HashMap::new(),
+                format_version: FORMAT_VERSION,
+            },
+            next_id: 0,
+        };
+        let module = ItemEnum::ModuleItem(Module { is_crate: true, items: vec![] });
+        builder.item("krate", ItemKind::Module, module);
+        builder
+    }
+
+    /// Adds an item without putting it in a module, like the fields or methods of a type.
+    fn item(&mut self, name: &str, kind: ItemKind, inner: ItemEnum) -> Id {
+        let id = Id(format!("0:{}", self.next_id));
+        self.next_id += 1;
+        let item = Item {
+            id: id.clone(),
+            crate_id: 0,
+            name: Some(name.to_string()),
+            source: None,
+            visibility: Visibility::Public,
+            docs: String::new(),
+            links: HashMap::new(),
+            attrs: vec![],
+            deprecation: None,
+            kind,
+            inner,
+        };
+        self.krate.index.insert(id.clone(), item);
+        id
+    }
+
+    /// Adds an item to the root module.
+    fn add(&mut self, name: &str, kind: ItemKind, inner: ItemEnum) -> Id {
+        let id = self.item(name, kind, inner);
+        self.root_items().push(id.clone());
+        id
+    }
+
+    fn root_items(&mut self) -> &mut Vec<Id> {
+        let root = self.krate.root.clone();
+        match &mut self.krate.index.get_mut(&root).unwrap().inner {
+            ItemEnum::ModuleItem(module) => &mut module.items,
+            _ => unreachable!(),
+        }
+    }
+
+    fn function(&mut self, name: &str, inputs: Vec<Type>, generics: Generics) -> Id {
+        let inner =
+            ItemEnum::FunctionItem(Function { decl: decl(inputs), generics, header: header() });
+        self.add(name, ItemKind::Function, inner)
+    }
+
+    fn field(&mut self, name: &str, ty: Type, visibility: Visibility) -> Id {
+        let id = self.item(name, ItemKind::StructField, ItemEnum::StructFieldItem(ty));
+        self.krate.index.get_mut(&id).unwrap().visibility = visibility;
+        id
+    }
+
+    fn method(&mut self, name: &str, has_body: bool) -> Id {
+        let method = Method {
+            decl: decl(vec![]),
+            generics: Generics::default(),
+            header: header(),
+            has_body,
+        };
+        self.item(name, ItemKind::Method, ItemEnum::MethodItem(method))
+    }
+
+    fn build(self) -> Crate {
+        self.krate
+    }
+}
+
+fn decl(inputs: Vec<Type>) -> FnDecl {
+    FnDecl {
+        inputs: inputs.into_iter().map(|ty| (String::from("arg"), ty)).collect(),
+        output: None,
+        c_variadic: false,
+    }
+}
+
+fn header() -> Header {
+    Header { const_: false, unsafe_: false, async_: false, abi: String::from("Rust") }
+}
+
+fn primitive(name: &str) -> Type {
+    Type::Primitive(name.to_string())
+}
+
+fn path(name: &str) -> Type {
+    Type::ResolvedPath {
+        name: name.to_string(),
+        id: Id(format!("1:{}", name.len())),
+        args: None,
+        param_names: vec![],
+    }
+}
+
+fn type_param(name: &str, bounds: &[&str]) -> Generics {
+    let bounds = bounds
+        .iter()
+        .map(|bound| GenericBound::TraitBound {
+            trait_: path(bound),
+            generic_params: vec![],
+            modifier: TraitBoundModifier::None,
+        })
+        .collect();
+    Generics {
+        params: vec![GenericParamDef {
+            name: name.to_string(),
+            kind: GenericParamDefKind::Type { bounds, default: None },
+        }],
+        where_predicates: vec![],
+    }
+}
+
+fn changes(old: Builder, new: Builder) -> Vec<(Severity, String, String)> {
+    compare(&old.build(), &new.build())
+        .into_iter()
+        .map(|change| (change.severity, change.path, change.message))
+        .collect()
+}
+
+fn change(severity: Severity, path: &str, message: &str) -> (Severity, String, String) {
+    (severity, path.to_string(), message.to_string())
+}
+
+#[test]
+fn no_changes() {
+    let (mut old, mut new) = (Builder::new(), Builder::new());
+    old.function("f", vec![primitive("u8")], Generics::default());
+    new.function("f", vec![primitive("u8")], Generics::default());
+    let changes = compare(&old.build(), &new.build());
+    assert!(changes.is_empty());
+    assert_eq!(required_bump(&changes), Severity::Patch);
+}
+
+#[test]
+fn added_and_removed_items() {
+    let (mut old, mut new) = (Builder::new(), Builder::new());
+    old.function("removed", vec![], Generics::default());
+    new.function("added", vec![], Generics::default());
+    assert_eq!(
+        changes(old, new),
+        [
+            change(Severity::Major, "krate::removed", "function was removed"),
+            change(Severity::Minor, "krate::added", "function was added"),
+        ]
+    );
+}
+
+#[test]
+fn removed_module_is_reported_once() {
+    let (mut old, new) = (Builder::new(), Builder::new());
+    let f = old.item(
+        "f",
+        ItemKind::Function,
+        ItemEnum::FunctionItem(Function {
+            decl: decl(vec![]),
+            generics: Generics::default(),
+            header: header(),
+        }),
+    );
+    old.add(
+        "inner",
+        ItemKind::Module,
+        ItemEnum::ModuleItem(Module { is_crate: false, items: vec![f] }),
+    );
+    assert_eq!(changes(old, new), [change(Severity::Major, "krate::inner", "module was removed")]);
+}
+
+#[test]
+fn changed_signature() {
+    let (mut old, mut new) = (Builder::new(), Builder::new());
+    old.function("f", vec![primitive("u8")], Generics::default());
+    new.function("f", vec![primitive("u16")], Generics::default());
+    assert_eq!(
+        changes(old, new),
+        [change(Severity::Major, "krate::f", "signature changed from `fn(u8)` to `fn(u16)`")]
+    );
+}
+
+#[test]
+fn renamed_generic_parameter() {
+    let (mut old, mut new) = (Builder::new(), Builder::new());
+    old.function("f", vec![Type::Generic(String::from("T"))], type_param("T", &["Clone"]));
+    new.function("f", vec![Type::Generic(String::from("U"))], type_param("U", &["Clone"]));
+    assert!(changes(old, new).is_empty());
+}
+
+#[test]
+fn tightened_and_loosened_bounds() {
+    let (mut old, mut new) = (Builder::new(), Builder::new());
+    old.function("tightened", vec![], type_param("T", &["Clone"]));
+    new.function("tightened", vec![], type_param("T", &["Clone", "Send"]));
+    old.function("loosened", vec![], type_param("T", &["Clone"]));
+    new.function("loosened", vec![], type_param("T", &[]));
+    assert_eq!(
+        changes(old, new),
+        [
+            change(Severity::Minor, "krate::loosened", "bound `T: Clone` was removed"),
+            change(Severity::Major, "krate::tightened", "bound `T: Send` was added"),
+        ]
+    );
+}
+
+#[test]
+fn new_trait_items() {
+    let (mut old, mut new) = (Builder::new(), Builder::new());
+    let trait_ = |items| {
+        ItemEnum::TraitItem(Trait {
+            is_auto: false,
+            is_unsafe: false,
+            items,
+            generics: Generics::default(),
+            bounds: vec![],
+            implementors: vec![],
+        })
+    };
+    old.add("Tr", ItemKind::Trait, trait_(vec![]));
+    let items = vec![new.method("required", false), new.method("provided", true)];
+    new.add("Tr", ItemKind::Trait, trait_(items));
+    assert_eq!(
+        changes(old, new),
+        [
+            change(Severity::Minor, "krate::Tr::provided", "method with a default was added"),
+            change(Severity::Major, "krate::Tr::required", "required method was added"),
+        ]
+    );
+}
+
+#[test]
+fn new_struct_fields() {
+    let (mut old, mut new) = (Builder::new(), Builder::new());
+    let struct_ = |fields, fields_stripped| {
+        ItemEnum::StructItem(Struct {
+            struct_type: StructType::Plain,
+            generics: Generics::default(),
+            fields_stripped,
+            fields,
+            impls: vec![],
+        })
+    };
+    for (name, stripped) in &[("Open", false), ("Sealed", true)] {
+        let fields = vec![old.field("a", primitive("u8"), Visibility::Public)];
+        old.add(name, ItemKind::Struct, struct_(fields, *stripped));
+        let fields = vec![
+            new.field("a", primitive("u8"), Visibility::Public),
+            new.field("b", primitive("u8"), Visibility::Public),
+        ];
+        new.add(name, ItemKind::Struct, struct_(fields, *stripped));
+    }
+    assert_eq!(
+        changes(old, new),
+        [
+            change(
+                Severity::Major,
+                "krate::Open::b",
+                "field was added to a struct with no private fields"
+            ),
+            change(Severity::Minor, "krate::Sealed::b", "field was added"),
+        ]
+    );
+}
+
+#[test]
+fn new_enum_variants() {
+    fn build(with_new_variant: bool) -> Builder {
+        let mut builder = Builder::new();
+        for name in &["Exhaustive", "NonExhaustive"] {
+            let mut variants =
+                vec![builder.item("A", ItemKind::Variant, ItemEnum::VariantItem(Variant::Plain))];
+            if with_new_variant {
+                variants.push(builder.item(
+                    "B",
+                    ItemKind::Variant,
+                    ItemEnum::VariantItem(Variant::Plain),
+                ));
+            }
+            let inner = ItemEnum::EnumItem(Enum {
+                generics: Generics::default(),
+                variants_stripped: false,
+                variants,
+                impls: vec![],
+            });
+            let id = builder.add(name, ItemKind::Enum, inner);
+            if *name == "NonExhaustive" {
+                builder
+                    .krate
+                    .index
+                    .get_mut(&id)
+                    .unwrap()
+                    .attrs
+                    .push(String::from("#[non_exhaustive]"));
+            }
+        }
+        builder
+    }
+    assert_eq!(
+        changes(build(false), build(true)),
+        [
+            change(
+                Severity::Major,
+                "krate::Exhaustive::B",
+                "variant was added to an exhaustive enum"
+            ),
+            change(Severity::Minor, "krate::NonExhaustive::B", "variant was added"),
+        ]
+    );
+}
+
+#[test]
+fn removed_trait_impl() {
+    let (mut old, mut new) = (Builder::new(), Builder::new());
+    let impl_ = ItemEnum::ImplItem(Impl {
+        is_unsafe: false,
+        generics: Generics::default(),
+        provided_trait_methods: vec![],
+        trait_: Some(path("Send")),
+        for_: path("S"),
+        items: vec![],
+        negative: false,
+        synthetic: true,
+        blanket_impl: None,
+    });
+    let struct_ = |impls| {
+        ItemEnum::StructItem(Struct {
+            struct_type: StructType::Unit,
+            generics: Generics::default(),
+            fields_stripped: false,
+            fields: vec![],
+            impls,
+        })
+    };
+    let impls = vec![old.item("", ItemKind::Impl, impl_)];
+    old.add("S", ItemKind::Struct, struct_(impls));
+    new.add("S", ItemKind::Struct, struct_(vec![]));
+    let changes = changes(old, new);
+    assert_eq!(changes, [change(Severity::Major, "krate::S", "`impl Send for S` was removed")]);
+}
+
+#[test]
+fn renamed_trait_impl_parameter() {
+    let (mut old, mut new) = (Builder::new(), Builder::new());
+    let impl_ = |param: &str| {
+        ItemEnum::ImplItem(Impl {
+            is_unsafe: false,
+            generics: type_param(param, &[]),
+            provided_trait_methods: vec![],
+            trait_: Some(path("Tr")),
+            for_: Type::ResolvedPath {
+                name: String::from("S"),
+                id: Id(String::from("0:100")),
+                args: Some(Box::new(GenericArgs::AngleBracketed {
+                    args: vec![GenericArg::Type(Type::Generic(param.to_string()))],
+                    bindings: vec![],
+                })),
+                param_names: vec![],
+            },
+            items: vec![],
+            negative: false,
+            synthetic: false,
+            blanket_impl: None,
+        })
+    };
+    let struct_ = |impls| {
+        ItemEnum::StructItem(Struct {
+            struct_type: StructType::Unit,
+            generics: type_param("T", &[]),
+            fields_stripped: false,
+            fields: vec![],
+            impls,
+        })
+    };
+    let impls = vec![old.item("", ItemKind::Impl, impl_("T"))];
+    old.add("S", ItemKind::Struct, struct_(impls));
+    let impls = vec![new.item("", ItemKind::Impl, impl_("U"))];
+    new.add("S", ItemKind::Struct, struct_(impls));
+    assert!(changes(old, new).is_empty());
+}
@@ -735,6 +735,7 @@ fn desugaring_name(des: hir::MatchSource) -> String {
             contains_else_clause
         ),
         hir::MatchSource::AwaitDesugar => "MatchSource::AwaitDesugar".to_string(),
+        hir::MatchSource::LetChainDesugar => "MatchSource::LetChainDesugar".to_string(),
     }
 }
 
@@ -227,7 +227,7 @@ function loadMainJsAndIndex(mainJs, searchIndex, storageJs, crate) {
     searchIndex = loadContent(fullSearchIndex);
     var finalJS = "";
 
-    var arraysToLoad = ["itemTypes"];
+    var arraysToLoad = ["itemTypes", "FULL_TEXT_STOP_WORDS"];
     var variablesToLoad = ["MAX_LEV_DISTANCE", "MAX_RESULTS", "NO_TYPE_FILTER",
                            "GENERICS_DATA", "NAME", "INPUTS_DATA", "OUTPUT_DATA",
                            "TY_PRIMITIVE", "TY_KEYWORD",
@@ -236,9 +236,11 @@ function loadMainJsAndIndex(mainJs, searchIndex, storageJs, crate) {
     // execQuery last parameter is built in buildIndex.
     // buildIndex requires the hashmap from search-index.
     var functionsToLoad = ["buildHrefAndPath", "pathSplitter", "levenshtein", "validateResult",
-                           "handleAliases", "getQuery", "buildIndex", "execQuery", "execSearch"];
+                           "handleAliases", "getQuery", "buildIndex", "execQuery", "execSearch",
+                           "fullTextWords", "execFullTextQuery"];
 
     ALIASES = {};
+    FULL_TEXT = {};
     finalJS += 'window = { "currentCrate": "' + crate + '" };\n';
     finalJS += 'var rootPath = "../";\n';
     finalJS += loadThings(["hasOwnProperty", "onEach"], 'function', extractFunction, storageJs);